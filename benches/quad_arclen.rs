@@ -83,6 +83,7 @@ fn gauss_arclen_7(q: QuadBez) -> f64 {
     )
 }
 
+#[allow(clippy::excessive_precision)]
 fn gauss_arclen_24(q: QuadBez) -> f64 {
     gauss_arclen_n(
         q,
@@ -147,7 +148,7 @@ const ACCURACY: f64 = 1e-6;
 fn bench_quad_arclen(b: &mut Bencher) {
     // This is a pretty easy case.
     let q = QuadBez::new((0.0, 0.0), (1.0, 0.0), (1.0, 1.0));
-    b.iter(|| (test::black_box(q).arclen(ACCURACY)))
+    b.iter(|| test::black_box(q).arclen(ACCURACY))
 }
 
 #[bench]
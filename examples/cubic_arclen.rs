@@ -102,35 +102,11 @@ fn est_gauss11_error_2(c: CubicBez) -> f64 {
         .sum::<f64>()
 }
 
-fn est_max_curvature(c: CubicBez) -> f64 {
-    let n = 100;
-    let mut max = 0.0;
-    for i in 0..=n {
-        let t = (i as f64) * (n as f64).recip();
-        let k = c.curvature(t).abs();
-        if !(k < max) {
-            max = k;
-        }
-    }
-    max
-}
-
-fn est_min_deriv_norm2(c: CubicBez) -> f64 {
-    let d = c.deriv();
-    let n = 100;
-    let mut min = d.eval(1.0).hypot2();
-    for i in 0..n {
-        let t = (i as f64) * (n as f64).recip();
-        min = min.min(d.eval(t).hypot2())
-    }
-    min
-}
-
 fn est_gauss11_error_3(c: CubicBez) -> f64 {
     let lc = (c.p3 - c.p0).hypot();
     let lp = (c.p1 - c.p0).hypot() + (c.p2 - c.p1).hypot() + (c.p3 - c.p2).hypot();
     let pc_err = (lp - lc) * 0.02;
-    let ks = est_max_curvature(c) * lp;
+    let ks = c.max_curvature() * lp;
     let est = ks.powi(3) * lp * 8e-9;
     if est < pc_err {
         est
@@ -143,7 +119,7 @@ fn est_gauss9_error_3(c: CubicBez) -> f64 {
     let lc = (c.p3 - c.p0).hypot();
     let lp = (c.p1 - c.p0).hypot() + (c.p2 - c.p1).hypot() + (c.p3 - c.p2).hypot();
     let pc_err = (lp - lc) * 0.02;
-    let ks = est_max_curvature(c) * lp;
+    let ks = c.max_curvature() * lp;
     let est = ks.powi(3) * lp * 5e-8;
     if est < pc_err {
         est
@@ -2,7 +2,7 @@
 
 use std::ops::{Mul, MulAssign};
 
-use crate::Vec2;
+use crate::{Angle, Vec2};
 
 /// A 2D affine transform.
 #[derive(Clone, Copy, Debug)]
@@ -43,8 +43,12 @@ impl Affine {
     /// positive X direction into positive Y. Thus, in a Y-down coordinate
     /// system (as is common for graphics), it is a clockwise rotation, and
     /// in Y-up (traditional for math), it is anti-clockwise.
+    ///
+    /// The angle is in radians if given as an `f64`, or may be given as an
+    /// [`Angle`](struct.Angle.html).
     #[inline]
-    pub fn rotate(th: f64) -> Affine {
+    pub fn rotate(th: impl Into<Angle>) -> Affine {
+        let th = th.into().radians();
         let s = th.sin();
         let c = th.cos();
         Affine([c, s, -s, c, 0.0, 0.0])
@@ -62,6 +66,52 @@ impl Affine {
     pub fn as_coeffs(self) -> [f64; 6] {
         self.0
     }
+
+    /// Compute the determinant of this transform.
+    ///
+    /// This is the factor by which the transform scales areas; it is
+    /// negative when the transform flips orientation.
+    #[inline]
+    pub fn determinant(self) -> f64 {
+        self.0[0] * self.0[3] - self.0[1] * self.0[2]
+    }
+
+    /// Compute the inverse transform.
+    ///
+    /// Produces NaN values when the determinant is zero.
+    pub fn inverse(self) -> Affine {
+        let inv_det = self.determinant().recip();
+        Affine([
+            inv_det * self.0[3],
+            -inv_det * self.0[1],
+            -inv_det * self.0[2],
+            inv_det * self.0[0],
+            inv_det * (self.0[2] * self.0[5] - self.0[3] * self.0[4]),
+            inv_det * (self.0[1] * self.0[4] - self.0[0] * self.0[5]),
+        ])
+    }
+
+    /// The transform applying this one, then `other`.
+    ///
+    /// This is `other * self`, written in the order the transforms apply,
+    /// so `a.then(b).then(c)` reads left to right.
+    #[inline]
+    pub fn then(self, other: Affine) -> Affine {
+        other * self
+    }
+
+    /// The transform from the coordinates of one space to those of another.
+    ///
+    /// Each space is given by the transform from its coordinates to a
+    /// common one, as for the world transforms of nodes in a scene graph.
+    /// A point with coordinates `p` in `from_space` has coordinates
+    /// `Affine::change_basis(from_space, to_space) * p` in `to_space`.
+    ///
+    /// Produces NaN values when `to_space` is not invertible.
+    #[inline]
+    pub fn change_basis(from_space: Affine, to_space: Affine) -> Affine {
+        to_space.inverse() * from_space
+    }
 }
 
 impl Default for Affine {
@@ -183,4 +233,34 @@ mod tests {
         assert_near(a1 * (a2 * py), (a1 * a2) * py);
         assert_near(a1 * (a2 * (px + py)), (a1 * a2) * (px + py));
     }
+
+    #[test]
+    fn affine_then_and_change_basis() {
+        let p = Vec2::new(3.0, 4.0);
+        let a = Affine::scale(2.0);
+        let b = Affine::translate((1.0, 0.0));
+        assert_near(a.then(b) * p, b * (a * p));
+        assert_near(a.then(b) * p, Vec2::new(7.0, 8.0));
+
+        // A child node, rotated within a translated parent.
+        let parent = Affine::translate((10.0, 0.0));
+        let child = parent * Affine::rotate(PI / 2.0);
+        let to_parent = Affine::change_basis(child, parent);
+        assert_near(to_parent * Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        assert_near(parent * (to_parent * p), child * p);
+
+        let world = child * p;
+        assert_near(world.to_space(child), p);
+        assert_near(p.to_parent(child), world);
+    }
+
+    #[test]
+    fn affine_inverse() {
+        let a = Affine::new([0.1, 1.2, 2.3, 3.4, 4.5, 5.6]);
+        assert!((a.determinant() - (0.1 * 3.4 - 1.2 * 2.3)).abs() < 1e-12);
+        let a_inv = a.inverse();
+        let p = Vec2::new(3.0, 4.0);
+        assert_near(a_inv * (a * p), p);
+        assert_near(a * (a_inv * p), p);
+    }
 }
@@ -0,0 +1,145 @@
+//! Angles.
+
+use std::f64::consts::PI;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// An angle, stored in radians.
+///
+/// Functions taking an angle accept `impl Into<Angle>`, and a bare `f64`
+/// converts as radians, so existing code passing radians keeps working
+/// while code working in degrees can say so with
+/// [`from_degrees`](#method.from_degrees) instead of converting by hand.
+///
+/// An angle is not normalized unless asked; a sweep of 3π is different from
+/// a sweep of π.
+#[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd)]
+pub struct Angle(f64);
+
+impl Angle {
+    /// An angle of the given number of radians.
+    #[inline]
+    pub fn from_radians(radians: f64) -> Angle {
+        Angle(radians)
+    }
+
+    /// An angle of the given number of degrees.
+    #[inline]
+    pub fn from_degrees(degrees: f64) -> Angle {
+        Angle(degrees.to_radians())
+    }
+
+    /// The angle in radians.
+    #[inline]
+    pub fn radians(self) -> f64 {
+        self.0
+    }
+
+    /// The angle in degrees.
+    #[inline]
+    pub fn degrees(self) -> f64 {
+        self.0.to_degrees()
+    }
+
+    /// The equivalent angle in the range (-π, π].
+    #[inline]
+    pub fn normalized(self) -> Angle {
+        Angle(self.0 - 2.0 * PI * ((self.0 - PI) / (2.0 * PI)).ceil())
+    }
+
+    /// Interpolate between two angles along the shorter arc.
+    ///
+    /// When the angles are exactly opposite, this turns in the positive
+    /// direction. The result is not normalized.
+    #[inline]
+    pub fn lerp(self, other: Angle, t: f64) -> Angle {
+        self + (other - self).normalized() * t
+    }
+}
+
+impl From<f64> for Angle {
+    /// Convert from radians.
+    #[inline]
+    fn from(radians: f64) -> Angle {
+        Angle(radians)
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn add(self, other: Angle) -> Angle {
+        Angle(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Angle {
+    #[inline]
+    fn add_assign(&mut self, other: Angle) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn sub(self, other: Angle) -> Angle {
+        Angle(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Angle {
+    #[inline]
+    fn sub_assign(&mut self, other: Angle) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul<f64> for Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn mul(self, other: f64) -> Angle {
+        Angle(self.0 * other)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    #[inline]
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Affine, Angle, Arc, Vec2};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn angle() {
+        assert!((Angle::from_degrees(90.0).radians() - PI / 2.0).abs() < 1e-12);
+        assert!((Angle::from_radians(PI).degrees() - 180.0).abs() < 1e-12);
+        assert_eq!(Angle::from(PI).normalized().radians(), PI);
+        assert_eq!(Angle::from(-PI).normalized().radians(), PI);
+        assert!((Angle::from(3.5 * PI).normalized().radians() + 0.5 * PI).abs() < 1e-12);
+        assert!((Angle::from_degrees(-270.0).normalized().degrees() - 90.0).abs() < 1e-12);
+
+        // 170° to -170° goes the short way, through 180°.
+        let a = Angle::from_degrees(170.0).lerp(Angle::from_degrees(-170.0), 0.5);
+        assert!((a.normalized().degrees() - 180.0).abs() < 1e-9);
+
+        let p = Affine::rotate(Angle::from_degrees(90.0)) * Vec2::new(1.0, 0.0);
+        assert!((p - Vec2::new(0.0, 1.0)).hypot() < 1e-12);
+        assert_eq!(
+            Affine::rotate(0.5) * p,
+            Affine::rotate(Angle::from(0.5)) * p
+        );
+        let arc = Arc::new((0.0, 0.0), (1.0, 1.0), Angle::from_degrees(90.0), PI, 0.0);
+        assert!((arc.start_angle - 0.5 * PI).abs() < 1e-12);
+        assert_eq!(arc.sweep_angle, PI);
+    }
+}
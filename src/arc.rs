@@ -0,0 +1,876 @@
+//! Elliptical arcs.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::iter::{once, Chain, Once};
+use std::ops::Range;
+
+use arrayvec::ArrayVec;
+
+use crate::common::{elliptic_e, solve_quadratic};
+use crate::ellipse::nearest_angle;
+use crate::shape::farthest;
+use crate::{
+    Angle, BezPath, CubicBez, Line, Moments, ParamCurve, ParamCurveArclen, ParamCurveArea,
+    ParamCurveCurvature, ParamCurveDeriv, ParamCurveExtrema, ParamCurveMoments, ParamCurveNearest,
+    PathEl, Rect, Shape, Vec2, MAX_EXTREMA,
+};
+
+/// A single elliptical arc segment.
+#[derive(Clone, Copy, Debug)]
+pub struct Arc {
+    /// The center of the ellipse.
+    pub center: Vec2,
+    /// The radii of the ellipse, before rotation.
+    pub radii: Vec2,
+    /// The angle of the start point, in radians.
+    pub start_angle: f64,
+    /// The angle swept by the arc, in radians.
+    ///
+    /// A positive sweep rotates positive X into positive Y, consistent
+    /// with [`Affine::rotate`](struct.Affine.html#method.rotate).
+    pub sweep_angle: f64,
+    /// The rotation of the ellipse's X axis, in radians.
+    pub x_rotation: f64,
+}
+
+impl Arc {
+    /// Create a new arc.
+    ///
+    /// The angles are in radians if given as `f64`, or may be given as
+    /// [`Angle`](struct.Angle.html)s. They are not normalized, so a sweep
+    /// may exceed a full turn.
+    pub fn new(
+        center: impl Into<Vec2>,
+        radii: impl Into<Vec2>,
+        start_angle: impl Into<Angle>,
+        sweep_angle: impl Into<Angle>,
+        x_rotation: impl Into<Angle>,
+    ) -> Arc {
+        Arc {
+            center: center.into(),
+            radii: radii.into(),
+            start_angle: start_angle.into().radians(),
+            sweep_angle: sweep_angle.into().radians(),
+            x_rotation: x_rotation.into().radians(),
+        }
+    }
+
+    /// The part of the ellipse between two angles.
+    ///
+    /// The angles are in the same terms as `start_angle`, not fractions of
+    /// the sweep as for [`subsegment`](#method.subsegment), and the range may
+    /// extend beyond the arc or run backwards.
+    pub fn subsegment_by_angle(&self, range: Range<f64>) -> Arc {
+        Arc {
+            start_angle: range.start,
+            sweep_angle: range.end - range.start,
+            ..*self
+        }
+    }
+
+    /// Split the arc in two at an angle.
+    ///
+    /// The first arc runs from the start to `angle` and the second from
+    /// `angle` to the end, so they join up to the original arc.
+    pub fn split_at_angle(&self, angle: impl Into<Angle>) -> (Arc, Arc) {
+        let angle = angle.into().radians();
+        let end = self.start_angle + self.sweep_angle;
+        (
+            self.subsegment_by_angle(self.start_angle..angle),
+            self.subsegment_by_angle(angle..end),
+        )
+    }
+
+    /// The angle of the point on the arc nearest to a point.
+    ///
+    /// The result is in the range swept by the arc, in the same terms as
+    /// `start_angle`, so it can be passed to
+    /// [`split_at_angle`](#method.split_at_angle).
+    pub fn nearest_angle(&self, p: impl Into<Vec2>) -> f64 {
+        let (t, _) = self.nearest(p.into(), 0.0);
+        self.start_angle + t * self.sweep_angle
+    }
+
+    /// The winding number contribution of the arc at a point.
+    ///
+    /// This casts a ray to the left of the point and counts crossings, as
+    /// for path segments, so the contributions of the arcs and segments
+    /// making up a closed outline sum to its winding number, without
+    /// converting the arcs to cubics. It differs from
+    /// [`Shape::winding`](trait.Shape.html#tymethod.winding), which is zero
+    /// for an arc, as an open curve encloses nothing.
+    pub fn winding_contribution(&self, p: Vec2) -> i32 {
+        // Pieces of at most a full turn have few enough extrema to report.
+        let n = (self.sweep_angle.abs() / (2.0 * PI)).ceil().max(1.0);
+        (0..n as usize)
+            .map(|i| {
+                let piece = self.subsegment(i as f64 / n..(i + 1) as f64 / n);
+                piece
+                    .extrema_ranges()
+                    .into_iter()
+                    .map(|range| piece.subsegment(range).winding_monotonic(p))
+                    .sum::<i32>()
+            })
+            .sum()
+    }
+
+    // Assumes the arc is monotonic in both x and y.
+    fn winding_monotonic(&self, p: Vec2) -> i32 {
+        let start = self.eval(0.0);
+        let end = self.eval(1.0);
+        let sign = if end.y > start.y {
+            if p.y < start.y || p.y >= end.y {
+                return 0;
+            }
+            1
+        } else if end.y < start.y {
+            if p.y < end.y || p.y >= start.y {
+                return 0;
+            }
+            -1
+        } else {
+            return 0;
+        };
+        if p.x < start.x.min(end.x) {
+            return 0;
+        }
+        if p.x >= start.x.max(end.x) {
+            return sign;
+        }
+        // Find where the arc crosses the ray's height, by bisection.
+        let (mut t0, mut t1) = (0.0, 1.0);
+        for _ in 0..64 {
+            let tm = 0.5 * (t0 + t1);
+            if (self.eval(tm).y < p.y) == (sign > 0) {
+                t0 = tm;
+            } else {
+                t1 = tm;
+            }
+        }
+        if p.x >= self.eval(0.5 * (t0 + t1)).x {
+            sign
+        } else {
+            0
+        }
+    }
+
+    /// The intersections of the arc with a line segment.
+    ///
+    /// Each intersection is reported as a pair of the parameter on the arc
+    /// and the parameter on the line. A point swept more than once, by an
+    /// arc of more than a full turn, is reported at its first parameter.
+    pub fn intersect_line(&self, line: Line) -> ArrayVec<[(f64, f64); 2]> {
+        let mut result = ArrayVec::new();
+        if self.radii.x == 0.0 || self.radii.y == 0.0 {
+            return result;
+        }
+        // Work in coordinates where the ellipse is the unit circle.
+        let (s, c) = self.x_rotation.sin_cos();
+        let unit = |pt: Vec2| {
+            let d = pt - self.center;
+            Vec2::new(
+                (c * d.x + s * d.y) / self.radii.x,
+                (c * d.y - s * d.x) / self.radii.y,
+            )
+        };
+        let q0 = unit(line.p0);
+        let dq = unit(line.p1) - q0;
+        for u in solve_quadratic(q0.hypot2() - 1.0, 2.0 * q0.dot(dq), dq.hypot2()) {
+            if !(0.0..=1.0).contains(&u) {
+                continue;
+            }
+            let angle = (q0 + u * dq).atan2();
+            if let Some(t) = self.param_of_angle(angle) {
+                result.push((t, u));
+            }
+        }
+        result
+    }
+
+    /// The parameter at which the arc reaches an angle, if it does.
+    fn param_of_angle(&self, angle: f64) -> Option<f64> {
+        if self.sweep_angle == 0.0 {
+            return None;
+        }
+        let delta = (angle - self.start_angle).rem_euclid(2.0 * PI);
+        let t = if self.sweep_angle > 0.0 || delta == 0.0 {
+            delta / self.sweep_angle
+        } else {
+            (delta - 2.0 * PI) / self.sweep_angle
+        };
+        if (0.0..=1.0).contains(&t) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Append the arc to a path, as a sequence of cubic Béziers.
+    ///
+    /// The path is expected to already be positioned at the start of
+    /// the arc; no `Moveto` is emitted.
+    pub fn append_to_path(&self, path: &mut BezPath, tolerance: f64) {
+        for el in self.append_iter(tolerance) {
+            path.push(el);
+        }
+    }
+
+    /// The cubic Béziers approximating the arc, within `accuracy`.
+    ///
+    /// The arc is split into pieces of equal angle, each approximated by the
+    /// cubic matching its endpoints and end tangents. The number of pieces
+    /// is the least for which the error bound, described in
+    /// [`append_iter`](#method.append_iter), is within `accuracy`.
+    pub fn to_cubics(&self, accuracy: f64) -> impl Iterator<Item = CubicBez> {
+        let mut p0 = self.eval(0.0);
+        self.append_iter(accuracy).map(move |el| match el {
+            PathEl::Curveto(p1, p2, p3) => {
+                let c = CubicBez::new(p0, p1, p2, p3);
+                p0 = p3;
+                c
+            }
+            _ => unreachable!(),
+        })
+    }
+
+    /// The `Curveto` elements approximating the arc, as an iterator.
+    ///
+    /// These are the elements [`append_to_path`](#method.append_to_path)
+    /// appends, produced without allocating.
+    ///
+    /// For a circular arc of radius r through angle θ, the cubic with arms
+    /// of length 4/3 tan(θ/4) r deviates from the arc by at most
+    /// 4/27 sin⁶(θ/4) / cos²(θ/4) r, outward. An elliptical arc is the image
+    /// of a unit circular arc under a linear map stretching by at most the
+    /// larger radius, so the same bound holds with r the larger radius. The
+    /// number of pieces is chosen from this bound, so the result is within
+    /// `tolerance` of the arc.
+    pub fn append_iter(&self, tolerance: f64) -> ArcAppendIter {
+        let r = self.radii.x.abs().max(self.radii.y.abs());
+        let sweep = self.sweep_angle.abs();
+        // Start from the small angle approximation of the bound, then step
+        // up until the exact bound is met.
+        let mut n = if r > 0.0 && tolerance > 0.0 {
+            let max_angle = 4.0 * (6.75 * tolerance / r).powf(1.0 / 6.0);
+            (sweep / max_angle.min(PI)).ceil()
+        } else {
+            (sweep / PI).ceil()
+        };
+        while n > 0.0 && n < 1e6 && r * arc_error(sweep / n) > tolerance {
+            n += 1.0;
+        }
+        let angle_step = self.sweep_angle / n;
+        let arm_len = (4.0 / 3.0) * (0.25 * angle_step).tan();
+        ArcAppendIter {
+            arc: *self,
+            idx: 0,
+            n: n as usize,
+            angle0: self.start_angle,
+            angle_step,
+            arm_len,
+            p0: sample_ellipse(self.radii, self.x_rotation, self.start_angle),
+        }
+    }
+}
+
+/// A trigonometric polynomial of degree at most 4, with the coefficients of
+/// `cos kθ` and `sin kθ`.
+#[derive(Clone, Copy)]
+struct Trig {
+    cos: [f64; 5],
+    sin: [f64; 5],
+}
+
+impl Trig {
+    /// `a + b cos θ + c sin θ`.
+    fn linear(a: f64, b: f64, c: f64) -> Trig {
+        Trig {
+            cos: [a, b, 0.0, 0.0, 0.0],
+            sin: [0.0, c, 0.0, 0.0, 0.0],
+        }
+    }
+
+    /// The product, which must have degree at most 4.
+    fn mul(&self, other: &Trig) -> Trig {
+        let mut result = Trig {
+            cos: [0.0; 5],
+            sin: [0.0; 5],
+        };
+        for j in 0..5 {
+            for k in 0..5 {
+                let (cc, ss) = (self.cos[j] * other.cos[k], self.sin[j] * other.sin[k]);
+                let (sc, cs) = (self.sin[j] * other.cos[k], self.cos[j] * other.sin[k]);
+                if cc == 0.0 && ss == 0.0 && sc == 0.0 && cs == 0.0 {
+                    continue;
+                }
+                // Product to sum identities, with sin(-n) = -sin(n).
+                let (diff, sign) = if j >= k { (j - k, 1.0) } else { (k - j, -1.0) };
+                result.cos[j + k] += 0.5 * (cc - ss);
+                result.cos[diff] += 0.5 * (cc + ss);
+                result.sin[j + k] += 0.5 * (sc + cs);
+                result.sin[diff] += 0.5 * sign * (sc - cs);
+            }
+        }
+        result.sin[0] = 0.0;
+        result
+    }
+
+    /// The integral from `th0` to `th1`.
+    fn integrate(&self, th0: f64, th1: f64) -> f64 {
+        let mut result = self.cos[0] * (th1 - th0);
+        for k in 1..5 {
+            let kf = k as f64;
+            result += self.cos[k] * ((kf * th1).sin() - (kf * th0).sin()) / kf;
+            result -= self.sin[k] * ((kf * th1).cos() - (kf * th0).cos()) / kf;
+        }
+        result
+    }
+}
+
+/// An iterator over the cubic Béziers approximating an arc.
+///
+/// This is returned by [`Arc::append_iter`](struct.Arc.html#method.append_iter).
+pub struct ArcAppendIter {
+    arc: Arc,
+    idx: usize,
+    n: usize,
+    angle0: f64,
+    angle_step: f64,
+    arm_len: f64,
+    p0: Vec2,
+}
+
+impl Iterator for ArcAppendIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        if self.idx >= self.n {
+            return None;
+        }
+        self.idx += 1;
+        let Arc {
+            center,
+            radii,
+            x_rotation,
+            ..
+        } = self.arc;
+        let angle1 = self.angle0 + self.angle_step;
+        let p1 =
+            self.p0 + self.arm_len * sample_ellipse(radii, x_rotation, self.angle0 + FRAC_PI_2);
+        let p3 = sample_ellipse(radii, x_rotation, angle1);
+        let p2 = p3 - self.arm_len * sample_ellipse(radii, x_rotation, angle1 + FRAC_PI_2);
+        self.angle0 = angle1;
+        self.p0 = p3;
+        Some(PathEl::Curveto(center + p1, center + p2, center + p3))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.n - self.idx;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ParamCurve for Arc {
+    #[inline]
+    fn eval(&self, t: f64) -> Vec2 {
+        let angle = self.start_angle + t * self.sweep_angle;
+        self.center + sample_ellipse(self.radii, self.x_rotation, angle)
+    }
+
+    #[inline]
+    fn subsegment(&self, range: Range<f64>) -> Arc {
+        Arc {
+            start_angle: self.start_angle + range.start * self.sweep_angle,
+            sweep_angle: (range.end - range.start) * self.sweep_angle,
+            ..*self
+        }
+    }
+}
+
+impl ParamCurveArclen for Arc {
+    /// The arc length of the elliptical arc.
+    ///
+    /// This is computed in closed form from the incomplete elliptic integral
+    /// of the second kind, using Carlson's symmetric forms, so the result is
+    /// accurate to full floating point precision regardless of `accuracy`.
+    fn arclen(&self, _accuracy: f64) -> f64 {
+        let (rx, ry) = (self.radii.x.abs(), self.radii.y.abs());
+        let th0 = self.start_angle;
+        let th1 = self.start_angle + self.sweep_angle;
+        // The speed is sqrt(rx² sin²θ + ry² cos²θ); factor out the larger radius
+        // so the parameter of the elliptic integral is in [0, 1].
+        let len = if rx >= ry {
+            if rx == 0.0 {
+                return 0.0;
+            }
+            let m = 1.0 - (ry / rx).powi(2);
+            rx * (elliptic_e(th1 - FRAC_PI_2, m) - elliptic_e(th0 - FRAC_PI_2, m))
+        } else {
+            let m = 1.0 - (rx / ry).powi(2);
+            ry * (elliptic_e(th1, m) - elliptic_e(th0, m))
+        };
+        len.abs()
+    }
+}
+
+impl ParamCurveDeriv for Arc {
+    type DerivResult = Arc;
+
+    /// The derivative of the arc, which is an arc about the origin.
+    fn deriv(&self) -> Arc {
+        Arc {
+            center: Vec2::default(),
+            radii: self.radii * self.sweep_angle,
+            start_angle: self.start_angle + FRAC_PI_2,
+            ..*self
+        }
+    }
+}
+
+impl ParamCurveArea for Arc {
+    /// The signed area under the arc, computed exactly.
+    fn signed_area(&self) -> f64 {
+        let p0 = sample_ellipse(self.radii, self.x_rotation, self.start_angle);
+        let p1 = sample_ellipse(
+            self.radii,
+            self.x_rotation,
+            self.start_angle + self.sweep_angle,
+        );
+        // Relative to the center, the area swept is proportional to the angle.
+        0.5 * (self.center.cross(p1 - p0) + self.radii.x * self.radii.y * self.sweep_angle)
+    }
+}
+
+impl ParamCurveMoments for Arc {
+    /// The contribution of the arc to the area moments of a closed path,
+    /// computed exactly.
+    fn moments(&self) -> Moments {
+        let (s, c) = self.x_rotation.sin_cos();
+        let (rx, ry) = (self.radii.x, self.radii.y);
+        // Each moment is the integral of a trigonometric polynomial in the
+        // angle, of degree at most 4.
+        let x = Trig::linear(self.center.x, rx * c, -ry * s);
+        let y = Trig::linear(self.center.y, rx * s, ry * c);
+        let dx = Trig::linear(0.0, -ry * s, -rx * c);
+        let dy = Trig::linear(0.0, ry * c, -rx * s);
+        let (th0, th1) = (self.start_angle, self.start_angle + self.sweep_angle);
+        let int = |p: Trig| p.integrate(th0, th1);
+        let (x2, y2) = (x.mul(&x), y.mul(&y));
+        Moments {
+            area: 0.5 * (int(x.mul(&dy)) - int(y.mul(&dx))),
+            first: Vec2::new(0.5 * int(x2.mul(&dy)), -0.5 * int(y2.mul(&dx))),
+            xx: int(x2.mul(&x).mul(&dy)) / 3.0,
+            yy: -int(y2.mul(&y).mul(&dx)) / 3.0,
+            xy: 0.25 * (int(x2.mul(&y).mul(&dy)) - int(y2.mul(&x).mul(&dx))),
+        }
+    }
+}
+
+impl ParamCurveCurvature for Arc {}
+
+impl ParamCurveExtrema for Arc {
+    /// The parameters at which the arc is horizontal or vertical.
+    ///
+    /// An arc sweeping more than a full turn has more extrema than can be
+    /// reported, and only the first ones are.
+    fn extrema(&self) -> ArrayVec<[f64; MAX_EXTREMA]> {
+        let (s, c) = self.x_rotation.sin_cos();
+        let (rx, ry) = (self.radii.x, self.radii.y);
+        // Angles at which the derivative of x, then y, vanishes; the others
+        // are at multiples of π from these.
+        let bases = [(-ry * s).atan2(rx * c), (ry * c).atan2(rx * s)];
+        let end = self.start_angle + self.sweep_angle;
+        let (lo, hi) = (self.start_angle.min(end), self.start_angle.max(end));
+        let mut ts = Vec::new();
+        for &base in &bases {
+            let mut angle = base + ((lo - base) / PI).ceil() * PI;
+            while angle < hi {
+                let t = (angle - self.start_angle) / self.sweep_angle;
+                if t > 0.0 && t < 1.0 {
+                    ts.push(t);
+                }
+                angle += PI;
+            }
+        }
+        ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        ts.into_iter().take(MAX_EXTREMA).collect()
+    }
+}
+
+impl ParamCurveNearest for Arc {
+    /// Find the nearest point on the arc.
+    ///
+    /// The nearest point on the full ellipse is found exactly. If it lies
+    /// outside the arc, the arc's interior critical points are found by
+    /// bisection on a fine subdivision, and compared with the endpoints.
+    fn nearest(&self, p: Vec2, _accuracy: f64) -> (f64, f64) {
+        let dist2 = |t: f64| (self.eval(t) - p).hypot2();
+        if self.sweep_angle == 0.0 {
+            return (0.0, dist2(0.0));
+        }
+        let angle = nearest_angle(self.radii, self.x_rotation, p - self.center);
+        if let Some(t) = self.param_of_angle(angle) {
+            return (t, dist2(t));
+        }
+        let mut best = (0.0, dist2(0.0));
+        let r1 = dist2(1.0);
+        if r1 < best.1 {
+            best = (1.0, r1);
+        }
+        // The derivative of the squared distance, up to a constant factor.
+        let deriv = |t: f64| {
+            let th = self.start_angle + t * self.sweep_angle;
+            let d = sample_ellipse(self.radii, self.x_rotation, th + FRAC_PI_2);
+            (self.eval(t) - p).dot(d) * self.sweep_angle
+        };
+        let n = (self.sweep_angle.abs() * (16.0 / PI)).ceil() as usize;
+        let step = 1.0 / n as f64;
+        for i in 0..n {
+            let (mut t0, mut t1) = (i as f64 * step, (i + 1) as f64 * step);
+            let d0 = deriv(t0);
+            // A local minimum is where the derivative goes from negative to positive.
+            if d0 >= 0.0 || deriv(t1) <= 0.0 {
+                continue;
+            }
+            for _ in 0..64 {
+                let tm = 0.5 * (t0 + t1);
+                if deriv(tm) < 0.0 {
+                    t0 = tm;
+                } else {
+                    t1 = tm;
+                }
+            }
+            let t = 0.5 * (t0 + t1);
+            let r = dist2(t);
+            if r < best.1 {
+                best = (t, r);
+            }
+        }
+        best
+    }
+}
+
+impl Shape for Arc {
+    type BezPathIter = Chain<Once<PathEl>, ArcAppendIter>;
+
+    fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+        once(PathEl::Moveto(self.start())).chain(self.append_iter(tolerance))
+    }
+
+    /// Zero, as for a [`Line`](struct.Line.html), since an arc is open.
+    fn area(&self) -> f64 {
+        0.0
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.arclen(accuracy)
+    }
+
+    /// Zero, as for `area`.
+    fn winding(&self, _pt: Vec2) -> i32 {
+        0
+    }
+
+    fn bounding_box(&self) -> Rect {
+        ParamCurveExtrema::bounding_box(self)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let (s, c) = self.x_rotation.sin_cos();
+        // The farthest point on the whole ellipse is where its tangent is
+        // perpendicular to the direction.
+        let w = Vec2::new(
+            self.radii.x * (c * direction.x + s * direction.y),
+            self.radii.y * (c * direction.y - s * direction.x),
+        );
+        if w != Vec2::default() {
+            if let Some(t) = self.param_of_angle(w.atan2()) {
+                return self.eval(t);
+            }
+        }
+        farthest([self.eval(0.0), self.eval(1.0)].iter().cloned(), direction)
+    }
+
+    fn as_arc(&self) -> Option<Arc> {
+        Some(*self)
+    }
+}
+
+/// The greatest distance between a unit circular arc through `angle` and its
+/// cubic approximation.
+fn arc_error(angle: f64) -> f64 {
+    let (s, c) = (0.25 * angle).sin_cos();
+    (4.0 / 27.0) * s.powi(6) / (c * c)
+}
+
+fn sample_ellipse(radii: Vec2, x_rotation: f64, angle: f64) -> Vec2 {
+    let u = radii.x * angle.cos();
+    let v = radii.y * angle.sin();
+    Vec2::new(u, v).rotate(x_rotation)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{
+        Arc, BezPath, CubicBez, Line, Moments, ParamCurve, ParamCurveArclen, ParamCurveArea,
+        ParamCurveCurvature, ParamCurveDeriv, ParamCurveExtrema, ParamCurveMoments,
+        ParamCurveNearest, PathEl, PathSeg, Rect, Vec2,
+    };
+
+    #[test]
+    fn arc_shape() {
+        use crate::Shape;
+        let arc = Arc {
+            center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(2.0, 2.0),
+            start_angle: 0.0,
+            sweep_angle: 0.5 * PI,
+            x_rotation: 0.0,
+        };
+        let path = arc.into_bez_path(1e-9);
+        assert!(matches!(path.elements()[0], PathEl::Moveto(p) if p == Vec2::new(3.0, 2.0)));
+        assert!((arc.perimeter(1e-9) - PI).abs() < 1e-9);
+        let bbox = Shape::bounding_box(&arc);
+        assert!((bbox.x0 - 1.0).abs() < 1e-12 && (bbox.y1 - 4.0).abs() < 1e-12);
+        assert_eq!(arc.area(), 0.0);
+        assert!(arc.as_arc().is_some());
+    }
+
+    #[test]
+    fn arc_arclen() {
+        let arc = Arc {
+            center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.3,
+            sweep_angle: 2.5,
+            x_rotation: 0.7,
+        };
+        let mut path = BezPath::new();
+        path.moveto(arc.eval(0.0));
+        arc.append_to_path(&mut path, 1e-9);
+        let expected = path.segments().map(|s| s.arclen(1e-12)).sum::<f64>();
+        assert!((arc.arclen(1e-9) - expected).abs() < 1e-7);
+        let reversed = Arc {
+            start_angle: 2.8,
+            sweep_angle: -2.5,
+            ..arc
+        };
+        assert!((reversed.arclen(1e-9) - expected).abs() < 1e-7);
+
+        let circle = Arc {
+            radii: Vec2::new(2.0, 2.0),
+            sweep_angle: 3.0 * PI,
+            ..arc
+        };
+        assert!((circle.arclen(1e-9) - 6.0 * PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn arc_moments() {
+        let arc = Arc {
+            center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.3,
+            sweep_angle: -4.5,
+            x_rotation: 0.7,
+        };
+        let m = arc.moments();
+        assert!((m.area - arc.signed_area()).abs() < 1e-12);
+        let cubics = arc
+            .to_cubics(1e-12)
+            .map(|c| c.moments())
+            .fold(Moments::default(), |a, b| a + b);
+        for &(a, b) in &[
+            (m.area, cubics.area),
+            (m.first.x, cubics.first.x),
+            (m.first.y, cubics.first.y),
+            (m.xx, cubics.xx),
+            (m.yy, cubics.yy),
+            (m.xy, cubics.xy),
+        ] {
+            assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+        }
+
+        // A whole ellipse.
+        let ellipse = Arc {
+            center: Vec2::new(-1.0, 3.0),
+            radii: Vec2::new(2.0, 1.0),
+            start_angle: 1.0,
+            sweep_angle: 2.0 * PI,
+            x_rotation: 0.0,
+        };
+        let m = ellipse.moments();
+        assert!((m.area - 2.0 * PI).abs() < 1e-12);
+        let c = m.centroid().unwrap();
+        assert!((c - ellipse.center).hypot() < 1e-12);
+        let (xx, yy, xy) = m.central().unwrap();
+        assert!((xx - 2.0 * PI).abs() < 1e-12);
+        assert!((yy - 0.5 * PI).abs() < 1e-12);
+        assert!(xy.abs() < 1e-12);
+    }
+
+    #[test]
+    fn arc_nearest() {
+        let arc = Arc {
+            center: Vec2::new(0.0, 0.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.5,
+            sweep_angle: -2.0,
+            x_rotation: 0.0,
+        };
+        // The nearest point on the full ellipse is on the other side.
+        for &p in &[
+            Vec2::new(0.0, -5.0),
+            Vec2::new(3.5, 0.2),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(0.5, 0.0),
+        ] {
+            let (t, dist2) = arc.nearest(p, 1e-9);
+            assert!((0.0..=1.0).contains(&t));
+            assert!(((arc.eval(t) - p).hypot2() - dist2).abs() < 1e-12);
+            for i in 0..=1000 {
+                let r = (arc.eval(i as f64 * 1e-3) - p).hypot2();
+                assert!(dist2 <= r + 1e-12, "{:?}: {} > {}", p, dist2, r);
+            }
+        }
+    }
+
+    #[test]
+    fn append_iter() {
+        let arc = Arc {
+            center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.3,
+            sweep_angle: 4.0,
+            x_rotation: 0.5,
+        };
+        let iter = arc.append_iter(1e-3);
+        let n = iter.size_hint().0;
+        let els: Vec<PathEl> = iter.collect();
+        assert_eq!(els.len(), n);
+        match els[n - 1] {
+            PathEl::Curveto(_, _, p3) => assert!((p3 - arc.eval(1.0)).hypot() < 1e-12),
+            _ => panic!("expected curveto"),
+        }
+    }
+
+    #[test]
+    fn arc_to_cubics() {
+        let arc = Arc {
+            center: Vec2::new(1.0, 2.0),
+            radii: Vec2::new(3.0, 1.0),
+            start_angle: 0.3,
+            sweep_angle: 4.0,
+            x_rotation: 0.5,
+        };
+        for &accuracy in &[1e-1, 1e-3, 1e-6, 1e-9] {
+            let cubics: Vec<CubicBez> = arc.to_cubics(accuracy).collect();
+            assert!((cubics[0].p0 - arc.eval(0.0)).hypot() < 1e-12);
+            let n = cubics.len();
+            for (i, c) in cubics.iter().enumerate() {
+                // Measure the error against the nearest point of the arc.
+                for j in 0..=8 {
+                    let p = c.eval(j as f64 / 8.0);
+                    let (_, dist2) = arc.nearest(p, 1e-12);
+                    assert!(dist2.sqrt() <= accuracy, "{} {} {}", accuracy, i, j);
+                }
+            }
+            // One fewer piece would not meet the bound.
+            if n > 1 {
+                let fewer = super::arc_error(arc.sweep_angle / (n - 1) as f64) * 3.0;
+                assert!(fewer > accuracy);
+            }
+        }
+        // A negative sweep goes the other way round.
+        let reversed = Arc {
+            start_angle: 4.3,
+            sweep_angle: -4.0,
+            ..arc
+        };
+        for c in reversed.to_cubics(1e-3) {
+            for j in 0..=8 {
+                let (_, dist2) = arc.nearest(c.eval(j as f64 / 8.0), 1e-12);
+                assert!(dist2.sqrt() <= 1e-3);
+            }
+        }
+        assert_eq!(
+            Arc {
+                sweep_angle: 0.0,
+                ..arc
+            }
+            .to_cubics(1e-3)
+            .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn arc_split() {
+        let arc = Arc::new((1.0, 2.0), (3.0, 1.0), 0.5, -2.0, 0.3);
+        let (a, b) = arc.split_at_angle(-0.5);
+        assert!((a.eval(0.0) - arc.eval(0.0)).hypot() < 1e-12);
+        assert!((a.eval(1.0) - b.eval(0.0)).hypot() < 1e-12);
+        assert!((b.eval(1.0) - arc.eval(1.0)).hypot() < 1e-12);
+        assert!((a.sweep_angle + b.sweep_angle - arc.sweep_angle).abs() < 1e-12);
+        assert!((a.eval(1.0) - arc.eval(0.5)).hypot() < 1e-12);
+
+        let sub = arc.subsegment_by_angle(0.0..-1.0);
+        assert!((sub.eval(0.0) - arc.eval(0.25)).hypot() < 1e-12);
+        assert!((sub.eval(1.0) - arc.eval(0.75)).hypot() < 1e-12);
+
+        // Project a point near the arc and get its angle back.
+        let p = arc.eval(0.3);
+        let angle = arc.nearest_angle(p + 0.01 * (p - arc.center));
+        assert!((angle - (0.5 - 0.6)).abs() < 1e-2);
+        let angle = arc.nearest_angle(p);
+        assert!((angle - (0.5 - 0.6)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn arc_direct() {
+        let arc = Arc::new((1.0, 2.0), (3.0, 1.0), 0.3, 4.0, 0.5);
+        let cubics: Vec<CubicBez> = arc.to_cubics(1e-9).collect();
+
+        let area: f64 = cubics.iter().map(|c| c.signed_area()).sum();
+        assert!((arc.signed_area() - area).abs() < 1e-7);
+
+        let d = arc.deriv();
+        let h = 1e-6;
+        let fd = (arc.eval(0.4 + h) - arc.eval(0.4 - h)) / (2.0 * h);
+        assert!((d.eval(0.4) - fd).hypot() < 1e-6);
+        let circle = Arc::new((0.0, 0.0), (2.0, 2.0), 0.0, 1.0, 0.0);
+        assert!((circle.curvature(0.5) + 0.5).abs() < 1e-12);
+
+        let bbox = arc.bounding_box();
+        let mut expected = Rect::from_points(arc.eval(0.0), arc.eval(1.0));
+        for i in 0..=1000 {
+            expected = expected.union_pt(arc.eval(i as f64 * 1e-3));
+        }
+        assert!((bbox.x0 - expected.x0).abs() < 1e-5 && (bbox.y1 - expected.y1).abs() < 1e-5);
+        assert!(bbox.x1 >= expected.x1 - 1e-12 && bbox.y0 <= expected.y0 + 1e-12);
+
+        let mut path = BezPath::new();
+        path.moveto(arc.eval(0.0));
+        arc.append_to_path(&mut path, 1e-9);
+        let segs: Vec<PathSeg> = path.segments().collect();
+        for &p in &[
+            Vec2::new(1.0, 2.0),
+            Vec2::new(3.5, 2.5),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(0.0, 2.9),
+        ] {
+            let expected: i32 = segs.iter().map(|s| s.winding(p)).sum();
+            assert_eq!(arc.winding_contribution(p), expected, "{:?}", p);
+            assert_eq!(crate::Shape::winding(&arc, p), 0);
+        }
+
+        let line = Line::new((-5.0, 2.0), (5.0, 2.0));
+        let hits = arc.intersect_line(line);
+        assert!(!hits.is_empty());
+        for &(t, u) in &hits {
+            assert!((arc.eval(t) - line.eval(u)).hypot() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! A lookup table between parameter and arclength.
+
+use crate::ParamCurveArclen;
+
+/// A precomputed table of arclengths along a curve.
+///
+/// The table holds the arclength at evenly spaced parameter values, and
+/// interpolates linearly between them. Building it costs `n` arclength
+/// computations; after that, [`arclen`](#method.arclen) and
+/// [`inv_arclen`](#method.inv_arclen) are constant time, as there is also an
+/// index from evenly spaced arclengths into the table. This suits animation,
+/// which moves many times along the same curve at constant speed.
+///
+/// The error of interpolation falls with the square of the spacing, and is
+/// largest where the speed along the curve changes quickly.
+#[derive(Clone, Debug)]
+pub struct ArcLenLut {
+    /// The arclength from the start to parameter `i / n`, for `i` in `0..=n`.
+    lens: Vec<f64>,
+    /// For each of `n` equal divisions of the total arclength, the index of
+    /// the table interval containing its start.
+    index: Vec<usize>,
+}
+
+impl ArcLenLut {
+    /// Build a table with `n` intervals for a curve.
+    ///
+    /// The arclengths in the table are accurate to `accuracy` in total.
+    ///
+    /// Panics if `n` is zero.
+    pub fn new<C: ParamCurveArclen>(c: &C, n: usize, accuracy: f64) -> ArcLenLut {
+        assert!(n > 0, "an arclength table needs at least one interval");
+        let step = (n as f64).recip();
+        let inner = accuracy * step;
+        let mut lens = Vec::with_capacity(n + 1);
+        let mut len = 0.0;
+        lens.push(len);
+        for i in 0..n {
+            let t0 = i as f64 * step;
+            let t1 = if i + 1 == n {
+                1.0
+            } else {
+                (i + 1) as f64 * step
+            };
+            len += c.subsegment(t0..t1).arclen(inner);
+            lens.push(len);
+        }
+        let mut index = Vec::with_capacity(n);
+        let mut j = 0;
+        for k in 0..n {
+            let s = len * k as f64 * step;
+            while j + 1 < n && lens[j + 1] <= s {
+                j += 1;
+            }
+            index.push(j);
+        }
+        ArcLenLut { lens, index }
+    }
+
+    /// The total arclength of the curve.
+    #[inline]
+    pub fn total(&self) -> f64 {
+        self.lens[self.lens.len() - 1]
+    }
+
+    /// The arclength from the start of the curve to parameter `t`.
+    ///
+    /// `t` is clamped to the range 0..1.
+    pub fn arclen(&self, t: f64) -> f64 {
+        let n = self.index.len();
+        let x = t.clamp(0.0, 1.0) * n as f64;
+        let i = (x as usize).min(n - 1);
+        let frac = x - i as f64;
+        self.lens[i] + frac * (self.lens[i + 1] - self.lens[i])
+    }
+
+    /// The parameter at arclength `arclen` from the start of the curve.
+    ///
+    /// `arclen` is clamped to the range from zero to the total arclength.
+    pub fn inv_arclen(&self, arclen: f64) -> f64 {
+        let n = self.index.len();
+        let total = self.total();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        let s = arclen.clamp(0.0, total);
+        let k = ((s / total * n as f64) as usize).min(n - 1);
+        let mut i = self.index[k];
+        while i + 1 < n && self.lens[i + 1] < s {
+            i += 1;
+        }
+        let seg_len = self.lens[i + 1] - self.lens[i];
+        let frac = if seg_len > 0.0 {
+            (s - self.lens[i]) / seg_len
+        } else {
+            0.0
+        };
+        (i as f64 + frac) / n as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArcLenLut, CubicBez, ParamCurve, ParamCurveArclen};
+
+    #[test]
+    fn arclen_lut() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 1.0), (2.0, 1.0), (3.0, -1.0));
+        let lut = ArcLenLut::new(&c, 256, 1e-9);
+        let total = c.arclen(1e-9);
+        assert!((lut.total() - total).abs() < 1e-9);
+        for i in 0..=20 {
+            let s = total * i as f64 / 20.0;
+            let t = lut.inv_arclen(s);
+            assert!((c.subsegment(0.0..t).arclen(1e-9) - s).abs() < 1e-4);
+            assert!((lut.arclen(t) - s).abs() < 1e-9);
+        }
+        assert_eq!(lut.inv_arclen(-1.0), 0.0);
+        assert_eq!(lut.inv_arclen(total + 1.0), 1.0);
+        assert_eq!(lut.arclen(2.0), lut.total());
+    }
+}
@@ -0,0 +1,775 @@
+//! Planar arrangements of filled paths, and dividing paths into faces.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::segment_index::overlaps;
+use crate::{
+    BezPath, CubicBez, FillRule, Line, ParamCurve, ParamCurveArea, ParamCurveNearest, PathSeg,
+    QuadBez, Rect, SegmentIndex, Shape, Vec2,
+};
+
+/// The deepest subdivision when searching for crossings of two segments.
+const MAX_DEPTH: usize = 48;
+
+/// The planar arrangement of the edges of some filled paths.
+///
+/// The segments of the paths are split wherever they meet, and coincident
+/// pieces merged, into edges that only touch at their ends. The edges bound
+/// faces, each with the winding number of every path within it.
+///
+/// Each edge has two half-edges, `2 * e` running along it and `2 * e + 1`
+/// running back, and each half-edge has the face on its left. Bounded faces
+/// are traced counterclockwise, with positive area, and the last face is
+/// the unbounded one.
+pub(crate) struct Arrangement {
+    vertices: Vec<Vec2>,
+    edges: Vec<Edge>,
+    /// The outgoing half-edges of each vertex, in counterclockwise order.
+    around: Vec<Vec<usize>>,
+    /// The position of each half-edge in the list of its origin.
+    pos: Vec<usize>,
+    /// The face on the left of each half-edge.
+    face: Vec<usize>,
+    /// The winding number of each path in each face.
+    windings: Vec<Vec<i32>>,
+}
+
+struct Edge {
+    seg: PathSeg,
+    from: usize,
+    to: usize,
+    /// How many times each path runs along the edge, less the times it
+    /// runs back.
+    winding: Vec<i32>,
+}
+
+impl Arrangement {
+    /// Build the arrangement of some paths, with open subpaths implicitly
+    /// closed, as for filling.
+    pub(crate) fn new(paths: &[BezPath]) -> Arrangement {
+        let pieces = pieces(paths);
+        let scale = pieces
+            .iter()
+            .flat_map(|&(seg, _)| vec![seg.start(), seg.end()])
+            .fold(1.0f64, |m, p| m.max(p.x.abs()).max(p.y.abs()));
+        let eps = 1e-9 * scale;
+        let splits = split_params(&pieces, eps);
+
+        // Cut the pieces at the splits, and merge points closer than eps.
+        let mut cuts = Vec::new();
+        let mut points = Vec::new();
+        for (&(seg, _), ts) in pieces.iter().zip(&splits) {
+            let mut ts: Vec<f64> = ts.iter().cloned().filter(|&t| t > 0.0 && t < 1.0).collect();
+            ts.sort_by(|a, b| a.total_cmp(b));
+            ts.insert(0, 0.0);
+            ts.push(1.0);
+            let start = points.len();
+            points.extend(ts.iter().map(|&t| seg.eval(t)));
+            cuts.push((start, ts));
+        }
+        let (vertices, vertex_of) = merge_points(&points, eps);
+
+        let mut edges: Vec<Edge> = Vec::new();
+        let mut by_ends: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (&(seg, path), (start, ts)) in pieces.iter().zip(&cuts) {
+            for (k, t) in ts.windows(2).enumerate() {
+                let (from, to) = (vertex_of[start + k], vertex_of[start + k + 1]);
+                if from == to {
+                    continue;
+                }
+                let piece = with_ends(seg.subsegment(t[0]..t[1]), vertices[from], vertices[to]);
+                let key = (from.min(to), from.max(to));
+                let same = by_ends.get(&key).and_then(|ixs| {
+                    ixs.iter().cloned().find(|&ix| {
+                        let (_, d2) = edges[ix].seg.nearest(piece.eval(0.5), eps);
+                        d2 <= 100.0 * eps * eps
+                    })
+                });
+                let ix = match same {
+                    Some(ix) => ix,
+                    None => {
+                        by_ends.entry(key).or_default().push(edges.len());
+                        edges.push(Edge {
+                            seg: piece,
+                            from,
+                            to,
+                            winding: vec![0; paths.len()],
+                        });
+                        edges.len() - 1
+                    }
+                };
+                edges[ix].winding[path] += if edges[ix].from == from { 1 } else { -1 };
+            }
+        }
+
+        let mut arrangement = Arrangement {
+            around: vec![Vec::new(); vertices.len()],
+            vertices,
+            pos: vec![0; 2 * edges.len()],
+            face: vec![0; 2 * edges.len()],
+            edges,
+            windings: Vec::new(),
+        };
+        arrangement.sort_around();
+        arrangement.find_faces();
+        arrangement.find_windings(paths.len());
+        arrangement
+    }
+
+    /// The number of faces, including the unbounded one, which is last.
+    pub(crate) fn n_faces(&self) -> usize {
+        self.windings.len()
+    }
+
+    /// The winding numbers of the paths in a face.
+    pub(crate) fn windings(&self, face: usize) -> &[i32] {
+        &self.windings[face]
+    }
+
+    /// The number of half-edges.
+    pub(crate) fn n_half_edges(&self) -> usize {
+        2 * self.edges.len()
+    }
+
+    /// The face on the left of a half-edge.
+    pub(crate) fn face(&self, h: usize) -> usize {
+        self.face[h]
+    }
+
+    /// The outline of a region made of faces, with the region on the left
+    /// of each edge, so outer boundaries have positive area and holes
+    /// negative, and the winding number is one inside and zero outside.
+    ///
+    /// Faces are in the region where `inside` is true.
+    pub(crate) fn boundary(&self, inside: impl Fn(usize) -> bool) -> BezPath {
+        let is_boundary = |h: usize| inside(self.face[h]) && !inside(self.face[h ^ 1]);
+        let mut path = BezPath::new();
+        let mut done = vec![false; self.n_half_edges()];
+        for start in 0..self.n_half_edges() {
+            if done[start] || !is_boundary(start) {
+                continue;
+            }
+            path.moveto(self.seg(start).start());
+            let mut h = start;
+            loop {
+                done[h] = true;
+                // The next boundary half-edge clockwise around the end.
+                let list = &self.around[self.origin(h ^ 1)];
+                let mut k = self.pos[h ^ 1];
+                let next = loop {
+                    k = (k + list.len() - 1) % list.len();
+                    if is_boundary(list[k]) {
+                        break list[k];
+                    }
+                };
+                let closing = next == start || done[next];
+                match self.seg(h) {
+                    PathSeg::Line(_) if closing => (),
+                    seg => path.push(seg.as_path_el()),
+                }
+                if closing {
+                    break;
+                }
+                h = next;
+            }
+            path.closepath();
+        }
+        path
+    }
+
+    fn origin(&self, h: usize) -> usize {
+        let edge = &self.edges[h / 2];
+        if h % 2 == 1 {
+            edge.to
+        } else {
+            edge.from
+        }
+    }
+
+    /// The segment of a half-edge, in its direction.
+    fn seg(&self, h: usize) -> PathSeg {
+        let seg = self.edges[h / 2].seg;
+        if h % 2 == 1 {
+            reverse(seg)
+        } else {
+            seg
+        }
+    }
+
+    /// Sort the half-edges leaving each vertex counterclockwise, by the
+    /// direction they leave in, and then by how sharply they turn left.
+    fn sort_around(&mut self) {
+        let mut keys = Vec::with_capacity(self.n_half_edges());
+        for h in 0..self.n_half_edges() {
+            let seg = self.edges[h / 2].seg;
+            let (tangent, curvature) = if h % 2 == 1 {
+                (-seg.tangent(1.0), -seg.frame(1.0).curvature)
+            } else {
+                (seg.tangent(0.0), seg.frame(0.0).curvature)
+            };
+            keys.push((tangent.atan2(), curvature));
+            let origin = self.origin(h);
+            self.around[origin].push(h);
+        }
+        for list in &mut self.around {
+            list.sort_by(|&a, &b| keys[a].0.total_cmp(&keys[b].0));
+            // Runs of equal directions are ordered by curvature.
+            let mut i = 0;
+            while i < list.len() {
+                let mut j = i + 1;
+                while j < list.len() && keys[list[j]].0 - keys[list[j - 1]].0 < 1e-9 {
+                    j += 1;
+                }
+                list[i..j].sort_by(|&a, &b| keys[a].1.total_cmp(&keys[b].1));
+                i = j;
+            }
+            for (k, &h) in list.iter().enumerate() {
+                self.pos[h] = k;
+            }
+        }
+    }
+
+    /// The half-edge after `h` around the face on its left.
+    fn next(&self, h: usize) -> usize {
+        let list = &self.around[self.origin(h ^ 1)];
+        list[(self.pos[h ^ 1] + list.len() - 1) % list.len()]
+    }
+
+    /// Trace the cycles of half-edges, and assign them to faces. Cycles with
+    /// negative area are the outer boundaries of connected parts of the
+    /// arrangement, and belong to the face they are in.
+    fn find_faces(&mut self) {
+        let mut parent: Vec<usize> = (0..self.vertices.len()).collect();
+        for edge in &self.edges {
+            union(&mut parent, edge.from, edge.to);
+        }
+        let mut done = vec![false; self.n_half_edges()];
+        let mut faces: Vec<(Vec<usize>, f64, BezPath)> = Vec::new();
+        let mut outers = Vec::new();
+        for start in 0..self.n_half_edges() {
+            if done[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut h = start;
+            while !done[h] {
+                done[h] = true;
+                cycle.push(h);
+                h = self.next(h);
+            }
+            let area: f64 = cycle.iter().map(|&h| self.seg(h).signed_area()).sum();
+            if area > 0.0 {
+                let mut path = BezPath::new();
+                path.moveto(self.seg(cycle[0]).start());
+                for &h in &cycle {
+                    path.push(self.seg(h).as_path_el());
+                }
+                path.closepath();
+                for &h in &cycle {
+                    self.face[h] = faces.len();
+                }
+                faces.push((cycle, area, path));
+            } else {
+                outers.push(cycle);
+            }
+        }
+        let unbounded = faces.len();
+        for cycle in outers {
+            let v = self.origin(cycle[0]);
+            let part = find(&mut parent, v);
+            let mut best = (unbounded, f64::INFINITY);
+            for (f, (face_cycle, area, path)) in faces.iter().enumerate() {
+                let other = self.origin(face_cycle[0]);
+                if *area < best.1
+                    && find(&mut parent, other) != part
+                    && path.winding(self.vertices[v]) != 0
+                {
+                    best = (f, *area);
+                }
+            }
+            for &h in &cycle {
+                self.face[h] = best.0;
+            }
+        }
+        self.windings = vec![Vec::new(); unbounded + 1];
+    }
+
+    /// Find the winding numbers of the faces, working inwards from the
+    /// unbounded face, adding the windings of the edges crossed.
+    fn find_windings(&mut self, n_paths: usize) {
+        let n_faces = self.windings.len();
+        let mut neighbors = vec![Vec::new(); n_faces];
+        for h in 0..self.n_half_edges() {
+            neighbors[self.face[h ^ 1]].push(h);
+        }
+        let mut windings: Vec<Option<Vec<i32>>> = vec![None; n_faces];
+        windings[n_faces - 1] = Some(vec![0; n_paths]);
+        let mut queue = VecDeque::new();
+        queue.push_back(n_faces - 1);
+        while let Some(f) = queue.pop_front() {
+            for &h in &neighbors[f] {
+                let g = self.face[h];
+                if windings[g].is_none() {
+                    let sign = if h % 2 == 0 { 1 } else { -1 };
+                    let w = windings[f]
+                        .as_ref()
+                        .unwrap()
+                        .iter()
+                        .zip(&self.edges[h / 2].winding)
+                        .map(|(a, b)| a + sign * b)
+                        .collect();
+                    windings[g] = Some(w);
+                    queue.push_back(g);
+                }
+            }
+        }
+        self.windings = windings
+            .into_iter()
+            .map(|w| w.unwrap_or_else(|| vec![0; n_paths]))
+            .collect();
+    }
+}
+
+impl BezPath {
+    /// The outline of the region filled by the path, without overlaps.
+    ///
+    /// The path is cut where it crosses itself, and contours are joined or
+    /// dropped, so the result fills the same region as the path does under
+    /// `fill_rule`, but never crosses itself, and has a winding number of
+    /// zero outside and one (or minus one, by the sign convention of
+    /// [`winding`](trait.Shape.html#tymethod.winding)) inside. This is the
+    /// "remove overlap" operation of font editors. Outer boundaries have
+    /// positive area and holes negative. Curves are kept, split where they
+    /// cross. Subpaths with non-finite coordinates are dropped.
+    pub fn remove_overlaps(&self, fill_rule: FillRule) -> BezPath {
+        let arrangement = Arrangement::new(std::slice::from_ref(self));
+        arrangement.boundary(|f| fill_rule.is_inside(arrangement.windings(f)[0]))
+    }
+
+    /// Rewrite a path filled with one fill rule into a path filling the
+    /// same region under another.
+    ///
+    /// Where the rules differ, this is
+    /// [`remove_overlaps`](#method.remove_overlaps) with the rule `from`, as
+    /// a path without overlaps fills alike under either rule. When the rules
+    /// are the same, the path is returned as it is.
+    pub fn convert_fill_rule(&self, from: FillRule, to: FillRule) -> BezPath {
+        if from == to {
+            return self.clone();
+        }
+        self.remove_overlaps(from)
+    }
+}
+
+/// Divide overlapping filled paths into faces that don't overlap, as the
+/// "divide" operation of a drawing application does.
+///
+/// Each path is filled with the nonzero fill rule, with open subpaths
+/// implicitly closed. The plane is cut along the outlines of the paths, and
+/// the result has a path for each region covered by a different set of the
+/// input paths, leaving out the regions covered by none. A region with
+/// holes has a subpath for each. The outer boundaries have positive area,
+/// and the holes negative, so each face fills alike under either fill
+/// rule.
+///
+/// Curves are kept, split where they cross; points closer together than
+/// about a billionth of the size of the coordinates are merged. Subpaths
+/// with non-finite coordinates are ignored.
+pub fn planarize(paths: &[BezPath]) -> Vec<BezPath> {
+    let arrangement = Arrangement::new(paths);
+    let cover =
+        |f: usize| -> Vec<bool> { arrangement.windings(f).iter().map(|&w| w != 0).collect() };
+    // Merge faces covered by the same paths across edges between them.
+    let n_faces = arrangement.n_faces();
+    let mut parent: Vec<usize> = (0..n_faces).collect();
+    for h in (0..arrangement.n_half_edges()).step_by(2) {
+        let (f, g) = (arrangement.face(h), arrangement.face(h + 1));
+        if cover(f) == cover(g) {
+            union(&mut parent, f, g);
+        }
+    }
+    let region: Vec<usize> = (0..n_faces).map(|f| find(&mut parent, f)).collect();
+    let mut result = Vec::new();
+    let mut done = vec![region[n_faces - 1]];
+    for f in 0..n_faces - 1 {
+        if done.contains(&region[f]) || !cover(f).contains(&true) {
+            continue;
+        }
+        done.push(region[f]);
+        result.push(arrangement.boundary(|g| region[g] == region[f]));
+    }
+    result
+}
+
+/// The pieces of the segments of the paths, each monotonic in x and y and
+/// of nonzero length, with the index of its path. Subpaths with non-finite
+/// coordinates are skipped.
+fn pieces(paths: &[BezPath]) -> Vec<(PathSeg, usize)> {
+    let mut result = Vec::new();
+    for (i, path) in paths.iter().enumerate() {
+        for sub in path.subpath_segs() {
+            let segs: Vec<PathSeg> = sub.segs.iter().map(|s| s.1).collect();
+            let (start, end) = match (segs.first(), segs.last()) {
+                (Some(first), Some(last)) => (first.start(), last.end()),
+                _ => continue,
+            };
+            if !segs.iter().all(|&seg| is_finite(seg)) {
+                continue;
+            }
+            let closing = if start != end {
+                Some(PathSeg::Line(Line::new(end, start)))
+            } else {
+                None
+            };
+            for seg in segs.into_iter().chain(closing) {
+                for piece in seg.split_monotonic() {
+                    if piece.start() != piece.end() {
+                        result.push((piece, i));
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// The parameters at which each piece should be split, where others cross
+/// or touch it.
+fn split_params(pieces: &[(PathSeg, usize)], eps: f64) -> Vec<Vec<f64>> {
+    let mut all = BezPath::new();
+    for &(seg, _) in pieces {
+        all.moveto(seg.start());
+        all.push(seg.as_path_el());
+    }
+    let index = SegmentIndex::new(&all);
+    let mut splits = vec![Vec::new(); pieces.len()];
+    for (i, &(a, _)) in pieces.iter().enumerate() {
+        for j in index.query_rect(bbox(a).inflate(eps, eps)) {
+            if j <= i {
+                continue;
+            }
+            let b = pieces[j].0;
+            let (mut ta, mut tb) = (Vec::new(), Vec::new());
+            // Ends of one on the other, at T junctions and overlaps.
+            for &t in &[0.0, 1.0] {
+                let (u, d2) = b.nearest(a.eval(t), eps);
+                if d2 <= eps * eps {
+                    tb.push(u);
+                }
+                let (u, d2) = a.nearest(b.eval(t), eps);
+                if d2 <= eps * eps {
+                    ta.push(u);
+                }
+            }
+            if !coincident(a, b, &ta, eps) {
+                let mut found = Vec::new();
+                crossings(a, (0.0, 1.0), b, (0.0, 1.0), eps, 0, &mut found);
+                for (s, u) in found {
+                    ta.push(s);
+                    tb.push(u);
+                }
+            }
+            splits[i].extend(ta);
+            splits[j].extend(tb);
+        }
+    }
+    splits
+}
+
+/// Whether two pieces run along each other between some of the points
+/// where one touches the other, given by their parameters on `a`.
+fn coincident(a: PathSeg, b: PathSeg, ts: &[f64], eps: f64) -> bool {
+    let mut ts = ts.to_vec();
+    for &t in &[0.0, 1.0] {
+        if b.nearest(a.eval(t), eps).1 <= eps * eps {
+            ts.push(t);
+        }
+    }
+    ts.sort_by(|x, y| x.total_cmp(y));
+    ts.windows(2).any(|w| {
+        w[1] - w[0] > 1e-9 && b.nearest(a.eval(0.5 * (w[0] + w[1])), eps).1 <= 4.0 * eps * eps
+    })
+}
+
+/// Find where two pieces cross, by subdividing them until they are flat
+/// and crossing their chords. The parameters found are within the ranges
+/// of the original pieces given.
+fn crossings(
+    a: PathSeg,
+    ra: (f64, f64),
+    b: PathSeg,
+    rb: (f64, f64),
+    eps: f64,
+    depth: usize,
+    out: &mut Vec<(f64, f64)>,
+) {
+    let (box_a, box_b) = (bbox(a), bbox(b));
+    if !overlaps(&box_a.inflate(eps, eps), &box_b) {
+        return;
+    }
+    let (flat_a, flat_b) = (is_flat(a, eps), is_flat(b, eps));
+    if (flat_a && flat_b) || depth >= MAX_DEPTH {
+        let (d, e) = (a.end() - a.start(), b.end() - b.start());
+        let denom = d.cross(e);
+        if denom != 0.0 {
+            let w = b.start() - a.start();
+            let (s, u) = (w.cross(e) / denom, w.cross(d) / denom);
+            let slack = 1e-9;
+            if s >= -slack && s <= 1.0 + slack && u >= -slack && u <= 1.0 + slack {
+                let (s, u) = (s.clamp(0.0, 1.0), u.clamp(0.0, 1.0));
+                out.push((ra.0 + s * (ra.1 - ra.0), rb.0 + u * (rb.1 - rb.0)));
+            }
+        }
+        return;
+    }
+    let size = |r: Rect| r.width().max(r.height());
+    if !flat_a && (flat_b || size(box_a) >= size(box_b)) {
+        let mid = 0.5 * (ra.0 + ra.1);
+        let (a0, a1) = (a.subsegment(0.0..0.5), a.subsegment(0.5..1.0));
+        crossings(a0, (ra.0, mid), b, rb, eps, depth + 1, out);
+        crossings(a1, (mid, ra.1), b, rb, eps, depth + 1, out);
+    } else {
+        let mid = 0.5 * (rb.0 + rb.1);
+        let (b0, b1) = (b.subsegment(0.0..0.5), b.subsegment(0.5..1.0));
+        crossings(a, ra, b0, (rb.0, mid), eps, depth + 1, out);
+        crossings(a, ra, b1, (mid, rb.1), eps, depth + 1, out);
+    }
+}
+
+/// Whether all the points of a segment are finite.
+fn is_finite(seg: PathSeg) -> bool {
+    let ok = |p: Vec2| p.x.is_finite() && p.y.is_finite();
+    match seg {
+        PathSeg::Line(l) => ok(l.p0) && ok(l.p1),
+        PathSeg::Quad(q) => ok(q.p0) && ok(q.p1) && ok(q.p2),
+        PathSeg::Cubic(c) => ok(c.p0) && ok(c.p1) && ok(c.p2) && ok(c.p3),
+    }
+}
+
+/// The bounding box of a piece, which is monotonic.
+fn bbox(seg: PathSeg) -> Rect {
+    Rect::from_points(seg.start(), seg.end())
+}
+
+/// Whether the control points of a segment are within `eps` of its chord.
+fn is_flat(seg: PathSeg, eps: f64) -> bool {
+    let (p0, p1) = (seg.start(), seg.end());
+    let chord = p1 - p0;
+    let len = chord.hypot();
+    let dist = |p: Vec2| {
+        if len > 0.0 {
+            (p - p0).cross(chord).abs() / len
+        } else {
+            (p - p0).hypot()
+        }
+    };
+    match seg {
+        PathSeg::Line(_) => true,
+        PathSeg::Quad(q) => dist(q.p1) <= eps,
+        PathSeg::Cubic(c) => dist(c.p1) <= eps && dist(c.p2) <= eps,
+    }
+}
+
+/// Merge points closer together than `eps`, returning the merged points
+/// and the index of the merged point for each one.
+fn merge_points(points: &[Vec2], eps: f64) -> (Vec<Vec2>, Vec<usize>) {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| points[a].x.total_cmp(&points[b].x));
+    let mut parent: Vec<usize> = (0..points.len()).collect();
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if points[j].x - points[i].x > eps {
+                break;
+            }
+            if (points[j] - points[i]).hypot() <= eps {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+    let mut merged = Vec::new();
+    let mut index_of_root = HashMap::new();
+    let mut vertex_of = Vec::with_capacity(points.len());
+    for i in 0..points.len() {
+        let root = find(&mut parent, i);
+        let v = *index_of_root.entry(root).or_insert_with(|| {
+            merged.push(points[root]);
+            merged.len() - 1
+        });
+        vertex_of.push(v);
+    }
+    (merged, vertex_of)
+}
+
+/// A segment with its ends moved.
+fn with_ends(seg: PathSeg, p0: Vec2, p1: Vec2) -> PathSeg {
+    match seg {
+        PathSeg::Line(_) => PathSeg::Line(Line::new(p0, p1)),
+        PathSeg::Quad(q) => PathSeg::Quad(QuadBez::new(p0, q.p1, p1)),
+        PathSeg::Cubic(c) => PathSeg::Cubic(CubicBez::new(p0, c.p1, c.p2, p1)),
+    }
+}
+
+/// A segment running the other way.
+fn reverse(seg: PathSeg) -> PathSeg {
+    match seg {
+        PathSeg::Line(l) => PathSeg::Line(Line::new(l.p1, l.p0)),
+        PathSeg::Quad(q) => PathSeg::Quad(QuadBez::new(q.p2, q.p1, q.p0)),
+        PathSeg::Cubic(c) => PathSeg::Cubic(CubicBez::new(c.p3, c.p2, c.p1, c.p0)),
+    }
+}
+
+/// The root of a set in a disjoint-set forest.
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Join the sets of two elements of a disjoint-set forest.
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[rb] = ra;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{planarize, BezPath, Circle, FillRule, ParamCurve, PathEl, Rect, Shape, Vec2};
+
+    fn areas(paths: &[BezPath]) -> Vec<f64> {
+        let mut areas: Vec<f64> = paths.iter().map(|p| p.area()).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        areas
+    }
+
+    #[test]
+    fn planarize_paths() {
+        // Two overlapping squares give three faces.
+        let a = BezPath::from_svg("M0 0L2 0L2 2L0 2Z").unwrap();
+        let b = BezPath::from_svg("M1 1L3 1L3 3L1 3Z").unwrap();
+        let faces = planarize(&[a.clone(), b]);
+        assert_eq!(areas(&faces), vec![1.0, 3.0, 3.0]);
+        let overlap = faces.iter().find(|f| f.area() == 1.0).unwrap();
+        assert!(overlap.winding((1.5, 1.5).into()) != 0);
+        assert!(overlap.winding((0.5, 0.5).into()) == 0);
+
+        // Squares sharing an edge stay apart, and a square inside another
+        // makes a hole.
+        let c = BezPath::from_svg("M2 0L4 0L4 2L2 2Z").unwrap();
+        assert_eq!(areas(&planarize(&[a.clone(), c])), vec![4.0, 4.0]);
+        let big = BezPath::from_svg("M-1 -1L5 -1L5 5L-1 5Z").unwrap();
+        let faces = planarize(&[big, a.clone()]);
+        assert_eq!(areas(&faces), vec![4.0, 32.0]);
+        let ring = faces.iter().find(|f| f.area() == 32.0).unwrap();
+        assert!(ring.winding((1.0, 1.0).into()) == 0);
+        assert!(ring.winding((4.0, 4.0).into()) != 0);
+
+        // A circle across the edge of a rectangle, with curves kept.
+        let circle = Circle::new((0.0, 0.0), 1.0).into_bez_path(1e-9);
+        let rect = Rect::new(0.0, -2.0, 2.0, 2.0).into_bez_path(1e-9);
+        let faces = planarize(&[circle.clone(), rect]);
+        let half = 0.5 * circle.area();
+        let expected = [half, half, 8.0 - half];
+        for (area, expected) in areas(&faces).iter().zip(&expected) {
+            assert!((area - expected).abs() < 1e-9, "{} {}", area, expected);
+        }
+        assert!((half - 0.5 * PI).abs() < 1e-3);
+    }
+
+    #[test]
+    fn convert_fill_rule() {
+        let inside = |path: &BezPath, rule: FillRule, x: f64, y: f64| {
+            rule.is_inside(path.winding(Vec2::new(x, y)))
+        };
+        // A pentagram, with a hole in the middle under the even-odd rule.
+        let mut star = BezPath::new();
+        for i in 0..5 {
+            let p = 2.0 * Vec2::from_angle(0.5 * PI + 0.8 * PI * i as f64);
+            if i == 0 {
+                star.moveto(p);
+            } else {
+                star.lineto(p);
+            }
+        }
+        star.closepath();
+        let even_odd = star.convert_fill_rule(FillRule::EvenOdd, FillRule::NonZero);
+        assert!(!inside(&even_odd, FillRule::NonZero, 0.0, 0.0));
+        assert!(inside(&even_odd, FillRule::NonZero, 0.0, 1.5));
+        // The five points, touching at their corners.
+        assert_eq!(even_odd.elements().len(), 5 * 4);
+        let nonzero = star.convert_fill_rule(FillRule::NonZero, FillRule::EvenOdd);
+        assert!(inside(&nonzero, FillRule::EvenOdd, 0.0, 0.0));
+        assert!(inside(&nonzero, FillRule::EvenOdd, 0.0, 1.5));
+        assert!(!inside(&nonzero, FillRule::EvenOdd, 1.5, 1.5));
+        assert!(nonzero.area() > 0.0);
+
+        // Overlapping subpaths turning the same way.
+        let squares = BezPath::from_svg("M0 0L2 0L2 2L0 2ZM1 1L3 1L3 3L1 3Z").unwrap();
+        let converted = squares.convert_fill_rule(FillRule::NonZero, FillRule::EvenOdd);
+        assert_eq!(converted.area(), 7.0);
+        assert!(inside(&converted, FillRule::EvenOdd, 1.5, 1.5));
+        let converted = squares.convert_fill_rule(FillRule::EvenOdd, FillRule::NonZero);
+        assert_eq!(converted.area(), 6.0);
+        assert!(!inside(&converted, FillRule::NonZero, 1.5, 1.5));
+        assert_eq!(
+            squares
+                .convert_fill_rule(FillRule::NonZero, FillRule::NonZero)
+                .to_svg(),
+            squares.to_svg()
+        );
+    }
+
+    #[test]
+    fn remove_overlaps() {
+        // A ring, with a bar across it.
+        let mut glyph = Circle::new((0.0, 0.0), 2.0).into_bez_path(1e-9);
+        let hole = Circle::new((0.0, 0.0), 1.0).into_bez_path(1e-9);
+        let segs: Vec<_> = hole.segments().collect();
+        glyph.moveto(segs[segs.len() - 1].end());
+        for seg in segs.iter().rev() {
+            glyph.push(super::reverse(*seg).as_path_el());
+        }
+        glyph.closepath();
+        for el in BezPath::from_svg("M-3 -0.5L3 -0.5L3 0.5L-3 0.5Z")
+            .unwrap()
+            .elements()
+        {
+            glyph.push(*el);
+        }
+        let clean = glyph.remove_overlaps(FillRule::NonZero);
+        // The outline, and the two halves of the hole.
+        let contours = clean
+            .elements()
+            .iter()
+            .filter(|el| matches!(el, PathEl::Moveto(_)));
+        assert_eq!(contours.count(), 3);
+        // The area of the ring, and the bar beyond it.
+        let strip = |r: f64| 2.0 * (0.5 * (r * r - 0.25f64).sqrt() + r * r * (0.5 / r).asin());
+        let expected = 3.0 * PI + 6.0 - (strip(2.0) - strip(1.0));
+        assert!((clean.area() - expected).abs() < 1e-6, "{}", clean.area());
+        for &(x, y) in &[(0.0, 0.0), (0.0, 1.5), (2.5, 0.0), (0.0, 0.7), (2.5, 1.0)] {
+            let (before, after) = (
+                glyph.winding(Vec2::new(x, y)),
+                clean.winding(Vec2::new(x, y)),
+            );
+            assert_eq!(before != 0, after != 0, "{} {}", x, y);
+            assert!(after.abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn non_finite() {
+        let mut path = BezPath::from_svg("M0 0L2 0L2 2L0 2Z").unwrap();
+        path.moveto((1.0, 1.0));
+        path.lineto((f64::NAN, 3.0));
+        path.lineto((3.0, f64::INFINITY));
+        path.closepath();
+        assert_eq!(path.remove_overlaps(FillRule::NonZero).area(), 4.0);
+        assert_eq!(areas(&planarize(&[path])), vec![4.0]);
+    }
+}
@@ -0,0 +1,270 @@
+//! Arrows for diagrams.
+
+use crate::{
+    BezPath, Cap, Circle, Join, ParamCurve, ParamCurveArclen, PathEl, PathSeg, Shape, Stroke, Vec2,
+};
+
+/// The accuracy of arclengths along the shaft.
+const ACCURACY: f64 = 1e-9;
+
+/// The shape of the head or tail of an [`Arrow`](struct.Arrow.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArrowHead {
+    /// A filled triangle.
+    Triangle,
+    /// A filled triangle with a notch in its back.
+    Stealth,
+    /// Two lines meeting at the tip, drawn like the shaft.
+    Open,
+    /// A filled circle touching the tip.
+    Circle,
+}
+
+/// An arrow, with a shaft along a path and a head at its end.
+///
+/// The arrow can be drawn by filling its [`outline`](#method.outline), or
+/// by stroking its [`centerline`](#method.centerline) with the width of
+/// the shaft and filling the closed heads. In both, the shaft is cut back
+/// where it would show through or beyond a head, so the tip is exactly at
+/// the end of the path.
+#[derive(Clone, Debug)]
+pub struct Arrow {
+    shaft: BezPath,
+    width: f64,
+    head: Option<(ArrowHead, f64, f64)>,
+    tail: Option<(ArrowHead, f64, f64)>,
+}
+
+impl Arrow {
+    /// An arrow along a path, with a shaft of width 1 and a triangular head
+    /// 6 long and 5 wide.
+    ///
+    /// The path should have a single open subpath.
+    pub fn new(shaft: BezPath) -> Arrow {
+        Arrow {
+            shaft,
+            width: 1.0,
+            head: Some((ArrowHead::Triangle, 6.0, 5.0)),
+            tail: None,
+        }
+    }
+
+    /// A straight arrow between two points.
+    pub fn line<V: Into<Vec2>>(from: V, to: V) -> Arrow {
+        let mut shaft = BezPath::new();
+        shaft.moveto(from);
+        shaft.lineto(to);
+        Arrow::new(shaft)
+    }
+
+    /// Builder method for setting the width of the shaft.
+    pub fn with_width(mut self, width: f64) -> Arrow {
+        self.width = width;
+        self
+    }
+
+    /// Builder method for setting the head, with its length along the shaft
+    /// and its width across it.
+    ///
+    /// For a circle, the width is the diameter, and the length is unused.
+    pub fn with_head(mut self, head: ArrowHead, length: f64, width: f64) -> Arrow {
+        self.head = Some((head, length, width));
+        self
+    }
+
+    /// Builder method for removing the head.
+    pub fn without_head(mut self) -> Arrow {
+        self.head = None;
+        self
+    }
+
+    /// Builder method for setting a tail at the start of the path, pointing
+    /// backwards, as for the head.
+    pub fn with_tail(mut self, tail: ArrowHead, length: f64, width: f64) -> Arrow {
+        self.tail = Some((tail, length, width));
+        self
+    }
+
+    /// The outline of the arrow, to be filled with the nonzero fill rule.
+    ///
+    /// The shaft is stroked with butt caps, and open heads with miter
+    /// joins, with `tolerance` as for [`stroke`](struct.BezPath.html#method.stroke).
+    pub fn outline(&self, tolerance: f64) -> BezPath {
+        let mut result = self
+            .cut_shaft(true)
+            .stroke(&Stroke::new(self.width), tolerance);
+        for (tip, dir, head) in self.ends() {
+            let (kind, length, width) = head;
+            let pts = head_points(tip, dir, kind, length, width);
+            match kind {
+                ArrowHead::Open => {
+                    let style = Stroke::new(self.width)
+                        .with_join(Join::Miter)
+                        .with_miter_limit(100.0)
+                        .with_caps(Cap::Butt);
+                    let path = polyline(&pts, false).stroke(&style, tolerance);
+                    append(&mut result, &path);
+                }
+                ArrowHead::Circle => {
+                    let circle = Circle::new(tip - 0.5 * width * dir, 0.5 * width);
+                    push_oriented(&mut result, circle.into_bez_path(tolerance));
+                }
+                _ => push_oriented(&mut result, polyline(&pts, true)),
+            }
+        }
+        result
+    }
+
+    /// The centerline of the shaft, followed by the heads.
+    ///
+    /// Filled heads are closed subpaths, to be both stroked and filled, and
+    /// open heads are open subpaths, to be stroked like the shaft. Circles
+    /// are approximated by cubic Béziers.
+    pub fn centerline(&self) -> BezPath {
+        let mut result = self.cut_shaft(false);
+        for (tip, dir, head) in self.ends() {
+            let (kind, length, width) = head;
+            let path = match kind {
+                ArrowHead::Circle => {
+                    let circle = Circle::new(tip - 0.5 * width * dir, 0.5 * width);
+                    circle.into_bez_path(1e-9)
+                }
+                ArrowHead::Open => polyline(&head_points(tip, dir, kind, length, width), false),
+                _ => polyline(&head_points(tip, dir, kind, length, width), true),
+            };
+            append(&mut result, &path);
+        }
+        result
+    }
+
+    /// The tip, the direction it points in, and the shape of each end
+    /// with a head.
+    fn ends(&self) -> Vec<(Vec2, Vec2, (ArrowHead, f64, f64))> {
+        let segs: Vec<_> = self.shaft.segments().collect();
+        let mut result = Vec::new();
+        if let (Some(head), Some(last)) = (self.head, segs.last()) {
+            result.push((last.end(), last.tangent(1.0), head));
+        }
+        if let (Some(tail), Some(first)) = (self.tail, segs.first()) {
+            result.push((first.start(), -first.tangent(0.0), tail));
+        }
+        result
+    }
+
+    /// The shaft, cut back at each end with a head. For a filled outline,
+    /// the shaft goes some way into filled heads, so they overlap.
+    fn cut_shaft(&self, overlap: bool) -> BezPath {
+        let cut = |head: Option<(ArrowHead, f64, f64)>| match head {
+            None | Some((ArrowHead::Open, _, _)) => 0.0,
+            Some((ArrowHead::Circle, _, width)) => 0.5 * width,
+            Some((_, length, _)) if overlap => 0.5 * length,
+            Some((ArrowHead::Stealth, length, _)) => STEALTH_NOTCH * length,
+            Some((_, length, _)) => length,
+        };
+        let total: f64 = self.shaft.segments().map(|s| s.arclen(ACCURACY)).sum();
+        let (start, end) = (cut(self.tail), cut(self.head));
+        if start + end >= total {
+            return BezPath::new();
+        }
+        self.shaft.trim(start / total, 1.0 - end / total, ACCURACY)
+    }
+}
+
+/// How far back from the tip the notch of a stealth head is, as a fraction
+/// of its length.
+const STEALTH_NOTCH: f64 = 0.7;
+
+/// The points of a head with its tip at `tip`, pointing in direction `dir`.
+fn head_points(tip: Vec2, dir: Vec2, kind: ArrowHead, length: f64, width: f64) -> Vec<Vec2> {
+    let n = Vec2::new(-dir.y, dir.x);
+    let back = tip - length * dir;
+    let (left, right) = (back + 0.5 * width * n, back - 0.5 * width * n);
+    match kind {
+        ArrowHead::Stealth => vec![tip, left, tip - STEALTH_NOTCH * length * dir, right],
+        ArrowHead::Open => vec![left, tip, right],
+        _ => vec![tip, left, right],
+    }
+}
+
+/// A path through points, closed or not.
+fn polyline(pts: &[Vec2], closed: bool) -> BezPath {
+    let mut path = BezPath::new();
+    path.moveto(pts[0]);
+    for &p in &pts[1..] {
+        path.lineto(p);
+    }
+    if closed {
+        path.closepath();
+    }
+    path
+}
+
+/// Add the elements of one path to another.
+fn append(result: &mut BezPath, path: &BezPath) {
+    for &el in path.elements() {
+        result.push(el);
+    }
+}
+
+/// Add a closed shape to an outline, with the same orientation as the
+/// outlines of strokes, so the union fills without holes.
+fn push_oriented(result: &mut BezPath, shape: BezPath) {
+    if shape.area() <= 0.0 {
+        append(result, &shape);
+        return;
+    }
+    // Reverse the shape, which has a single closed subpath.
+    let segs: Vec<_> = shape.segments().collect();
+    result.push(PathEl::Moveto(segs[segs.len() - 1].end()));
+    for seg in segs.iter().rev() {
+        result.push(match *seg {
+            PathSeg::Line(l) => PathEl::Lineto(l.p0),
+            PathSeg::Quad(q) => PathEl::Quadto(q.p1, q.p0),
+            PathSeg::Cubic(c) => PathEl::Curveto(c.p2, c.p1, c.p0),
+        });
+    }
+    result.push(PathEl::Closepath);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arrow, ArrowHead, BezPath, PathEl, Shape};
+
+    #[test]
+    fn arrow() {
+        let arrow = Arrow::line((0.0, 0.0), (20.0, 0.0)).with_width(2.0);
+        let outline = arrow.outline(1e-3);
+        // The shaft and a triangle 6 long and 5 wide, overlapping by 3.
+        let expected = 2.0 * 17.0 + 0.5 * 6.0 * 5.0;
+        assert!((outline.area().abs() - expected).abs() < 1e-6);
+        assert!(outline.winding((19.5, 0.0).into()) != 0);
+        assert!(outline.winding((15.0, 2.0).into()) != 0);
+        assert!(outline.winding((20.5, 0.0).into()) == 0);
+        // The shaft stops at the base of the head.
+        let centerline = arrow.centerline();
+        assert!(matches!(centerline.elements()[1], PathEl::Lineto(p) if (p.x - 14.0).abs() < 1e-9));
+
+        // A curved shaft, other heads and a tail.
+        let shaft = BezPath::from_svg("M0 0C10 0 20 10 20 20").unwrap();
+        for &head in &[
+            ArrowHead::Triangle,
+            ArrowHead::Stealth,
+            ArrowHead::Open,
+            ArrowHead::Circle,
+        ] {
+            let arrow = Arrow::new(shaft.clone())
+                .with_head(head, 4.0, 4.0)
+                .with_tail(head, 4.0, 4.0);
+            let outline = arrow.outline(1e-3);
+            // Filled, without holes where pieces overlap.
+            assert!(outline.winding((20.0, 19.0).into()) != 0, "{:?}", head);
+            assert!(outline.winding((0.5, 0.0).into()) != 0, "{:?}", head);
+            assert!(outline.winding((10.0, 10.0).into()) == 0, "{:?}", head);
+        }
+        let open = Arrow::new(shaft)
+            .with_head(ArrowHead::Open, 4.0, 4.0)
+            .outline(1e-3);
+        // An open head is two lines, with a gap between them.
+        assert!(open.winding((20.7, 17.0).into()) == 0);
+    }
+}
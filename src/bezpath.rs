@@ -7,13 +7,38 @@ use arrayvec::ArrayVec;
 use crate::common::{solve_cubic, solve_quadratic};
 use crate::MAX_EXTREMA;
 use crate::{
-    Affine, CubicBez, Line, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveExtrema,
-    ParamCurveNearest, QuadBez, Rect, Shape, Vec2,
+    Affine, CubicBez, Line, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveDeriv,
+    ParamCurveExtrema, ParamCurveNearest, QuadBez, Rect, Shape, Vec2,
 };
 
 /// A path that can Bézier segments up to cubic, possibly with multiple subpaths.
+///
+/// With the `smallvec` feature enabled, paths of up to
+/// [`INLINE_PATH_ELEMENTS`](constant.INLINE_PATH_ELEMENTS.html) elements,
+/// which covers rectangles and rounded rectangles, are stored inline rather
+/// than on the heap. This makes `BezPath` itself larger, but saves an
+/// allocation for each of the many tiny paths typical of UI workloads.
 #[derive(Clone, Default, Debug)]
-pub struct BezPath(Vec<PathEl>);
+pub struct BezPath(PathElVec);
+
+/// The number of path elements a `BezPath` stores without allocating, when
+/// the `smallvec` feature is enabled.
+#[cfg(feature = "smallvec")]
+pub const INLINE_PATH_ELEMENTS: usize = 10;
+
+#[cfg(feature = "smallvec")]
+type PathElVec = smallvec::SmallVec<[PathEl; INLINE_PATH_ELEMENTS]>;
+
+#[cfg(not(feature = "smallvec"))]
+type PathElVec = Vec<PathEl>;
+
+/// A borrowed view of a sequence of path elements.
+///
+/// This provides the read-only operations of [`BezPath`](struct.BezPath.html)
+/// over elements stored elsewhere, such as in a buffer owned by another
+/// library, without copying them. It is cheap to copy.
+#[derive(Clone, Copy, Debug)]
+pub struct BezPathSlice<'a>(&'a [PathEl]);
 
 /// The element of a Bézier path.
 ///
@@ -35,6 +60,27 @@ pub enum PathSeg {
     Cubic(CubicBez),
 }
 
+/// The segments of one subpath, as produced by `BezPath::subpath_segs`.
+#[derive(Default)]
+pub(crate) struct SubpathSegs {
+    /// Each segment, with the index of the element that produced it.
+    pub segs: Vec<(usize, PathSeg)>,
+    pub closed: bool,
+}
+
+/// A moving frame at a point along a curve.
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    /// The point on the curve.
+    pub point: Vec2,
+    /// The unit tangent vector.
+    pub tangent: Vec2,
+    /// The unit normal vector, the tangent rotated towards positive Y.
+    pub normal: Vec2,
+    /// The signed curvature, positive when the curve turns towards the normal.
+    pub curvature: f64,
+}
+
 impl BezPath {
     /// Create a new path.
     pub fn new() -> BezPath {
@@ -42,8 +88,10 @@ impl BezPath {
     }
 
     /// Create a path from a vector of path elements.
+    // The conversion is only needed when the `smallvec` feature is enabled.
+    #[allow(clippy::useless_conversion)]
     pub fn from_vec(v: Vec<PathEl>) -> BezPath {
-        BezPath(v)
+        BezPath(v.into())
     }
 
     /// Push a generic path element onto the path.
@@ -81,31 +129,288 @@ impl BezPath {
         &self.0
     }
 
+    /// Get a borrowed view of the path.
+    pub fn as_slice(&self) -> BezPathSlice<'_> {
+        BezPathSlice(&self.0)
+    }
+
     /// Iterate over the path segments.
     pub fn segments<'a>(&'a self) -> impl Iterator<Item = PathSeg> + 'a {
         BezPath::segments_of_slice(&self.0)
     }
 
     // TODO: expose as pub method? Maybe should be a trait so slice.segments() works?
-    fn segments_of_slice<'a>(slice: &'a [PathEl]) -> BezPathSegs<'a> {
-        let first = match slice.get(0) {
+    fn segments_of_slice<'a>(
+        slice: &'a [PathEl],
+    ) -> BezPathSegs<std::iter::Cloned<std::slice::Iter<'a, PathEl>>> {
+        let first = match slice.first() {
             Some(PathEl::Moveto(ref p)) => *p,
             Some(_) => panic!("First element has to be a PathEl::Moveto!"),
             None => Default::default(),
         };
 
         BezPathSegs {
-            c: slice.iter(),
+            c: slice.iter().cloned(),
             start: first,
             last: first,
         }
     }
 
+    /// Iterate over the segments of a sequence of path elements.
+    ///
+    /// The first element should be a `Moveto`.
+    pub(crate) fn segments_of_iter<I: Iterator<Item = PathEl>>(iter: I) -> BezPathSegs<I> {
+        BezPathSegs {
+            c: iter,
+            start: Vec2::default(),
+            last: Vec2::default(),
+        }
+    }
+
     /// Get the segment at the given element index.
     ///
     /// The element index counts [`PathEl`](enum.PathEl.html) elements, so
     /// for example includes an initial `Moveto`.
     pub fn get_seg(&self, ix: usize) -> Option<PathSeg> {
+        self.as_slice().get_seg(ix)
+    }
+
+    /// The smallest rectangle enclosing all points of the path, including
+    /// off-curve control points.
+    ///
+    /// This contains the path, since each Bézier segment lies within the
+    /// convex hull of its control points, but may be larger than
+    /// [`bounding_box`](trait.Shape.html#tymethod.bounding_box). It is
+    /// cheap to compute, requiring no root finding, so is useful as a
+    /// conservative bound for culling. Returns the default (empty) rectangle
+    /// for a path with no points.
+    pub fn control_box(&self) -> Rect {
+        self.as_slice().control_box()
+    }
+
+    /// A hash of the path geometry, with coordinates quantized to a grid.
+    ///
+    /// Coordinates are rounded to the nearest multiple of `grid` before
+    /// hashing, so paths differing only by less than about half the grid
+    /// spacing usually hash the same, though points near the middle between
+    /// grid lines can round either way. The element structure is hashed as
+    /// well. The hash is FNV-1a, computed without allocating, and is stable
+    /// across runs and platforms, so it can key persistent render caches.
+    pub fn geometry_hash(&self, grid: f64) -> u64 {
+        self.as_slice().geometry_hash(grid)
+    }
+
+    /// The positions dividing the path into `n` pieces of equal arclength.
+    ///
+    /// Each position is given as the index of a segment, as produced by
+    /// [`segments`](#method.segments), and a parameter within it. There are
+    /// `n + 1` positions, from the start of the path to its end, with
+    /// lengths measured along the path across subpath boundaries, as for
+    /// [`frames`](#method.frames); a position on the boundary between two
+    /// segments is given at the start of the later one. The result is empty
+    /// if the path has no segments. Use [`split_even`](#method.split_even)
+    /// for the pieces.
+    ///
+    /// Panics if `n` is zero.
+    pub fn subdivide_even(&self, n: usize, accuracy: f64) -> Vec<(usize, f64)> {
+        self.as_slice().subdivide_even(n, accuracy)
+    }
+
+    /// Split the path into `n` pieces of equal arclength.
+    ///
+    /// The pieces are found by [`trim`](#method.trim), so a piece spanning
+    /// subpaths has a subpath for each.
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_even(&self, n: usize, accuracy: f64) -> Vec<BezPath> {
+        self.as_slice().split_even(n, accuracy)
+    }
+
+    /// Split segments at their x and y extrema, so every segment is monotonic.
+    ///
+    /// The result has the same shape and subpath structure as the original
+    /// path; curves are subdivided using
+    /// [`PathSeg::split_monotonic`](enum.PathSeg.html#method.split_monotonic),
+    /// and lines, which are already monotonic, are kept as they are.
+    pub fn to_monotonic(&self) -> BezPath {
+        self.as_slice().to_monotonic()
+    }
+
+    /// Returns `true` if the path contains no segments.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Split the path into subpaths of segments.
+    ///
+    /// The closing line of a closed subpath is included as a segment unless it
+    /// has zero length. Subpaths without segments are omitted.
+    pub(crate) fn subpath_segs(&self) -> Vec<SubpathSegs> {
+        self.as_slice().subpath_segs()
+    }
+
+    /// Apply an affine transform to the path.
+    pub fn apply_affine(&mut self, affine: Affine) {
+        for el in self.0.iter_mut() {
+            *el = affine * (*el);
+        }
+    }
+
+    /// Apply an affine transform to the path in place.
+    ///
+    /// This is the same as [`apply_affine`](#method.apply_affine), named to
+    /// match the other in-place editing methods. Like them, it never
+    /// reallocates, so is suitable for transforming large paths every frame
+    /// of an interactive drag.
+    pub fn apply_affine_mut(&mut self, affine: Affine) {
+        self.apply_affine(affine);
+    }
+
+    /// Translate the path in place.
+    pub fn translate_mut(&mut self, offset: Vec2) {
+        self.map_points(|p| p + offset);
+    }
+
+    /// Replace every point of the path, on-curve and control points alike,
+    /// by the result of a function, in place.
+    pub fn map_points(&mut self, mut f: impl FnMut(Vec2) -> Vec2) {
+        for el in self.0.iter_mut() {
+            *el = match *el {
+                PathEl::Moveto(p) => PathEl::Moveto(f(p)),
+                PathEl::Lineto(p) => PathEl::Lineto(f(p)),
+                PathEl::Quadto(p1, p2) => PathEl::Quadto(f(p1), f(p2)),
+                PathEl::Curveto(p1, p2, p3) => PathEl::Curveto(f(p1), f(p2), f(p3)),
+                PathEl::Closepath => PathEl::Closepath,
+            };
+        }
+    }
+
+    /// Keep only the elements for which a predicate returns `true`, in
+    /// place, preserving their order.
+    ///
+    /// No memory is allocated or freed. Removing elements can change the
+    /// shape of the path, or make it invalid if a leading `Moveto` is
+    /// removed; it is up to the caller to keep the path meaningful.
+    pub fn retain_elements(&mut self, mut f: impl FnMut(&PathEl) -> bool) {
+        self.0.retain(|el| f(el));
+    }
+
+    /// Find the nearest point.
+    ///
+    /// Panics if path is empty or invalid.
+    ///
+    /// Note that the returned index counts segments, not elements. Thus, the
+    /// initial `Moveto` is not counted. For a simple path consisting of a `Moveto`
+    /// followed by `Lineto/Quadto/Cubicto` elements, the element index is the
+    /// segment index + 1.
+    ///
+    /// Returns the index of the segment, the parameter within that segment, and
+    /// the square of the distance to the point.
+    pub fn nearest(&self, p: Vec2, accuracy: f64) -> (usize, f64, f64) {
+        self.as_slice().nearest(p, accuracy)
+    }
+
+    /// Linearly interpolate between two paths with the same structure.
+    ///
+    /// The paths must have the same sequence of element types; corresponding
+    /// points are interpolated. Returns `None` if the structures differ. Use
+    /// [`morph`](fn.morph.html) to bring arbitrary paths into a compatible form.
+    pub fn try_lerp(&self, other: &BezPath, t: f64) -> Option<BezPath> {
+        self.as_slice().try_lerp(other.as_slice(), t)
+    }
+
+    /// The unit tangent vector at the given arclength from the start of the path.
+    ///
+    /// Lengths beyond the ends of the path are clamped. Returns `None` if the
+    /// path has no segments.
+    pub fn tangent_at_len(&self, len: f64, accuracy: f64) -> Option<Vec2> {
+        self.as_slice().tangent_at_len(len, accuracy)
+    }
+
+    /// Sample frames along the path at regular arclength spacing.
+    ///
+    /// The first frame is at the start of the path, and subsequent frames are
+    /// `spacing` apart, measured along the path across subpath boundaries.
+    /// Zero-length segments are skipped.
+    ///
+    /// Panics if `spacing` is not positive.
+    pub fn frames<'a>(&'a self, spacing: f64, accuracy: f64) -> impl Iterator<Item = Frame> + 'a {
+        self.as_slice().frames(spacing, accuracy)
+    }
+
+    /// Trim the path to a range of its arclength.
+    ///
+    /// The `start` and `end` values are fractions of the total length of the
+    /// path, clamped to the range 0..1. This matches the semantics of trim
+    /// paths in Lottie. If the path consists of a single closed subpath and
+    /// `start` is greater than `end`, the result wraps around the start point of
+    /// the path; otherwise the two values are swapped.
+    ///
+    /// The result contains a `Closepath` only for closed subpaths that are kept
+    /// entirely.
+    pub fn trim(&self, start: f64, end: f64, accuracy: f64) -> BezPath {
+        self.as_slice().trim(start, end, accuracy)
+    }
+
+    /// Flatten the path, invoking the callback repeatedly.
+    ///
+    /// Flattening is the action of approximating a curve with a succession of line segments.
+    /// The callback only receives `Moveto`, `Lineto`, and `Closepath` elements, and the
+    /// distance between the flattened path and the original is at most `tolerance`.
+    ///
+    /// Quadratic segments are subdivided uniformly using the bound on their second
+    /// derivative; cubics are first approximated by quadratics. No memory is
+    /// allocated; see [`flatten_into`](#method.flatten_into) to collect the
+    /// points into reusable buffers.
+    pub fn flatten(&self, tolerance: f64, callback: impl FnMut(PathEl)) {
+        self.as_slice().flatten(tolerance, callback)
+    }
+
+    /// Flatten the path into reusable buffers of points.
+    ///
+    /// This is [`flatten`](#method.flatten), but writes the points of the
+    /// resulting polylines into `points`, recording in `subpath_ends` the
+    /// index just past the last point of each subpath. Closed subpaths end
+    /// with a repeat of their first point. Both vectors are cleared first,
+    /// so calling this repeatedly with the same buffers allocates only when
+    /// a path needs more capacity than any before it.
+    pub fn flatten_into(
+        &self,
+        tolerance: f64,
+        points: &mut Vec<Vec2>,
+        subpath_ends: &mut Vec<usize>,
+    ) {
+        self.as_slice()
+            .flatten_into(tolerance, points, subpath_ends)
+    }
+}
+
+impl<'a> BezPathSlice<'a> {
+    /// Create a view of a sequence of path elements.
+    pub fn new(elements: &'a [PathEl]) -> BezPathSlice<'a> {
+        BezPathSlice(elements)
+    }
+
+    /// Get the path elements.
+    pub fn elements(self) -> &'a [PathEl] {
+        self.0
+    }
+
+    /// Iterate over the path segments.
+    pub fn segments(self) -> impl Iterator<Item = PathSeg> + 'a {
+        BezPath::segments_of_slice(self.0)
+    }
+
+    /// Copy the elements into an owned path.
+    pub fn to_bez_path_exact(self) -> BezPath {
+        BezPath::from_vec(self.0.to_vec())
+    }
+
+    /// Get the segment at the given element index.
+    ///
+    /// The element index counts [`PathEl`](enum.PathEl.html) elements, so
+    /// for example includes an initial `Moveto`.
+    pub fn get_seg(self, ix: usize) -> Option<PathSeg> {
         if ix == 0 || ix >= self.0.len() {
             return None;
         }
@@ -128,19 +433,218 @@ impl BezPath {
         }
     }
 
+    /// The smallest rectangle enclosing all points of the path, including
+    /// off-curve control points.
+    ///
+    /// This contains the path, since each Bézier segment lies within the
+    /// convex hull of its control points, but may be larger than
+    /// [`bounding_box`](trait.Shape.html#tymethod.bounding_box). It is
+    /// cheap to compute, requiring no root finding, so is useful as a
+    /// conservative bound for culling. Returns the default (empty) rectangle
+    /// for a path with no points.
+    pub fn control_box(self) -> Rect {
+        let mut bbox: Option<Rect> = None;
+        let mut add = |p: Vec2| {
+            bbox = Some(match bbox {
+                Some(bb) => bb.union_pt(p),
+                None => Rect::from_points(p, p),
+            });
+        };
+        for el in self.0 {
+            match *el {
+                PathEl::Moveto(p) | PathEl::Lineto(p) => add(p),
+                PathEl::Quadto(p1, p2) => {
+                    add(p1);
+                    add(p2);
+                }
+                PathEl::Curveto(p1, p2, p3) => {
+                    add(p1);
+                    add(p2);
+                    add(p3);
+                }
+                PathEl::Closepath => (),
+            }
+        }
+        bbox.unwrap_or_default()
+    }
+
+    /// A hash of the path geometry, with coordinates quantized to a grid.
+    ///
+    /// Coordinates are rounded to the nearest multiple of `grid` before
+    /// hashing, so paths differing only by less than about half the grid
+    /// spacing usually hash the same, though points near the middle between
+    /// grid lines can round either way. The element structure is hashed as
+    /// well. The hash is FNV-1a, computed without allocating, and is stable
+    /// across runs and platforms, so it can key persistent render caches.
+    pub fn geometry_hash(self, grid: f64) -> u64 {
+        let scale = grid.recip();
+        let mut hash = FNV_OFFSET;
+        let mut add = |bytes: &[u8]| {
+            for &b in bytes {
+                hash = (hash ^ b as u64).wrapping_mul(FNV_PRIME);
+            }
+        };
+        let mut add_point = |tag: u8, pts: &[Vec2]| {
+            add(&[tag]);
+            for p in pts {
+                add(&((p.x * scale).round() as i64).to_le_bytes());
+                add(&((p.y * scale).round() as i64).to_le_bytes());
+            }
+        };
+        for el in self.0 {
+            match *el {
+                PathEl::Moveto(p) => add_point(0, &[p]),
+                PathEl::Lineto(p) => add_point(1, &[p]),
+                PathEl::Quadto(p1, p2) => add_point(2, &[p1, p2]),
+                PathEl::Curveto(p1, p2, p3) => add_point(3, &[p1, p2, p3]),
+                PathEl::Closepath => add_point(4, &[]),
+            }
+        }
+        hash
+    }
+
+    /// The positions dividing the path into `n` pieces of equal arclength.
+    ///
+    /// Each position is given as the index of a segment, as produced by
+    /// [`segments`](#method.segments), and a parameter within it. There are
+    /// `n + 1` positions, from the start of the path to its end, with
+    /// lengths measured along the path across subpath boundaries, as for
+    /// [`frames`](#method.frames); a position on the boundary between two
+    /// segments is given at the start of the later one. The result is empty
+    /// if the path has no segments. Use [`split_even`](#method.split_even)
+    /// for the pieces.
+    ///
+    /// Panics if `n` is zero.
+    pub fn subdivide_even(self, n: usize, accuracy: f64) -> Vec<(usize, f64)> {
+        assert!(n > 0, "n must be positive");
+        let segs: Vec<PathSeg> = self.segments().collect();
+        if segs.is_empty() {
+            return Vec::new();
+        }
+        let lens: Vec<f64> = segs.iter().map(|seg| seg.arclen(accuracy)).collect();
+        let total: f64 = lens.iter().sum();
+        let mut result = Vec::with_capacity(n + 1);
+        result.push((0, 0.0));
+        let mut ix = 0;
+        let mut start = 0.0;
+        for i in 1..n {
+            let target = total * i as f64 / n as f64;
+            while ix + 1 < segs.len() && start + lens[ix] <= target {
+                start += lens[ix];
+                ix += 1;
+            }
+            let t = segs[ix].inv_arclen((target - start).min(lens[ix]), accuracy);
+            result.push((ix, t));
+        }
+        result.push((segs.len() - 1, 1.0));
+        result
+    }
+
+    /// Split the path into `n` pieces of equal arclength.
+    ///
+    /// The pieces are found by [`trim`](#method.trim), so a piece spanning
+    /// subpaths has a subpath for each.
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_even(self, n: usize, accuracy: f64) -> Vec<BezPath> {
+        assert!(n > 0, "n must be positive");
+        (0..n)
+            .map(|i| self.trim(i as f64 / n as f64, (i + 1) as f64 / n as f64, accuracy))
+            .collect()
+    }
+
+    /// Split segments at their x and y extrema, so every segment is monotonic.
+    ///
+    /// The result has the same shape and subpath structure as the original
+    /// path; curves are subdivided using
+    /// [`PathSeg::split_monotonic`](enum.PathSeg.html#method.split_monotonic),
+    /// and lines, which are already monotonic, are kept as they are.
+    pub fn to_monotonic(self) -> BezPath {
+        let mut result = BezPath::new();
+        let mut last = Vec2::default();
+        let mut start = Vec2::default();
+        for &el in self.0 {
+            let seg = match el {
+                PathEl::Moveto(p) => {
+                    start = p;
+                    last = p;
+                    result.push(el);
+                    continue;
+                }
+                PathEl::Lineto(p) => {
+                    last = p;
+                    result.push(el);
+                    continue;
+                }
+                PathEl::Quadto(p1, p2) => PathSeg::Quad(QuadBez::new(last, p1, p2)),
+                PathEl::Curveto(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(last, p1, p2, p3)),
+                PathEl::Closepath => {
+                    last = start;
+                    result.push(el);
+                    continue;
+                }
+            };
+            for piece in seg.split_monotonic() {
+                result.push(piece.as_path_el());
+            }
+            last = seg.end();
+        }
+        result
+    }
+
     /// Returns `true` if the path contains no segments.
-    pub fn is_empty(&self) -> bool {
-        !self.0.iter().any(|el| match *el {
-            PathEl::Lineto(..) | PathEl::Quadto(..) | PathEl::Curveto(..) => true,
-            _ => false,
+    pub fn is_empty(self) -> bool {
+        !self.0.iter().any(|el| {
+            matches!(
+                *el,
+                PathEl::Lineto(..) | PathEl::Quadto(..) | PathEl::Curveto(..)
+            )
         })
     }
 
-    /// Apply an affine transform to the path.
-    pub fn apply_affine(&mut self, affine: Affine) {
-        for el in self.0.iter_mut() {
-            *el = affine * (*el);
+    /// Split the path into subpaths of segments.
+    ///
+    /// The closing line of a closed subpath is included as a segment unless it
+    /// has zero length. Subpaths without segments are omitted.
+    pub(crate) fn subpath_segs(self) -> Vec<SubpathSegs> {
+        let mut result = Vec::new();
+        let mut current = SubpathSegs::default();
+        let mut start = Vec2::default();
+        let mut last = Vec2::default();
+        for (ix, el) in self.0.iter().enumerate() {
+            let seg = match *el {
+                PathEl::Moveto(p) => {
+                    if !current.segs.is_empty() {
+                        result.push(std::mem::take(&mut current));
+                    }
+                    start = p;
+                    last = p;
+                    continue;
+                }
+                PathEl::Lineto(p) => PathSeg::Line(Line::new(last, p)),
+                PathEl::Quadto(p1, p2) => PathSeg::Quad(QuadBez::new(last, p1, p2)),
+                PathEl::Curveto(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(last, p1, p2, p3)),
+                PathEl::Closepath => {
+                    if last != start {
+                        current
+                            .segs
+                            .push((ix, PathSeg::Line(Line::new(last, start))));
+                    }
+                    if !current.segs.is_empty() {
+                        current.closed = true;
+                        result.push(std::mem::take(&mut current));
+                    }
+                    last = start;
+                    continue;
+                }
+            };
+            last = seg.end();
+            current.segs.push((ix, seg));
+        }
+        if !current.segs.is_empty() {
+            result.push(current);
         }
+        result
     }
 
     /// Find the nearest point.
@@ -154,7 +658,7 @@ impl BezPath {
     ///
     /// Returns the index of the segment, the parameter within that segment, and
     /// the square of the distance to the point.
-    pub fn nearest(&self, p: Vec2, accuracy: f64) -> (usize, f64, f64) {
+    pub fn nearest(self, p: Vec2, accuracy: f64) -> (usize, f64, f64) {
         let mut best = None;
         for (ix, seg) in self.segments().enumerate() {
             let (t, r) = seg.nearest(p, accuracy);
@@ -164,6 +668,268 @@ impl BezPath {
         }
         best.unwrap()
     }
+
+    /// Linearly interpolate between two paths with the same structure.
+    ///
+    /// The paths must have the same sequence of element types; corresponding
+    /// points are interpolated. Returns `None` if the structures differ. Use
+    /// [`morph`](fn.morph.html) to bring arbitrary paths into a compatible form.
+    pub fn try_lerp(self, other: BezPathSlice<'_>, t: f64) -> Option<BezPath> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0
+            .iter()
+            .zip(other.0)
+            .map(|(&a, &b)| match (a, b) {
+                (PathEl::Moveto(p), PathEl::Moveto(q)) => Some(PathEl::Moveto(p.lerp(q, t))),
+                (PathEl::Lineto(p), PathEl::Lineto(q)) => Some(PathEl::Lineto(p.lerp(q, t))),
+                (PathEl::Quadto(p1, p2), PathEl::Quadto(q1, q2)) => {
+                    Some(PathEl::Quadto(p1.lerp(q1, t), p2.lerp(q2, t)))
+                }
+                (PathEl::Curveto(p1, p2, p3), PathEl::Curveto(q1, q2, q3)) => Some(
+                    PathEl::Curveto(p1.lerp(q1, t), p2.lerp(q2, t), p3.lerp(q3, t)),
+                ),
+                (PathEl::Closepath, PathEl::Closepath) => Some(PathEl::Closepath),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(BezPath::from_vec)
+    }
+
+    /// The unit tangent vector at the given arclength from the start of the path.
+    ///
+    /// Lengths beyond the ends of the path are clamped. Returns `None` if the
+    /// path has no segments.
+    pub fn tangent_at_len(self, len: f64, accuracy: f64) -> Option<Vec2> {
+        let mut remaining = len.max(0.0);
+        let mut last = None;
+        for seg in self.segments() {
+            let seg_len = seg.arclen(accuracy);
+            if remaining <= seg_len && seg_len > 0.0 {
+                return Some(seg.tangent(seg.inv_arclen(remaining, accuracy)));
+            }
+            remaining -= seg_len;
+            last = Some(seg);
+        }
+        last.map(|seg| seg.tangent(1.0))
+    }
+
+    /// Sample frames along the path at regular arclength spacing.
+    ///
+    /// The first frame is at the start of the path, and subsequent frames are
+    /// `spacing` apart, measured along the path across subpath boundaries.
+    /// Zero-length segments are skipped.
+    ///
+    /// Panics if `spacing` is not positive.
+    pub fn frames(self, spacing: f64, accuracy: f64) -> impl Iterator<Item = Frame> + 'a {
+        assert!(spacing > 0.0, "spacing must be positive");
+        Frames {
+            segs: BezPath::segments_of_slice(self.0),
+            seg: None,
+            offset: 0.0,
+            spacing,
+            accuracy,
+        }
+    }
+
+    /// Trim the path to a range of its arclength.
+    ///
+    /// The `start` and `end` values are fractions of the total length of the
+    /// path, clamped to the range 0..1. This matches the semantics of trim
+    /// paths in Lottie. If the path consists of a single closed subpath and
+    /// `start` is greater than `end`, the result wraps around the start point of
+    /// the path; otherwise the two values are swapped.
+    ///
+    /// The result contains a `Closepath` only for closed subpaths that are kept
+    /// entirely.
+    pub fn trim(self, start: f64, end: f64, accuracy: f64) -> BezPath {
+        let start = start.clamp(0.0, 1.0);
+        let end = end.clamp(0.0, 1.0);
+        let lens: Vec<f64> = self.segments().map(|seg| seg.arclen(accuracy)).collect();
+        let total: f64 = lens.iter().sum();
+        let mut result = BezPath::new();
+        let n_subpaths = self
+            .0
+            .iter()
+            .filter(|el| matches!(el, PathEl::Moveto(_)))
+            .count();
+        let closed = n_subpaths == 1 && matches!(self.0.last(), Some(PathEl::Closepath));
+        if start > end && closed {
+            self.trim_range(&lens, start * total..total, false, accuracy, &mut result);
+            let connect = !result.is_empty();
+            self.trim_range(&lens, 0.0..end * total, connect, accuracy, &mut result);
+        } else {
+            let range = start.min(end) * total..start.max(end) * total;
+            self.trim_range(&lens, range, false, accuracy, &mut result);
+        }
+        result
+    }
+
+    /// Append the part of the path within an arclength range.
+    ///
+    /// The `lens` are the arclengths of the segments. If `connect` is set, the
+    /// initial `Moveto` is omitted, continuing the current subpath of `result`.
+    fn trim_range(
+        self,
+        lens: &[f64],
+        range: Range<f64>,
+        connect: bool,
+        accuracy: f64,
+        result: &mut BezPath,
+    ) {
+        let mut segs = self.segments().zip(lens);
+        let mut len = 0.0;
+        let mut subpath_len = 0.0;
+        let mut need_moveto = false;
+        let mut last = Vec2::default();
+        let mut start = Vec2::default();
+        for el in self.0 {
+            let is_close = matches!(el, PathEl::Closepath);
+            let end = match *el {
+                PathEl::Moveto(p) => {
+                    need_moveto = !connect;
+                    subpath_len = len;
+                    start = p;
+                    last = p;
+                    continue;
+                }
+                PathEl::Closepath => start,
+                PathEl::Lineto(p) => p,
+                PathEl::Quadto(_, p2) => p2,
+                PathEl::Curveto(_, _, p3) => p3,
+            };
+            // A `Closepath` only produces a segment if the subpath isn't already closed.
+            if !is_close || last != start {
+                let (seg, &seg_len) = segs.next().unwrap();
+                let lo = range.start.max(len);
+                let hi = range.end.min(len + seg_len);
+                // The closing segment of a subpath kept entirely is drawn by `Closepath`.
+                let whole = is_close && range.start <= subpath_len && range.end >= len + seg_len;
+                if lo < hi && !whole {
+                    let t0 = if lo > len {
+                        seg.inv_arclen(lo - len, accuracy)
+                    } else {
+                        0.0
+                    };
+                    let t1 = if hi < len + seg_len {
+                        seg.inv_arclen(hi - len, accuracy)
+                    } else {
+                        1.0
+                    };
+                    let sub = seg.subsegment(t0..t1);
+                    if need_moveto {
+                        result.moveto(sub.start());
+                        need_moveto = false;
+                    }
+                    result.push(sub.as_path_el());
+                }
+                len += seg_len;
+            }
+            if is_close && !need_moveto && range.start <= subpath_len && range.end >= len {
+                result.closepath();
+            }
+            last = end;
+        }
+    }
+
+    /// Flatten the path, invoking the callback repeatedly.
+    ///
+    /// Flattening is the action of approximating a curve with a succession of line segments.
+    /// The callback only receives `Moveto`, `Lineto`, and `Closepath` elements, and the
+    /// distance between the flattened path and the original is at most `tolerance`.
+    ///
+    /// Quadratic segments are subdivided uniformly using the bound on their second
+    /// derivative; cubics are first approximated by quadratics. No memory is
+    /// allocated; see [`flatten_into`](#method.flatten_into) to collect the
+    /// points into reusable buffers.
+    pub fn flatten(self, tolerance: f64, mut callback: impl FnMut(PathEl)) {
+        let mut last = Vec2::default();
+        for el in self.0 {
+            match *el {
+                PathEl::Moveto(p) => {
+                    callback(PathEl::Moveto(p));
+                    last = p;
+                }
+                PathEl::Lineto(p) => {
+                    callback(PathEl::Lineto(p));
+                    last = p;
+                }
+                PathEl::Quadto(p1, p2) => {
+                    flatten_quad(&QuadBez::new(last, p1, p2), tolerance, &mut callback);
+                    last = p2;
+                }
+                PathEl::Curveto(p1, p2, p3) => {
+                    let c = CubicBez::new(last, p1, p2, p3);
+                    // Split the error budget between cubic-to-quad and quad-to-line.
+                    for (_, _, q) in c.to_quads(0.5 * tolerance) {
+                        flatten_quad(&q, 0.5 * tolerance, &mut callback);
+                    }
+                    last = p3;
+                }
+                PathEl::Closepath => callback(PathEl::Closepath),
+            }
+        }
+    }
+
+    /// Flatten the path into reusable buffers of points.
+    ///
+    /// This is [`flatten`](#method.flatten), but writes the points of the
+    /// resulting polylines into `points`, recording in `subpath_ends` the
+    /// index just past the last point of each subpath. Closed subpaths end
+    /// with a repeat of their first point. Both vectors are cleared first,
+    /// so calling this repeatedly with the same buffers allocates only when
+    /// a path needs more capacity than any before it.
+    pub fn flatten_into(
+        self,
+        tolerance: f64,
+        points: &mut Vec<Vec2>,
+        subpath_ends: &mut Vec<usize>,
+    ) {
+        points.clear();
+        subpath_ends.clear();
+        let mut start = 0;
+        self.flatten(tolerance, |el| match el {
+            PathEl::Moveto(p) => {
+                if points.len() > start {
+                    subpath_ends.push(points.len());
+                }
+                start = points.len();
+                points.push(p);
+            }
+            PathEl::Lineto(p) => points.push(p),
+            PathEl::Closepath => {
+                if points.len() > start {
+                    let first = points[start];
+                    points.push(first);
+                    subpath_ends.push(points.len());
+                    start = points.len();
+                }
+            }
+            _ => unreachable!(),
+        });
+        if points.len() > start {
+            subpath_ends.push(points.len());
+        }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x100_0000_01b3;
+
+/// Flatten a quadratic Bézier, not including its start point.
+///
+/// The maximum distance between a quadratic and its chord over a parameter
+/// interval of length `h` is `|p0 - 2 p1 + p2| h² / 4`, which determines the
+/// number of uniform subdivisions needed to stay within tolerance.
+fn flatten_quad(q: &QuadBez, tolerance: f64, callback: &mut impl FnMut(PathEl)) {
+    let dd = (q.p0 - 2.0 * q.p1 + q.p2).hypot();
+    let n = ((dd / (4.0 * tolerance)).sqrt().ceil() as usize).max(1);
+    let step = (n as f64).recip();
+    for i in 1..n {
+        callback(PathEl::Lineto(q.eval(i as f64 * step)));
+    }
+    callback(PathEl::Lineto(q.p2));
 }
 
 impl<'a> IntoIterator for &'a BezPath {
@@ -197,7 +963,7 @@ impl Mul<BezPath> for Affine {
     }
 }
 
-impl<'a> Mul<&'a BezPath> for Affine {
+impl Mul<&BezPath> for Affine {
     type Output = BezPath;
 
     fn mul(self, other: &BezPath) -> BezPath {
@@ -205,18 +971,18 @@ impl<'a> Mul<&'a BezPath> for Affine {
     }
 }
 
-struct BezPathSegs<'a> {
-    c: std::slice::Iter<'a, PathEl>,
+pub(crate) struct BezPathSegs<I> {
+    c: I,
     start: Vec2,
     last: Vec2,
 }
 
-impl<'a> Iterator for BezPathSegs<'a> {
+impl<I: Iterator<Item = PathEl>> Iterator for BezPathSegs<I> {
     type Item = PathSeg;
 
     fn next(&mut self) -> Option<PathSeg> {
         for el in &mut self.c {
-            let (ret, last) = match *el {
+            let (ret, last) = match el {
                 PathEl::Moveto(p) => {
                     self.start = p;
                     self.last = p;
@@ -243,27 +1009,66 @@ impl<'a> Iterator for BezPathSegs<'a> {
     }
 }
 
-impl<'a> BezPathSegs<'a> {
+struct Frames<'a> {
+    segs: BezPathSegs<std::iter::Cloned<std::slice::Iter<'a, PathEl>>>,
+    // The current segment and its arclength.
+    seg: Option<(PathSeg, f64)>,
+    // The arclength of the next sample, relative to the start of the current segment.
+    offset: f64,
+    spacing: f64,
+    accuracy: f64,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Frame> {
+        loop {
+            let (seg, len) = match self.seg {
+                Some(seg) => seg,
+                None => {
+                    let seg = self.segs.next()?;
+                    let len = seg.arclen(self.accuracy);
+                    self.seg = Some((seg, len));
+                    (seg, len)
+                }
+            };
+            if len > 0.0 && self.offset <= len {
+                let t = if self.offset > 0.0 {
+                    seg.inv_arclen(self.offset, self.accuracy)
+                } else {
+                    0.0
+                };
+                self.offset += self.spacing;
+                return Some(seg.frame(t));
+            }
+            self.offset -= len;
+            self.seg = None;
+        }
+    }
+}
+
+impl<I: Iterator<Item = PathEl>> BezPathSegs<I> {
     /// Here, `accuracy` specifies the accuracy for each Bézier segment. At worst,
     /// the total error is `accuracy` times the number of Bézier segments.
-
+    //
     // TODO: pub? Or is this subsumed by method of &[PathEl]?
-    fn arclen(self, accuracy: f64) -> f64 {
+    pub(crate) fn arclen(self, accuracy: f64) -> f64 {
         self.map(|seg| seg.arclen(accuracy)).sum()
     }
 
     // Same
-    fn area(self) -> f64 {
+    pub(crate) fn area(self) -> f64 {
         self.map(|seg| seg.signed_area()).sum()
     }
 
     // Same
-    fn winding(self, p: Vec2) -> i32 {
+    pub(crate) fn winding(self, p: Vec2) -> i32 {
         self.map(|seg| seg.winding(p)).sum()
     }
 
     // Same
-    fn bounding_box(self) -> Rect {
+    pub(crate) fn bounding_box(self) -> Rect {
         let mut bbox: Option<Rect> = None;
         for seg in self {
             let seg_bb = seg.bounding_box();
@@ -286,6 +1091,14 @@ impl ParamCurve for PathSeg {
         }
     }
 
+    fn eval_many(&self, ts: &[f64], out: &mut [Vec2]) {
+        match *self {
+            PathSeg::Line(line) => line.eval_many(ts, out),
+            PathSeg::Quad(quad) => quad.eval_many(ts, out),
+            PathSeg::Cubic(cubic) => cubic.eval_many(ts, out),
+        }
+    }
+
     fn subsegment(&self, range: Range<f64>) -> PathSeg {
         match *self {
             PathSeg::Line(line) => PathSeg::Line(line.subsegment(range)),
@@ -336,6 +1149,151 @@ impl ParamCurveExtrema for PathSeg {
 }
 
 impl PathSeg {
+    /// The parameters dividing the segment into `n` pieces of equal arclength.
+    ///
+    /// The result has `n + 1` entries, starting at 0 and ending at 1, so
+    /// places `n + 1` evenly spaced objects including both ends; the pieces
+    /// themselves are given by
+    /// [`split_even`](#method.split_even).
+    ///
+    /// Panics if `n` is zero.
+    pub fn subdivide_even(&self, n: usize, accuracy: f64) -> Vec<f64> {
+        assert!(n > 0, "n must be positive");
+        let len = self.arclen(accuracy);
+        let mut result = Vec::with_capacity(n + 1);
+        result.push(0.0);
+        for i in 1..n {
+            result.push(self.inv_arclen(len * i as f64 / n as f64, accuracy));
+        }
+        result.push(1.0);
+        result
+    }
+
+    /// Split the segment into `n` pieces of equal arclength.
+    ///
+    /// Panics if `n` is zero.
+    pub fn split_even(&self, n: usize, accuracy: f64) -> Vec<PathSeg> {
+        let ts = self.subdivide_even(n, accuracy);
+        ts.windows(2).map(|w| self.subsegment(w[0]..w[1])).collect()
+    }
+
+    /// Split the segment at its x and y extrema.
+    ///
+    /// Each resulting piece is monotonic in both x and y, which is what
+    /// sweep-line algorithms and scanline rasterizers need. Adjacent pieces
+    /// share endpoints exactly.
+    pub fn split_monotonic(&self) -> ArrayVec<[PathSeg; MAX_EXTREMA + 1]> {
+        self.extrema_ranges()
+            .into_iter()
+            .map(|range| self.subsegment(range))
+            .collect()
+    }
+
+    /// The smallest rectangle that encloses the segment after applying a transform.
+    ///
+    /// The segment is transformed first, so the result is tight.
+    pub fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        match *self {
+            PathSeg::Line(line) => ParamCurveExtrema::bounding_box(&(affine * line)),
+            PathSeg::Quad(quad) => (affine * quad).bounding_box(),
+            PathSeg::Cubic(cubic) => (affine * cubic).bounding_box(),
+        }
+    }
+
+    /// Convert the segment to a cubic Bézier, exactly.
+    pub fn to_cubic(&self) -> CubicBez {
+        match *self {
+            PathSeg::Line(line) => CubicBez::new(
+                line.p0,
+                line.p0.lerp(line.p1, 1.0 / 3.0),
+                line.p0.lerp(line.p1, 2.0 / 3.0),
+                line.p1,
+            ),
+            PathSeg::Quad(q) => q.raise(),
+            PathSeg::Cubic(c) => c,
+        }
+    }
+
+    /// The unit tangent vector at parameter `t`.
+    ///
+    /// At the endpoints, this is the same as
+    /// [`CubicBez::start_tangent`](struct.CubicBez.html#method.start_tangent)
+    /// and [`CubicBez::end_tangent`](struct.CubicBez.html#method.end_tangent)
+    /// of the segment raised to a cubic. Elsewhere, where the derivative
+    /// vanishes, as at a cusp, this is the limit of the direction of travel
+    /// approaching `t` from above. A segment collapsed to a single point has a
+    /// zero tangent.
+    pub fn tangent(&self, t: f64) -> Vec2 {
+        let c = self.to_cubic();
+        if t <= 0.0 {
+            return c.start_tangent();
+        } else if t >= 1.0 {
+            return c.end_tangent();
+        }
+        let d1 = c.deriv();
+        let d2 = d1.deriv();
+        let scale = (c.p1 - c.p0).hypot2() + (c.p2 - c.p1).hypot2() + (c.p3 - c.p2).hypot2();
+        let eps = 1e-24 * scale;
+        let mut d = d1.eval(t);
+        if d.hypot2() <= eps {
+            // Near t, the derivative is approximately (s - t) times the second
+            // derivative, or (s - t)² / 2 times the third.
+            d = d2.eval(t);
+            if d.hypot2() <= eps {
+                d = d2.deriv().eval(t);
+            }
+        }
+        let len = d.hypot();
+        if len > 0.0 {
+            d / len
+        } else {
+            Vec2::default()
+        }
+    }
+
+    /// The unit normal vector at parameter `t`.
+    ///
+    /// This is the tangent rotated by 90 degrees, from the positive X axis
+    /// towards positive Y. For a path with positive area, it points inward.
+    pub fn normal(&self, t: f64) -> Vec2 {
+        let tangent = self.tangent(t);
+        Vec2::new(-tangent.y, tangent.x)
+    }
+
+    /// The frame at parameter `t`.
+    ///
+    /// The tangent and normal are as in [`tangent`](#method.tangent) and
+    /// [`normal`](#method.normal). Where the derivative vanishes, the
+    /// curvature is reported as zero.
+    pub fn frame(&self, t: f64) -> Frame {
+        let c = self.to_cubic();
+        let d1 = c.deriv();
+        let d = d1.eval(t);
+        let d2 = d1.deriv().eval(t);
+        let speed2 = d.hypot2();
+        let curvature = if speed2 > 0.0 {
+            d.cross(d2) * speed2.powf(-1.5)
+        } else {
+            0.0
+        };
+        let tangent = self.tangent(t);
+        Frame {
+            point: c.eval(t),
+            tangent,
+            normal: Vec2::new(-tangent.y, tangent.x),
+            curvature,
+        }
+    }
+
+    /// The path element that draws this segment from its start point.
+    pub(crate) fn as_path_el(&self) -> PathEl {
+        match *self {
+            PathSeg::Line(line) => PathEl::Lineto(line.p1),
+            PathSeg::Quad(q) => PathEl::Quadto(q.p1, q.p2),
+            PathSeg::Cubic(c) => PathEl::Curveto(c.p1, c.p2, c.p3),
+        }
+    }
+
     // Assumes split at extrema.
     fn winding_inner(&self, p: Vec2) -> i32 {
         let start = self.start();
@@ -383,7 +1341,7 @@ impl PathSeg {
                 let b = 2.0 * (p1.y - start.y);
                 let c = start.y - p.y;
                 for t in solve_quadratic(c, b, a) {
-                    if t >= 0.0 && t <= 1.0 {
+                    if (0.0..=1.0).contains(&t) {
                         let x = quad.eval(t).x;
                         if p.x >= x {
                             return sign;
@@ -408,7 +1366,7 @@ impl PathSeg {
                 let c = 3.0 * (p1.y - start.y);
                 let d = start.y - p.y;
                 for t in solve_cubic(d, c, b, a) {
-                    if t >= 0.0 && t <= 1.0 {
+                    if (0.0..=1.0).contains(&t) {
                         let x = cubic.eval(t).x;
                         if p.x >= x {
                             return sign;
@@ -425,7 +1383,7 @@ impl PathSeg {
     /// Compute the winding number contribution of a single segment.
     ///
     /// Cast a ray to the left and count intersections.
-    fn winding(&self, p: Vec2) -> i32 {
+    pub(crate) fn winding(&self, p: Vec2) -> i32 {
         self.extrema_ranges()
             .into_iter()
             .map(|range| self.subsegment(range).winding_inner(p))
@@ -437,7 +1395,7 @@ impl Shape for BezPath {
     type BezPathIter = std::vec::IntoIter<PathEl>;
 
     fn to_bez_path(&self, _tolerance: f64) -> Self::BezPathIter {
-        self.clone().0.into_iter()
+        Vec::from(self.elements()).into_iter()
     }
 
     /// Signed area.
@@ -462,6 +1420,10 @@ impl Shape for BezPath {
         self.elements().bounding_box()
     }
 
+    fn centroid(&self) -> Vec2 {
+        BezPath::centroid(self)
+    }
+
     fn as_path_slice(&self) -> Option<&[PathEl]> {
         Some(&self.0)
     }
@@ -502,3 +1464,427 @@ impl<'a> Shape for &'a [PathEl] {
         Some(self)
     }
 }
+
+impl<'a> From<&'a [PathEl]> for BezPathSlice<'a> {
+    fn from(elements: &'a [PathEl]) -> BezPathSlice<'a> {
+        BezPathSlice(elements)
+    }
+}
+
+impl<'a> From<&'a BezPath> for BezPathSlice<'a> {
+    fn from(path: &'a BezPath) -> BezPathSlice<'a> {
+        path.as_slice()
+    }
+}
+
+impl<'a> IntoIterator for BezPathSlice<'a> {
+    type Item = PathEl;
+    type IntoIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().cloned()
+    }
+}
+
+impl<'a> Shape for BezPathSlice<'a> {
+    type BezPathIter = std::iter::Cloned<std::slice::Iter<'a, PathEl>>;
+
+    #[inline]
+    fn to_bez_path(&self, _tolerance: f64) -> Self::BezPathIter {
+        self.0.iter().cloned()
+    }
+
+    fn area(&self) -> f64 {
+        self.0.area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.0.perimeter(accuracy)
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        self.0.winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.0.bounding_box()
+    }
+
+    #[inline]
+    fn as_path_slice(&self) -> Option<&[PathEl]> {
+        Some(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Affine, BezPath, BezPathSlice, Circle, CubicBez, Line, ParamCurve, ParamCurveArclen,
+        ParamCurveExtrema, PathEl, PathSeg, QuadBez, Shape, Vec2,
+    };
+
+    #[test]
+    fn try_lerp() {
+        let a = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10Z").unwrap();
+        let b = BezPath::from_svg("M0 0L20 0C20 10 10 20 0 20Z").unwrap();
+        let mid = a.try_lerp(&b, 0.5).unwrap();
+        assert_eq!(mid.to_svg(), "M0 0L15 0C15 7.5 7.5 15 0 15Z");
+        assert_eq!(a.try_lerp(&b, 0.0).unwrap().to_svg(), a.to_svg());
+
+        let c = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        assert!(a.try_lerp(&c, 0.5).is_none());
+        let d = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10").unwrap();
+        assert!(a.try_lerp(&d, 0.5).is_none());
+    }
+
+    #[test]
+    fn tangent_normal() {
+        fn assert_near(p0: Vec2, p1: Vec2) {
+            assert!((p1 - p0).hypot() < 1e-9, "{:?} != {:?}", p0, p1);
+        }
+        let seg = PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (0.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 0.0),
+        ));
+        assert_near(seg.tangent(0.0), Vec2::new(1.0, 1.0) / 2f64.sqrt());
+        assert_near(seg.normal(0.0), Vec2::new(-1.0, 1.0) / 2f64.sqrt());
+        assert_near(seg.tangent(1.0), Vec2::new(1.0, -1.0) / 2f64.sqrt());
+
+        // A cusp in the middle: the tangent is the limit from above.
+        let cusp = PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (2.0, 0.0),
+        ));
+        assert_near(cusp.tangent(0.5), Vec2::new(0.0, -1.0));
+
+        let seg = PathSeg::Quad(QuadBez::new((0.0, 0.0), (2.0, 2.0), (2.0, 2.0)));
+        assert_near(seg.tangent(1.0), Vec2::new(1.0, 1.0) / 2f64.sqrt());
+        let point = PathSeg::Line(Line::new((1.0, 1.0), (1.0, 1.0)));
+        assert_near(point.tangent(0.5), Vec2::new(0.0, 0.0));
+
+        let path = BezPath::from_svg("M0 0L10 0L10 10").unwrap();
+        assert_near(path.tangent_at_len(5.0, 1e-9).unwrap(), Vec2::new(1.0, 0.0));
+        assert_near(
+            path.tangent_at_len(15.0, 1e-9).unwrap(),
+            Vec2::new(0.0, 1.0),
+        );
+        assert_near(
+            path.tangent_at_len(50.0, 1e-9).unwrap(),
+            Vec2::new(0.0, 1.0),
+        );
+        assert!(BezPath::new().tangent_at_len(0.0, 1e-9).is_none());
+    }
+
+    #[test]
+    fn frames() {
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let spacing = 1.0;
+        let frames: Vec<_> = circle.frames(spacing, 1e-9).collect();
+        let perimeter = circle.perimeter(1e-9);
+        assert_eq!(frames.len(), perimeter.floor() as usize + 1);
+        for pair in frames.windows(2) {
+            // Chords are slightly shorter than the arcs they span.
+            let chord = (pair[1].point - pair[0].point).hypot();
+            assert!(chord < spacing && chord > 0.99 * spacing);
+        }
+        for frame in &frames {
+            assert!((frame.point.hypot() - 10.0).abs() < 1e-3);
+            assert!((frame.curvature - 0.1).abs() < 1e-3);
+            // The normal of a counterclockwise circle points to the center.
+            assert!((frame.normal + frame.point / 10.0).hypot() < 1e-3);
+        }
+
+        let line = PathSeg::Line(Line::new((0.0, 0.0), (3.0, 4.0)));
+        let frame = line.frame(0.5);
+        assert_eq!(frame.curvature, 0.0);
+        assert!((frame.tangent - Vec2::new(0.6, 0.8)).hypot() < 1e-12);
+    }
+
+    #[test]
+    fn trim() {
+        fn assert_els(path: &BezPath, expected: &[Vec2]) {
+            let points: Vec<Vec2> = path
+                .elements()
+                .iter()
+                .filter_map(|el| match *el {
+                    PathEl::Moveto(p) | PathEl::Lineto(p) => Some(p),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(points.len(), expected.len(), "{:?}", path);
+            for (p, q) in points.iter().zip(expected) {
+                assert!((*p - *q).hypot() < 1e-6, "{:?} != {:?}", p, q);
+            }
+        }
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let half = square.trim(0.0, 0.5, 1e-9);
+        assert_els(
+            &half,
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(10.0, 10.0),
+            ],
+        );
+        assert_eq!(square.trim(0.0, 1.0, 1e-9).to_svg(), square.to_svg());
+
+        // Wrap around the start of a closed path.
+        let wrapped = square.trim(0.875, 0.125, 1e-9);
+        assert_els(
+            &wrapped,
+            &[
+                Vec2::new(0.0, 5.0),
+                Vec2::new(0.0, 0.0),
+                Vec2::new(5.0, 0.0),
+            ],
+        );
+        assert!((wrapped.perimeter(1e-9) - 10.0).abs() < 1e-6);
+
+        // Wrapping with one of the ranges empty.
+        let head = square.trim(1.0, 0.5, 1e-9);
+        assert!(matches!(head.elements()[0], PathEl::Moveto(_)));
+        assert_els(
+            &head,
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(10.0, 10.0),
+            ],
+        );
+        let tail = square.trim(0.5, 0.0, 1e-9);
+        assert_els(
+            &tail,
+            &[
+                Vec2::new(10.0, 10.0),
+                Vec2::new(0.0, 10.0),
+                Vec2::new(0.0, 0.0),
+            ],
+        );
+
+        // Open paths swap the endpoints instead.
+        let open = BezPath::from_svg("M0 0L10 0M0 10L10 10").unwrap();
+        let middle = open.trim(0.75, 0.25, 1e-9);
+        assert_els(
+            &middle,
+            &[
+                Vec2::new(5.0, 0.0),
+                Vec2::new(10.0, 0.0),
+                Vec2::new(0.0, 10.0),
+                Vec2::new(5.0, 10.0),
+            ],
+        );
+
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let arc = circle.trim(0.25, 0.5, 1e-9);
+        assert!((arc.perimeter(1e-9) - 5.0 * std::f64::consts::PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn flatten_within_tolerance() {
+        let circle = Circle::new((0.0, 0.0), 10.0);
+        let path = circle.into_bez_path(1e-9);
+        for &tolerance in &[1.0, 0.1, 1e-3] {
+            let mut points = Vec::new();
+            path.flatten(tolerance, |el| match el {
+                PathEl::Moveto(p) | PathEl::Lineto(p) => points.push(p),
+                _ => (),
+            });
+            for pair in points.windows(2) {
+                // The midpoint of each chord is within tolerance of the circle.
+                let err = 10.0 - pair[0].lerp(pair[1], 0.5).hypot();
+                assert!(err <= tolerance, "{} > {}", err, tolerance);
+            }
+            assert_eq!(points[0], Vec2::new(10.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn bounding_box_affine() {
+        let path = BezPath::from_svg("M0 0C10 0 10 10 0 10L-5 5Z").unwrap();
+        let affine = Affine::rotate(0.4) * Affine::new([2.0, 0.0, 0.0, 1.0, 1.0, 0.0]);
+        let expected = (affine * &path).bounding_box();
+        let bbox = path.bounding_box_affine(affine);
+        assert!((bbox.x0 - expected.x0).abs() < 1e-12);
+        assert!((bbox.y0 - expected.y0).abs() < 1e-12);
+        assert!((bbox.x1 - expected.x1).abs() < 1e-12);
+        assert!((bbox.y1 - expected.y1).abs() < 1e-12);
+        let seg = path.get_seg(1).unwrap();
+        let seg_bbox = seg.bounding_box_affine(affine);
+        assert!(seg_bbox.x0 >= bbox.x0 && seg_bbox.x1 <= bbox.x1);
+        assert!(seg_bbox.y0 >= bbox.y0 && seg_bbox.y1 <= bbox.y1);
+    }
+
+    #[test]
+    fn control_box() {
+        let path = BezPath::from_svg("M0 0C10 -5 10 15 0 10L-5 5Z").unwrap();
+        let cbox = path.control_box();
+        assert_eq!(
+            (cbox.x0, cbox.y0, cbox.x1, cbox.y1),
+            (-5.0, -5.0, 10.0, 15.0)
+        );
+        let bbox = path.bounding_box();
+        assert!(cbox.x0 <= bbox.x0 && cbox.y0 <= bbox.y0);
+        assert!(cbox.x1 >= bbox.x1 && cbox.y1 >= bbox.y1);
+        assert_eq!(BezPath::new().control_box().area(), 0.0);
+    }
+
+    #[test]
+    fn monotonic() {
+        let path =
+            BezPath::from_svg("M0 0C10 -10 20 20 30 0C20 -20 10 -20 0 0ZM40 0L50 10").unwrap();
+        let mono = path.to_monotonic();
+        assert!(mono.elements().len() > path.elements().len());
+        assert!((mono.area() - path.area()).abs() < 1e-9);
+        let mut n_moveto = 0;
+        for el in mono.elements() {
+            if let PathEl::Moveto(_) = el {
+                n_moveto += 1;
+            }
+        }
+        assert_eq!(n_moveto, 2);
+        for seg in mono.segments() {
+            // Extrema may be found again at the piece ends, to within rounding.
+            let interior = seg
+                .extrema()
+                .into_iter()
+                .any(|t| t > 1e-9 && t < 1.0 - 1e-9);
+            assert!(!interior, "{:?}", seg);
+        }
+        let pieces = path.get_seg(1).unwrap().split_monotonic();
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].start(), Vec2::new(0.0, 0.0));
+        assert_eq!(pieces[2].end(), Vec2::new(30.0, 0.0));
+        assert_eq!(pieces[0].end(), pieces[1].start());
+    }
+
+    #[test]
+    fn flatten_into() {
+        let mut path = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10ZM20 0L30 0").unwrap();
+        let mut points = Vec::new();
+        let mut ends = Vec::new();
+        path.flatten_into(0.1, &mut points, &mut ends);
+        assert_eq!(ends.len(), 2);
+        assert_eq!(points[ends[0] - 1], Vec2::new(0.0, 0.0));
+        assert_eq!(
+            &points[ends[0]..],
+            &[Vec2::new(20.0, 0.0), Vec2::new(30.0, 0.0)]
+        );
+        assert_eq!(ends[1], points.len());
+
+        // The buffers are reused.
+        let capacity = points.capacity();
+        path = BezPath::from_svg("M0 0L1 1").unwrap();
+        path.flatten_into(0.1, &mut points, &mut ends);
+        assert_eq!(points, vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0)]);
+        assert_eq!(ends, vec![2]);
+        assert_eq!(points.capacity(), capacity);
+    }
+
+    #[test]
+    fn path_slice() {
+        let path = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10Z").unwrap();
+        // An externally owned buffer of elements.
+        let buf: Vec<PathEl> = path.elements().to_vec();
+        let slice = BezPathSlice::new(&buf);
+        assert_eq!(slice.area(), path.area());
+        assert_eq!(slice.perimeter(1e-9), path.perimeter(1e-9));
+        assert_eq!(slice.segments().count(), 3);
+        assert_eq!(slice.nearest(Vec2::new(5.0, -1.0), 1e-9).0, 0);
+        assert!(slice.get_seg(3).is_some());
+        let trimmed = slice.trim(0.0, 0.5, 1e-9);
+        assert_eq!(trimmed.to_svg(), path.trim(0.0, 0.5, 1e-9).to_svg());
+        assert_eq!(slice.to_bez_path_exact().to_svg(), path.to_svg());
+        assert_eq!(slice.geometry_hash(0.1), path.geometry_hash(0.1));
+        assert_eq!(slice.subdivide_even(3, 1e-9), path.subdivide_even(3, 1e-9));
+        assert_eq!(slice.split_even(3, 1e-9).len(), 3);
+        let lerped = slice.try_lerp(path.as_slice(), 0.5).unwrap();
+        assert_eq!(lerped.to_svg(), path.to_svg());
+        // Paths, borrowed or owned, are enclosed exactly.
+        let circle = path.bounding_circle();
+        assert_eq!(slice.bounding_circle().radius, circle.radius);
+        assert_eq!(buf.as_slice().bounding_circle().radius, circle.radius);
+        assert!((circle.radius - 0.5 * 200f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn edit_in_place() {
+        let mut path = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10Z").unwrap();
+        let capacity = path.0.capacity();
+        path.translate_mut(Vec2::new(1.0, 2.0));
+        assert_eq!(path.to_svg(), "M1 2L11 2C11 7 6 12 1 12Z");
+        path.apply_affine_mut(Affine::scale(2.0));
+        assert_eq!(path.to_svg(), "M2 4L22 4C22 14 12 24 2 24Z");
+        path.map_points(|p| Vec2::new(p.y, p.x));
+        assert_eq!(path.to_svg(), "M4 2L4 22C14 22 24 12 24 2Z");
+        path.retain_elements(|el| !matches!(el, PathEl::Curveto(..)));
+        assert_eq!(path.to_svg(), "M4 2L4 22Z");
+        assert_eq!(path.0.capacity(), capacity);
+    }
+
+    #[test]
+    fn geometry_hash() {
+        let a = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10Z").unwrap();
+        let b = BezPath::from_svg("M0.01 0L10 -0.01C10 5 5 10 0 10.02Z").unwrap();
+        assert_eq!(a.geometry_hash(0.1), b.geometry_hash(0.1));
+        assert_ne!(a.geometry_hash(0.001), b.geometry_hash(0.001));
+        let c = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10").unwrap();
+        assert_ne!(a.geometry_hash(0.1), c.geometry_hash(0.1));
+        // The hash is stable, so it may be stored.
+        assert_eq!(BezPath::new().geometry_hash(1.0), 0xcbf2_9ce4_8422_2325);
+        let line = BezPath::from_svg("M0 0L1 0").unwrap();
+        assert_eq!(line.geometry_hash(1.0), 0xb555_7aec_3499_883b);
+    }
+
+    #[test]
+    fn subdivide_even() {
+        let seg = PathSeg::Cubic(CubicBez::new(
+            (0.0, 0.0),
+            (0.0, 50.0),
+            (10.0, 50.0),
+            (100.0, 0.0),
+        ));
+        let ts = seg.subdivide_even(4, 1e-9);
+        assert_eq!(ts.len(), 5);
+        let len = seg.arclen(1e-9);
+        for piece in seg.split_even(4, 1e-9) {
+            assert!((piece.arclen(1e-9) - 0.25 * len).abs() < 1e-6);
+        }
+
+        let path = BezPath::from_svg("M0 0L10 0L10 10M20 0L30 0").unwrap();
+        let cuts = path.subdivide_even(6, 1e-9);
+        let expected = [
+            (0, 0.0),
+            (0, 0.5),
+            (1, 0.0),
+            (1, 0.5),
+            (2, 0.0),
+            (2, 0.5),
+            (2, 1.0),
+        ];
+        assert_eq!(cuts.len(), expected.len());
+        for (&(ix, t), &(ix0, t0)) in cuts.iter().zip(&expected) {
+            let p = path.segments().nth(ix).unwrap().eval(t);
+            let q = path.segments().nth(ix0).unwrap().eval(t0);
+            assert!((p - q).hypot() < 1e-9, "{:?} {:?}", p, q);
+        }
+        let pieces = path.split_even(3, 1e-9);
+        assert_eq!(pieces.len(), 3);
+        for piece in &pieces {
+            assert!((piece.perimeter(1e-9) - 10.0).abs() < 1e-9);
+        }
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn inline_storage() {
+        let rect = crate::Rect::new(0.0, 0.0, 10.0, 5.0).into_bez_path(0.1);
+        assert!(!rect.0.spilled());
+        let circle = Circle::new((0.0, 0.0), 1.0).into_bez_path(1e-9);
+        assert!(circle.0.spilled());
+        assert!((circle.area() - std::f64::consts::PI).abs() < 1e-6);
+    }
+}
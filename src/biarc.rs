@@ -0,0 +1,294 @@
+//! Approximating curves with circular arcs (biarc fitting).
+
+use std::f64::consts::PI;
+
+use crate::{Arc, BezPath, CubicBez, InterestingPoint, Line, ParamCurve, PathSeg, QuadBez, Vec2};
+
+/// The maximum depth of subdivision when fitting arcs.
+const MAX_DEPTH: usize = 16;
+
+/// The most points at which the error of a fit is measured.
+const MAX_ERROR_SAMPLES: usize = 1024;
+
+/// A segment of an arc spline, a curve made of circular arcs and lines.
+#[derive(Clone, Copy, Debug)]
+pub enum ArcSplineSeg {
+    Line(Line),
+    Arc(Arc),
+}
+
+impl CubicBez {
+    /// Approximate the curve with circular arcs and lines.
+    ///
+    /// The curve is split at inflections and cusps, then each piece is fit with
+    /// a biarc: a pair of arcs meeting tangentially, matching the endpoints and
+    /// end tangents of the piece. Pieces are subdivided until the distance from
+    /// every point of the curve to the arcs is within `tolerance`. This is
+    /// checked with a bound for pieces up to about a thousand times longer than
+    /// the tolerance, and estimated from a thousand samples for longer ones;
+    /// subdivision stops at a fixed depth, so a tolerance tiny compared to the
+    /// curve may not be met. The result is G1 continuous except at cusps, and
+    /// arcs that are straight are emitted as lines.
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn fit_arcs(&self, tolerance: f64) -> Vec<ArcSplineSeg> {
+        assert!(tolerance > 0.0, "tolerance must be positive");
+        let mut result = Vec::new();
+        let mut t0 = 0.0;
+        for p in self.interesting_points() {
+            match p {
+                InterestingPoint::Inflection(t) | InterestingPoint::Cusp(t) => {
+                    fit_rec(&self.subsegment(t0..t), tolerance, 0, &mut result);
+                    t0 = t;
+                }
+                _ => (),
+            }
+        }
+        fit_rec(&self.subsegment(t0..1.0), tolerance, 0, &mut result);
+        result
+    }
+}
+
+impl BezPath {
+    /// Approximate the path with circular arcs and lines.
+    ///
+    /// Returns the segments of each subpath in order; the start of each subpath
+    /// is the start of its first segment. Lines are kept as they are, and
+    /// curves are fit as in [`CubicBez::fit_arcs`](struct.CubicBez.html#method.fit_arcs).
+    ///
+    /// Panics if `tolerance` is not positive.
+    pub fn fit_arcs(&self, tolerance: f64) -> Vec<Vec<ArcSplineSeg>> {
+        assert!(tolerance > 0.0, "tolerance must be positive");
+        self.subpath_segs()
+            .into_iter()
+            .map(|sub| {
+                let mut result = Vec::new();
+                for (_, seg) in sub.segs {
+                    match seg {
+                        PathSeg::Line(line) => result.push(ArcSplineSeg::Line(line)),
+                        PathSeg::Quad(q) => result.extend(q.fit_arcs(tolerance)),
+                        PathSeg::Cubic(c) => result.extend(c.fit_arcs(tolerance)),
+                    }
+                }
+                result
+            })
+            .collect()
+    }
+}
+
+impl ArcSplineSeg {
+    /// The start point of the segment.
+    pub fn start(&self) -> Vec2 {
+        match self {
+            ArcSplineSeg::Line(line) => line.p0,
+            ArcSplineSeg::Arc(arc) => arc.start(),
+        }
+    }
+
+    /// The end point of the segment.
+    pub fn end(&self) -> Vec2 {
+        match self {
+            ArcSplineSeg::Line(line) => line.p1,
+            ArcSplineSeg::Arc(arc) => arc.end(),
+        }
+    }
+
+    /// The distance from a point to the segment.
+    fn distance(&self, p: Vec2) -> f64 {
+        match self {
+            ArcSplineSeg::Line(line) => {
+                let d = line.p1 - line.p0;
+                let t = ((p - line.p0).dot(d) / d.hypot2()).clamp(0.0, 1.0);
+                if t.is_nan() {
+                    (p - line.p0).hypot()
+                } else {
+                    (p - line.p0.lerp(line.p1, t)).hypot()
+                }
+            }
+            ArcSplineSeg::Arc(arc) => {
+                let v = p - arc.center;
+                // Angle from the start, measured in the direction of the sweep.
+                let mut angle = (v.atan2() - arc.start_angle) * arc.sweep_angle.signum();
+                angle = angle.rem_euclid(2.0 * PI);
+                if angle <= arc.sweep_angle.abs() {
+                    (v.hypot() - arc.radii.x).abs()
+                } else {
+                    (p - arc.start()).hypot().min((p - arc.end()).hypot())
+                }
+            }
+        }
+    }
+}
+
+fn fit_rec(c: &CubicBez, tolerance: f64, depth: usize, result: &mut Vec<ArcSplineSeg>) {
+    let (a, b) = biarc(c);
+    if depth >= MAX_DEPTH || fit_error_within(c, &a, &b, tolerance) {
+        result.push(a);
+        result.push(b);
+    } else {
+        let (c0, c1) = c.subdivide();
+        fit_rec(&c0, tolerance, depth + 1, result);
+        fit_rec(&c1, tolerance, depth + 1, result);
+    }
+}
+
+/// Whether every point of a cubic is within `tolerance` of a biarc.
+///
+/// A coarse estimate rejects most bad fits cheaply. To accept a fit, the
+/// curve is sampled densely enough that the error between samples is
+/// bounded: the distance to the arcs changes no faster than the point on the
+/// curve moves, and the speed of the curve is at most three times its longest
+/// control leg. Past `MAX_ERROR_SAMPLES`, the samples are only an estimate.
+fn fit_error_within(c: &CubicBez, a: &ArcSplineSeg, b: &ArcSplineSeg, tolerance: f64) -> bool {
+    let err = |n: usize| {
+        (1..n)
+            .map(|i| {
+                let p = c.eval(i as f64 / n as f64);
+                a.distance(p).min(b.distance(p))
+            })
+            .fold(0.0, f64::max)
+    };
+    if err(16) > tolerance {
+        return false;
+    }
+    let legs = [c.p1 - c.p0, c.p2 - c.p1, c.p3 - c.p2];
+    let speed = 3.0 * legs.iter().map(|v| v.hypot()).fold(0.0, f64::max);
+    let n = (speed / tolerance).ceil();
+    if n <= MAX_ERROR_SAMPLES as f64 {
+        let n = (n as usize).max(16);
+        err(n) + 0.5 * speed / n as f64 <= tolerance
+    } else {
+        err(MAX_ERROR_SAMPLES) <= tolerance
+    }
+}
+
+/// Fit a biarc to the endpoints and end tangents of a cubic.
+///
+/// The joint is placed so that the two arcs have equal tangent lengths; see
+/// <https://www.ryanjuckett.com/biarc-interpolation/>.
+fn biarc(c: &CubicBez) -> (ArcSplineSeg, ArcSplineSeg) {
+    let seg = PathSeg::Cubic(*c);
+    let (p0, p3) = (c.p0, c.p3);
+    let (t0, t1) = (seg.tangent(0.0), seg.tangent(1.0));
+    let v = p3 - p0;
+    let t = t0 + t1;
+    let denom = 2.0 * (1.0 - t0.dot(t1));
+    let d = if denom.abs() < 1e-12 {
+        // Parallel tangents; the equation degenerates to a linear one.
+        let vt1 = v.dot(t1);
+        if vt1.abs() < 1e-12 {
+            0.5 * v.hypot()
+        } else {
+            v.hypot2() / (4.0 * vt1)
+        }
+    } else {
+        let vt = v.dot(t);
+        (-vt + (vt * vt + denom * v.hypot2()).sqrt()) / denom
+    };
+    let joint = (p0 + d * t0).lerp(p3 - d * t1, 0.5);
+    let first = arc_from_tangent(p0, t0, joint);
+    // The second arc is fit backwards from the end, then reversed.
+    let second = match arc_from_tangent(p3, -t1, joint) {
+        ArcSplineSeg::Arc(arc) => {
+            let start_angle = arc.start_angle + arc.sweep_angle;
+            ArcSplineSeg::Arc(Arc {
+                start_angle,
+                sweep_angle: -arc.sweep_angle,
+                ..arc
+            })
+        }
+        ArcSplineSeg::Line(_) => ArcSplineSeg::Line(Line::new(joint, p3)),
+    };
+    (first, second)
+}
+
+/// The circular arc from `p` with tangent `t` at `p`, ending at `q`.
+fn arc_from_tangent(p: Vec2, t: Vec2, q: Vec2) -> ArcSplineSeg {
+    let n = Vec2::new(-t.y, t.x);
+    let chord = q - p;
+    let nc = n.dot(chord);
+    if nc.abs() <= 1e-9 * chord.hypot() {
+        return ArcSplineSeg::Line(Line::new(p, q));
+    }
+    // Signed radius, positive when turning towards the normal.
+    let r = 0.5 * chord.hypot2() / nc;
+    let center = p + r * n;
+    let a0 = (p - center).atan2();
+    let a1 = (q - center).atan2();
+    let mut sweep = a1 - a0;
+    if r > 0.0 && sweep < 0.0 {
+        sweep += 2.0 * PI;
+    } else if r < 0.0 && sweep > 0.0 {
+        sweep -= 2.0 * PI;
+    }
+    ArcSplineSeg::Arc(Arc {
+        center,
+        radii: Vec2::new(r.abs(), r.abs()),
+        start_angle: a0,
+        sweep_angle: sweep,
+        x_rotation: 0.0,
+    })
+}
+
+impl QuadBez {
+    /// Approximate the curve with circular arcs and lines.
+    ///
+    /// See [`CubicBez::fit_arcs`](struct.CubicBez.html#method.fit_arcs).
+    pub fn fit_arcs(&self, tolerance: f64) -> Vec<ArcSplineSeg> {
+        self.raise().fit_arcs(tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArcSplineSeg, BezPath, Circle, CubicBez, ParamCurve, Shape, Vec2};
+
+    fn check_fit(c: &CubicBez, segs: &[ArcSplineSeg], tolerance: f64) {
+        assert!((segs[0].start() - c.p0).hypot() < 1e-9);
+        assert!((segs[segs.len() - 1].end() - c.p3).hypot() < 1e-9);
+        for pair in segs.windows(2) {
+            assert!((pair[0].end() - pair[1].start()).hypot() < 1e-9);
+        }
+        for i in 0..=100 {
+            let p = c.eval(i as f64 / 100.0);
+            let d = segs.iter().map(|s| s.distance(p)).fold(1e300, f64::min);
+            assert!(d <= tolerance, "{} > {}", d, tolerance);
+        }
+    }
+
+    #[test]
+    fn fit_arcs_cubic() {
+        let c = CubicBez::new((0.0, 0.0), (30.0, 0.0), (60.0, 50.0), (100.0, 20.0));
+        // Tight tolerances stay cheap, as the error samples are capped.
+        for &tolerance in &[1.0, 0.1, 1e-3, 1e-6] {
+            let segs = c.fit_arcs(tolerance);
+            check_fit(&c, &segs, tolerance);
+        }
+        // An S curve is split at its inflection.
+        let s = CubicBez::new((0.0, 0.0), (10.0, 10.0), (0.0, 10.0), (10.0, 20.0));
+        let segs = s.fit_arcs(0.01);
+        check_fit(&s, &segs, 0.01);
+    }
+
+    #[test]
+    fn fit_arcs_path() {
+        // A circle is fit closely by a small number of arcs.
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let subpaths = circle.fit_arcs(1e-3);
+        assert_eq!(subpaths.len(), 1);
+        for seg in &subpaths[0] {
+            match seg {
+                ArcSplineSeg::Arc(arc) => {
+                    assert!((arc.radii.x - 10.0).abs() < 1e-2);
+                    assert!(arc.center.hypot() < 1e-2);
+                    assert!(arc.sweep_angle > 0.0);
+                }
+                ArcSplineSeg::Line(_) => panic!("unexpected line"),
+            }
+        }
+        let path = BezPath::from_svg("M0 0L10 0").unwrap();
+        let subpaths = path.fit_arcs(1e-3);
+        assert_eq!(subpaths[0].len(), 1);
+        assert_eq!(subpaths[0][0].end(), Vec2::new(10.0, 0.0));
+    }
+}
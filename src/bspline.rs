@@ -0,0 +1,215 @@
+//! B-spline curves.
+
+use crate::{BezPath, Vec2};
+
+/// A B-spline curve.
+///
+/// The curve has `control_points.len() + degree + 1` knots, and is defined
+/// over the parameter range from `knots[degree]` to `knots[control_points.len()]`.
+#[derive(Clone, Debug)]
+pub struct BSpline {
+    /// The control points.
+    pub control_points: Vec<Vec2>,
+    /// The polynomial degree of the pieces of the curve.
+    pub degree: usize,
+    /// The knot vector, in nondecreasing order.
+    pub knots: Vec<f64>,
+}
+
+impl BSpline {
+    /// Create a new B-spline from control points, degree, and knots.
+    ///
+    /// Panics if the number of knots is not the number of control points plus
+    /// the degree plus one, if there are no more control points than the degree,
+    /// or if the knots are not in nondecreasing order.
+    pub fn new(control_points: Vec<Vec2>, degree: usize, knots: Vec<f64>) -> BSpline {
+        assert!(control_points.len() > degree, "too few control points");
+        assert_eq!(
+            knots.len(),
+            control_points.len() + degree + 1,
+            "wrong number of knots"
+        );
+        assert!(
+            knots.windows(2).all(|w| w[0] <= w[1]),
+            "knots must be nondecreasing"
+        );
+        BSpline {
+            control_points,
+            degree,
+            knots,
+        }
+    }
+
+    /// Create a uniform B-spline, with knots at consecutive integers.
+    ///
+    /// The curve doesn't pass through the first and last control points in
+    /// general. The parameter range is from `degree` to the number of control points.
+    pub fn uniform(control_points: Vec<Vec2>, degree: usize) -> BSpline {
+        let knots = (0..control_points.len() + degree + 1)
+            .map(|i| i as f64)
+            .collect();
+        BSpline::new(control_points, degree, knots)
+    }
+
+    /// The parameter range over which the curve is defined.
+    pub fn domain(&self) -> (f64, f64) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    /// Evaluate the curve at parameter `t`, which is clamped to the domain.
+    pub fn eval(&self, t: f64) -> Vec2 {
+        let (t0, t1) = self.domain();
+        let t = t.clamp(t0, t1);
+        self.eval_in_span(self.span(t), t)
+    }
+
+    /// Evaluate the polynomial piece over knot span `k` at parameter `t`.
+    ///
+    /// Unlike [`eval`](#method.eval), this picks the piece explicitly, which
+    /// matters at knots where the curve isn't smooth.
+    pub(crate) fn eval_in_span(&self, k: usize, t: f64) -> Vec2 {
+        self.blossom(k, &vec![t; self.degree])
+    }
+
+    /// The derivative of the curve, a B-spline of one lower degree.
+    ///
+    /// Panics if the degree is zero.
+    pub fn deriv(&self) -> BSpline {
+        let p = self.degree;
+        assert!(p > 0, "can't differentiate a degree 0 B-spline");
+        let u = &self.knots;
+        let control_points = self
+            .control_points
+            .windows(2)
+            .enumerate()
+            .map(|(i, w)| {
+                let dt = u[i + p + 1] - u[i + 1];
+                if dt > 0.0 {
+                    (p as f64 / dt) * (w[1] - w[0])
+                } else {
+                    Vec2::default()
+                }
+            })
+            .collect();
+        BSpline {
+            control_points,
+            degree: p - 1,
+            knots: u[1..u.len() - 1].to_vec(),
+        }
+    }
+
+    /// Convert the curve to a path of Bézier segments.
+    ///
+    /// This is exact, and is equivalent to inserting each knot in the domain
+    /// until its multiplicity equals the degree. Pieces of degree 1, 2 and 3
+    /// become lines, quadratic and cubic Béziers respectively.
+    ///
+    /// Panics if the degree is greater than 3.
+    pub fn to_path(&self) -> BezPath {
+        let p = self.degree;
+        assert!(p <= 3, "only B-splines up to degree 3 can be converted");
+        let mut path = BezPath::new();
+        path.moveto(self.eval(self.domain().0));
+        for k in p..self.control_points.len() {
+            let (a, b) = (self.knots[k], self.knots[k + 1]);
+            if a == b {
+                continue;
+            }
+            // The Bézier control points of the piece are its blossom values
+            // with the arguments at the ends of the knot span.
+            let pts: Vec<Vec2> = (1..=p)
+                .map(|j| {
+                    let args: Vec<f64> = (0..p).map(|i| if i < j { b } else { a }).collect();
+                    self.blossom(k, &args)
+                })
+                .collect();
+            match p {
+                0 => (),
+                1 => path.lineto(pts[0]),
+                2 => path.quadto(pts[0], pts[1]),
+                _ => path.curveto(pts[0], pts[1], pts[2]),
+            }
+        }
+        path
+    }
+
+    /// The index of the knot span containing `t`, within the domain.
+    pub(crate) fn span(&self, t: f64) -> usize {
+        let n = self.control_points.len();
+        (self.degree..n)
+            .rev()
+            .find(|&k| self.knots[k] <= t && self.knots[k] < self.knots[k + 1])
+            .unwrap_or(self.degree)
+    }
+
+    /// Evaluate the blossom of the piece over knot span `k`, using de Boor's algorithm.
+    fn blossom(&self, k: usize, args: &[f64]) -> Vec2 {
+        let p = self.degree;
+        let u = &self.knots;
+        let mut d: Vec<Vec2> = self.control_points[k - p..=k].to_vec();
+        for (r, &x) in (1..=p).zip(args) {
+            for j in (r..=p).rev() {
+                let i = j + k - p;
+                let alpha = (x - u[i]) / (u[i + p + 1 - r] - u[i]);
+                d[j] = d[j - 1].lerp(d[j], alpha);
+            }
+        }
+        d[p]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BSpline, ParamCurve, Vec2};
+
+    fn assert_near(p0: Vec2, p1: Vec2) {
+        assert!((p1 - p0).hypot() < 1e-9, "{:?} != {:?}", p0, p1);
+    }
+
+    #[test]
+    fn bspline_uniform_cubic() {
+        let pts = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(6.0, 0.0),
+            Vec2::new(6.0, 6.0),
+            Vec2::new(0.0, 6.0),
+            Vec2::new(0.0, 12.0),
+        ];
+        let spline = BSpline::uniform(pts.clone(), 3);
+        assert_eq!(spline.domain(), (3.0, 5.0));
+        // A uniform cubic B-spline starts at (P0 + 4 P1 + P2) / 6.
+        assert_near(spline.eval(3.0), (pts[0] + 4.0 * pts[1] + pts[2]) / 6.0);
+        assert_near(spline.eval(5.0), (pts[2] + 4.0 * pts[3] + pts[4]) / 6.0);
+
+        let path = spline.to_path();
+        let segs: Vec<_> = path.segments().collect();
+        assert_eq!(segs.len(), 2);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert_near(segs[0].eval(t), spline.eval(3.0 + t));
+            assert_near(segs[1].eval(t), spline.eval(4.0 + t));
+        }
+
+        let deriv = spline.deriv();
+        assert_eq!(deriv.degree, 2);
+        let h = 1e-6;
+        let numeric = (spline.eval(3.5 + h) - spline.eval(3.5 - h)) / (2.0 * h);
+        assert!((deriv.eval(3.5) - numeric).hypot() < 1e-6);
+    }
+
+    #[test]
+    fn bspline_clamped_quadratic() {
+        let pts = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(2.0, 0.0),
+        ];
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let spline = BSpline::new(pts, 2, knots);
+        let path = spline.to_path();
+        assert_eq!(path.to_svg(), "M0 0Q1 2 2 0");
+    }
+}
@@ -0,0 +1,173 @@
+//! A Bézier path that memoizes derived quantities.
+
+use std::cell::Cell;
+
+use crate::{Affine, BezPath, Circle, PathEl, Rect, Shape, Vec2};
+
+/// A Bézier path that caches its bounding box and segment count.
+///
+/// Both are computed on first use and reused until the path is mutated.
+/// All mutation goes through `&mut self`, which clears the cache, so
+/// repeated queries on an unchanged path (for example once per frame in
+/// an interactive application) are cheap.
+#[derive(Clone, Default, Debug)]
+pub struct CachedBezPath {
+    path: BezPath,
+    bbox: Cell<Option<Rect>>,
+    n_segs: Cell<Option<usize>>,
+}
+
+impl CachedBezPath {
+    /// Wrap a path.
+    pub fn new(path: BezPath) -> CachedBezPath {
+        CachedBezPath {
+            path,
+            bbox: Cell::new(None),
+            n_segs: Cell::new(None),
+        }
+    }
+
+    /// Get the underlying path.
+    #[inline]
+    pub fn path(&self) -> &BezPath {
+        &self.path
+    }
+
+    /// Get mutable access to the underlying path.
+    ///
+    /// This clears the cache.
+    pub fn path_mut(&mut self) -> &mut BezPath {
+        self.invalidate();
+        &mut self.path
+    }
+
+    /// Unwrap the underlying path.
+    pub fn into_inner(self) -> BezPath {
+        self.path
+    }
+
+    /// Push a generic path element onto the path.
+    pub fn push(&mut self, el: PathEl) {
+        self.path_mut().push(el);
+    }
+
+    /// Push a "move to" element onto the path.
+    pub fn moveto<V: Into<Vec2>>(&mut self, p: V) {
+        self.path_mut().moveto(p);
+    }
+
+    /// Push a "line to" element onto the path.
+    pub fn lineto<V: Into<Vec2>>(&mut self, p: V) {
+        self.path_mut().lineto(p);
+    }
+
+    /// Push a "quad to" element onto the path.
+    pub fn quadto<V: Into<Vec2>>(&mut self, p1: V, p2: V) {
+        self.path_mut().quadto(p1, p2);
+    }
+
+    /// Push a "curve to" element onto the path.
+    pub fn curveto<V: Into<Vec2>>(&mut self, p1: V, p2: V, p3: V) {
+        self.path_mut().curveto(p1, p2, p3);
+    }
+
+    /// Push a "close path" element onto the path.
+    pub fn closepath(&mut self) {
+        self.path_mut().closepath();
+    }
+
+    /// Apply an affine transform to the path.
+    pub fn apply_affine(&mut self, affine: Affine) {
+        self.path_mut().apply_affine(affine);
+    }
+
+    /// The number of segments in the path, computed once.
+    pub fn segment_count(&self) -> usize {
+        if let Some(n) = self.n_segs.get() {
+            return n;
+        }
+        let n = self.path.segments().count();
+        self.n_segs.set(Some(n));
+        n
+    }
+
+    fn invalidate(&mut self) {
+        self.bbox.set(None);
+        self.n_segs.set(None);
+    }
+}
+
+impl From<BezPath> for CachedBezPath {
+    fn from(path: BezPath) -> CachedBezPath {
+        CachedBezPath::new(path)
+    }
+}
+
+impl Shape for CachedBezPath {
+    type BezPathIter = std::vec::IntoIter<PathEl>;
+
+    fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+        self.path.to_bez_path(tolerance)
+    }
+
+    fn area(&self) -> f64 {
+        self.path.area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.path.perimeter(accuracy)
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        self.path.winding(pt)
+    }
+
+    /// The bounding box, computed once.
+    fn bounding_box(&self) -> Rect {
+        if let Some(bbox) = self.bbox.get() {
+            return bbox;
+        }
+        let bbox = self.path.bounding_box();
+        self.bbox.set(Some(bbox));
+        bbox
+    }
+
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        self.path.bounding_box_affine(affine)
+    }
+
+    fn centroid(&self) -> Vec2 {
+        self.path.centroid()
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        self.path.bounding_circle()
+    }
+
+    fn as_path_slice(&self) -> Option<&[PathEl]> {
+        Some(self.path.elements())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, CachedBezPath, Shape};
+
+    #[test]
+    fn cache_invalidation() {
+        let mut path = CachedBezPath::new(BezPath::from_svg("M0 0L10 0L10 5Z").unwrap());
+        assert_eq!(path.segment_count(), 3);
+        let bbox = path.bounding_box();
+        assert_eq!((bbox.x1, bbox.y1), (10.0, 5.0));
+        assert_eq!(path.bounding_box().y1, 5.0);
+
+        path.moveto((20.0, 20.0));
+        path.lineto((30.0, 25.0));
+        assert_eq!(path.segment_count(), 4);
+        let bbox = path.bounding_box();
+        assert_eq!((bbox.x1, bbox.y1), (30.0, 25.0));
+
+        *path.path_mut() = BezPath::new();
+        assert_eq!(path.segment_count(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Implementation of capsule shape.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{Arc, Circle, Line, PathEl, Rect, Shape, Vec2};
+
+/// A capsule: the points within a radius of a line segment.
+///
+/// This is the shape of a line stroked with round caps.
+#[derive(Clone, Copy, Debug)]
+pub struct Capsule {
+    /// The segment at the core of the capsule.
+    pub segment: Line,
+    /// The radius, half the width of the capsule.
+    pub radius: f64,
+}
+
+impl Capsule {
+    /// A new capsule from the endpoints of its segment and its radius.
+    #[inline]
+    pub fn new(p0: impl Into<Vec2>, p1: impl Into<Vec2>, radius: f64) -> Capsule {
+        Capsule {
+            segment: Line::new(p0.into(), p1.into()),
+            radius,
+        }
+    }
+
+    /// The length of the segment.
+    fn segment_length(&self) -> f64 {
+        (self.segment.p1 - self.segment.p0).hypot()
+    }
+
+    /// The distance from a point to the segment.
+    fn segment_distance(&self, pt: Vec2) -> f64 {
+        let Line { p0, p1 } = self.segment;
+        let d = p1 - p0;
+        let len2 = d.hypot2();
+        let t = if len2 > 0.0 {
+            ((pt - p0).dot(d) / len2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (pt - p0.lerp(p1, t)).hypot()
+    }
+}
+
+impl Shape for Capsule {
+    type BezPathIter = std::vec::IntoIter<PathEl>;
+
+    fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+        let Line { p0, p1 } = self.segment;
+        let r = self.radius.abs();
+        let angle = (p1 - p0).atan2();
+        let mut els = vec![PathEl::Moveto(p0 + r * Vec2::from_angle(angle - FRAC_PI_2))];
+        for &(center, start_angle) in &[(p1, angle - FRAC_PI_2), (p0, angle + FRAC_PI_2)] {
+            els.push(PathEl::Lineto(center + r * Vec2::from_angle(start_angle)));
+            if r > 0.0 {
+                let arc = Arc {
+                    center,
+                    radii: Vec2::new(r, r),
+                    start_angle,
+                    sweep_angle: PI,
+                    x_rotation: 0.0,
+                };
+                els.extend(arc.append_iter(tolerance));
+            }
+        }
+        els.push(PathEl::Closepath);
+        els.into_iter()
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        let r = self.radius.abs();
+        2.0 * r * self.segment_length() + PI * r * r
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        2.0 * self.segment_length() + 2.0 * PI * self.radius.abs()
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        if self.segment_distance(pt) < self.radius.abs() {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn bounding_box(&self) -> Rect {
+        let r = self.radius.abs();
+        let Line { p0, p1 } = self.segment;
+        let b = Rect::from_points(p0, p1);
+        Rect::new(b.x0 - r, b.y0 - r, b.x1 + r, b.y1 + r)
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.segment.p0.lerp(self.segment.p1, 0.5)
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        let radius = 0.5 * self.segment_length() + self.radius.abs();
+        Circle::new(self.centroid(), radius)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let Line { p0, p1 } = self.segment;
+        let end = if (p1 - p0).dot(direction) > 0.0 {
+            p1
+        } else {
+            p0
+        };
+        let len = direction.hypot();
+        if len > 0.0 {
+            end + direction * (self.radius.abs() / len)
+        } else {
+            end
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Capsule, Shape, Vec2};
+
+    #[test]
+    fn capsule_shape() {
+        let capsule = Capsule::new((0.0, 0.0), (3.0, 4.0), 1.0);
+        let path = capsule.into_bez_path(1e-9);
+        assert!((capsule.area() - path.area().abs()).abs() < 1e-6);
+        assert!((capsule.perimeter(1e-9) - path.perimeter(1e-9)).abs() < 1e-6);
+        for &pt in &[
+            Vec2::new(1.5, 2.0),
+            Vec2::new(3.5, 4.5),
+            Vec2::new(-0.8, 0.0),
+            Vec2::new(2.0, 0.0),
+        ] {
+            assert_eq!(capsule.winding(pt), path.winding(pt).abs(), "{:?}", pt);
+        }
+        let bb = capsule.bounding_box();
+        assert_eq!((bb.x0, bb.y0, bb.x1, bb.y1), (-1.0, -1.0, 4.0, 5.0));
+
+        let d = Vec2::new(1.0, 1.0);
+        let s = capsule.support(d);
+        let expected = Vec2::new(3.0, 4.0) + d / 2f64.sqrt();
+        assert!((s - expected).hypot() < 1e-12);
+        assert!((s.dot(d) - path.support(d).dot(d)).abs() < 1e-6);
+    }
+}
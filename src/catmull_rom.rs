@@ -0,0 +1,143 @@
+//! Catmull-Rom splines.
+
+use crate::{BezPath, Vec2};
+
+/// A Catmull-Rom spline, a smooth curve interpolating a sequence of points.
+///
+/// The `alpha` parameter selects the parametrization: 0 for the uniform
+/// spline, 0.5 for the centripetal spline, and 1 for the chordal spline. The
+/// centripetal spline is generally the best choice, as it never forms cusps or
+/// self-intersections within a segment.
+#[derive(Clone, Debug)]
+pub struct CatmullRom {
+    /// The points the curve passes through.
+    pub points: Vec<Vec2>,
+    /// The exponent of the knot spacing.
+    pub alpha: f64,
+    /// Whether the curve connects the last point back to the first.
+    pub closed: bool,
+}
+
+impl CatmullRom {
+    /// Create a new open spline through the points.
+    pub fn new(points: &[Vec2], alpha: f64) -> CatmullRom {
+        CatmullRom {
+            points: points.to_vec(),
+            alpha,
+            closed: false,
+        }
+    }
+
+    /// Convert the spline to a path of cubic Béziers.
+    ///
+    /// The conversion is exact. The tangents at the ends of an open spline are
+    /// chosen by reflecting the neighboring points, which makes the first and
+    /// last segments curve in the same way as their neighbors.
+    pub fn to_path(&self) -> BezPath {
+        let pts = &self.points;
+        let n = pts.len();
+        let mut path = BezPath::new();
+        if n == 0 {
+            return path;
+        }
+        path.moveto(pts[0]);
+        if n == 1 {
+            return path;
+        }
+        let point = |i: isize| -> Vec2 {
+            if self.closed {
+                pts[i.rem_euclid(n as isize) as usize]
+            } else if i < 0 {
+                2.0 * pts[0] - pts[1]
+            } else if i as usize >= n {
+                2.0 * pts[n - 1] - pts[n - 2]
+            } else {
+                pts[i as usize]
+            }
+        };
+        let n_segs = if self.closed { n } else { n - 1 };
+        for i in 0..n_segs as isize {
+            let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+            let d1 = (p1 - p0).hypot().powf(self.alpha);
+            let d2 = (p2 - p1).hypot().powf(self.alpha);
+            let d3 = (p3 - p2).hypot().powf(self.alpha);
+            // See "Parameterization and Applications of Catmull-Rom Curves"
+            // by Yuksel, Schaefer, and Keyser.
+            let b1 = if d1 > 0.0 && d2 > 0.0 {
+                (d1 * d1 * p2 - d2 * d2 * p0 + (2.0 * d1 * d1 + 3.0 * d1 * d2 + d2 * d2) * p1)
+                    / (3.0 * d1 * (d1 + d2))
+            } else {
+                p1
+            };
+            let b2 = if d3 > 0.0 && d2 > 0.0 {
+                (d3 * d3 * p1 - d2 * d2 * p3 + (2.0 * d3 * d3 + 3.0 * d3 * d2 + d2 * d2) * p2)
+                    / (3.0 * d3 * (d3 + d2))
+            } else {
+                p2
+            };
+            path.curveto(b1, b2, p2);
+        }
+        if self.closed {
+            path.closepath();
+        }
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CatmullRom, ParamCurve, PathEl, Vec2};
+
+    #[test]
+    fn catmull_rom_interpolates() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 5.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(30.0, 30.0),
+        ];
+        for &alpha in &[0.0, 0.5, 1.0] {
+            let path = CatmullRom::new(&points, alpha).to_path();
+            let segs: Vec<_> = path.segments().collect();
+            assert_eq!(segs.len(), 3);
+            for (i, seg) in segs.iter().enumerate() {
+                assert_eq!(seg.start(), points[i]);
+                assert_eq!(seg.end(), points[i + 1]);
+            }
+            // The tangents at interior points are continuous.
+            for pair in segs.windows(2) {
+                let t0 = pair[0].tangent(1.0);
+                let t1 = pair[1].tangent(0.0);
+                assert!((t0 - t1).hypot() < 1e-9);
+            }
+        }
+        // The uniform spline has tangents (p[i+1] - p[i-1]) / 2.
+        let path = CatmullRom::new(&points, 0.0).to_path();
+        match path.elements()[2] {
+            PathEl::Curveto(p1, _, _) => {
+                assert_eq!(p1, Vec2::new(10.0, 5.0) + Vec2::new(20.0, 0.0) / 6.0)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn catmull_rom_closed() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ];
+        let mut spline = CatmullRom::new(&points, 0.5);
+        spline.closed = true;
+        let path = spline.to_path();
+        assert_eq!(path.segments().count(), 4);
+        assert!(matches!(path.elements().last(), Some(PathEl::Closepath)));
+        let segs: Vec<_> = path.segments().collect();
+        let t0 = segs[3].tangent(1.0);
+        let t1 = segs[0].tangent(0.0);
+        assert!((t0 - t1).hypot() < 1e-9);
+        assert!(CatmullRom::new(&[], 0.5).to_path().is_empty());
+    }
+}
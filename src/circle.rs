@@ -3,7 +3,10 @@
 use std::f64::consts::{FRAC_PI_2, PI};
 use std::ops::{Add, Sub};
 
-use crate::{PathEl, Rect, Shape, Vec2};
+use arrayvec::ArrayVec;
+
+use crate::common::solve_3x3;
+use crate::{Affine, Ellipse, Line, PathEl, Rect, Shape, Vec2};
 
 /// A circle.
 #[derive(Clone, Copy, Default, Debug)]
@@ -23,8 +26,329 @@ impl Circle {
             radius,
         }
     }
+
+    /// Fit a circle to a set of points.
+    ///
+    /// An initial estimate is found by the algebraic method of Kåsa, then
+    /// refined by Gauss-Newton iteration to minimize the sum of squared
+    /// geometric distances from the points to the circle.
+    ///
+    /// Returns the circle and the root mean square distance of the points
+    /// to it. Returns `None` if there are fewer than three points or they
+    /// are collinear.
+    pub fn fit(points: &[Vec2]) -> Option<(Circle, f64)> {
+        if points.len() < 3 {
+            return None;
+        }
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec2::default(), |a, &p| a + p) / n;
+        let mut m = [[0.0; 3]; 3];
+        let mut b = [0.0; 3];
+        for &p in points {
+            let q = p - mean;
+            let z = q.hypot2();
+            let row = [q.x, q.y, 1.0];
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] += row[i] * row[j];
+                }
+                b[i] -= row[i] * z;
+            }
+        }
+        let [d, e, f] = solve_3x3(m, b)?;
+        let mut center = Vec2::new(-0.5 * d, -0.5 * e);
+        let mut radius = (center.hypot2() - f).max(0.0).sqrt();
+        for _ in 0..MAX_FIT_ITER {
+            let mut jtj = [[0.0; 3]; 3];
+            let mut jtr = [0.0; 3];
+            for &p in points {
+                let v = p - mean - center;
+                let dist = v.hypot();
+                if dist == 0.0 {
+                    continue;
+                }
+                let row = [-v.x / dist, -v.y / dist, -1.0];
+                let r = dist - radius;
+                for i in 0..3 {
+                    for j in 0..3 {
+                        jtj[i][j] += row[i] * row[j];
+                    }
+                    jtr[i] -= row[i] * r;
+                }
+            }
+            let delta = match solve_3x3(jtj, jtr) {
+                Some(delta) => delta,
+                None => break,
+            };
+            center += Vec2::new(delta[0], delta[1]);
+            radius += delta[2];
+            if delta
+                .iter()
+                .all(|x| x.abs() <= 1e-12 * radius.abs().max(1.0))
+            {
+                break;
+            }
+        }
+        let sum: f64 = points
+            .iter()
+            .map(|&p| ((p - mean - center).hypot() - radius).powi(2))
+            .sum();
+        Some((Circle::new(mean + center, radius), (sum / n).sqrt()))
+    }
+}
+
+/// The relative tolerance for classifying configurations as tangent or coincident.
+const TANGENT_EPSILON: f64 = 1e-12;
+
+/// The result of intersecting a circle with another circle or a line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CircleIntersection {
+    /// The shapes don't meet, and neither is inside the other.
+    Disjoint,
+    /// The first circle lies inside the second without touching it.
+    Inside,
+    /// The first circle encloses the second without touching it.
+    Encloses,
+    /// The shapes touch at a single point.
+    Tangent(Vec2),
+    /// The shapes cross at two points.
+    ///
+    /// For two circles, the first point is on the side of the line from the
+    /// first center to the second that a clockwise rotation turns towards
+    /// (the negative side, in the sense of `Vec2::cross`). For a line, the
+    /// points are in order along the line's direction.
+    Crossing(Vec2, Vec2),
+    /// The circles are the same, so they meet everywhere.
+    Coincident,
+}
+
+impl CircleIntersection {
+    /// The intersection points, if there are finitely many.
+    pub fn points(&self) -> ArrayVec<[Vec2; 2]> {
+        let mut result = ArrayVec::new();
+        match *self {
+            CircleIntersection::Tangent(p) => result.push(p),
+            CircleIntersection::Crossing(p0, p1) => {
+                result.push(p0);
+                result.push(p1);
+            }
+            _ => (),
+        }
+        result
+    }
+}
+
+impl Circle {
+    /// Intersect with another circle.
+    ///
+    /// Configurations within a small relative tolerance of tangency are
+    /// reported as tangent, so that touching circles built from rounded
+    /// coordinates are classified as such.
+    pub fn intersect_circle(&self, other: &Circle) -> CircleIntersection {
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        let v = other.center - self.center;
+        let d = v.hypot();
+        let eps = TANGENT_EPSILON * (r0 + r1 + d);
+        if d <= eps && (r0 - r1).abs() <= eps {
+            return CircleIntersection::Coincident;
+        }
+        if d > r0 + r1 + eps {
+            return CircleIntersection::Disjoint;
+        }
+        if d < (r0 - r1).abs() - eps {
+            return if r0 < r1 {
+                CircleIntersection::Inside
+            } else {
+                CircleIntersection::Encloses
+            };
+        }
+        let dir = v / d;
+        // Distance from our center to the radical line, along the center line.
+        let a = (d * d + r0 * r0 - r1 * r1) / (2.0 * d);
+        let h2 = r0 * r0 - a * a;
+        let base = self.center + a * dir;
+        if d >= r0 + r1 - eps || d <= (r0 - r1).abs() + eps || h2 <= 0.0 {
+            return CircleIntersection::Tangent(self.center + a.signum() * r0 * dir);
+        }
+        let h = h2.sqrt();
+        let perp = Vec2::new(-dir.y, dir.x);
+        CircleIntersection::Crossing(base - h * perp, base + h * perp)
+    }
+
+    /// Intersect with a line.
+    ///
+    /// The line is treated as extending infinitely through its two points.
+    /// The result is `Disjoint`, `Tangent` or `Crossing`; a degenerate line
+    /// with coincident points is `Disjoint`.
+    pub fn intersect_line(&self, line: Line) -> CircleIntersection {
+        let r = self.radius.abs();
+        let d = line.p1 - line.p0;
+        let len = d.hypot();
+        if len == 0.0 {
+            return CircleIntersection::Disjoint;
+        }
+        let dir = d / len;
+        // The foot of the perpendicular from the center.
+        let foot = line.p0 + (self.center - line.p0).dot(dir) * dir;
+        let dist = (self.center - foot).hypot();
+        let eps = TANGENT_EPSILON * (r + dist);
+        if dist > r + eps {
+            CircleIntersection::Disjoint
+        } else if dist >= r - eps {
+            CircleIntersection::Tangent(foot)
+        } else {
+            let h = (r * r - dist * dist).sqrt();
+            CircleIntersection::Crossing(foot - h * dir, foot + h * dir)
+        }
+    }
+}
+
+impl Circle {
+    /// The external common tangents with another circle.
+    ///
+    /// These are the lines touching both circles with both circles on the
+    /// same side. Each line runs from the tangent point on this circle to the
+    /// tangent point on the other. There are two such lines unless one circle
+    /// is inside the other, in which case there are none; internally tangent
+    /// circles have a single one.
+    pub fn external_tangents(&self, other: &Circle) -> ArrayVec<[Line; 2]> {
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        self.common_tangents(other, r0 - r1, r1)
+    }
+
+    /// The internal common tangents with another circle.
+    ///
+    /// These are the lines touching both circles and passing between them,
+    /// as used for crossed belts. Each line runs from the tangent point on this
+    /// circle to the tangent point on the other. There are two such lines if
+    /// the circles are disjoint, one if they touch, and none otherwise.
+    pub fn internal_tangents(&self, other: &Circle) -> ArrayVec<[Line; 2]> {
+        let (r0, r1) = (self.radius.abs(), other.radius.abs());
+        self.common_tangents(other, r0 + r1, -r1)
+    }
+
+    /// Common tangents whose unit normal `n` satisfies `n · (c1 - c0) = k`.
+    ///
+    /// The tangent points are at `c0 + r0 n` and `c1 + r1_signed n`.
+    fn common_tangents(&self, other: &Circle, k: f64, r1_signed: f64) -> ArrayVec<[Line; 2]> {
+        let mut result = ArrayVec::new();
+        let r0 = self.radius.abs();
+        let v = other.center - self.center;
+        let d = v.hypot();
+        if d == 0.0 {
+            return result;
+        }
+        let cos = k / d;
+        let eps = TANGENT_EPSILON * (d + r0 + r1_signed.abs());
+        if cos.abs() > 1.0 + eps / d {
+            return result;
+        }
+        let dir = v / d;
+        let perp = Vec2::new(-dir.y, dir.x);
+        let sin = (1.0 - cos * cos).max(0.0).sqrt();
+        let mut push = |n: Vec2| {
+            result.push(Line::new(
+                self.center + r0 * n,
+                other.center + r1_signed * n,
+            ))
+        };
+        if sin * d <= eps {
+            push(cos.signum() * dir);
+        } else {
+            push(cos * dir - sin * perp);
+            push(cos * dir + sin * perp);
+        }
+        result
+    }
+
+    /// The tangent lines from a point to the circle.
+    ///
+    /// Each line runs from the point to the tangent point on the circle.
+    /// There are two lines for a point outside the circle, one for a point
+    /// on it (a degenerate line), and none for a point inside.
+    pub fn tangents_from_point(&self, p: Vec2) -> ArrayVec<[Line; 2]> {
+        let mut result = ArrayVec::new();
+        let r = self.radius.abs();
+        let v = p - self.center;
+        let d = v.hypot();
+        let eps = TANGENT_EPSILON * (d + r);
+        if d < r - eps || d == 0.0 {
+            return result;
+        }
+        if d <= r + eps {
+            result.push(Line::new(p, p));
+            return result;
+        }
+        let dir = v / d;
+        let perp = Vec2::new(-dir.y, dir.x);
+        let cos = r / d;
+        let sin = (1.0 - cos * cos).sqrt();
+        for &s in &[-sin, sin] {
+            result.push(Line::new(p, self.center + r * (cos * dir + s * perp)));
+        }
+        result
+    }
+}
+
+impl Circle {
+    /// The smallest circle enclosing a set of points.
+    ///
+    /// This is Welzl's algorithm in its incremental form, which is fast for
+    /// points in general position. Returns a circle of zero radius at the
+    /// origin if there are no points.
+    pub fn enclosing(points: &[Vec2]) -> Circle {
+        let contains = |c: &Circle, p: Vec2| {
+            (p - c.center).hypot() <= c.radius * (1.0 + TANGENT_EPSILON) + TANGENT_EPSILON
+        };
+        let mut c = match points.first() {
+            Some(&p) => Circle::new(p, 0.0),
+            None => return Circle::default(),
+        };
+        for i in 1..points.len() {
+            if contains(&c, points[i]) {
+                continue;
+            }
+            // points[i] is on the boundary of the enclosing circle of the first i + 1.
+            c = Circle::new(
+                points[0].lerp(points[i], 0.5),
+                0.5 * (points[i] - points[0]).hypot(),
+            );
+            for j in 1..i {
+                if contains(&c, points[j]) {
+                    continue;
+                }
+                c = Circle::new(
+                    points[i].lerp(points[j], 0.5),
+                    0.5 * (points[i] - points[j]).hypot(),
+                );
+                for k in 0..j {
+                    if !contains(&c, points[k]) {
+                        c = circumcircle(points[i], points[j], points[k]).unwrap_or(c);
+                    }
+                }
+            }
+        }
+        c
+    }
+}
+
+/// The circle through three points, or `None` if they are collinear.
+pub(crate) fn circumcircle(a: Vec2, b: Vec2, c: Vec2) -> Option<Circle> {
+    let (ab, ac) = (b - a, c - a);
+    let d = 2.0 * ab.cross(ac);
+    if d == 0.0 {
+        return None;
+    }
+    let offset = Vec2::new(
+        ac.y * ab.hypot2() - ab.y * ac.hypot2(),
+        ab.x * ac.hypot2() - ac.x * ab.hypot2(),
+    ) / d;
+    Some(Circle::new(a + offset, offset.hypot()))
 }
 
+/// The maximum number of Gauss-Newton iterations in circle fitting.
+const MAX_FIT_ITER: usize = 20;
+
 impl Add<Vec2> for Circle {
     type Output = Circle;
 
@@ -108,9 +432,39 @@ impl Shape for Circle {
         Rect::new(x - r, y - r, x + r, y + r)
     }
 
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        let [a, b, c, d, _, _] = affine.as_coeffs();
+        let r = self.radius.abs();
+        let w = r * a.hypot(c);
+        let h = r * b.hypot(d);
+        let (x, y) = (affine * self.center).into();
+        Rect::new(x - w, y - h, x + w, y + h)
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.center
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        Circle::new(self.center, self.radius.abs())
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        if direction == Vec2::default() {
+            return self.center;
+        }
+        self.center + self.radius.abs() / direction.hypot() * direction
+    }
+
     fn as_circle(&self) -> Option<Circle> {
         Some(*self)
     }
+
+    fn as_ellipse(&self) -> Option<Ellipse> {
+        Some(Ellipse::new(self.center, (self.radius, self.radius), 0.0))
+    }
 }
 
 impl Iterator for CirclePathIter {
@@ -145,3 +499,166 @@ impl Iterator for CirclePathIter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circle, CircleIntersection, Line, Vec2};
+
+    #[test]
+    fn fit_circle() {
+        let pts: Vec<Vec2> = (0..8)
+            .map(|i| Vec2::new(2.0, -3.0) + 4.0 * Vec2::from_angle(i as f64 * 0.4))
+            .collect();
+        let (c, residual) = Circle::fit(&pts).unwrap();
+        assert!((c.center - Vec2::new(2.0, -3.0)).hypot() < 1e-9);
+        assert!((c.radius - 4.0).abs() < 1e-9);
+        assert!(residual < 1e-9);
+
+        // Alternating noise of ±0.1 in the radius.
+        let noisy: Vec<Vec2> = (0..16)
+            .map(|i| {
+                (10.0 + if i % 2 == 1 { 0.1 } else { -0.1 }) * Vec2::from_angle(i as f64 * 0.2)
+            })
+            .collect();
+        let (c, residual) = Circle::fit(&noisy).unwrap();
+        assert!(c.center.hypot() < 0.1);
+        assert!((c.radius - 10.0).abs() < 0.05);
+        assert!((residual - 0.1).abs() < 0.01);
+
+        let line = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 2.0),
+        ];
+        assert!(Circle::fit(&line).is_none());
+    }
+
+    #[test]
+    fn intersect_circles() {
+        let c = Circle::new((0.0, 0.0), 5.0);
+        let other = Circle::new((8.0, 0.0), 5.0);
+        match c.intersect_circle(&other) {
+            CircleIntersection::Crossing(p0, p1) => {
+                assert!((p0 - Vec2::new(4.0, -3.0)).hypot() < 1e-12);
+                assert!((p1 - Vec2::new(4.0, 3.0)).hypot() < 1e-12);
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+        let touching = Circle::new((10.0, 0.0), 5.0);
+        assert_eq!(
+            c.intersect_circle(&touching),
+            CircleIntersection::Tangent(Vec2::new(5.0, 0.0))
+        );
+        let inner = Circle::new((-3.0, 0.0), 2.0);
+        assert_eq!(
+            c.intersect_circle(&inner),
+            CircleIntersection::Tangent(Vec2::new(-5.0, 0.0))
+        );
+        assert_eq!(
+            inner.intersect_circle(&c),
+            CircleIntersection::Tangent(Vec2::new(-5.0, 0.0))
+        );
+        let small = Circle::new((1.0, 0.0), 1.0);
+        assert_eq!(c.intersect_circle(&small), CircleIntersection::Encloses);
+        assert_eq!(small.intersect_circle(&c), CircleIntersection::Inside);
+        let far = Circle::new((20.0, 0.0), 1.0);
+        assert_eq!(c.intersect_circle(&far), CircleIntersection::Disjoint);
+        assert_eq!(c.intersect_circle(&c), CircleIntersection::Coincident);
+        let concentric = Circle::new((0.0, 0.0), 2.0);
+        assert_eq!(
+            c.intersect_circle(&concentric),
+            CircleIntersection::Encloses
+        );
+    }
+
+    #[test]
+    fn intersect_line() {
+        let c = Circle::new((1.0, 1.0), 5.0);
+        let line = Line::new((-10.0, 4.0), (10.0, 4.0));
+        assert_eq!(
+            c.intersect_line(line),
+            CircleIntersection::Crossing(Vec2::new(-3.0, 4.0), Vec2::new(5.0, 4.0))
+        );
+        assert_eq!(c.intersect_line(line).points().len(), 2);
+        let tangent = Line::new((6.0, 0.0), (6.0, 1.0));
+        assert_eq!(
+            c.intersect_line(tangent),
+            CircleIntersection::Tangent(Vec2::new(6.0, 1.0))
+        );
+        let miss = Line::new((0.0, 7.0), (1.0, 7.0));
+        assert_eq!(c.intersect_line(miss), CircleIntersection::Disjoint);
+    }
+
+    #[test]
+    fn enclosing_circle() {
+        let pts = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(2.0, -1.0),
+            Vec2::new(2.0, 1.5),
+        ];
+        let c = Circle::enclosing(&pts);
+        assert!((c.center - Vec2::new(2.0, 0.0)).hypot() < 1e-12);
+        assert!((c.radius - 2.0).abs() < 1e-12);
+        // An acute triangle needs its circumcircle.
+        let tri = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(2.0, 3.0),
+        ];
+        let c = Circle::enclosing(&tri);
+        for &p in &tri {
+            assert!(((p - c.center).hypot() - c.radius).abs() < 1e-12);
+        }
+        assert_eq!(Circle::enclosing(&[]).radius, 0.0);
+    }
+
+    #[test]
+    fn common_tangents() {
+        let a = Circle::new((0.0, 0.0), 2.0);
+        let b = Circle::new((10.0, 0.0), 1.0);
+        let check = |l: Line, r1: f64| {
+            // Each endpoint is on its circle, and the line is perpendicular
+            // to the radius there.
+            assert!(((l.p0 - a.center).hypot() - a.radius).abs() < 1e-12);
+            assert!(((l.p1 - b.center).hypot() - r1).abs() < 1e-12);
+            let d = l.p1 - l.p0;
+            assert!(d.dot(l.p0 - a.center).abs() < 1e-9);
+            assert!(d.dot(l.p1 - b.center).abs() < 1e-9);
+        };
+        let ext = a.external_tangents(&b);
+        assert_eq!(ext.len(), 2);
+        for &l in &ext {
+            check(l, 1.0);
+            // Both centers on the same side.
+            let d = l.p1 - l.p0;
+            assert!(d.cross(a.center - l.p0) * d.cross(b.center - l.p0) > 0.0);
+        }
+        let int = a.internal_tangents(&b);
+        assert_eq!(int.len(), 2);
+        for &l in &int {
+            check(l, 1.0);
+            let d = l.p1 - l.p0;
+            assert!(d.cross(a.center - l.p0) * d.cross(b.center - l.p0) < 0.0);
+        }
+        let touching = Circle::new((3.0, 0.0), 1.0);
+        let int = a.internal_tangents(&touching);
+        assert_eq!(int.len(), 1);
+        assert!((int[0].p0 - Vec2::new(2.0, 0.0)).hypot() < 1e-12);
+        let inside = Circle::new((0.5, 0.0), 1.0);
+        assert!(a.external_tangents(&inside).is_empty());
+        assert!(a.internal_tangents(&inside).is_empty());
+    }
+
+    #[test]
+    fn tangents_from_point() {
+        let c = Circle::new((0.0, 0.0), 3.0);
+        let lines = c.tangents_from_point(Vec2::new(5.0, 0.0));
+        assert_eq!(lines.len(), 2);
+        assert!((lines[0].p1 - Vec2::new(1.8, -2.4)).hypot() < 1e-12);
+        assert!((lines[1].p1 - Vec2::new(1.8, 2.4)).hypot() < 1e-12);
+        assert_eq!(c.tangents_from_point(Vec2::new(3.0, 0.0)).len(), 1);
+        assert!(c.tangents_from_point(Vec2::new(1.0, 1.0)).is_empty());
+    }
+}
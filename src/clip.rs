@@ -0,0 +1,206 @@
+//! Clipping by half-planes and convex regions.
+
+use crate::common::solve_poly_interval;
+use crate::{BezPath, InfiniteLine, ParamCurve, PathSeg, Shape, Vec2};
+
+/// The region on one side of a line.
+///
+/// The half-plane contains the points `p` for which `normal.dot(p) <= offset`,
+/// including its boundary. An intersection of half-planes is a convex region,
+/// so a slice of them can describe a clip rectangle, a convex polygon, or a
+/// view frustum in 2D.
+#[derive(Clone, Copy, Debug)]
+pub struct HalfPlane {
+    /// The outward normal of the boundary.
+    pub normal: Vec2,
+    /// The value of `normal.dot(p)` on the boundary.
+    pub offset: f64,
+}
+
+impl HalfPlane {
+    /// A new half-plane from its outward normal and offset.
+    #[inline]
+    pub fn new(normal: impl Into<Vec2>, offset: f64) -> HalfPlane {
+        HalfPlane {
+            normal: normal.into(),
+            offset,
+        }
+    }
+
+    /// The half-plane on the positive side of a line.
+    ///
+    /// This is the side that [`InfiniteLine::side`](struct.InfiniteLine.html#method.side)
+    /// reports as 1, to the left of the direction when positive Y is
+    /// counterclockwise from positive X.
+    pub fn from_line(line: &InfiniteLine) -> HalfPlane {
+        let normal = Vec2::new(line.direction.y, -line.direction.x);
+        HalfPlane::new(normal, normal.dot(line.point))
+    }
+
+    /// The half-planes bounding a convex polygon.
+    ///
+    /// The vertices may be in either orientation. Consecutive duplicate
+    /// vertices are ignored.
+    pub fn from_convex_polygon(vertices: &[Vec2]) -> Vec<HalfPlane> {
+        let n = vertices.len();
+        let area: f64 = (0..n)
+            .map(|i| vertices[i].cross(vertices[(i + 1) % n]))
+            .sum();
+        (0..n)
+            .filter(|&i| vertices[i] != vertices[(i + 1) % n])
+            .map(|i| {
+                let (p0, p1) = (vertices[i], vertices[(i + 1) % n]);
+                let line = if area >= 0.0 {
+                    InfiniteLine::through(p0, p1)
+                } else {
+                    InfiniteLine::through(p1, p0)
+                };
+                HalfPlane::from_line(&line)
+            })
+            .collect()
+    }
+
+    /// The signed distance from the boundary, scaled by the length of the normal.
+    ///
+    /// This is negative inside the half-plane and positive outside.
+    #[inline]
+    pub fn eval(&self, p: Vec2) -> f64 {
+        self.normal.dot(p) - self.offset
+    }
+
+    /// Whether the point is in the half-plane.
+    #[inline]
+    pub fn contains(&self, p: Vec2) -> bool {
+        self.eval(p) <= 0.0
+    }
+
+    /// The parameters where a segment crosses the boundary, in increasing order.
+    fn crossings(&self, seg: &PathSeg) -> Vec<f64> {
+        let c = seg.to_cubic();
+        let coeffs = [
+            self.eval(c.p0),
+            3.0 * self.normal.dot(c.p1 - c.p0),
+            3.0 * self.normal.dot(c.p2 - 2.0 * c.p1 + c.p0),
+            self.normal.dot(c.p3 - 3.0 * c.p2 + 3.0 * c.p1 - c.p0),
+        ];
+        solve_poly_interval(&coeffs, 0.0, 1.0)
+            .into_iter()
+            .filter(|&t| t > 1e-9 && t < 1.0 - 1e-9)
+            .collect()
+    }
+
+    /// Clip a path to this half-plane.
+    fn clip_path(&self, path: &BezPath) -> BezPath {
+        let mut result = BezPath::new();
+        for sub in path.subpath_segs() {
+            // Split at crossings, and classify each piece by its midpoint.
+            let mut pieces = Vec::new();
+            for &(_, seg) in &sub.segs {
+                let mut t0 = 0.0;
+                for t in self.crossings(&seg).into_iter().chain(Some(1.0)) {
+                    let piece = seg.subsegment(t0..t);
+                    pieces.push((piece, self.contains(piece.eval(0.5))));
+                    t0 = t;
+                }
+            }
+            if pieces.iter().all(|&(_, inside)| !inside) {
+                continue;
+            }
+            if !sub.closed {
+                let mut drawing = false;
+                for &(piece, inside) in &pieces {
+                    if inside {
+                        if !drawing {
+                            result.moveto(piece.start());
+                        }
+                        result.push(piece.as_path_el());
+                    }
+                    drawing = inside;
+                }
+                continue;
+            }
+            // Start at the beginning of an inside run, so that each outside run
+            // is replaced by a line along the boundary from exit to re-entry.
+            let n = pieces.len();
+            let first = (0..n)
+                .find(|&i| pieces[i].1 && !pieces[(i + n - 1) % n].1)
+                .unwrap_or(0);
+            pieces.rotate_left(first);
+            result.moveto(pieces[0].0.start());
+            let mut skipped = false;
+            for &(piece, inside) in &pieces {
+                if inside {
+                    if skipped {
+                        result.lineto(piece.start());
+                        skipped = false;
+                    }
+                    result.push(piece.as_path_el());
+                } else {
+                    skipped = true;
+                }
+            }
+            result.closepath();
+        }
+        result
+    }
+}
+
+/// Clip a shape to the intersection of half-planes.
+///
+/// The shape is converted to a Bézier path with the given tolerance, and
+/// then each half-plane is applied in turn. Curves are split exactly where
+/// they cross a boundary, so no flattening is involved.
+///
+/// Open subpaths are cut into the pieces inside the region. Closed subpaths
+/// remain closed, with the parts outside replaced by lines along the
+/// boundary; this is exact for filling, but a non-convex subpath may leave
+/// zero-area slivers along the boundary where it exits and re-enters.
+pub fn clip(shape: &impl Shape, planes: &[HalfPlane], tolerance: f64) -> BezPath {
+    let mut path = shape.into_bez_path(tolerance);
+    for plane in planes {
+        path = plane.clip_path(&path);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{clip, BezPath, Circle, HalfPlane, InfiniteLine, Shape, Vec2};
+
+    #[test]
+    fn clip_square_by_triangle() {
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let planes = HalfPlane::from_convex_polygon(&[
+            Vec2::new(5.0, -5.0),
+            Vec2::new(15.0, 5.0),
+            Vec2::new(5.0, 15.0),
+        ]);
+        assert_eq!(planes.len(), 3);
+        let clipped = clip(&square, &planes, 1e-9);
+        // The triangle covers the right half of the square exactly, its
+        // slanted sides meeting the square only at the right corners.
+        assert!((clipped.area().abs() - 50.0).abs() < 1e-9);
+        assert_eq!(clipped.elements().len(), 5);
+    }
+
+    #[test]
+    fn clip_circle() {
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let plane = HalfPlane::from_line(&InfiniteLine::new((0.0, 0.0), (-1.0, 0.0)));
+        assert!(plane.contains(Vec2::new(0.0, -1.0)));
+        let half = clip(&circle, &[plane], 1e-9);
+        let expected = 0.5 * std::f64::consts::PI;
+        assert!((half.area() - expected).abs() < 1e-6);
+        assert!(half.bounding_box().y1 <= 1e-12);
+    }
+
+    #[test]
+    fn clip_open_path() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10L0 10").unwrap();
+        let plane = HalfPlane::new((1.0, 0.0), 5.0);
+        let clipped = clip(&path, &[plane], 1e-9);
+        assert_eq!(clipped.to_svg(), "M0 0L5 0M5 10L0 10");
+        let outside = HalfPlane::new((1.0, 0.0), -1.0);
+        assert!(clip(&path, &[outside], 1e-9).is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+//! Collision queries between shapes.
+
+use crate::{Rect, Shape, Vec2};
+
+/// The most iterations of GJK, which converges in a few for polygons, and
+/// approaches curved shapes quickly.
+const MAX_GJK_ITERATIONS: usize = 64;
+
+/// The most iterations of EPA, each of which adds a vertex to its polygon.
+const MAX_EPA_ITERATIONS: usize = 128;
+
+/// The closest points of two convex shapes.
+#[derive(Clone, Copy, Debug)]
+pub struct ConvexDistance {
+    /// The distance between the shapes, zero if they overlap.
+    pub distance: f64,
+    /// The point of the first shape closest to the second.
+    pub point_a: Vec2,
+    /// The point of the second shape closest to the first.
+    pub point_b: Vec2,
+}
+
+/// A point of the Minkowski difference `a - b`, with the points of the
+/// shapes it is the difference of.
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    w: Vec2,
+    a: Vec2,
+    b: Vec2,
+}
+
+/// The distance between two convex shapes, and their closest points.
+///
+/// This uses the GJK algorithm on the [support points](trait.Shape.html#method.support)
+/// of the shapes, so a shape that isn't convex is treated as its convex
+/// hull. For shapes with curved sides, the points are accurate to about
+/// 1e-7 of the size of the shapes, and the distance much more so. If the
+/// shapes overlap, the distance is zero and both points are a point they
+/// have in common.
+pub fn convex_distance(a: &impl Shape, b: &impl Shape) -> ConvexDistance {
+    let (eps, progress) = tolerances(a, b);
+    let (simplex, weights) = gjk(a, b, eps, progress);
+    let point_a = combine(&simplex, &weights, |v| v.a);
+    let point_b = combine(&simplex, &weights, |v| v.b);
+    let distance = combine(&simplex, &weights, |v| v.w).hypot();
+    ConvexDistance {
+        distance: if distance <= eps { 0.0 } else { distance },
+        point_a,
+        point_b,
+    }
+}
+
+/// The shortest vector by which to move the second of two convex shapes so
+/// they no longer overlap, or `None` if they don't.
+///
+/// Once GJK finds that the shapes overlap, this uses EPA to find the point
+/// of their Minkowski difference nearest the origin. Shapes that aren't
+/// convex are treated as their convex hulls, as for
+/// [`convex_distance`](fn.convex_distance.html).
+pub fn convex_penetration(a: &impl Shape, b: &impl Shape) -> Option<Vec2> {
+    let (eps, progress) = tolerances(a, b);
+    let (simplex, weights) = gjk(a, b, eps, progress);
+    if combine(&simplex, &weights, |v| v.w).hypot() > eps {
+        return None;
+    }
+    let mut poly = simplex;
+    // Make a triangle, unless the difference has no area.
+    if poly.len() == 1 {
+        let dir = if poly[0].w.hypot() > eps {
+            -poly[0].w
+        } else {
+            Vec2::new(1.0, 0.0)
+        };
+        poly.push(support(a, b, dir));
+    }
+    if poly.len() == 2 {
+        let e = poly[1].w - poly[0].w;
+        if e.hypot() <= eps {
+            return Some(Vec2::default());
+        }
+        let n = Vec2::new(-e.y, e.x) / e.hypot();
+        let v = [support(a, b, n), support(a, b, -n)]
+            .iter()
+            .cloned()
+            .find(|v| (v.w - poly[0].w).dot(n).abs() > eps);
+        match v {
+            Some(v) => poly.push(v),
+            None => return Some(Vec2::default()),
+        }
+    }
+    if (poly[1].w - poly[0].w).cross(poly[2].w - poly[0].w) < 0.0 {
+        poly.swap(1, 2);
+    }
+    let mut best = (Vec2::default(), f64::INFINITY);
+    for _ in 0..MAX_EPA_ITERATIONS {
+        // The edge nearest the origin, with its outward normal.
+        best = (Vec2::default(), f64::INFINITY);
+        let mut best_ix = 0;
+        for i in 0..poly.len() {
+            let e = poly[(i + 1) % poly.len()].w - poly[i].w;
+            let len = e.hypot();
+            if len <= eps {
+                continue;
+            }
+            let n = Vec2::new(e.y, -e.x) / len;
+            let dist = n.dot(poly[i].w);
+            if dist < best.1 {
+                best = (n, dist);
+                best_ix = i;
+            }
+        }
+        let (n, dist) = best;
+        let v = support(a, b, n);
+        if v.w.dot(n) - dist <= progress {
+            break;
+        }
+        poly.insert(best_ix + 1, v);
+    }
+    Some(best.0 * best.1.max(0.0))
+}
+
+/// The pairs of shapes whose bounding boxes overlap.
+///
+/// This is a broad phase, for marquee selection or as a first pass before
+/// finer tests such as [`convex_distance`](fn.convex_distance.html): the
+/// boxes are sorted by their left edges and swept from left to right, so
+/// shapes far apart are never compared. Boxes that only touch count as
+/// overlapping. Each pair is reported once, as `(i, j)` with `i < j`, in
+/// increasing order.
+pub fn find_overlaps(shapes: &[impl Shape]) -> Vec<(usize, usize)> {
+    let boxes: Vec<Rect> = shapes.iter().map(|s| s.bounding_box().abs()).collect();
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by(|&i, &j| boxes[i].x0.total_cmp(&boxes[j].x0));
+    let mut active: Vec<usize> = Vec::new();
+    let mut result = Vec::new();
+    for &i in &order {
+        let b = boxes[i];
+        active.retain(|&j| boxes[j].x1 >= b.x0);
+        for &j in &active {
+            if boxes[j].y0 <= b.y1 && b.y0 <= boxes[j].y1 {
+                result.push((i.min(j), i.max(j)));
+            }
+        }
+        active.push(i);
+    }
+    result.sort_unstable();
+    result
+}
+
+/// The tolerances for two shapes, relative to their size: the distance at
+/// which they count as touching, and the least progress towards the origin
+/// for which the searches continue.
+///
+/// Near a smooth part of the boundary, the distance converges much faster
+/// than the point where it is attained, so the second is far smaller.
+fn tolerances(a: &impl Shape, b: &impl Shape) -> (f64, f64) {
+    let size = a.bounding_box().union(b.bounding_box()).size();
+    let size = size.x.max(size.y);
+    (1e-9 * size, 1e-14 * size)
+}
+
+/// The support point of the Minkowski difference `a - b`.
+fn support(a: &impl Shape, b: &impl Shape, dir: Vec2) -> Vertex {
+    let (pa, pb) = (a.support(dir), b.support(-dir));
+    Vertex {
+        w: pa - pb,
+        a: pa,
+        b: pb,
+    }
+}
+
+/// Run GJK, returning the simplex of the Minkowski difference nearest the
+/// origin and the weights of its nearest point. A simplex of three vertices
+/// contains the origin.
+fn gjk(a: &impl Shape, b: &impl Shape, eps: f64, progress: f64) -> (Vec<Vertex>, Vec<f64>) {
+    let mut simplex = vec![support(a, b, Vec2::new(1.0, 0.0))];
+    let mut weights = vec![1.0];
+    for _ in 0..MAX_GJK_ITERATIONS {
+        let v = combine(&simplex, &weights, |v| v.w);
+        let dist = v.hypot();
+        if dist <= eps || simplex.len() == 3 {
+            break;
+        }
+        let w = support(a, b, -v);
+        // Stop when the new vertex brings the difference no closer.
+        if v.hypot2() - v.dot(w.w) <= progress * dist {
+            break;
+        }
+        simplex.push(w);
+        let (s, ws) = nearest(&simplex);
+        simplex = s;
+        weights = ws;
+    }
+    (simplex, weights)
+}
+
+/// The point of a simplex nearest the origin, as the smallest simplex
+/// containing it and its weights.
+fn nearest(simplex: &[Vertex]) -> (Vec<Vertex>, Vec<f64>) {
+    match *simplex {
+        [p] => (vec![p], vec![1.0]),
+        [p, q] => {
+            let e = q.w - p.w;
+            let len2 = e.hypot2();
+            let t = if len2 == 0.0 {
+                0.0
+            } else {
+                (-p.w.dot(e) / len2).clamp(0.0, 1.0)
+            };
+            if t == 0.0 {
+                (vec![p], vec![1.0])
+            } else if t == 1.0 {
+                (vec![q], vec![1.0])
+            } else {
+                (vec![p, q], vec![1.0 - t, t])
+            }
+        }
+        [p, q, r] => {
+            let area = (q.w - p.w).cross(r.w - p.w);
+            if area != 0.0 {
+                let l = [
+                    q.w.cross(r.w) / area,
+                    r.w.cross(p.w) / area,
+                    p.w.cross(q.w) / area,
+                ];
+                if l.iter().all(|&l| l >= 0.0) {
+                    return (vec![p, q, r], l.to_vec());
+                }
+            }
+            [[p, q], [q, r], [r, p]]
+                .iter()
+                .map(|edge| nearest(edge))
+                .min_by(|x, y| {
+                    let dx = combine(&x.0, &x.1, |v| v.w).hypot2();
+                    let dy = combine(&y.0, &y.1, |v| v.w).hypot2();
+                    dx.total_cmp(&dy)
+                })
+                .unwrap()
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// The weighted sum of a point of each vertex.
+fn combine(simplex: &[Vertex], weights: &[f64], f: impl Fn(&Vertex) -> Vec2) -> Vec2 {
+    simplex
+        .iter()
+        .zip(weights)
+        .fold(Vec2::default(), |sum, (v, &w)| sum + w * f(v))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        convex_distance, convex_penetration, find_overlaps, Affine, Capsule, Circle, Ellipse, Rect,
+        TransformedShape, Triangle, Vec2,
+    };
+
+    fn assert_near(a: Vec2, b: Vec2, epsilon: f64) {
+        assert!((a - b).hypot() < epsilon, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn distance() {
+        let a = Circle::new((0.0, 0.0), 1.0);
+        let b = Circle::new((4.0, 3.0), 2.0);
+        let d = convex_distance(&a, &b);
+        assert!((d.distance - 2.0).abs() < 1e-6);
+        assert_near(d.point_a, Vec2::new(0.8, 0.6), 1e-6);
+        assert_near(d.point_b, Vec2::new(2.4, 1.8), 1e-6);
+
+        let r = Rect::new(2.0, 2.0, 5.0, 4.0);
+        let d = convex_distance(&Rect::new(0.0, 0.0, 1.0, 1.0), &r);
+        assert!((d.distance - 2f64.sqrt()).abs() < 1e-12);
+        assert_near(d.point_a, Vec2::new(1.0, 1.0), 1e-12);
+        assert_near(d.point_b, Vec2::new(2.0, 2.0), 1e-12);
+        // From a side.
+        let t = Triangle::new((0.0, 3.0), (-2.0, 5.0), (-2.0, 1.0));
+        let d = convex_distance(&t, &r);
+        assert!((d.distance - 2.0).abs() < 1e-12);
+        assert_near(d.point_b, Vec2::new(2.0, 3.0), 1e-12);
+
+        // Overlapping shapes share the closest point.
+        let d = convex_distance(&Circle::new((3.0, 3.0), 1.0), &r);
+        assert_eq!(d.distance, 0.0);
+        assert_near(d.point_a, d.point_b, 1e-9);
+
+        // A stroked line, as a capsule.
+        let capsule = Capsule::new((0.0, 0.0), (0.0, 10.0), 1.0);
+        let d = convex_distance(&capsule, &r);
+        assert!((d.distance - 1.0).abs() < 1e-6);
+        assert!((d.point_a.x - 1.0).abs() < 1e-6);
+        assert!(d.point_a.y >= 2.0 - 1e-6 && d.point_a.y <= 4.0 + 1e-6);
+        let d = convex_distance(&capsule, &Circle::new((3.0, 13.0), 1.0));
+        assert!((d.distance - (18f64.sqrt() - 2.0)).abs() < 1e-6);
+        let v = convex_penetration(&capsule, &Rect::new(0.5, 4.0, 3.0, 5.0)).unwrap();
+        assert_near(v, Vec2::new(0.5, 0.0), 1e-6);
+    }
+
+    #[test]
+    fn penetration() {
+        let a = Circle::new((0.0, 0.0), 2.0);
+        let b = Circle::new((3.0, 0.0), 2.0);
+        let v = convex_penetration(&a, &b).unwrap();
+        assert_near(v, Vec2::new(1.0, 0.0), 1e-6);
+        assert!(convex_penetration(&a, &Circle::new((5.0, 0.0), 2.0)).is_none());
+
+        // The shortest way out of a rectangle is through its nearest side.
+        let r = Rect::new(0.0, 0.0, 10.0, 4.0);
+        let v = convex_penetration(&r, &Rect::new(3.0, 3.0, 5.0, 6.0)).unwrap();
+        assert_near(v, Vec2::new(0.0, 1.0), 1e-12);
+        let v = convex_penetration(&r, &Rect::new(4.0, 1.0, 5.0, 2.0)).unwrap();
+        assert_near(v, Vec2::new(0.0, -2.0), 1e-12);
+
+        // Moving by the vector leaves the shapes just touching.
+        let e = Ellipse::new((0.0, 0.0), (3.0, 1.0), 0.5);
+        let c = TransformedShape::new(Circle::new((0.0, 0.0), 1.0), Affine::translate((1.0, 1.0)));
+        let v = convex_penetration(&e, &c).unwrap();
+        let moved = TransformedShape::new(c.shape, Affine::translate(Vec2::new(1.0, 1.0) + v));
+        assert!(convex_distance(&e, &moved).distance < 1e-6);
+        let moved =
+            TransformedShape::new(c.shape, Affine::translate(Vec2::new(1.0, 1.0) + 1.001 * v));
+        assert!(convex_penetration(&e, &moved).is_none());
+    }
+
+    #[test]
+    fn overlaps() {
+        let rects = [
+            Rect::new(0.0, 0.0, 2.0, 2.0),
+            Rect::new(5.0, 0.0, 6.0, 1.0),
+            Rect::new(1.0, 1.0, 3.0, 3.0),
+            // Beside the first, but above the second.
+            Rect::new(2.0, 5.0, 5.5, 6.0),
+            // Reversed, touching the second.
+            Rect::new(7.0, 2.0, 6.0, 1.0),
+        ];
+        assert_eq!(find_overlaps(&rects), vec![(0, 2), (1, 4)]);
+        let circles = [Circle::new((0.0, 0.0), 1.0), Circle::new((1.5, 1.5), 1.0)];
+        // The boxes overlap though the circles don't.
+        assert_eq!(find_overlaps(&circles), vec![(0, 1)]);
+        assert!(find_overlaps(&[] as &[Rect]).is_empty());
+        // A box with NaN coordinates overlaps nothing.
+        let with_nan = [
+            rects[0],
+            Rect::new(f64::NAN, f64::NAN, f64::NAN, f64::NAN),
+            rects[2],
+        ];
+        assert_eq!(find_overlaps(&with_nan), vec![(0, 2)]);
+    }
+}
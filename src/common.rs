@@ -1,5 +1,7 @@
 //! Common mathematical operations
 
+use std::f64::consts::PI;
+
 use arrayvec::ArrayVec;
 
 /// Find real roots of cubic equation.
@@ -67,8 +69,178 @@ pub fn solve_quadratic(c0: f64, c1: f64, c2: f64) -> ArrayVec<[f64; 2]> {
     result
 }
 
-/// Tables of Legendre-Gauss quadrature coefficients, adapted from:
-/// <https://pomax.github.io/bezierinfo/legendre-gauss.html>
+/// Find real roots of a polynomial within an interval.
+///
+/// The coefficients are given in increasing order of degree, so the
+/// polynomial is `coeffs[0] + coeffs[1] x + coeffs[2] x² + ...`. Leading
+/// coefficients that are negligible compared to the others are ignored.
+///
+/// The polynomial is split into monotonic pieces by recursively finding the
+/// roots of its derivative, and each piece with a sign change is bisected.
+/// Roots of even multiplicity that don't coincide with roots of the derivative
+/// in floating point may be missed.
+///
+/// Returns the roots in the range `t0..=t1`, in increasing order.
+pub fn solve_poly_interval(coeffs: &[f64], t0: f64, t1: f64) -> Vec<f64> {
+    let max = coeffs.iter().fold(0.0f64, |m, c| m.max(c.abs()));
+    let mut n = coeffs.len();
+    while n > 0 && coeffs[n - 1].abs() <= 1e-12 * max {
+        n -= 1;
+    }
+    let coeffs = &coeffs[..n];
+    let mut result = Vec::new();
+    if n <= 1 {
+        return result;
+    }
+    let eval = |x: f64| coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c);
+    let deriv: Vec<f64> = coeffs[1..]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i + 1) as f64 * c)
+        .collect();
+    let mut bounds = vec![t0];
+    bounds.extend(solve_poly_interval(&deriv, t0, t1));
+    bounds.push(t1);
+    for w in bounds.windows(2) {
+        let (mut a, mut b) = (w[0], w[1]);
+        let fa = eval(a);
+        if fa == 0.0 {
+            if result.last() != Some(&a) {
+                result.push(a);
+            }
+            continue;
+        }
+        if fa * eval(b) >= 0.0 {
+            continue;
+        }
+        loop {
+            let m = 0.5 * (a + b);
+            if m <= a || m >= b {
+                break;
+            }
+            if (eval(m) < 0.0) == (fa < 0.0) {
+                a = m;
+            } else {
+                b = m;
+            }
+        }
+        result.push(0.5 * (a + b));
+    }
+    if eval(t1) == 0.0 && result.last() != Some(&t1) {
+        result.push(t1);
+    }
+    result
+}
+
+/// Solve a 3x3 linear system `m x = b` by Cramer's rule.
+///
+/// Returns `None` if the matrix is singular.
+pub(crate) fn solve_3x3(m: [[f64; 3]; 3], b: [f64; 3]) -> Option<[f64; 3]> {
+    let det3 = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let det = det3(&m);
+    let scale = m.iter().flatten().fold(0.0f64, |a, x| a.max(x.abs()));
+    if det.abs() <= 1e-14 * scale.powi(3) {
+        return None;
+    }
+    let mut x = [0.0; 3];
+    for (i, xi) in x.iter_mut().enumerate() {
+        let mut mi = m;
+        for (row, bj) in mi.iter_mut().zip(&b) {
+            row[i] = *bj;
+        }
+        *xi = det3(&mi) / det;
+    }
+    Some(x)
+}
+
+/// Carlson's symmetric elliptic integral of the first kind, R_F(x, y, z).
+///
+/// At most one of the arguments may be zero, and all must be nonnegative.
+/// This is the duplication algorithm from Carlson, "Numerical computation of
+/// real or complex elliptic integrals" (1995), evaluated to full precision.
+pub(crate) fn carlson_rf(x: f64, y: f64, z: f64) -> f64 {
+    const ERRTOL: f64 = 0.0025;
+    let (mut x, mut y, mut z) = (x, y, z);
+    loop {
+        let mu = (x + y + z) * (1.0 / 3.0);
+        let dx = 1.0 - x / mu;
+        let dy = 1.0 - y / mu;
+        let dz = 1.0 - z / mu;
+        if dx.abs().max(dy.abs()).max(dz.abs()) < ERRTOL {
+            let e2 = dx * dy - dz * dz;
+            let e3 = dx * dy * dz;
+            return (1.0 + (e2 * (1.0 / 24.0 * e2 - 0.1 - 3.0 / 44.0 * e3) + 1.0 / 14.0 * e3))
+                / mu.sqrt();
+        }
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        x = 0.25 * (x + lambda);
+        y = 0.25 * (y + lambda);
+        z = 0.25 * (z + lambda);
+    }
+}
+
+/// Carlson's symmetric elliptic integral of the second kind, R_D(x, y, z).
+///
+/// At most one of `x` and `y` may be zero, and `z` must be positive.
+pub(crate) fn carlson_rd(x: f64, y: f64, z: f64) -> f64 {
+    const ERRTOL: f64 = 0.0015;
+    let (mut x, mut y, mut z) = (x, y, z);
+    let mut sum = 0.0;
+    let mut fac = 1.0;
+    loop {
+        let mu = (x + y + 3.0 * z) * 0.2;
+        let dx = 1.0 - x / mu;
+        let dy = 1.0 - y / mu;
+        let dz = 1.0 - z / mu;
+        if dx.abs().max(dy.abs()).max(dz.abs()) < ERRTOL {
+            let ea = dx * dy;
+            let eb = dz * dz;
+            let ec = ea - eb;
+            let ed = ea - 6.0 * eb;
+            let ee = ed + ec + ec;
+            let s1 = ed * (-3.0 / 14.0 + 9.0 / 88.0 * ed - 9.0 / 52.0 * dz * ee);
+            let s2 = dz * (1.0 / 6.0 * ee + dz * (-9.0 / 22.0 * ec + dz * 3.0 / 26.0 * ea));
+            return 3.0 * sum + fac * (1.0 + s1 + s2) / (mu * mu.sqrt());
+        }
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        sum += fac / (sz * (z + lambda));
+        fac *= 0.25;
+        x = 0.25 * (x + lambda);
+        y = 0.25 * (y + lambda);
+        z = 0.25 * (z + lambda);
+    }
+}
+
+/// The incomplete elliptic integral of the second kind, E(φ | m).
+///
+/// This is the integral of `sqrt(1 - m sin²t)` from 0 to φ, for `0 <= m <= 1`
+/// and any real φ.
+pub(crate) fn elliptic_e(phi: f64, m: f64) -> f64 {
+    // Reduce φ to (-π/2, π/2], using E(φ + kπ | m) = E(φ | m) + 2k E(m).
+    let k = (phi / PI - 0.5).ceil();
+    let phi = phi - k * PI;
+    let (s, c) = phi.sin_cos();
+    if m >= 1.0 {
+        return s + 2.0 * k;
+    }
+    let q = 1.0 - m * s * s;
+    let inc = s * carlson_rf(c * c, q, 1.0) - m / 3.0 * s * s * s * carlson_rd(c * c, q, 1.0);
+    if k == 0.0 {
+        inc
+    } else {
+        let complete = carlson_rf(0.0, 1.0 - m, 1.0) - m / 3.0 * carlson_rd(0.0, 1.0 - m, 1.0);
+        inc + 2.0 * k * complete
+    }
+}
+
+// Tables of Legendre-Gauss quadrature coefficients, adapted from:
+// <https://pomax.github.io/bezierinfo/legendre-gauss.html>
 
 pub const GAUSS_LEGENDRE_COEFFS_3: &[(f64, f64)] = &[
     (0.8888888888888888, 0.0000000000000000),
@@ -76,6 +248,7 @@ pub const GAUSS_LEGENDRE_COEFFS_3: &[(f64, f64)] = &[
     (0.5555555555555556, 0.7745966692414834),
 ];
 
+#[allow(clippy::excessive_precision)]
 pub const GAUSS_LEGENDRE_COEFFS_5: &[(f64, f64)] = &[
     (0.5688888888888889, 0.0000000000000000),
     (0.4786286704993665, -0.5384693101056831),
@@ -106,6 +279,7 @@ pub const GAUSS_LEGENDRE_COEFFS_9: &[(f64, f64)] = &[
     (0.2606106964029354, 0.6133714327005904),
 ];
 
+#[allow(clippy::excessive_precision)]
 pub const GAUSS_LEGENDRE_COEFFS_11: &[(f64, f64)] = &[
     (0.2729250867779006, 0.0000000000000000),
     (0.2628045445102467, -0.2695431559523450),
@@ -120,6 +294,7 @@ pub const GAUSS_LEGENDRE_COEFFS_11: &[(f64, f64)] = &[
     (0.0556685671161737, 0.9782286581460570),
 ];
 
+#[allow(clippy::excessive_precision)]
 pub const GAUSS_LEGENDRE_COEFFS_24: &[(f64, f64)] = &[
     (0.1279381953467522, -0.0640568928626056),
     (0.1279381953467522, 0.0640568928626056),
@@ -183,4 +358,33 @@ mod tests {
         verify(solve_quadratic(5.0, 1.0, 0.0), &[-5.0]);
         verify(solve_quadratic(1.0, 2.0, 1.0), &[-1.0]);
     }
+
+    #[test]
+    fn test_solve_poly_interval() {
+        let roots = |coeffs: &[f64]| solve_poly_interval(coeffs, 0.0, 1.0);
+        // (x - 0.1)(x - 0.5)(x - 0.9)(x - 2)
+        let p = [0.09, -1.225, 3.59, -3.5, 1.0];
+        let r = roots(&p);
+        assert_eq!(r.len(), 3);
+        for (x, y) in r.iter().zip(&[0.1, 0.5, 0.9]) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        assert_eq!(roots(&[0.0, 1.0]), vec![0.0]);
+        assert_eq!(roots(&[-1.0, 1.0]), vec![1.0]);
+        assert!(roots(&[1.0, 0.0, 1.0]).is_empty());
+        assert!(roots(&[0.0, 0.0]).is_empty());
+    }
+
+    #[test]
+    fn test_elliptic_e() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        // Reference values computed by numerical quadrature.
+        assert!((elliptic_e(FRAC_PI_2, 0.0) - FRAC_PI_2).abs() < 1e-15);
+        assert!((elliptic_e(FRAC_PI_2, 1.0) - 1.0).abs() < 1e-15);
+        assert!((elliptic_e(FRAC_PI_2, 0.5) - 1.350_643_881_047_675_5).abs() < 1e-14);
+        assert!((elliptic_e(PI / 6.0, 0.5) - 0.512_049_322_350_419_9).abs() < 1e-13);
+        assert!((elliptic_e(-PI / 6.0, 0.5) + 0.512_049_322_350_419_9).abs() < 1e-13);
+        let e = elliptic_e(FRAC_PI_2, 0.3);
+        assert!((elliptic_e(PI + 0.2, 0.3) - 2.0 * e - elliptic_e(0.2, 0.3)).abs() < 1e-14);
+    }
 }
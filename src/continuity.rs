@@ -0,0 +1,248 @@
+//! Checking the continuity of paths at segment junctions, and finding corners.
+
+use std::f64::consts::PI;
+
+use crate::{BezPath, InterestingPoint, ParamCurve, PathSeg, Vec2};
+
+/// Tolerances for [`continuity_report`](struct.BezPath.html#method.continuity_report).
+#[derive(Clone, Copy, Debug)]
+pub struct ContinuityTolerances {
+    /// The largest gap in a closed subpath that counts as a junction.
+    pub position: f64,
+    /// The largest change in tangent direction, in radians, that counts as smooth.
+    pub angle: f64,
+    /// The largest change in curvature that counts as curvature continuous.
+    pub curvature: f64,
+}
+
+impl Default for ContinuityTolerances {
+    fn default() -> ContinuityTolerances {
+        ContinuityTolerances {
+            position: 1e-6,
+            angle: 1e-6,
+            curvature: 1e-6,
+        }
+    }
+}
+
+/// The kind of a discontinuity at a junction between segments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscontinuityKind {
+    /// The segments don't meet; the amount is the distance between them.
+    Position,
+    /// The tangent direction changes (not G1); the amount is the angle in radians.
+    Tangent,
+    /// The curvature changes (G1 but not G2); the amount is the difference.
+    Curvature,
+}
+
+/// A discontinuity found by [`continuity_report`](struct.BezPath.html#method.continuity_report).
+#[derive(Clone, Copy, Debug)]
+pub struct Discontinuity {
+    /// The index of the element whose segment starts at the junction.
+    pub index: usize,
+    /// The location of the junction.
+    pub point: Vec2,
+    /// The kind of the discontinuity.
+    pub kind: DiscontinuityKind,
+    /// The size of the discontinuity, as described by its kind.
+    pub amount: f64,
+}
+
+/// A corner found by [`corners`](struct.BezPath.html#method.corners).
+#[derive(Clone, Copy, Debug)]
+pub struct Corner {
+    /// The index of the element containing the corner.
+    ///
+    /// For corners at junctions, this is the element whose segment starts at
+    /// the junction.
+    pub index: usize,
+    /// The parameter of the corner within the segment.
+    pub t: f64,
+    /// The location of the corner.
+    pub point: Vec2,
+    /// The angle the tangent turns through, in radians.
+    pub angle: f64,
+}
+
+impl BezPath {
+    /// Find the discontinuities at the junctions between segments.
+    ///
+    /// Each pair of consecutive segments in a subpath is checked, as is the
+    /// junction at the start of a closed subpath. Only the most severe kind of
+    /// discontinuity is reported for each junction, and junctions that are
+    /// continuous within the tolerances are not reported at all.
+    ///
+    /// Consecutive segments in a path always share an endpoint, so position
+    /// discontinuities are only reported for closed subpaths whose closing line
+    /// is shorter than the position tolerance, which usually indicates a contour
+    /// that was meant to end exactly at its start point. The closing line is then
+    /// skipped, and the junction is checked between the segments on either side.
+    pub fn continuity_report(&self, tolerances: &ContinuityTolerances) -> Vec<Discontinuity> {
+        let mut result = Vec::new();
+        for sub in self.subpath_segs() {
+            let mut segs = sub.segs;
+            let mut gap = None;
+            if sub.closed && segs.len() > 1 {
+                if let (_, PathSeg::Line(line)) = segs[segs.len() - 1] {
+                    let len = (line.p1 - line.p0).hypot();
+                    if len <= tolerances.position {
+                        gap = Some(len);
+                        segs.pop();
+                    }
+                }
+            }
+            let n = segs.len();
+            let n_junctions = if sub.closed { n } else { n - 1 };
+            for i in 0..n_junctions {
+                let (_, prev) = segs[i];
+                let (index, next) = segs[(i + 1) % n];
+                let point = next.start();
+                let (kind, amount) = match gap {
+                    Some(len) if i == n - 1 => (DiscontinuityKind::Position, len),
+                    _ => {
+                        let t0 = prev.tangent(1.0);
+                        let t1 = next.tangent(0.0);
+                        let angle = t0.cross(t1).atan2(t0.dot(t1)).abs();
+                        if angle > tolerances.angle {
+                            (DiscontinuityKind::Tangent, angle)
+                        } else {
+                            let k0 = prev.frame(1.0).curvature;
+                            let k1 = next.frame(0.0).curvature;
+                            let jump = (k1 - k0).abs();
+                            if jump > tolerances.curvature {
+                                (DiscontinuityKind::Curvature, jump)
+                            } else {
+                                continue;
+                            }
+                        }
+                    }
+                };
+                result.push(Discontinuity {
+                    index,
+                    point,
+                    kind,
+                    amount,
+                });
+            }
+        }
+        result
+    }
+}
+
+impl BezPath {
+    /// Find the corners of the path, where the tangent turns sharply.
+    ///
+    /// Corners are reported at junctions between segments, including the start of
+    /// closed subpaths, where the tangent direction changes by more than
+    /// `angle_threshold` radians, and at cusps in the interior of cubic segments,
+    /// where it reverses. They are ordered along the path.
+    pub fn corners(&self, angle_threshold: f64) -> Vec<Corner> {
+        let mut result = Vec::new();
+        for sub in self.subpath_segs() {
+            let segs = &sub.segs;
+            let n = segs.len();
+            let mut wrap = None;
+            for i in 0..n {
+                let (index, seg) = segs[i];
+                let prev = if i > 0 {
+                    Some(segs[i - 1].1)
+                } else if sub.closed {
+                    Some(segs[n - 1].1)
+                } else {
+                    None
+                };
+                if let Some(prev) = prev {
+                    let t0 = prev.tangent(1.0);
+                    let t1 = seg.tangent(0.0);
+                    let angle = t0.cross(t1).atan2(t0.dot(t1)).abs();
+                    if angle > angle_threshold {
+                        let corner = Corner {
+                            index,
+                            t: 0.0,
+                            point: seg.start(),
+                            angle,
+                        };
+                        if i == 0 {
+                            // Report the closing corner with the end of the subpath.
+                            wrap = Some(corner);
+                        } else {
+                            result.push(corner);
+                        }
+                    }
+                }
+                if let PathSeg::Cubic(c) = seg {
+                    for p in c.interesting_points() {
+                        if let InterestingPoint::Cusp(t) = p {
+                            if PI > angle_threshold {
+                                result.push(Corner {
+                                    index,
+                                    t,
+                                    point: c.eval(t),
+                                    angle: PI,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            result.extend(wrap);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, ContinuityTolerances, DiscontinuityKind, Shape, Vec2};
+
+    #[test]
+    fn corners() {
+        let path = BezPath::from_svg("M0 0L10 0L20 1C25 1 20 5 30 0L30 10Z").unwrap();
+        let corners = path.corners(0.5);
+        let indices: Vec<_> = corners.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![4, 5, 1]);
+        assert!((corners[0].angle - 10f64.atan2(-5.0)).abs() < 1e-9);
+        assert_eq!(corners[2].point, Vec2::new(0.0, 0.0));
+
+        // A cusp in the middle of a cubic.
+        let cusp = BezPath::from_svg("M0 0C2 2 0 2 2 0").unwrap();
+        let corners = cusp.corners(0.5);
+        assert_eq!(corners.len(), 1);
+        assert_eq!(corners[0].t, 0.5);
+        assert_eq!(corners[0].point, Vec2::new(1.0, 1.5));
+
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        assert!(circle.corners(1e-6).is_empty());
+    }
+
+    #[test]
+    fn continuity_report() {
+        let tolerances = ContinuityTolerances {
+            position: 1e-3,
+            angle: 1e-6,
+            curvature: 1e-3,
+        };
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let report = square.continuity_report(&tolerances);
+        assert_eq!(report.len(), 4);
+        assert!(report.iter().all(|d| d.kind == DiscontinuityKind::Tangent));
+        assert_eq!(report[3].index, 1);
+
+        // A line leading smoothly into a circular arc is G1 but not G2.
+        let path = BezPath::from_svg("M-10 10L0 10C5.523 10 10 5.523 10 0").unwrap();
+        let report = path.continuity_report(&tolerances);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, DiscontinuityKind::Curvature);
+        assert!((report[0].amount - 0.1).abs() < 0.01);
+
+        // The arcs approximating a circle are identical, so their curvatures match.
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        assert!(circle.continuity_report(&tolerances).is_empty());
+
+        let nearly_closed = BezPath::from_svg("M0 0L10 0L10 10L0.0001 0Z").unwrap();
+        let report = nearly_closed.continuity_report(&tolerances);
+        assert_eq!(report.len(), 3);
+        assert_eq!(report[2].kind, DiscontinuityKind::Position);
+    }
+}
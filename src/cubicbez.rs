@@ -5,11 +5,11 @@ use std::ops::{Mul, Range};
 use crate::MAX_EXTREMA;
 use arrayvec::ArrayVec;
 
-use crate::common::solve_quadratic;
 use crate::common::GAUSS_LEGENDRE_COEFFS_9;
+use crate::common::{solve_poly_interval, solve_quadratic};
 use crate::{
     Affine, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature, ParamCurveDeriv,
-    ParamCurveExtrema, ParamCurveNearest, QuadBez, Vec2,
+    ParamCurveExtrema, ParamCurveNearest, PathSeg, QuadBez, Vec2,
 };
 
 /// A single cubic Bézier segment.
@@ -21,6 +21,49 @@ pub struct CubicBez {
     pub p3: Vec2,
 }
 
+/// A point of interest for curve analysis, as found by
+/// [`interesting_points`](struct.CubicBez.html#method.interesting_points).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterestingPoint {
+    /// An inflection point, where the curvature changes sign.
+    Inflection(f64),
+    /// A local maximum of the magnitude of curvature.
+    CurvatureExtremum(f64),
+    /// A cusp, where the derivative vanishes.
+    Cusp(f64),
+}
+
+/// The result of [`arclen_with_stats`](struct.CubicBez.html#method.arclen_with_stats).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArclenStats {
+    /// The estimated arclength.
+    pub length: f64,
+    /// The estimated bound on the error of `length`, summed over the pieces.
+    ///
+    /// This is below the requested accuracy unless subdivision hit its
+    /// depth limit.
+    pub error: f64,
+    /// The number of pieces the curve was subdivided into.
+    pub subdivisions: usize,
+}
+
+/// The kind of a place where a cubic nearly stops, as found by
+/// [`cusps`](struct.CubicBez.html#method.cusps).
+///
+/// In the classification of Stone and DeRose, cubics with a cusp lie on the
+/// boundary between those with a loop and those with two inflections, so a
+/// small change turns a cusp into a tiny loop or a sharp double bend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CuspType {
+    /// The derivative vanishes, and the curve reverses direction.
+    Cusp,
+    /// The curve turns through a small loop.
+    Loop,
+    /// The curve turns sharply one way and then back, with two inflection
+    /// points close together.
+    DoubleInflection,
+}
+
 /// An iterator which produces quadratic Bézier segments.
 struct ToQuads {
     c: CubicBez,
@@ -60,6 +103,417 @@ impl CubicBez {
     }
 }
 
+impl CubicBez {
+    /// The coefficients of the first derivative, as a polynomial in `t`.
+    fn deriv_poly(&self) -> [Vec2; 3] {
+        let q0 = 3.0 * (self.p1 - self.p0);
+        let q1 = 3.0 * (self.p2 - self.p1);
+        let q2 = 3.0 * (self.p3 - self.p2);
+        [q0, 2.0 * (q1 - q0), q0 - 2.0 * q1 + q2]
+    }
+
+    /// A threshold below which the squared norm of the derivative is considered zero.
+    fn deriv_epsilon2(&self) -> f64 {
+        let scale = (self.p1 - self.p0)
+            .hypot2()
+            .max((self.p2 - self.p1).hypot2())
+            .max((self.p3 - self.p2).hypot2());
+        1e-18 * scale
+    }
+
+    /// Find the local maxima of the magnitude of curvature.
+    ///
+    /// The extrema of curvature are the roots of its derivative, whose numerator
+    /// is a polynomial of degree 6 in `t`. Of these, the parameters in the
+    /// interior of the curve where the magnitude of curvature is a local maximum
+    /// are returned, in increasing order. Cusps, where curvature is unbounded,
+    /// are not included.
+    pub fn curvature_extrema(&self) -> Vec<f64> {
+        let [a0, a1, a2] = self.deriv_poly();
+        // First, second, and third derivatives as polynomials.
+        let d1 = [a0, a1, a2];
+        let d2 = [a1, 2.0 * a2];
+        let d3 = [2.0 * a2];
+        // d/dt (d1 × d2) / |d1|³ has numerator (d1 × d3) |d1|² - 3 (d1 × d2) (d1 · d2).
+        let cross = |a: Vec2, b: Vec2| a.cross(b);
+        let dot = |a: Vec2, b: Vec2| a.dot(b);
+        let lhs = poly_mul(&vec_poly(&d1, &d3, cross), &vec_poly(&d1, &d1, dot));
+        let rhs = poly_mul(&vec_poly(&d1, &d2, cross), &vec_poly(&d1, &d2, dot));
+        let mut numer = vec![0.0; lhs.len().max(rhs.len())];
+        for (i, c) in lhs.iter().enumerate() {
+            numer[i] += c;
+        }
+        for (i, c) in rhs.iter().enumerate() {
+            numer[i] -= 3.0 * c;
+        }
+        let numer_deriv: Vec<f64> = numer[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i + 1) as f64 * c)
+            .collect();
+        let eps = self.deriv_epsilon2();
+        let deriv = self.deriv();
+        let deriv2 = deriv.deriv();
+        solve_poly_interval(&numer, 0.0, 1.0)
+            .into_iter()
+            .filter(|&t| {
+                let d = deriv.eval(t);
+                if t <= 0.0 || t >= 1.0 || d.hypot2() <= eps {
+                    return false;
+                }
+                // The magnitude of curvature has a maximum where the derivative of
+                // curvature crosses zero towards the opposite sign of curvature.
+                let k = d.cross(deriv2.eval(t));
+                k * eval_poly(&numer_deriv, t) < 0.0
+            })
+            .collect()
+    }
+
+    /// The unit tangent vector at the start of the curve.
+    ///
+    /// This is the direction from the start point to the first control point
+    /// that does not coincide with it, which is the direction of the first
+    /// nonvanishing derivative. Unlike normalizing the derivative, it is
+    /// well defined when control handles are retracted; it is zero only if
+    /// all four points coincide.
+    pub fn start_tangent(&self) -> Vec2 {
+        first_direction(
+            self.p0,
+            &[self.p1, self.p2, self.p3],
+            self.tangent_epsilon2(),
+        )
+    }
+
+    /// The unit tangent vector at the end of the curve.
+    ///
+    /// This is the direction of travel into the end point, found as for
+    /// [`start_tangent`](#method.start_tangent).
+    pub fn end_tangent(&self) -> Vec2 {
+        -first_direction(
+            self.p3,
+            &[self.p2, self.p1, self.p0],
+            self.tangent_epsilon2(),
+        )
+    }
+
+    /// A threshold below which squared distances between control points are
+    /// considered zero, for tangent directions.
+    fn tangent_epsilon2(&self) -> f64 {
+        let scale = (self.p1 - self.p0).hypot2()
+            + (self.p2 - self.p1).hypot2()
+            + (self.p3 - self.p2).hypot2();
+        1e-24 * scale
+    }
+
+    /// The greatest magnitude of the derivative over the curve.
+    ///
+    /// The derivative is a quadratic Bézier, the hodograph, and its squared
+    /// magnitude is extremal at the endpoints or where its own derivative,
+    /// a cubic, vanishes. These are all checked, so the result is exact up
+    /// to rounding, unlike estimates by sampling.
+    pub fn max_deriv_norm(&self) -> f64 {
+        let deriv = self.deriv();
+        self.deriv_norm_extrema()
+            .map(|t| deriv.eval(t).hypot())
+            .fold(0.0, f64::max)
+    }
+
+    /// The least magnitude of the derivative over the curve.
+    ///
+    /// This is exact up to rounding, as for
+    /// [`max_deriv_norm`](#method.max_deriv_norm). It is zero, or nearly so,
+    /// at a cusp.
+    pub fn min_deriv_norm(&self) -> f64 {
+        let deriv = self.deriv();
+        self.deriv_norm_extrema()
+            .map(|t| deriv.eval(t).hypot())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Candidate parameters for the extrema of the magnitude of the derivative.
+    fn deriv_norm_extrema(&self) -> impl Iterator<Item = f64> {
+        let d1 = self.deriv_poly();
+        let d2 = [d1[1], 2.0 * d1[2]];
+        let roots = solve_poly_interval(&vec_poly(&d1, &d2, |a, b| a.dot(b)), 0.0, 1.0);
+        roots.into_iter().chain(vec![0.0, 1.0])
+    }
+
+    /// The greatest magnitude of curvature over the curve.
+    ///
+    /// This is the largest of the curvature at the endpoints and at the
+    /// [local maxima](#method.curvature_extrema) in between, found by root
+    /// solving, so is exact up to rounding. Where the derivative vanishes,
+    /// at a cusp or an endpoint coinciding with its control point, the
+    /// curvature is unbounded or its formula breaks down, and the result is
+    /// infinite, which is still a valid upper bound.
+    pub fn max_curvature(&self) -> f64 {
+        let deriv = self.deriv();
+        let eps = self.deriv_epsilon2();
+        if deriv.eval(0.0).hypot2() <= eps
+            || deriv.eval(1.0).hypot2() <= eps
+            || !self.exact_cusps().is_empty()
+        {
+            return f64::INFINITY;
+        }
+        self.curvature_extrema()
+            .into_iter()
+            .chain(vec![0.0, 1.0])
+            .map(|t| self.curvature(t).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Parameters of inflection points in the interior of the curve.
+    fn inflections(&self) -> Vec<f64> {
+        let [a0, a1, a2] = self.deriv_poly();
+        // The cubic term of d1 × d2 cancels.
+        let c0 = a0.cross(a1);
+        let c1 = 2.0 * a0.cross(a2);
+        let c2 = a1.cross(a2);
+        let eps = self.deriv_epsilon2();
+        let deriv = self.deriv();
+        let mut result: Vec<f64> = solve_quadratic(c0, c1, c2)
+            .into_iter()
+            .filter(|&t| t > 0.0 && t < 1.0 && deriv.eval(t).hypot2() > eps)
+            .collect();
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+    /// The places in the interior of the curve where it nearly stops.
+    ///
+    /// These are the cusps, where the derivative vanishes, and the local
+    /// minima of the magnitude of the derivative where it is at most
+    /// `tolerance`, in increasing order of parameter. The tolerance is in
+    /// units of distance per unit of parameter, comparable to the distances
+    /// between control points; the curve makes sharp turns in these places,
+    /// which stroking and offsetting need to handle specially. Each is
+    /// classified according to the shape of the curve around it.
+    pub fn cusps(&self, tolerance: f64) -> Vec<(f64, CuspType)> {
+        let exact = self.exact_cusps();
+        let [a0, a1, a2] = self.deriv_poly();
+        // The sign of the discriminant of the inflection polynomial tells a
+        // loop from a double inflection.
+        let c0 = a0.cross(a1);
+        let c1 = 2.0 * a0.cross(a2);
+        let c2 = a1.cross(a2);
+        let kind = if c1 * c1 - 4.0 * c0 * c2 < 0.0 {
+            CuspType::Loop
+        } else {
+            CuspType::DoubleInflection
+        };
+        let deriv = self.deriv();
+        let deriv2 = deriv.deriv();
+        let mut result: Vec<(f64, CuspType)> = exact.iter().map(|&t| (t, CuspType::Cusp)).collect();
+        for t in self.deriv_norm_extrema() {
+            if t <= 0.0 || t >= 1.0 || exact.iter().any(|&c| (c - t).abs() < 1e-6) {
+                continue;
+            }
+            let d = deriv.eval(t);
+            let dd = deriv2.eval(t);
+            // The second derivative of |d|² / 2 is positive at a minimum.
+            let curv = dd.hypot2() + d.dot(deriv2.deriv().eval(t));
+            if d.hypot() <= tolerance && curv > 0.0 {
+                result.push((t, kind));
+            }
+        }
+        result.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        result
+    }
+
+    /// Parameters of cusps in the interior of the curve.
+    fn exact_cusps(&self) -> Vec<f64> {
+        let [a0, a1, a2] = self.deriv_poly();
+        let eps = self.deriv_epsilon2();
+        let deriv = self.deriv();
+        // Either coordinate of the derivative may vanish identically.
+        let mut roots = solve_quadratic(a0.x, a1.x, a2.x);
+        if roots.is_empty() {
+            roots = solve_quadratic(a0.y, a1.y, a2.y);
+        }
+        let mut result: Vec<f64> = roots
+            .into_iter()
+            .filter(|&t| t > 0.0 && t < 1.0 && deriv.eval(t).hypot2() <= eps)
+            .collect();
+        result.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        result
+    }
+
+    /// Find the points of interest for curve analysis.
+    ///
+    /// These are the inflection points, the local maxima of the magnitude of
+    /// curvature, and the cusps in the interior of the curve, ordered by
+    /// parameter. They are good places to subdivide a curve, as the pieces
+    /// between them have well-behaved curvature.
+    pub fn interesting_points(&self) -> Vec<InterestingPoint> {
+        let mut result: Vec<InterestingPoint> = self
+            .inflections()
+            .into_iter()
+            .map(InterestingPoint::Inflection)
+            .chain(
+                self.curvature_extrema()
+                    .into_iter()
+                    .map(InterestingPoint::CurvatureExtremum),
+            )
+            .chain(self.exact_cusps().into_iter().map(InterestingPoint::Cusp))
+            .collect();
+        result.sort_by(|a, b| a.t().partial_cmp(&b.t()).unwrap());
+        result
+    }
+
+    /// The parameters of points where the tangent line passes through `p`.
+    ///
+    /// These are the points touched by lines drawn from `p` tangent to the
+    /// curve, as used for connectors that leave a point and meet the curve
+    /// smoothly. Endpoints are only included if they are tangent points.
+    /// If `p` lies on the curve, its own parameter is also included.
+    ///
+    /// The result is in increasing order.
+    pub fn tangents_from_point(&self, p: Vec2) -> Vec<f64> {
+        let pos = [
+            self.p0 - p,
+            3.0 * (self.p1 - self.p0),
+            3.0 * (self.p2 - 2.0 * self.p1 + self.p0),
+            self.p3 - 3.0 * self.p2 + 3.0 * self.p1 - self.p0,
+        ];
+        let numer = vec_poly(&pos, &self.deriv_poly(), |a, b| a.cross(b));
+        solve_poly_interval(&numer, 0.0, 1.0)
+    }
+
+    /// Approximate the curve by cubics parametrized by arclength.
+    ///
+    /// Each resulting cubic covers a piece of the curve, and evaluating it at
+    /// `u` gives, to within `accuracy`, the point a fraction `u` of the way
+    /// along that piece by arclength. Stepping the parameter uniformly thus
+    /// moves at constant speed, as wanted for animation along a path or for
+    /// evenly spaced dashes. The pieces are in order and join continuously,
+    /// with matching tangent directions except at cusps.
+    ///
+    /// Each piece is the cubic Hermite interpolant of the arclength
+    /// parametrization of its part of the curve, matching positions and unit
+    /// speed tangents at its ends. Pieces are halved by arclength until the
+    /// positions at a quarter, half, and three quarters of the way along are
+    /// within `accuracy`.
+    pub fn reparam_arclen(&self, accuracy: f64) -> Vec<CubicBez> {
+        let mut result = Vec::new();
+        let inner = 0.1 * accuracy;
+        let len = self.arclen(inner);
+        if len <= accuracy {
+            // Too short to parametrize meaningfully; a straight cubic will do.
+            let (p0, p3) = (self.p0, self.p3);
+            result.push(CubicBez::new(
+                p0,
+                p0.lerp(p3, 1.0 / 3.0),
+                p0.lerp(p3, 2.0 / 3.0),
+                p3,
+            ));
+            return result;
+        }
+        self.reparam_rec(0.0, 1.0, len, accuracy, 0, &mut result);
+        result
+    }
+
+    fn reparam_rec(
+        &self,
+        t0: f64,
+        t1: f64,
+        len: f64,
+        accuracy: f64,
+        depth: usize,
+        result: &mut Vec<CubicBez>,
+    ) {
+        const MAX_DEPTH: usize = 16;
+        let sub = self.subsegment(t0..t1);
+        let seg = PathSeg::Cubic(sub);
+        let arm = len / 3.0;
+        let piece = CubicBez::new(
+            sub.p0,
+            sub.p0 + arm * seg.tangent(0.0),
+            sub.p3 - arm * seg.tangent(1.0),
+            sub.p3,
+        );
+        let inner = 0.1 * accuracy;
+        let ok = depth >= MAX_DEPTH
+            || [0.25, 0.5, 0.75].iter().all(|&u| {
+                let t = sub.inv_arclen(u * len, inner);
+                (piece.eval(u) - sub.eval(t)).hypot() <= accuracy
+            });
+        if ok {
+            result.push(piece);
+            return;
+        }
+        let tm = t0 + (t1 - t0) * sub.inv_arclen(0.5 * len, inner);
+        let len0 = self.subsegment(t0..tm).arclen(inner);
+        let len1 = self.subsegment(tm..t1).arclen(inner);
+        self.reparam_rec(t0, tm, len0, accuracy, depth + 1, result);
+        self.reparam_rec(tm, t1, len1, accuracy, depth + 1, result);
+    }
+
+    /// Arclength, with an error estimate and the cost of computing it.
+    ///
+    /// This is the computation behind [`arclen`](#method.arclen): the curve
+    /// is subdivided until an estimate of the error of Legendre-Gauss
+    /// quadrature on each piece is within its share of `accuracy`. The
+    /// statistics let callers see what a given accuracy costs.
+    pub fn arclen_with_stats(&self, accuracy: f64) -> ArclenStats {
+        const MAX_DEPTH: usize = 16;
+        fn rec(c: &CubicBez, accuracy: f64, depth: usize, stats: &mut ArclenStats) {
+            let err = est_gauss9_error(c);
+            if depth == MAX_DEPTH || err < accuracy {
+                stats.length += c.gauss_arclen(GAUSS_LEGENDRE_COEFFS_9);
+                stats.error += err;
+                stats.subdivisions += 1;
+            } else {
+                let (c0, c1) = c.subdivide();
+                rec(&c0, accuracy * 0.5, depth + 1, stats);
+                rec(&c1, accuracy * 0.5, depth + 1, stats);
+            }
+        }
+        let mut stats = ArclenStats {
+            length: 0.0,
+            error: 0.0,
+            subdivisions: 0,
+        };
+        rec(self, accuracy, 0, &mut stats);
+        stats
+    }
+}
+
+impl InterestingPoint {
+    /// The parameter of the point on the curve.
+    pub fn t(&self) -> f64 {
+        match *self {
+            InterestingPoint::Inflection(t)
+            | InterestingPoint::CurvatureExtremum(t)
+            | InterestingPoint::Cusp(t) => t,
+        }
+    }
+}
+
+/// Multiply two polynomials with vector coefficients, combining coefficients with `f`.
+fn vec_poly(a: &[Vec2], b: &[Vec2], f: impl Fn(Vec2, Vec2) -> f64) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            result[i + j] += f(ai, bj);
+        }
+    }
+    result
+}
+
+fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        for (j, bj) in b.iter().enumerate() {
+            result[i + j] += ai * bj;
+        }
+    }
+    result
+}
+
+fn eval_poly(coeffs: &[f64], t: f64) -> f64 {
+    coeffs.iter().rev().fold(0.0, |acc, c| acc * t + c)
+}
+
 impl ParamCurve for CubicBez {
     #[inline]
     fn eval(&self, t: f64) -> Vec2 {
@@ -78,6 +532,17 @@ impl ParamCurve for CubicBez {
         self.p3
     }
 
+    fn eval_many(&self, ts: &[f64], out: &mut [Vec2]) {
+        assert_eq!(ts.len(), out.len(), "ts and out must have equal length");
+        let c0 = self.p0;
+        let c1 = 3.0 * (self.p1 - self.p0);
+        let c2 = 3.0 * (self.p2 - 2.0 * self.p1 + self.p0);
+        let c3 = self.p3 - 3.0 * self.p2 + 3.0 * self.p1 - self.p0;
+        for (p, &t) in out.iter_mut().zip(ts) {
+            *p = c0 + (c1 + (c2 + c3 * t) * t) * t;
+        }
+    }
+
     fn subsegment(&self, range: Range<f64>) -> CubicBez {
         let (t0, t1) = (range.start, range.end);
         let p0 = self.eval(t0);
@@ -129,29 +594,34 @@ impl ParamCurveArclen for CubicBez {
     /// This is an adaptive subdivision approach using Legendre-Gauss quadrature
     /// in the base case, and an error estimate to decide when to subdivide.
     fn arclen(&self, accuracy: f64) -> f64 {
-        // Squared L2 norm of the second derivative of the cubic.
-        fn cubic_errnorm(c: &CubicBez) -> f64 {
-            let d = c.deriv().deriv();
-            let dd = d.end() - d.start();
-            d.start().hypot2() + d.start().dot(dd) + dd.hypot2() * (1.0 / 3.0)
-        }
-        fn est_gauss9_error(c: &CubicBez) -> f64 {
-            let lc = (c.p3 - c.p0).hypot();
-            let lp = (c.p1 - c.p0).hypot() + (c.p2 - c.p1).hypot() + (c.p3 - c.p2).hypot();
+        self.arclen_with_stats(accuracy).length
+    }
+}
 
-            2.56e-8 * (cubic_errnorm(c) / (lc * lc)).powi(8) * lp
-        }
-        const MAX_DEPTH: usize = 16;
-        fn rec(c: &CubicBez, accuracy: f64, depth: usize) -> f64 {
-            if depth == MAX_DEPTH || est_gauss9_error(c) < accuracy {
-                c.gauss_arclen(GAUSS_LEGENDRE_COEFFS_9)
-            } else {
-                let (c0, c1) = c.subdivide();
-                rec(&c0, accuracy * 0.5, depth + 1) + rec(&c1, accuracy * 0.5, depth + 1)
-            }
+/// The unit vector from `p` to the first of `pts` not within `sqrt(eps2)`.
+fn first_direction(p: Vec2, pts: &[Vec2], eps2: f64) -> Vec2 {
+    for &q in pts {
+        let d = q - p;
+        if d.hypot2() > eps2 {
+            return d / d.hypot();
         }
-        rec(self, accuracy, 0)
     }
+    Vec2::default()
+}
+
+// Squared L2 norm of the second derivative of the cubic.
+fn cubic_errnorm(c: &CubicBez) -> f64 {
+    let d = c.deriv().deriv();
+    let dd = d.end() - d.start();
+    d.start().hypot2() + d.start().dot(dd) + dd.hypot2() * (1.0 / 3.0)
+}
+
+/// Estimated error of 9-point Legendre-Gauss quadrature of the arclength.
+fn est_gauss9_error(c: &CubicBez) -> f64 {
+    let lc = (c.p3 - c.p0).hypot();
+    let lp = (c.p1 - c.p0).hypot() + (c.p2 - c.p1).hypot() + (c.p3 - c.p2).hypot();
+
+    2.56e-8 * (cubic_errnorm(c) / (lc * lc)).powi(8) * lp
 }
 
 impl ParamCurveArea for CubicBez {
@@ -257,10 +727,57 @@ impl Iterator for ToQuads {
 #[cfg(test)]
 mod tests {
     use crate::{
-        Affine, CubicBez, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveDeriv,
-        ParamCurveExtrema, ParamCurveNearest, Vec2,
+        Affine, CubicBez, CuspType, InterestingPoint, Line, ParamCurve, ParamCurveArclen,
+        ParamCurveArea, ParamCurveCurvature, ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest,
+        PathSeg, QuadBez, Vec2,
     };
 
+    #[test]
+    fn cubicbez_tangents_from_point() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0));
+        // By symmetry, the tangent points from above straddle the middle.
+        let p = Vec2::new(1.0, 3.0);
+        let ts = c.tangents_from_point(p);
+        assert_eq!(ts.len(), 2);
+        assert!((ts[0] + ts[1] - 1.0).abs() < 1e-12);
+        for &t in &ts {
+            let d = c.deriv().eval(t);
+            assert!((c.eval(t) - p).cross(d).abs() < 1e-9);
+        }
+        // The apex is the tangent point from a point on its tangent line.
+        let ts = c.tangents_from_point(Vec2::new(5.0, 1.5));
+        assert!(ts.iter().any(|&t| (t - 0.5).abs() < 1e-9));
+    }
+
+    #[test]
+    fn cubicbez_curvature_extrema() {
+        // A symmetric arch has its sharpest point in the middle.
+        let c = CubicBez::new((0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0));
+        let extrema = c.curvature_extrema();
+        assert_eq!(extrema.len(), 1);
+        assert!((extrema[0] - 0.5).abs() < 1e-9);
+        let k = |t: f64| c.curvature(t).abs();
+        assert!(k(0.5) > k(0.49) && k(0.5) > k(0.51));
+
+        // An S curve has an inflection in the middle, flanked by two extrema.
+        let s = CubicBez::new((0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 2.0));
+        let points = s.interesting_points();
+        assert_eq!(points.len(), 3);
+        assert!(matches!(points[0], InterestingPoint::CurvatureExtremum(_)));
+        assert_eq!(points[1], InterestingPoint::Inflection(0.5));
+        assert!(matches!(points[2], InterestingPoint::CurvatureExtremum(_)));
+        assert!((points[0].t() + points[2].t() - 1.0).abs() < 1e-9);
+
+        let cusp = CubicBez::new((0.0, 0.0), (2.0, 2.0), (0.0, 2.0), (2.0, 0.0));
+        let points = cusp.interesting_points();
+        assert!(points.contains(&InterestingPoint::Cusp(0.5)));
+        assert!(
+            CubicBez::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0))
+                .interesting_points()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn cubicbez_deriv() {
         // y = x^2
@@ -399,6 +916,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::explicit_counter_loop)]
     fn cubicbez_toquads() {
         // y = x^3
         let c = CubicBez::new((0.0, 0.0), (1.0 / 3.0, 0.0), (2.0 / 3.0, 0.0), (1.0, 1.0));
@@ -423,4 +941,118 @@ mod tests {
             //println!("accuracy {:e}: got {:e}, {} quads", accuracy, worst, _count);
         }
     }
+
+    #[test]
+    fn cubicbez_reparam_arclen() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 100.0), (30.0, 100.0), (100.0, 0.0));
+        let accuracy = 1e-3;
+        let pieces = c.reparam_arclen(accuracy);
+        assert!(pieces.len() > 1);
+        let total = c.arclen(1e-9);
+        let sum: f64 = pieces.iter().map(|p| p.arclen(1e-9)).sum();
+        assert!((sum - total).abs() < 1e-2);
+        assert_eq!(pieces[0].p0, c.p0);
+        assert_eq!(pieces.last().unwrap().p3, c.p3);
+        for piece in &pieces {
+            let len = piece.arclen(1e-9);
+            for i in 1..10 {
+                let u = i as f64 * 0.1;
+                // The parameter is proportional to arclength along the piece.
+                let s = piece.subsegment(0.0..u).arclen(1e-9);
+                assert!((s - u * len).abs() < 10.0 * accuracy, "{} {}", s, u * len);
+                // And the piece lies on the original curve.
+                assert!(c.nearest(piece.eval(u), 1e-9).1.sqrt() < accuracy);
+            }
+        }
+    }
+
+    #[test]
+    fn cubicbez_eval_many() {
+        let c = CubicBez::new((0.0, 0.0), (1.0, 3.0), (4.0, -1.0), (5.0, 2.0));
+        let ts: Vec<f64> = (0..=16).map(|i| i as f64 / 16.0).collect();
+        let mut out = vec![Vec2::default(); ts.len()];
+        for seg in &[
+            PathSeg::Cubic(c),
+            PathSeg::Quad(QuadBez::new((0.0, 0.0), (1.0, 3.0), (4.0, -1.0))),
+            PathSeg::Line(Line::new((0.0, 0.0), (1.0, 3.0))),
+        ] {
+            seg.eval_many(&ts, &mut out);
+            for (&t, &p) in ts.iter().zip(&out) {
+                assert!((seg.eval(t) - p).hypot() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn cubicbez_deriv_curvature_bounds() {
+        let c = CubicBez::new((0.0, 0.0), (1.0, 3.0), (4.0, -1.0), (5.0, 2.0));
+        let d = c.deriv();
+        let (mut max_d, mut min_d, mut max_k) = (0.0f64, f64::INFINITY, 0.0f64);
+        for i in 0..=10000 {
+            let t = i as f64 * 1e-4;
+            max_d = max_d.max(d.eval(t).hypot());
+            min_d = min_d.min(d.eval(t).hypot());
+            max_k = max_k.max(c.curvature(t).abs());
+        }
+        // The bounds hold, and are tight.
+        assert!(c.max_deriv_norm() >= max_d && c.max_deriv_norm() < max_d + 1e-6);
+        assert!(c.min_deriv_norm() <= min_d && c.min_deriv_norm() > min_d - 1e-6);
+        assert!(c.max_curvature() >= max_k && c.max_curvature() < max_k * (1.0 + 1e-6));
+
+        let cusp = CubicBez::new((0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 0.0));
+        assert!(cusp.min_deriv_norm() < 1e-9);
+        assert!(cusp.max_curvature().is_infinite());
+    }
+
+    #[test]
+    fn cubicbez_arclen_with_stats() {
+        // y = x^2
+        let c = CubicBez::new(
+            (0.0, 0.0),
+            (1.0 / 3.0, 0.0),
+            (2.0 / 3.0, 1.0 / 3.0),
+            (1.0, 1.0),
+        );
+        let true_arclen = 0.5 * 5.0f64.sqrt() + 0.25 * (2.0 + 5.0f64.sqrt()).ln();
+        let coarse = c.arclen_with_stats(1e-2);
+        let fine = c.arclen_with_stats(1e-9);
+        assert_eq!(fine.length, c.arclen(1e-9));
+        assert!(coarse.error < 1e-2 && fine.error < 1e-9);
+        assert!((fine.length - true_arclen).abs() < 1e-9);
+        assert!(coarse.subdivisions >= 1);
+        assert!(fine.subdivisions > coarse.subdivisions);
+    }
+
+    #[test]
+    fn cubicbez_cusps() {
+        let cusp = CubicBez::new((0.0, 0.0), (2.0, 2.0), (0.0, 2.0), (2.0, 0.0));
+        assert_eq!(cusp.cusps(0.1), vec![(0.5, CuspType::Cusp)]);
+        // Pulling the ends apart opens the cusp into a double bend, and
+        // pushing them together makes a loop.
+        let bend = CubicBez::new((-0.1, 0.0), (2.0, 2.0), (0.0, 2.0), (2.1, 0.0));
+        let cusps = bend.cusps(1.0);
+        assert_eq!(cusps.len(), 1);
+        assert!((cusps[0].0 - 0.5).abs() < 1e-9);
+        assert_eq!(cusps[0].1, CuspType::DoubleInflection);
+        assert!(bend.cusps(0.01).is_empty());
+        let lp = CubicBez::new((0.1, 0.0), (2.0, 2.0), (0.0, 2.0), (1.9, 0.0));
+        assert_eq!(lp.cusps(1.0)[0].1, CuspType::Loop);
+        let smooth = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 3.0), (4.0, 0.0));
+        assert!(smooth.cusps(1.0).is_empty());
+    }
+
+    #[test]
+    fn cubicbez_end_tangents() {
+        let c = CubicBez::new((0.0, 0.0), (0.0, 0.0), (3.0, 4.0), (5.0, 4.0));
+        assert!((c.start_tangent() - Vec2::new(0.6, 0.8)).hypot() < 1e-12);
+        assert!((c.end_tangent() - Vec2::new(1.0, 0.0)).hypot() < 1e-12);
+        let c = CubicBez::new((0.0, 0.0), (0.0, 0.0), (2.0, 0.0), (2.0, 0.0));
+        assert!((c.start_tangent() - Vec2::new(1.0, 0.0)).hypot() < 1e-12);
+        assert!((c.end_tangent() - Vec2::new(1.0, 0.0)).hypot() < 1e-12);
+        let c = CubicBez::new((1.0, 1.0), (1.0, 1.0), (1.0, 1.0), (1.0, 3.0));
+        assert!((c.start_tangent() - Vec2::new(0.0, 1.0)).hypot() < 1e-12);
+        let point = CubicBez::new((1.0, 1.0), (1.0, 1.0), (1.0, 1.0), (1.0, 1.0));
+        assert_eq!(point.start_tangent(), Vec2::default());
+        assert!(point.end_tangent().hypot() == 0.0);
+    }
 }
@@ -0,0 +1,237 @@
+//! Dash patterns.
+
+use crate::{BezPath, ParamCurve, ParamCurveArclen, PathEl, PathSeg};
+
+/// The most times a dash pattern is repeated along a subpath.
+///
+/// Patterns that are tiny compared to the path would make more dashes than
+/// could be told apart or stored, so the subpath is drawn solid instead.
+const MAX_PERIODS: f64 = 1e5;
+
+/// A pattern of dashes and gaps along a stroke.
+///
+/// The lengths alternate between dashes and gaps, starting with a dash; as
+/// in SVG, a list of odd length is repeated to make it even. The pattern
+/// starts afresh at the start of each subpath, shifted along by the phase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DashPattern {
+    /// The lengths of the dashes and gaps, in turn.
+    pub lengths: Vec<f64>,
+    /// How far into the pattern the start of each subpath is.
+    pub phase: f64,
+    /// Whether each dash gets the caps of the stroke, rather than only the
+    /// ends of open subpaths.
+    pub caps_per_dash: bool,
+}
+
+impl DashPattern {
+    /// Create a pattern from the lengths of dashes and gaps.
+    ///
+    /// The phase is zero, and each dash gets caps, as in SVG.
+    pub fn new(lengths: impl IntoIterator<Item = f64>) -> DashPattern {
+        DashPattern {
+            lengths: lengths.into_iter().collect(),
+            phase: 0.0,
+            caps_per_dash: true,
+        }
+    }
+
+    /// Builder method for setting the phase.
+    pub fn with_phase(mut self, phase: f64) -> DashPattern {
+        self.phase = phase;
+        self
+    }
+
+    /// Builder method for setting whether each dash gets caps.
+    pub fn with_caps_per_dash(mut self, caps_per_dash: bool) -> DashPattern {
+        self.caps_per_dash = caps_per_dash;
+        self
+    }
+
+    /// The ranges of arclength covered by dashes along a subpath of length
+    /// `total`.
+    ///
+    /// Dashes of zero length are left out. A pattern with no positive
+    /// lengths is solid, as is one that would repeat more than
+    /// `MAX_PERIODS` times along the subpath. On a closed subpath, a dash running over the end
+    /// joins up with one from the start, so its range ends beyond `total`,
+    /// wrapping around; a range of exactly `0.0..total` there means the
+    /// subpath is solid, and stays closed.
+    pub(crate) fn ranges(&self, total: f64, closed: bool) -> Vec<(f64, f64)> {
+        let mut lengths: Vec<f64> = self.lengths.iter().map(|l| l.max(0.0)).collect();
+        if lengths.len() % 2 == 1 {
+            lengths.extend_from_slice(&lengths.clone());
+        }
+        let period: f64 = lengths.iter().sum();
+        if !(period > 0.0 && total > 0.0 && total / period <= MAX_PERIODS) {
+            return vec![(0.0, total)];
+        }
+        let mut phase = if self.phase.is_finite() {
+            self.phase.rem_euclid(period)
+        } else {
+            0.0
+        };
+        let mut i = 0;
+        while phase >= lengths[i] {
+            phase -= lengths[i];
+            i = (i + 1) % lengths.len();
+        }
+        let mut remaining = lengths[i] - phase;
+        let mut result = Vec::new();
+        let mut s = 0.0;
+        loop {
+            let end = s + remaining;
+            if i % 2 == 0 && end.min(total) > s {
+                result.push((s, end.min(total)));
+            }
+            if end >= total {
+                break;
+            }
+            s = end;
+            i = (i + 1) % lengths.len();
+            remaining = lengths[i];
+        }
+        let n = result.len();
+        if closed && n >= 2 && result[0].0 == 0.0 && result[n - 1].1 == total {
+            let first = result.remove(0);
+            result[n - 2].1 = total + first.1;
+        }
+        result
+    }
+}
+
+impl BezPath {
+    /// The dashes of the path, as a path with an open subpath for each.
+    ///
+    /// Lengths along the path are measured to within `accuracy`. A closed
+    /// subpath with no gap in it stays closed.
+    pub fn dash(&self, pattern: &DashPattern, accuracy: f64) -> BezPath {
+        let mut result = BezPath::new();
+        for sub in self.subpath_segs() {
+            let segs: Vec<PathSeg> = sub.segs.iter().map(|s| s.1).collect();
+            let lens: Vec<f64> = segs.iter().map(|s| s.arclen(accuracy)).collect();
+            let total = lens.iter().sum();
+            let ranges = pattern.ranges(total, sub.closed);
+            if sub.closed && ranges == [(0.0, total)] {
+                result.moveto(segs[0].start());
+                for seg in &segs {
+                    result.push(seg.as_path_el());
+                }
+                result.closepath();
+                continue;
+            }
+            for (s0, s1) in ranges {
+                let mut started = false;
+                push_range(
+                    &segs,
+                    &lens,
+                    s0,
+                    s1.min(total),
+                    accuracy,
+                    &mut started,
+                    &mut result,
+                );
+                if s1 > total {
+                    push_range(
+                        &segs,
+                        &lens,
+                        0.0,
+                        s1 - total,
+                        accuracy,
+                        &mut started,
+                        &mut result,
+                    );
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Add the part of a subpath between two arclengths, starting a new subpath
+/// unless `started`.
+fn push_range(
+    segs: &[PathSeg],
+    lens: &[f64],
+    s0: f64,
+    s1: f64,
+    accuracy: f64,
+    started: &mut bool,
+    path: &mut BezPath,
+) {
+    let mut start = 0.0;
+    for (seg, &len) in segs.iter().zip(lens) {
+        let end = start + len;
+        if end > s0 && start < s1 {
+            let t0 = if s0 > start {
+                seg.inv_arclen(s0 - start, accuracy)
+            } else {
+                0.0
+            };
+            let t1 = if s1 < end {
+                seg.inv_arclen(s1 - start, accuracy)
+            } else {
+                1.0
+            };
+            let piece = seg.subsegment(t0..t1);
+            if !*started {
+                path.push(PathEl::Moveto(piece.start()));
+                *started = true;
+            }
+            path.push(piece.as_path_el());
+        }
+        start = end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, DashPattern, PathEl};
+
+    #[test]
+    fn dash() {
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        let dashed = line.dash(&DashPattern::new(vec![2.0, 1.0]), 1e-9);
+        assert_eq!(dashed.to_svg(), "M0 0L2 0M3 0L5 0M6 0L8 0M9 0L10 0");
+        // Odd lists repeat, and the phase shifts the pattern.
+        let dashed = line.dash(&DashPattern::new(vec![3.0]).with_phase(4.0), 1e-9);
+        assert_eq!(dashed.to_svg(), "M2 0L5 0M8 0L10 0");
+        // No positive lengths is solid.
+        let solid = line.dash(&DashPattern::new(vec![0.0, 0.0]), 1e-9);
+        assert_eq!(solid.to_svg(), line.to_svg());
+
+        // On a closed path, the dash over the seam joins up.
+        let square = BezPath::from_svg("M0 0L4 0L4 4L0 4Z").unwrap();
+        let dashed = square.dash(&DashPattern::new(vec![3.0, 2.0]).with_phase(1.0), 1e-9);
+        assert_eq!(dashed.to_svg(), "M4 0L4 3M3 4L0 4M0 2L0 0L2 0");
+        let dashed = square.dash(&DashPattern::new(vec![3.0, 1.0]).with_phase(1.0), 1e-9);
+        let moves = dashed
+            .elements()
+            .iter()
+            .filter(|el| matches!(el, PathEl::Moveto(_)))
+            .count();
+        assert_eq!(moves, 4);
+        assert!(matches!(dashed.elements()[0], PathEl::Moveto(p) if p.x == 3.0 && p.y == 0.0));
+        // A pattern without gaps leaves the subpath closed.
+        let closed = square.dash(&DashPattern::new(vec![20.0, 0.0]), 1e-9);
+        assert!(matches!(closed.elements().last(), Some(PathEl::Closepath)));
+
+        // Curves are cut by arclength.
+        let mut arc = BezPath::new();
+        arc.moveto((0.0, 0.0));
+        arc.quadto((5.0, 5.0), (10.0, 0.0));
+        let dashed = arc.dash(&DashPattern::new(vec![1.0, 1.0]), 1e-9);
+        let dash_count = dashed
+            .elements()
+            .iter()
+            .filter(|el| matches!(el, PathEl::Moveto(_)))
+            .count();
+        assert_eq!(dash_count, 6);
+
+        // Degenerate patterns are solid rather than making countless dashes.
+        let tiny = line.dash(&DashPattern::new(vec![1e-9, 1e-9]), 1e-9);
+        assert_eq!(tiny.to_svg(), line.to_svg());
+        let nan = line.dash(&DashPattern::new(vec![2.0, 1.0]).with_phase(f64::NAN), 1e-9);
+        assert_eq!(nan.to_svg(), "M0 0L2 0M3 0L5 0M6 0L8 0M9 0L10 0");
+    }
+}
@@ -0,0 +1,202 @@
+//! Changing the degree of Bézier segments.
+
+use crate::{CubicBez, Line, ParamCurve, ParamCurveNearest, PathSeg, QuadBez, Vec2};
+
+/// The simplest kind of segment that can represent a
+/// [`PathSeg`](enum.PathSeg.html), as found by
+/// [`classify`](enum.PathSeg.html#method.classify).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegKind {
+    /// All points of the segment are at its start.
+    Point,
+    /// The segment is a straight line.
+    Line,
+    /// The segment is a quadratic Bézier.
+    Quad,
+    /// The segment needs a cubic Bézier.
+    Cubic,
+}
+
+/// A curve that can be represented exactly by a curve of higher degree.
+///
+/// Elevating a [`Line`](struct.Line.html) gives a
+/// [`QuadBez`](struct.QuadBez.html), and a quadratic gives a
+/// [`CubicBez`](struct.CubicBez.html), so generic code can bring segments
+/// of mixed types to a common one.
+pub trait DegreeElevate {
+    /// The type of the elevated curve.
+    type Elevated;
+
+    /// The same curve, with one more control point.
+    ///
+    /// The result has the same parametrization, so it evaluates to the same
+    /// point at every parameter.
+    fn elevate(&self) -> Self::Elevated;
+}
+
+/// A curve that can be approximated by a curve of lower degree.
+pub trait DegreeReduce {
+    /// The type of the reduced curve.
+    type Reduced;
+
+    /// An approximation with one fewer control point, and its error.
+    ///
+    /// The approximation has the same endpoints. The error is the greatest
+    /// distance between the two curves at the same parameter, so it bounds
+    /// the distance between them as shapes; it is zero if the curve is
+    /// exactly the elevation of the result.
+    fn reduce(&self) -> (Self::Reduced, f64);
+}
+
+impl DegreeElevate for Line {
+    type Elevated = QuadBez;
+
+    #[inline]
+    fn elevate(&self) -> QuadBez {
+        QuadBez::new(self.p0, self.p0.lerp(self.p1, 0.5), self.p1)
+    }
+}
+
+impl DegreeElevate for QuadBez {
+    type Elevated = CubicBez;
+
+    #[inline]
+    fn elevate(&self) -> CubicBez {
+        self.raise()
+    }
+}
+
+impl DegreeReduce for QuadBez {
+    type Reduced = Line;
+
+    fn reduce(&self) -> (Line, f64) {
+        // The difference from the line is (p1 - mid) * 2t(1 - t).
+        let err = 0.5 * (self.p1 - self.p0.lerp(self.p2, 0.5)).hypot();
+        (Line::new(self.p0, self.p2), err)
+    }
+}
+
+impl DegreeReduce for CubicBez {
+    type Reduced = QuadBez;
+
+    /// Reduce to the quadratic whose control point is the average of the two
+    /// given by extending the cubic's control arms.
+    ///
+    /// This is the quadratic used by
+    /// [`to_quads`](struct.CubicBez.html#method.to_quads).
+    fn reduce(&self) -> (QuadBez, f64) {
+        let p1x2 = 3.0 * self.p1 - self.p0;
+        let p2x2 = 3.0 * self.p2 - self.p3;
+        // See: http://caffeineowl.com/graphics/2d/vectorial/cubic2quad01.html
+        let err = (p2x2 - p1x2).hypot() * (3.0f64.sqrt() / 36.0);
+        (QuadBez::new(self.p0, (p1x2 + p2x2) / 4.0, self.p3), err)
+    }
+}
+
+impl PathSeg {
+    /// The simplest kind of segment within `tolerance` of this one.
+    ///
+    /// A segment is a line if its control points are within `tolerance` of
+    /// the chord between its endpoints, so that the curve stays that close
+    /// to the chord; uneven speed along the chord is allowed, but not
+    /// overshooting its ends. It is a quadratic if the
+    /// [reduction](trait.DegreeReduce.html) of a cubic is within
+    /// `tolerance`. A tolerance of zero asks whether the segment is exactly
+    /// of the simpler kind, which holds for elevated segments only up to
+    /// rounding.
+    pub fn classify(&self, tolerance: f64) -> PathSegKind {
+        let (p0, ctrl, p3): (Vec2, &[Vec2], Vec2) = match self {
+            PathSeg::Line(l) => (l.p0, &[], l.p1),
+            PathSeg::Quad(q) => (q.p0, &[q.p1][..], q.p2),
+            PathSeg::Cubic(c) => (c.p0, &[c.p1, c.p2][..], c.p3),
+        };
+        let tol2 = tolerance * tolerance;
+        if (p3 - p0).hypot2() <= tol2 && ctrl.iter().all(|&p| (p - p0).hypot2() <= tol2) {
+            return PathSegKind::Point;
+        }
+        let chord = Line::new(p0, p3);
+        if ctrl.iter().all(|&p| chord.nearest(p, 0.0).1 <= tol2) {
+            return PathSegKind::Line;
+        }
+        match self {
+            PathSeg::Cubic(c) if c.reduce().1 > tolerance => PathSegKind::Cubic,
+            _ => PathSegKind::Quad,
+        }
+    }
+
+    /// The simplest segment within `tolerance` of this one.
+    ///
+    /// The result is of the kind reported by
+    /// [`classify`](#method.classify), with the same endpoints; a point is
+    /// reduced to a line between the endpoints. This lets exporters emit the
+    /// simplest commands.
+    pub fn try_reduce(&self, tolerance: f64) -> PathSeg {
+        match (self.classify(tolerance), self) {
+            (PathSegKind::Point, _) | (PathSegKind::Line, _) => {
+                PathSeg::Line(Line::new(self.start(), self.end()))
+            }
+            (PathSegKind::Quad, PathSeg::Cubic(c)) => PathSeg::Quad(c.reduce().0),
+            _ => *self,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CubicBez, DegreeElevate, DegreeReduce, Line, ParamCurve, PathSeg, PathSegKind, QuadBez,
+    };
+
+    #[test]
+    fn degree_elevate_reduce() {
+        let line = Line::new((1.0, 2.0), (5.0, -1.0));
+        let cubic = line.elevate().elevate();
+        for i in 0..=10 {
+            let t = i as f64 * 0.1;
+            assert!((cubic.eval(t) - line.eval(t)).hypot() < 1e-12);
+        }
+        let (quad, err) = cubic.reduce();
+        assert!(err < 1e-12);
+        let (back, err) = quad.reduce();
+        assert!(err < 1e-12);
+        assert_eq!(back.p1, line.p1);
+
+        let c = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 3.0), (4.0, 0.0));
+        let (q, err) = c.reduce();
+        let mut max_err = 0.0f64;
+        for i in 0..=1000 {
+            let t = i as f64 * 1e-3;
+            max_err = max_err.max((c.eval(t) - q.eval(t)).hypot());
+        }
+        assert!(max_err <= err + 1e-12 && max_err > 0.9 * err);
+
+        let q = QuadBez::new((0.0, 0.0), (1.0, 2.0), (2.0, 0.0));
+        let (_, err) = q.reduce();
+        assert!((err - (q.eval(0.5).y)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn classify() {
+        let line = Line::new((1.0, 2.0), (5.0, -1.0));
+        let seg = PathSeg::Cubic(line.elevate().elevate());
+        assert_eq!(seg.classify(1e-9), PathSegKind::Line);
+        assert!(matches!(seg.try_reduce(1e-9), PathSeg::Line(_)));
+
+        let q = QuadBez::new((0.0, 0.0), (1.0, 2.0), (2.0, 0.0));
+        let seg = PathSeg::Cubic(q.elevate());
+        assert_eq!(seg.classify(1e-9), PathSegKind::Quad);
+        match seg.try_reduce(1e-9) {
+            PathSeg::Quad(r) => assert!((r.p1 - q.p1).hypot() < 1e-12),
+            _ => panic!("expected a quad"),
+        }
+        assert_eq!(seg.classify(1.5), PathSegKind::Line);
+
+        let c = CubicBez::new((0.0, 0.0), (1.0, 2.0), (3.0, 3.0), (4.0, 0.0));
+        assert_eq!(PathSeg::Cubic(c).classify(1e-3), PathSegKind::Cubic);
+        let dot = CubicBez::new((1.0, 1.0), (1.0, 1.0), (1.0, 1.0), (1.0, 1.0));
+        assert_eq!(PathSeg::Cubic(dot).classify(0.0), PathSegKind::Point);
+        // Control points on the line but past its ends make it go back.
+        let back = CubicBez::new((0.0, 0.0), (2.0, 0.0), (-1.0, 0.0), (1.0, 0.0));
+        assert_eq!(PathSeg::Cubic(back).classify(1e-9), PathSegKind::Cubic);
+    }
+}
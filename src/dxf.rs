@@ -0,0 +1,288 @@
+//! Conversion to and from DXF lightweight polylines.
+
+use std::f64::consts::PI;
+
+use crate::{Arc, BezPath, CubicBez, ParamCurve, PathEl, QuadBez, Vec2};
+
+/// A vertex of a DXF lightweight polyline (`LWPOLYLINE`).
+#[derive(Clone, Copy, Debug)]
+pub struct DxfVertex {
+    /// The position of the vertex.
+    pub point: Vec2,
+    /// The bulge of the segment starting at this vertex.
+    ///
+    /// This is the tangent of a quarter of the angle included by the arc.
+    /// It is positive when the arc turns from positive X towards positive Y
+    /// (counterclockwise in the y-up coordinates of DXF), and zero for a
+    /// straight segment.
+    pub bulge: f64,
+}
+
+/// A DXF lightweight polyline, made of straight and circular arc segments.
+#[derive(Clone, Debug, Default)]
+pub struct DxfPolyline {
+    /// The vertices of the polyline.
+    pub vertices: Vec<DxfVertex>,
+    /// Whether the last vertex connects back to the first.
+    pub closed: bool,
+}
+
+impl Arc {
+    /// Create a circular arc from two points and a DXF bulge value.
+    ///
+    /// Returns `None` if the bulge is zero or the points coincide, in which
+    /// case the segment is a straight line.
+    pub fn from_bulge(p0: Vec2, p1: Vec2, bulge: f64) -> Option<Arc> {
+        let chord = p1 - p0;
+        let d = chord.hypot();
+        if bulge == 0.0 || d == 0.0 {
+            return None;
+        }
+        let sweep_angle = 4.0 * bulge.atan();
+        let half = 0.5 * sweep_angle;
+        // Offset of the center from the chord midpoint, to its left.
+        let h = 0.5 * d / half.tan();
+        let left = Vec2::new(-chord.y, chord.x) / d;
+        let center = p0.lerp(p1, 0.5) + h * left;
+        let r = 0.5 * d / half.sin().abs();
+        Some(Arc {
+            center,
+            radii: Vec2::new(r, r),
+            start_angle: (p0 - center).atan2(),
+            sweep_angle,
+            x_rotation: 0.0,
+        })
+    }
+
+    /// The DXF bulge value of the arc.
+    ///
+    /// Returns `None` if the arc is not circular, or if it is a full circle,
+    /// which cannot be represented by a single bulge.
+    pub fn bulge(&self) -> Option<f64> {
+        let (rx, ry) = (self.radii.x.abs(), self.radii.y.abs());
+        if (rx - ry).abs() > 1e-9 * rx.max(ry) || self.sweep_angle.abs() >= 2.0 * PI {
+            return None;
+        }
+        Some((0.25 * self.sweep_angle).tan())
+    }
+}
+
+impl BezPath {
+    /// Create a path from DXF lightweight polylines.
+    ///
+    /// Arc segments are converted to cubic Béziers within the given tolerance;
+    /// the endpoints of each segment are preserved exactly.
+    pub fn from_dxf_polylines(polylines: &[DxfPolyline], tolerance: f64) -> BezPath {
+        let mut path = BezPath::new();
+        for poly in polylines {
+            let vertices = &poly.vertices;
+            if vertices.is_empty() {
+                continue;
+            }
+            path.moveto(vertices[0].point);
+            let n = vertices.len();
+            let n_segs = if poly.closed { n } else { n - 1 };
+            for i in 0..n_segs {
+                let v = vertices[i];
+                let next = vertices[(i + 1) % n].point;
+                if let Some(arc) = Arc::from_bulge(v.point, next, v.bulge) {
+                    let mut arc_path = BezPath::new();
+                    arc.append_to_path(&mut arc_path, tolerance);
+                    let els = arc_path.elements();
+                    for (j, el) in els.iter().enumerate() {
+                        match *el {
+                            // Snap the last point to the vertex, avoiding roundoff.
+                            PathEl::Curveto(p1, p2, _) if j == els.len() - 1 => {
+                                path.curveto(p1, p2, next)
+                            }
+                            _ => path.push(*el),
+                        }
+                    }
+                } else if !(poly.closed && i == n - 1) {
+                    path.lineto(next);
+                }
+            }
+            if poly.closed {
+                path.closepath();
+            }
+        }
+        path
+    }
+
+    /// Convert the path to DXF lightweight polylines, one per subpath.
+    ///
+    /// Curve segments that are circular arcs within `tolerance` are emitted as
+    /// single bulged segments, so circles and arcs survive the round trip.
+    /// Other curves are flattened within `tolerance`.
+    pub fn to_dxf_polylines(&self, tolerance: f64) -> Vec<DxfPolyline> {
+        let mut result = Vec::new();
+        let mut current: Option<DxfPolyline> = None;
+        let mut last = Vec2::default();
+        for el in self.elements() {
+            let end = match *el {
+                PathEl::Moveto(p) => {
+                    result.extend(current.take());
+                    last = p;
+                    continue;
+                }
+                PathEl::Closepath => {
+                    if let Some(mut poly) = current.take() {
+                        let start = poly.vertices[0].point;
+                        if poly.vertices.len() > 1 && poly.vertices.last().unwrap().point == start {
+                            poly.vertices.pop();
+                        }
+                        poly.closed = true;
+                        last = start;
+                        result.push(poly);
+                    }
+                    continue;
+                }
+                PathEl::Lineto(p) => p,
+                PathEl::Quadto(_, p2) => p2,
+                PathEl::Curveto(_, _, p3) => p3,
+            };
+            let poly = current.get_or_insert_with(|| DxfPolyline {
+                vertices: vec![DxfVertex {
+                    point: last,
+                    bulge: 0.0,
+                }],
+                closed: false,
+            });
+            let sweep = match *el {
+                PathEl::Quadto(p1, p2) => circular_sweep(&QuadBez::new(last, p1, p2), tolerance),
+                PathEl::Curveto(p1, p2, p3) => {
+                    circular_sweep(&CubicBez::new(last, p1, p2, p3), tolerance)
+                }
+                _ => Some(0.0),
+            };
+            if let Some(sweep) = sweep {
+                poly.vertices.last_mut().unwrap().bulge = (0.25 * sweep).tan();
+                poly.vertices.push(DxfVertex {
+                    point: end,
+                    bulge: 0.0,
+                });
+            } else {
+                let mut seg_path = BezPath::new();
+                seg_path.moveto(last);
+                seg_path.push(*el);
+                seg_path.flatten(tolerance, |el| {
+                    if let PathEl::Lineto(p) = el {
+                        poly.vertices.push(DxfVertex {
+                            point: p,
+                            bulge: 0.0,
+                        });
+                    }
+                });
+            }
+            last = end;
+        }
+        result.extend(current);
+        result
+    }
+}
+
+/// Determine whether a curve is a circular arc, within tolerance.
+///
+/// Returns the signed angle swept by the arc if it is.
+fn circular_sweep<C: ParamCurve>(c: &C, tolerance: f64) -> Option<f64> {
+    const N: usize = 8;
+    let p0 = c.start();
+    let center = circumcenter(p0, c.eval(0.5), c.end())?;
+    let r = (p0 - center).hypot();
+    let mut sweep = 0.0;
+    let mut last_v = p0 - center;
+    for i in 1..=N {
+        let v = c.eval(i as f64 * (N as f64).recip()) - center;
+        if (v.hypot() - r).abs() > tolerance {
+            return None;
+        }
+        let delta = last_v.cross(v).atan2(last_v.dot(v));
+        // The curve must trace the circle monotonically.
+        if delta * sweep < 0.0 {
+            return None;
+        }
+        sweep += delta;
+        last_v = v;
+    }
+    Some(sweep)
+}
+
+/// The center of the circle through three points, if they are not collinear.
+fn circumcenter(p0: Vec2, p1: Vec2, p2: Vec2) -> Option<Vec2> {
+    let a = p1 - p0;
+    let b = p2 - p0;
+    let d = 2.0 * a.cross(b);
+    if d.abs() <= 1e-12 * a.hypot2().max(b.hypot2()) {
+        return None;
+    }
+    let (a2, b2) = (a.hypot2(), b.hypot2());
+    Some(p0 + Vec2::new(b.y * a2 - a.y * b2, a.x * b2 - b.x * a2) / d)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{Arc, BezPath, Circle, DxfPolyline, DxfVertex, ParamCurve, Shape, Vec2};
+
+    fn assert_near(p0: Vec2, p1: Vec2) {
+        assert!((p1 - p0).hypot() < 1e-9, "{:?} != {:?}", p0, p1);
+    }
+
+    #[test]
+    fn bulge_arc() {
+        // A semicircle, turning from +x to +y, passes below the chord.
+        let arc = Arc::from_bulge(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), 1.0).unwrap();
+        assert_near(arc.center, Vec2::new(1.0, 0.0));
+        assert_near(arc.eval(0.5), Vec2::new(1.0, -1.0));
+        assert_near(arc.end(), Vec2::new(2.0, 0.0));
+        assert!((arc.bulge().unwrap() - 1.0).abs() < 1e-12);
+
+        let arc = Arc::from_bulge(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), -0.5).unwrap();
+        assert_near(arc.end(), Vec2::new(2.0, 0.0));
+        assert!(arc.eval(0.5).y > 0.0);
+        assert!((arc.bulge().unwrap() + 0.5).abs() < 1e-12);
+
+        assert!(Arc::from_bulge(Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), 0.0).is_none());
+    }
+
+    #[test]
+    fn dxf_circle_roundtrip() {
+        let circle = Circle::new((5.0, 5.0), 10.0);
+        let polys = circle.into_bez_path(1e-2).to_dxf_polylines(1e-2);
+        assert_eq!(polys.len(), 1);
+        assert!(polys[0].closed);
+        assert_eq!(polys[0].vertices.len(), 4);
+        for v in &polys[0].vertices {
+            assert!((v.bulge - (PI / 8.0).tan()).abs() < 1e-3);
+        }
+        let path = BezPath::from_dxf_polylines(&polys, 1e-6);
+        // Area error is bounded by perimeter times tolerance.
+        assert!((path.area() - 100.0 * PI).abs() < 20.0 * PI * 1e-2);
+    }
+
+    #[test]
+    fn dxf_polyline_lines() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10Z").unwrap();
+        let polys = path.to_dxf_polylines(1e-3);
+        assert_eq!(polys.len(), 1);
+        assert_eq!(polys[0].vertices.len(), 3);
+        assert!(polys[0].vertices.iter().all(|v| v.bulge == 0.0));
+
+        let poly = DxfPolyline {
+            vertices: vec![
+                DxfVertex {
+                    point: Vec2::new(0.0, 0.0),
+                    bulge: 0.0,
+                },
+                DxfVertex {
+                    point: Vec2::new(10.0, 0.0),
+                    bulge: 0.0,
+                },
+            ],
+            closed: false,
+        };
+        let path = BezPath::from_dxf_polylines(&[poly], 1e-3);
+        assert_eq!(path.segments().count(), 1);
+    }
+}
@@ -0,0 +1,176 @@
+//! Easing curves for animation.
+
+use crate::{CubicBez, Vec2};
+
+/// The precision in x of the solution for the curve parameter.
+const EPSILON: f64 = 1e-12;
+
+/// A timing function defined by a cubic Bézier, as in CSS.
+///
+/// The curve runs from (0, 0) to (1, 1), with two control points given as
+/// for the CSS `cubic-bezier()` function. Evaluating it maps input progress
+/// (x, usually time) to output progress (y), following the [CSS Easing
+/// Functions] specification.
+///
+/// [CSS Easing Functions]: https://www.w3.org/TR/css-easing-1/#cubic-bezier-easing-functions
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezEasing {
+    /// The x coordinate of the first control point.
+    pub x1: f64,
+    /// The y coordinate of the first control point.
+    pub y1: f64,
+    /// The x coordinate of the second control point.
+    pub x2: f64,
+    /// The y coordinate of the second control point.
+    pub y2: f64,
+}
+
+impl CubicBezEasing {
+    /// The CSS `linear` timing function.
+    pub const LINEAR: CubicBezEasing = CubicBezEasing::new(0.0, 0.0, 1.0, 1.0);
+    /// The CSS `ease` timing function.
+    pub const EASE: CubicBezEasing = CubicBezEasing::new(0.25, 0.1, 0.25, 1.0);
+    /// The CSS `ease-in` timing function.
+    pub const EASE_IN: CubicBezEasing = CubicBezEasing::new(0.42, 0.0, 1.0, 1.0);
+    /// The CSS `ease-out` timing function.
+    pub const EASE_OUT: CubicBezEasing = CubicBezEasing::new(0.0, 0.0, 0.58, 1.0);
+    /// The CSS `ease-in-out` timing function.
+    pub const EASE_IN_OUT: CubicBezEasing = CubicBezEasing::new(0.42, 0.0, 0.58, 1.0);
+
+    /// Create a timing function from the control points, in the order of
+    /// the arguments of CSS `cubic-bezier()`.
+    ///
+    /// The x coordinates must be in the range 0..1, as CSS requires, so
+    /// that the curve is a function of x.
+    pub const fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> CubicBezEasing {
+        CubicBezEasing { x1, y1, x2, y2 }
+    }
+
+    /// The curve as a cubic Bézier.
+    pub fn to_cubic_bez(&self) -> CubicBez {
+        CubicBez::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(self.x1, self.y1),
+            Vec2::new(self.x2, self.y2),
+            Vec2::new(1.0, 1.0),
+        )
+    }
+
+    /// The output progress for an input progress.
+    ///
+    /// The curve parameter giving `x` is found with Newton's method, falling
+    /// back to bisection if that fails to converge. Outside the range 0..1,
+    /// the curve is extended along its tangents at the ends, as the
+    /// specification requires.
+    pub fn eval(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return x * self.start_slope();
+        }
+        if x > 1.0 {
+            return 1.0 + (x - 1.0) * self.end_slope();
+        }
+        let t = self.solve_t(x);
+        bezier(self.y1, self.y2, t)
+    }
+
+    /// The curve parameter at which the x coordinate is `x`, in 0..1.
+    fn solve_t(&self, x: f64) -> f64 {
+        let mut t = x;
+        for _ in 0..8 {
+            let err = bezier(self.x1, self.x2, t) - x;
+            if err.abs() < EPSILON {
+                return t;
+            }
+            let d = bezier_deriv(self.x1, self.x2, t);
+            if d.abs() < 1e-6 {
+                break;
+            }
+            t -= err / d;
+        }
+        // The x coordinate is monotonic, so bisection always converges.
+        let (mut lo, mut hi) = (0.0, 1.0);
+        t = x;
+        while hi - lo > EPSILON {
+            let err = bezier(self.x1, self.x2, t) - x;
+            if err.abs() < EPSILON {
+                break;
+            }
+            if err < 0.0 {
+                lo = t;
+            } else {
+                hi = t;
+            }
+            t = 0.5 * (lo + hi);
+        }
+        t
+    }
+
+    fn start_slope(&self) -> f64 {
+        if self.x1 > 0.0 {
+            self.y1 / self.x1
+        } else if self.y1 == 0.0 && self.x2 > 0.0 {
+            self.y2 / self.x2
+        } else {
+            0.0
+        }
+    }
+
+    fn end_slope(&self) -> f64 {
+        if self.x2 < 1.0 {
+            (self.y2 - 1.0) / (self.x2 - 1.0)
+        } else if self.y2 == 1.0 && self.x1 < 1.0 {
+            (self.y1 - 1.0) / (self.x1 - 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// One coordinate of the curve, with end points at 0 and 1.
+#[inline]
+fn bezier(c1: f64, c2: f64, t: f64) -> f64 {
+    let mt = 1.0 - t;
+    3.0 * mt * t * (mt * c1 + t * c2) + t * t * t
+}
+
+/// The derivative of `bezier` with respect to `t`.
+#[inline]
+fn bezier_deriv(c1: f64, c2: f64, t: f64) -> f64 {
+    let mt = 1.0 - t;
+    3.0 * (mt * mt * c1 + 2.0 * mt * t * (c2 - c1) + t * t * (1.0 - c2))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CubicBezEasing, ParamCurve};
+
+    #[test]
+    fn easing() {
+        let linear = CubicBezEasing::LINEAR;
+        for i in 0..=10 {
+            let x = i as f64 * 0.1;
+            assert!((linear.eval(x) - x).abs() < 1e-9);
+        }
+        let ease = CubicBezEasing::EASE;
+        assert_eq!(ease.eval(0.0), 0.0);
+        assert!((ease.eval(1.0) - 1.0).abs() < 1e-12);
+        // Evaluation inverts the x coordinate of the curve.
+        let c = ease.to_cubic_bez();
+        for i in 0..=20 {
+            let p = c.eval(i as f64 * 0.05);
+            assert!((ease.eval(p.x) - p.y).abs() < 1e-9);
+        }
+        // A curve with a vertical tangent, where Newton's method struggles.
+        let steep = CubicBezEasing::new(0.0, 1.0, 1.0, 0.0);
+        let c = steep.to_cubic_bez();
+        for i in 0..=20 {
+            let p = c.eval(i as f64 * 0.05);
+            assert!((steep.eval(p.x) - p.y).abs() < 1e-6, "{:?}", p);
+        }
+        // Extrapolation along the end tangents.
+        let back = CubicBezEasing::new(0.5, -0.5, 0.5, 1.5);
+        assert!((back.eval(-1.0) - 1.0).abs() < 1e-12);
+        assert!(back.eval(2.0).abs() < 1e-12);
+        assert_eq!(CubicBezEasing::EASE_IN.eval(-1.0), 0.0);
+    }
+}
@@ -0,0 +1,471 @@
+//! Implementation of ellipse shape.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::iter::{once, Chain, Once};
+
+use crate::common::{solve_3x3, solve_cubic};
+use crate::{
+    Affine, Arc, ArcAppendIter, Circle, ParamCurve, ParamCurveArclen, PathEl, Rect, Shape, Vec2,
+};
+
+/// An ellipse.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Ellipse {
+    /// The center.
+    pub center: Vec2,
+    /// The radii along the ellipse's axes, before rotation.
+    pub radii: Vec2,
+    /// The rotation of the ellipse's X axis, in radians.
+    pub x_rotation: f64,
+}
+
+impl Ellipse {
+    /// A new ellipse from center, radii, and rotation.
+    #[inline]
+    pub fn new(center: impl Into<Vec2>, radii: impl Into<Vec2>, x_rotation: f64) -> Ellipse {
+        Ellipse {
+            center: center.into(),
+            radii: radii.into(),
+            x_rotation,
+        }
+    }
+
+    /// The ellipse as a full arc, starting on the positive X axis.
+    fn full_arc(&self) -> Arc {
+        Arc {
+            center: self.center,
+            radii: self.radii,
+            start_angle: 0.0,
+            sweep_angle: 2.0 * PI,
+            x_rotation: self.x_rotation,
+        }
+    }
+
+    /// The perimeter of the ellipse, from the complete elliptic integral.
+    ///
+    /// Unlike measuring a Bézier approximation, this is accurate to full
+    /// floating point precision, so `accuracy` is only a hint.
+    pub fn perimeter_exact(&self, accuracy: f64) -> f64 {
+        self.full_arc().arclen(accuracy)
+    }
+
+    /// Find the nearest point on the ellipse.
+    ///
+    /// Returns the angle parameter of the nearest point, in the same sense
+    /// as [`Arc::start_angle`](struct.Arc.html#structfield.start_angle), and
+    /// the square of the distance to it. The result is exact up to roundoff,
+    /// so `accuracy` is unused.
+    pub fn nearest(&self, p: Vec2, _accuracy: f64) -> (f64, f64) {
+        let angle = nearest_angle(self.radii, self.x_rotation, p - self.center);
+        let q = self.full_arc().eval(angle / (2.0 * PI));
+        (angle, (q - p).hypot2())
+    }
+
+    /// Map a point into the coordinate system where the ellipse is a unit circle.
+    fn unit_coords(&self, pt: Vec2) -> Vec2 {
+        let (s, c) = self.x_rotation.sin_cos();
+        let d = pt - self.center;
+        Vec2::new(
+            (c * d.x + s * d.y) / self.radii.x,
+            (c * d.y - s * d.x) / self.radii.y,
+        )
+    }
+
+    /// Fit an ellipse to a set of points.
+    ///
+    /// This is the direct least squares method of Fitzgibbon, Pilu and Fisher,
+    /// in the numerically stable formulation of Halíř and Flusser, which
+    /// always produces an ellipse (rather than another conic) when one exists.
+    ///
+    /// The resulting ellipse has its major axis along its X axis, with a
+    /// rotation in the range `(-π/2, π/2]`.
+    ///
+    /// Returns the ellipse and the root mean square of the Sampson distances
+    /// of the points to it, a first-order approximation of the geometric
+    /// distance. Returns `None` if there are fewer than five points or they
+    /// are degenerate, for example collinear.
+    pub fn fit(points: &[Vec2]) -> Option<(Ellipse, f64)> {
+        if points.len() < 5 {
+            return None;
+        }
+        // Normalize the points for numerical stability.
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec2::default(), |a, &p| a + p) / n;
+        let scale = (points.iter().map(|&p| (p - mean).hypot2()).sum::<f64>() / n).sqrt();
+        if scale == 0.0 {
+            return None;
+        }
+        let mut s1 = [[0.0; 3]; 3];
+        let mut s2 = [[0.0; 3]; 3];
+        let mut s3 = [[0.0; 3]; 3];
+        for &p in points {
+            let q = (p - mean) / scale;
+            let d1 = [q.x * q.x, q.x * q.y, q.y * q.y];
+            let d2 = [q.x, q.y, 1.0];
+            for i in 0..3 {
+                for j in 0..3 {
+                    s1[i][j] += d1[i] * d1[j];
+                    s2[i][j] += d1[i] * d2[j];
+                    s3[i][j] += d2[i] * d2[j];
+                }
+            }
+        }
+        // t = -s3⁻¹ s2ᵀ, which gives the linear coefficients from the quadratic ones.
+        let mut t = [[0.0; 3]; 3];
+        for j in 0..3 {
+            let col = solve_3x3(s3, [-s2[j][0], -s2[j][1], -s2[j][2]])?;
+            for i in 0..3 {
+                t[i][j] = col[i];
+            }
+        }
+        let mut m = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                m[i][j] = s1[i][j] + (0..3).map(|k| s2[i][k] * t[k][j]).sum::<f64>();
+            }
+        }
+        // Premultiply by the inverse of the constraint matrix.
+        let m = [
+            [0.5 * m[2][0], 0.5 * m[2][1], 0.5 * m[2][2]],
+            [-m[1][0], -m[1][1], -m[1][2]],
+            [0.5 * m[0][0], 0.5 * m[0][1], 0.5 * m[0][2]],
+        ];
+        let a1 = eigenvectors_3x3(&m)
+            .into_iter()
+            .find(|v| 4.0 * v[0] * v[2] - v[1] * v[1] > 0.0)?;
+        let a2: Vec<f64> = (0..3)
+            .map(|i| (0..3).map(|k| t[i][k] * a1[k]).sum())
+            .collect();
+        let conic = [a1[0], a1[1], a1[2], a2[0], a2[1], a2[2]];
+        let unit = conic_to_ellipse(&conic)?;
+        let ellipse = Ellipse::new(
+            mean + scale * unit.center,
+            scale * unit.radii,
+            unit.x_rotation,
+        );
+        let sum: f64 = points
+            .iter()
+            .map(|&p| {
+                let q = (p - mean) / scale;
+                let [a, b, c, d, e, f] = conic;
+                let val = a * q.x * q.x + b * q.x * q.y + c * q.y * q.y + d * q.x + e * q.y + f;
+                let grad = Vec2::new(2.0 * a * q.x + b * q.y + d, b * q.x + 2.0 * c * q.y + e);
+                let g2 = grad.hypot2();
+                if g2 > 0.0 {
+                    scale * scale * val * val / g2
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        Some((ellipse, (sum / n).sqrt()))
+    }
+}
+
+/// The angle parameter of the nearest point on an ellipse centered at the origin.
+///
+/// This is the robust algorithm from David Eberly, "Distance from a Point to
+/// an Ellipse, an Ellipsoid, or a Hyperellipsoid", which reduces the problem
+/// to the first quadrant and finds the root of a monotonic function by
+/// bisection. Unlike solving the quartic directly, it is well behaved for
+/// points near the center and the axes.
+pub(crate) fn nearest_angle(radii: Vec2, x_rotation: f64, p: Vec2) -> f64 {
+    let (s, c) = x_rotation.sin_cos();
+    let (u, v) = (c * p.x + s * p.y, c * p.y - s * p.x);
+    let (rx, ry) = (radii.x.abs(), radii.y.abs());
+    // Solve with the major axis first.
+    let swap = rx < ry;
+    let (e0, e1, y0, y1) = if swap {
+        (ry, rx, v.abs(), u.abs())
+    } else {
+        (rx, ry, u.abs(), v.abs())
+    };
+    let (x0, x1) = if e1 == 0.0 {
+        (y0.min(e0), 0.0)
+    } else if y1 > 0.0 {
+        if y0 > 0.0 {
+            let z0 = y0 / e0;
+            let z1 = y1 / e1;
+            let g = z0 * z0 + z1 * z1 - 1.0;
+            if g != 0.0 {
+                let r0 = (e0 / e1).powi(2);
+                let sbar = ellipse_root(r0, z0, z1, g);
+                (r0 * y0 / (sbar + r0), y1 / (sbar + 1.0))
+            } else {
+                (y0, y1)
+            }
+        } else {
+            (0.0, e1)
+        }
+    } else {
+        let numer = e0 * y0;
+        let denom = e0 * e0 - e1 * e1;
+        if numer < denom {
+            let xde0 = numer / denom;
+            (e0 * xde0, e1 * (1.0 - xde0 * xde0).max(0.0).sqrt())
+        } else {
+            (e0, 0.0)
+        }
+    };
+    // Restore the quadrant and axis order, then convert to an angle.
+    let (x, y) = if swap { (x1, x0) } else { (x0, x1) };
+    let x = x.copysign(u);
+    let y = y.copysign(v);
+    let cos = if rx == 0.0 { 0.0 } else { x / radii.x };
+    let sin = if ry == 0.0 { 0.0 } else { y / radii.y };
+    sin.atan2(cos)
+}
+
+/// Find the root of Eberly's function `G(s) = (r0 z0 / (s + r0))² + (z1 / (s + 1))² - 1`.
+fn ellipse_root(r0: f64, z0: f64, z1: f64, g: f64) -> f64 {
+    let n0 = r0 * z0;
+    let mut s0 = z1 - 1.0;
+    let mut s1 = if g < 0.0 { 0.0 } else { n0.hypot(z1) - 1.0 };
+    let mut s = 0.0;
+    for _ in 0..MAX_BISECT_ITER {
+        s = 0.5 * (s0 + s1);
+        if s == s0 || s == s1 {
+            break;
+        }
+        let ratio0 = n0 / (s + r0);
+        let ratio1 = z1 / (s + 1.0);
+        let g = ratio0 * ratio0 + ratio1 * ratio1 - 1.0;
+        if g > 0.0 {
+            s0 = s;
+        } else if g < 0.0 {
+            s1 = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
+
+/// Enough bisection steps to exhaust the precision of an `f64`.
+const MAX_BISECT_ITER: usize = 1100;
+
+/// The real eigenvectors of a 3x3 matrix, one per distinct eigenvalue.
+fn eigenvectors_3x3(m: &[[f64; 3]; 3]) -> Vec<[f64; 3]> {
+    let tr = m[0][0] + m[1][1] + m[2][2];
+    let minors = m[0][0] * m[1][1] - m[0][1] * m[1][0] + m[0][0] * m[2][2] - m[0][2] * m[2][0]
+        + m[1][1] * m[2][2]
+        - m[1][2] * m[2][1];
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let cross = |a: [f64; 3], b: [f64; 3]| {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    };
+    let norm2 = |v: &[f64; 3]| v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    solve_cubic(-det, minors, -tr, 1.0)
+        .into_iter()
+        .filter_map(|lambda| {
+            let mut r = *m;
+            for (i, row) in r.iter_mut().enumerate() {
+                row[i] -= lambda;
+            }
+            // The eigenvector is orthogonal to all rows of m - λI.
+            let candidates = [cross(r[0], r[1]), cross(r[0], r[2]), cross(r[1], r[2])];
+            let best = candidates
+                .iter()
+                .cloned()
+                .max_by(|a, b| norm2(a).partial_cmp(&norm2(b)).unwrap())?;
+            if norm2(&best) > 0.0 {
+                Some(best)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Convert the conic `a x² + b xy + c y² + d x + e y + f = 0` to an ellipse.
+fn conic_to_ellipse(conic: &[f64; 6]) -> Option<Ellipse> {
+    let [a, b, c, d, e, f] = *conic;
+    let disc = 4.0 * a * c - b * b;
+    if disc <= 0.0 {
+        return None;
+    }
+    let cx = (b * e - 2.0 * c * d) / disc;
+    let cy = (b * d - 2.0 * a * e) / disc;
+    let f0 = f + 0.5 * (d * cx + e * cy);
+    let mut th = 0.5 * b.atan2(a - c);
+    let (s, co) = th.sin_cos();
+    let l1 = a * co * co + b * co * s + c * s * s;
+    let l2 = a * s * s - b * co * s + c * co * co;
+    let mut r1 = -f0 / l1;
+    let mut r2 = -f0 / l2;
+    if !(r1 > 0.0 && r2 > 0.0) {
+        return None;
+    }
+    if r1 < r2 {
+        std::mem::swap(&mut r1, &mut r2);
+        th += FRAC_PI_2;
+    }
+    if th > FRAC_PI_2 {
+        th -= PI;
+    } else if th <= -FRAC_PI_2 {
+        th += PI;
+    }
+    Some(Ellipse::new((cx, cy), (r1.sqrt(), r2.sqrt()), th))
+}
+
+impl Shape for Ellipse {
+    type BezPathIter = Chain<Chain<Once<PathEl>, ArcAppendIter>, Once<PathEl>>;
+
+    fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+        let arc = self.full_arc();
+        let start = arc.center + Vec2::from_angle(self.x_rotation) * self.radii.x;
+        once(PathEl::Moveto(start))
+            .chain(arc.append_iter(tolerance))
+            .chain(once(PathEl::Closepath))
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        PI * self.radii.x * self.radii.y
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        self.perimeter_exact(accuracy)
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        if self.unit_coords(pt).hypot2() < 1.0 {
+            (self.radii.x * self.radii.y).signum() as i32
+        } else {
+            0
+        }
+    }
+
+    fn bounding_box(&self) -> Rect {
+        let (s, c) = self.x_rotation.sin_cos();
+        let (rx, ry) = (self.radii.x, self.radii.y);
+        let w = (rx * rx * c * c + ry * ry * s * s).sqrt();
+        let h = (rx * rx * s * s + ry * ry * c * c).sqrt();
+        let (x, y) = self.center.into();
+        Rect::new(x - w, y - h, x + w, y + h)
+    }
+
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        let [a, b, c, d, _, _] = affine.as_coeffs();
+        let (s, co) = self.x_rotation.sin_cos();
+        // Columns of the linear map taking the unit circle to the ellipse.
+        let u = Vec2::new(co, s) * self.radii.x;
+        let v = Vec2::new(-s, co) * self.radii.y;
+        let w = (a * u.x + c * u.y).hypot(a * v.x + c * v.y);
+        let h = (b * u.x + d * u.y).hypot(b * v.x + d * v.y);
+        let (x, y) = (affine * self.center).into();
+        Rect::new(x - w, y - h, x + w, y + h)
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.center
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        Circle::new(self.center, self.radii.x.abs().max(self.radii.y.abs()))
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.full_arc().support(direction)
+    }
+
+    fn as_ellipse(&self) -> Option<Ellipse> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Affine, Ellipse, Shape, Vec2};
+
+    fn assert_near(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() < epsilon, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn ellipse_shape() {
+        let e = Ellipse::new((1.0, 2.0), (4.0, 2.0), 0.0);
+        assert_near(e.area(), 8.0 * std::f64::consts::PI, 1e-12);
+        assert_near(e.perimeter(1e-6), 19.376_896_441_095_9, 1e-12);
+        let path = e.into_bez_path(1e-9);
+        assert_near(path.perimeter(1e-9), e.perimeter_exact(1e-9), 1e-6);
+        assert_eq!(e.winding(Vec2::new(4.5, 2.0)), 1);
+        assert_eq!(e.winding(Vec2::new(1.0, 4.5)), 0);
+        let bbox = e.bounding_box();
+        assert_near(bbox.x0, -3.0, 1e-12);
+        assert_near(bbox.y1, 4.0, 1e-12);
+        let path_bbox = e.into_bez_path(1e-6).bounding_box();
+        assert_near(path_bbox.x1, bbox.x1, 1e-6);
+        assert_near(path_bbox.y0, bbox.y0, 1e-6);
+    }
+
+    #[test]
+    fn fit_ellipse() {
+        let e = Ellipse::new((3.0, -1.0), (5.0, 2.0), 0.5);
+        let (s, c) = e.x_rotation.sin_cos();
+        let pts: Vec<Vec2> = (0..20)
+            .map(|i| {
+                let th = i as f64 * 0.3;
+                let u = Vec2::new(e.radii.x * th.cos(), e.radii.y * th.sin());
+                e.center + Vec2::new(c * u.x - s * u.y, s * u.x + c * u.y)
+            })
+            .collect();
+        let (fit, residual) = Ellipse::fit(&pts).unwrap();
+        assert_near(fit.center.x, 3.0, 1e-9);
+        assert_near(fit.center.y, -1.0, 1e-9);
+        assert_near(fit.radii.x, 5.0, 1e-9);
+        assert_near(fit.radii.y, 2.0, 1e-9);
+        assert_near(fit.x_rotation, 0.5, 1e-9);
+        assert!(residual < 1e-9);
+
+        let line: Vec<Vec2> = (0..10)
+            .map(|i| Vec2::new(i as f64, 2.0 * i as f64))
+            .collect();
+        assert!(Ellipse::fit(&line).is_none());
+    }
+
+    #[test]
+    fn ellipse_nearest() {
+        let e = Ellipse::new((1.0, 2.0), (4.0, 2.0), 0.3);
+        for &p in &[
+            Vec2::new(7.0, 5.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(1.5, 2.1),
+            Vec2::new(-6.0, 0.0),
+            Vec2::new(1.0, -3.0),
+        ] {
+            let (angle, dist2) = e.nearest(p, 1e-9);
+            let mut best = f64::INFINITY;
+            for i in 0..100_000 {
+                let th = i as f64 * 2.0 * std::f64::consts::PI / 100_000.0;
+                let (s, c) = e.x_rotation.sin_cos();
+                let u = Vec2::new(e.radii.x * th.cos(), e.radii.y * th.sin());
+                let q = e.center + Vec2::new(c * u.x - s * u.y, s * u.x + c * u.y);
+                best = best.min((q - p).hypot2());
+            }
+            assert!(dist2 <= best + 1e-9, "{:?}: {} > {}", p, dist2, best);
+            assert!(dist2 >= best - 1e-6);
+            assert!(angle.is_finite());
+        }
+    }
+
+    #[test]
+    fn ellipse_bbox_affine() {
+        let e = Ellipse::new((1.0, 2.0), (4.0, 2.0), 0.7);
+        let affine = Affine::new([1.0, 0.5, -0.3, 2.0, 3.0, -1.0]);
+        let bbox = e.bounding_box_affine(affine);
+        let path_bbox = (affine * e.into_bez_path(1e-9)).bounding_box();
+        assert_near(bbox.x0, path_bbox.x0, 1e-6);
+        assert_near(bbox.y0, path_bbox.y0, 1e-6);
+        assert_near(bbox.x1, path_bbox.x1, 1e-6);
+        assert_near(bbox.y1, path_bbox.y1, 1e-6);
+    }
+}
@@ -0,0 +1,311 @@
+//! Assessing and improving the fairness of curves.
+
+use crate::common::GAUSS_LEGENDRE_COEFFS_9;
+use crate::{BezPath, CubicBez, Line, ParamCurve, ParamCurveDeriv, PathEl, PathSeg, Vec2};
+
+/// What [`BezPath::fair`](struct.BezPath.html#method.fair) must preserve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FairConstraints {
+    /// Keep the endpoints of each segment and the directions of its end
+    /// tangents, changing only the lengths of the control arms. Smoothness
+    /// at the joins between segments is preserved.
+    Tangents,
+    /// Keep only the endpoints of each segment. The least energy is then a
+    /// straight line, so this flattens the path towards a polyline.
+    Endpoints,
+}
+
+impl CubicBez {
+    /// The bending energy of the curve, the integral of the square of
+    /// curvature with respect to arclength.
+    ///
+    /// This is the classic measure of fairness, and the objective minimized
+    /// by fairing: it is small for curves that bend gently and evenly. It
+    /// scales inversely with the size of the curve. Near a cusp the
+    /// integral diverges, and the result is merely large.
+    pub fn bending_energy(&self, accuracy: f64) -> f64 {
+        let d1 = self.deriv();
+        let d2 = d1.deriv();
+        integrate(
+            &|t| {
+                let d = d1.eval(t);
+                let speed2 = d.hypot2();
+                if speed2 > 0.0 {
+                    d.cross(d2.eval(t)).powi(2) * speed2.powf(-2.5)
+                } else {
+                    0.0
+                }
+            },
+            accuracy,
+        )
+    }
+
+    /// The total absolute curvature of the curve, the integral of the
+    /// magnitude of curvature with respect to arclength.
+    ///
+    /// This is the total angle, in radians, through which the tangent
+    /// turns, counting turns in either direction as positive. Unlike
+    /// [bending energy](#method.bending_energy), it does not depend on the
+    /// size of the curve.
+    pub fn total_curvature(&self, accuracy: f64) -> f64 {
+        let d1 = self.deriv();
+        let d2 = d1.deriv();
+        integrate(
+            &|t| {
+                let d = d1.eval(t);
+                let speed2 = d.hypot2();
+                if speed2 > 0.0 {
+                    d.cross(d2.eval(t)).abs() / speed2
+                } else {
+                    0.0
+                }
+            },
+            accuracy,
+        )
+    }
+}
+
+impl PathSeg {
+    /// The teeth of a curvature comb for the segment.
+    ///
+    /// A curvature comb shows how smoothly a curve bends: each tooth starts
+    /// on the curve and sticks out along the normal, away from the center of
+    /// curvature, with length `scale` times the magnitude of the curvature.
+    /// Joining the ends of the teeth gives the outline of the comb, which is
+    /// smooth when the curvature varies smoothly.
+    ///
+    /// The teeth are at `samples + 1` evenly spaced parameter values,
+    /// including both ends of the segment.
+    pub fn curvature_comb(&self, scale: f64, samples: usize) -> Vec<Line> {
+        let step = (samples.max(1) as f64).recip();
+        (0..=samples)
+            .map(|i| {
+                let frame = self.frame((i as f64 * step).min(1.0));
+                let tip = frame.point - (scale * frame.curvature) * frame.normal;
+                Line::new(frame.point, tip)
+            })
+            .collect()
+    }
+}
+
+impl BezPath {
+    /// A smoother version of the path, with less bending energy.
+    ///
+    /// Each cubic segment is moved towards the curve of least
+    /// [bending energy](struct.CubicBez.html#method.bending_energy) that
+    /// satisfies the constraints, by the fraction `strength`, from 0 (no
+    /// change) to 1 (the optimum). With
+    /// [`FairConstraints::Tangents`](enum.FairConstraints.html), the optimum
+    /// is found by a search over the two arm lengths. Lines and quadratic
+    /// segments are kept as they are.
+    ///
+    /// This suits cleaning up sketched paths, whose control points wobble.
+    pub fn fair(&self, strength: f64, constraints: FairConstraints) -> BezPath {
+        let mut result = Vec::with_capacity(self.elements().len());
+        let mut start = Vec2::default();
+        let mut last = Vec2::default();
+        for &el in self.elements() {
+            match el {
+                PathEl::Moveto(p) => {
+                    start = p;
+                    last = p;
+                }
+                PathEl::Lineto(p) | PathEl::Quadto(_, p) => last = p,
+                PathEl::Curveto(p1, p2, p3) => {
+                    let c = CubicBez::new(last, p1, p2, p3);
+                    let target = match constraints {
+                        FairConstraints::Tangents => fair_arms(&c),
+                        FairConstraints::Endpoints => CubicBez::new(
+                            last,
+                            last.lerp(p3, 1.0 / 3.0),
+                            last.lerp(p3, 2.0 / 3.0),
+                            p3,
+                        ),
+                    };
+                    result.push(PathEl::Curveto(
+                        p1.lerp(target.p1, strength),
+                        p2.lerp(target.p2, strength),
+                        p3,
+                    ));
+                    last = p3;
+                    continue;
+                }
+                PathEl::Closepath => last = start,
+            }
+            result.push(el);
+        }
+        BezPath::from_vec(result)
+    }
+
+    /// The teeth of a curvature comb for the path.
+    ///
+    /// This is the concatenation of the
+    /// [combs](enum.PathSeg.html#method.curvature_comb) of the segments, with
+    /// `samples + 1` teeth each. Where segments join, there are two teeth at
+    /// the same point, which differ if the curvature is discontinuous there.
+    pub fn curvature_comb(&self, scale: f64, samples: usize) -> Vec<Line> {
+        self.segments()
+            .flat_map(|seg| seg.curvature_comb(scale, samples))
+            .collect()
+    }
+}
+
+/// The cubic of least bending energy with the same endpoints and end
+/// tangent directions, searching over the arm lengths.
+fn fair_arms(c: &CubicBez) -> CubicBez {
+    const ROUNDS: usize = 4;
+    let chord = (c.p3 - c.p0).hypot();
+    if chord == 0.0 {
+        return *c;
+    }
+    let seg = PathSeg::Cubic(*c);
+    let (t0, t1) = (seg.tangent(0.0), seg.tangent(1.0));
+    let accuracy = 1e-6 / chord;
+    let energy = |a: f64, b: f64| {
+        CubicBez::new(c.p0, c.p0 + a * t0, c.p3 - b * t1, c.p3).bending_energy(accuracy)
+    };
+    let (lo, hi) = (1e-3 * chord, 2.0 * chord);
+    let mut a = (c.p1 - c.p0).hypot().clamp(lo, hi);
+    let mut b = (c.p3 - c.p2).hypot().clamp(lo, hi);
+    for _ in 0..ROUNDS {
+        a = golden_min(|x| energy(x, b), lo, hi, 1e-4 * chord);
+        b = golden_min(|x| energy(a, x), lo, hi, 1e-4 * chord);
+    }
+    CubicBez::new(c.p0, c.p0 + a * t0, c.p3 - b * t1, c.p3)
+}
+
+/// The minimum of a unimodal function on an interval, by golden section search.
+fn golden_min(f: impl Fn(f64) -> f64, mut lo: f64, mut hi: f64, tol: f64) -> f64 {
+    let r = 0.5 * (5.0f64.sqrt() - 1.0);
+    let mut x1 = hi - r * (hi - lo);
+    let mut x2 = lo + r * (hi - lo);
+    let (mut f1, mut f2) = (f(x1), f(x2));
+    while hi - lo > tol {
+        if f1 < f2 {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - r * (hi - lo);
+            f1 = f(x1);
+        } else {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + r * (hi - lo);
+            f2 = f(x2);
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Integrate a function over 0..1 by adaptive Legendre-Gauss quadrature.
+fn integrate(f: &impl Fn(f64) -> f64, accuracy: f64) -> f64 {
+    const MAX_DEPTH: usize = 16;
+    fn gauss(f: &impl Fn(f64) -> f64, t0: f64, t1: f64) -> f64 {
+        let half = 0.5 * (t1 - t0);
+        GAUSS_LEGENDRE_COEFFS_9
+            .iter()
+            .map(|&(wi, xi)| wi * f(t0 + half * (xi + 1.0)))
+            .sum::<f64>()
+            * half
+    }
+    fn rec(
+        f: &impl Fn(f64) -> f64,
+        t0: f64,
+        t1: f64,
+        whole: f64,
+        accuracy: f64,
+        depth: usize,
+    ) -> f64 {
+        let tm = 0.5 * (t0 + t1);
+        let left = gauss(f, t0, tm);
+        let right = gauss(f, tm, t1);
+        if depth == MAX_DEPTH || (left + right - whole).abs() < accuracy {
+            left + right
+        } else {
+            rec(f, t0, tm, left, 0.5 * accuracy, depth + 1)
+                + rec(f, tm, t1, right, 0.5 * accuracy, depth + 1)
+        }
+    }
+    rec(f, 0.0, 1.0, gauss(f, 0.0, 1.0), accuracy, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BezPath, Circle, CubicBez, FairConstraints, ParamCurve, PathEl, PathSeg, Shape, Vec2,
+    };
+    use std::f64::consts::PI;
+
+    #[test]
+    fn curvature_comb() {
+        let circle = Circle::new((0.0, 0.0), 2.0);
+        let path = BezPath::from_vec(circle.to_bez_path(1e-9).collect());
+        let comb = path.curvature_comb(4.0, 8);
+        assert_eq!(comb.len(), path.segments().count() * 9);
+        for tooth in comb {
+            // The teeth of a circle point outward with length scale / radius.
+            assert!((tooth.p0.hypot() - 2.0).abs() < 1e-3);
+            assert!((tooth.p1.hypot() - 4.0).abs() < 1e-2);
+        }
+
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        for tooth in line.curvature_comb(1.0, 4) {
+            assert_eq!(tooth.p0, tooth.p1);
+        }
+        let comb = line.curvature_comb(1.0, 4);
+        assert_eq!(comb[4].p0, Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn bending_energy() {
+        // A quarter circle of radius r, closely; the energy is π / 2r.
+        let k = 0.5522847498;
+        let r = 3.0;
+        let c = CubicBez::new((r, 0.0), (r, k * r), (k * r, r), (0.0, r));
+        assert!((c.bending_energy(1e-9) - 0.5 * PI / r).abs() < 1e-3);
+        assert!((c.total_curvature(1e-9) - 0.5 * PI).abs() < 1e-3);
+
+        let line = CubicBez::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0));
+        assert_eq!(line.bending_energy(1e-9), 0.0);
+        // An S curve turns one way and then back.
+        let s = CubicBez::new((0.0, 0.0), (1.0, 1.0), (2.0, -1.0), (3.0, 0.0));
+        // The tangent is at 45° at the ends and atan(-1/2) in the middle.
+        let turn = 1.0f64.atan() + 0.5f64.atan();
+        assert!((s.total_curvature(1e-9) - 2.0 * turn).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fair() {
+        fn energy(path: &BezPath) -> f64 {
+            path.segments()
+                .map(|seg| seg.to_cubic().bending_energy(1e-9))
+                .sum()
+        }
+        let path = BezPath::from_svg("M0 0C1 3 2 3 10 0C14 -1.5 18 1 20 0").unwrap();
+        let faired = path.fair(1.0, FairConstraints::Tangents);
+        assert!(energy(&faired) < energy(&path));
+        for (a, b) in path.segments().zip(faired.segments()) {
+            assert_eq!(a.start(), b.start());
+            assert_eq!(a.end(), b.end());
+            assert!((a.tangent(0.0) - b.tangent(0.0)).hypot() < 1e-12);
+            assert!((a.tangent(1.0) - b.tangent(1.0)).hypot() < 1e-12);
+        }
+        let half = path.fair(0.5, FairConstraints::Tangents);
+        let e = energy(&half);
+        assert!(e < energy(&path) && e > energy(&faired));
+
+        let flat = path.fair(1.0, FairConstraints::Endpoints);
+        assert!(energy(&flat) < 1e-12);
+        let unchanged = path.fair(0.0, FairConstraints::Endpoints);
+        assert_eq!(unchanged.to_svg(), path.to_svg());
+        let lines = BezPath::from_vec(vec![
+            PathEl::Moveto(Vec2::new(0.0, 0.0)),
+            PathEl::Lineto(Vec2::new(1.0, 0.0)),
+        ]);
+        assert!(matches!(
+            lines.fair(1.0, FairConstraints::Tangents).segments().next(),
+            Some(PathSeg::Line(_))
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+//! Rounding the corners of paths.
+
+use std::f64::consts::PI;
+
+use crate::{Arc, BezPath, Corner, ParamCurve, PathEl, PathSeg, Vec2};
+
+/// The accuracy of fillet arcs, relative to their radius.
+const ARC_ACCURACY: f64 = 1e-6;
+
+/// The rounding of a corner, from its start on the incoming line to its end
+/// on the outgoing one.
+#[derive(Clone)]
+struct Rounding {
+    start: Vec2,
+    end: Vec2,
+    /// The elements drawing the rounding, after `start`.
+    els: Vec<PathEl>,
+}
+
+impl BezPath {
+    /// Round the corners of the path with circular arcs of a given radius.
+    ///
+    /// This is [`fillet_with`](#method.fillet_with) with the same radius at
+    /// every corner.
+    pub fn fillet(&self, radius: f64) -> BezPath {
+        self.fillet_with(|_| radius)
+    }
+
+    /// Round the corners of the path with circular arcs, choosing the radius
+    /// for each corner.
+    ///
+    /// The callback is given each corner between two line segments,
+    /// including the corner at the start of a closed subpath, and returns
+    /// the radius of the fillet there; a radius of zero leaves the corner
+    /// sharp. The [`index`](struct.Corner.html#structfield.index) of the
+    /// corner is that of the element whose segment leaves it, as for
+    /// [`corners`](#method.corners).
+    ///
+    /// The arc is tangent to both lines, so it cuts back each of them by
+    /// the radius times the tangent of half the turning angle. Where that is
+    /// more than half the length of either line, the radius is reduced to
+    /// fit, so fillets at the two ends of a short line meet in its middle.
+    /// Corners involving curves are left as they are.
+    pub fn fillet_with(&self, mut radius: impl FnMut(&Corner) -> f64) -> BezPath {
+        self.smooth_corners_with(|corner| (radius(corner), 0.0))
+    }
+
+    /// Round the corners of the path with curvature continuous blends.
+    ///
+    /// This is [`smooth_corners_with`](#method.smooth_corners_with) with the
+    /// same radius and smoothing at every corner.
+    pub fn smooth_corners(&self, radius: f64, smoothing: f64) -> BezPath {
+        self.smooth_corners_with(|_| (radius, smoothing))
+    }
+
+    /// Round the corners of the path with blends whose curvature changes
+    /// gradually, choosing the radius and smoothing for each corner.
+    ///
+    /// This is the corner smoothing of design tools, which gives the
+    /// "squircle" look of app icons. The callback is given each corner
+    /// between two line segments, as for [`fillet_with`](#method.fillet_with),
+    /// and returns the radius and the smoothing there.
+    ///
+    /// With a smoothing of 0, the corner is a circular arc of the radius,
+    /// exactly as a fillet. With more smoothing, up to 1, a growing share of
+    /// the turn is taken by a cubic at each end, whose curvature rises from
+    /// zero where it leaves the line to that of the arc, so the curvature of
+    /// the whole outline is continuous and the rounding reaches further along
+    /// the lines. At a smoothing of 1, the arc disappears, and the two
+    /// cubics meet. Where the rounding would need more than half the length
+    /// of a line, it is scaled down to fit.
+    pub fn smooth_corners_with(&self, mut params: impl FnMut(&Corner) -> (f64, f64)) -> BezPath {
+        let mut result = BezPath::new();
+        for sub in self.subpath_segs() {
+            let segs = &sub.segs;
+            let n = segs.len();
+            // The fillet at the start of each segment.
+            let mut roundings: Vec<Option<Rounding>> = vec![None; n];
+            for (i, rounding) in roundings.iter_mut().enumerate() {
+                if n < 2 || (i == 0 && !sub.closed) {
+                    continue;
+                }
+                let (index, seg) = segs[i];
+                if let (PathSeg::Line(l0), PathSeg::Line(l1)) = (segs[(i + n - 1) % n].1, seg) {
+                    let (len0, len1) = ((l0.p1 - l0.p0).hypot(), (l1.p1 - l1.p0).hypot());
+                    let t0 = (l0.p1 - l0.p0) / len0;
+                    let t1 = (l1.p1 - l1.p0) / len1;
+                    let angle = t0.cross(t1).atan2(t0.dot(t1));
+                    if angle == 0.0 || angle.abs() >= PI {
+                        continue;
+                    }
+                    let corner = Corner {
+                        index,
+                        t: 0.0,
+                        point: l1.p0,
+                        angle: angle.abs(),
+                    };
+                    let (r, smoothing) = params(&corner);
+                    if r <= 0.0 {
+                        continue;
+                    }
+                    let max_cut = 0.5 * len0.min(len1);
+                    *rounding = Some(round_corner(l1.p0, t0, angle, r, smoothing, max_cut));
+                }
+            }
+
+            let first = segs[0].1;
+            let mut last = match &roundings[0] {
+                Some(r) => r.end,
+                None => first.start(),
+            };
+            result.moveto(last);
+            for (i, &(index, seg)) in segs.iter().enumerate() {
+                let next = if i + 1 < n {
+                    roundings[i + 1].as_ref()
+                } else if sub.closed {
+                    roundings[0].as_ref()
+                } else {
+                    None
+                };
+                match seg {
+                    PathSeg::Line(line) => {
+                        let end = next.map(|f| f.start).unwrap_or(line.p1);
+                        let closing = matches!(self.elements()[index], PathEl::Closepath);
+                        // Skip what remains of a line used up by roundings.
+                        let used_up = (end - last).hypot() <= 1e-12 * (line.p1 - line.p0).hypot();
+                        let implicit_close = closing && next.is_none();
+                        if !used_up && !implicit_close {
+                            result.lineto(end);
+                        }
+                    }
+                    PathSeg::Quad(q) => result.quadto(q.p1, q.p2),
+                    PathSeg::Cubic(c) => result.curveto(c.p1, c.p2, c.p3),
+                }
+                if let Some(r) = next {
+                    for &el in &r.els {
+                        result.push(el);
+                    }
+                    last = r.end;
+                } else {
+                    last = seg.end();
+                }
+            }
+            if sub.closed {
+                result.closepath();
+            }
+        }
+        result
+    }
+}
+
+/// Round a corner at `vertex`, turning from direction `t0` through
+/// `angle`, with a cut back along each line of at most `max_cut`.
+fn round_corner(
+    vertex: Vec2,
+    t0: Vec2,
+    angle: f64,
+    radius: f64,
+    smoothing: f64,
+    max_cut: f64,
+) -> Rounding {
+    let theta = angle.abs();
+    let smoothing = smoothing.clamp(0.0, 1.0);
+    // The angle turned by each transition cubic, and by the arc between them.
+    let theta_t = 0.5 * smoothing * theta;
+    let theta_arc = theta - 2.0 * theta_t;
+    // In a frame with the incoming line along X, turning towards positive Y,
+    // the transition starts at the origin with control points at (a, 0) and
+    // (2a, 0), so that its curvature is zero there, and ends a distance a
+    // from the second control point. Its curvature at the end, that of the
+    // arc, is then 2 sin(theta_t) / 3a.
+    let (s, c) = theta_t.sin_cos();
+    let unit_a = (2.0 / 3.0) * s;
+    // The center of the arc, and so of the symmetric rounding, for unit radius.
+    let center = Vec2::new((2.0 + c) * unit_a - s, unit_a * s + c);
+    let unit_cut = center.x + center.y * (0.5 * theta).tan();
+    let r = radius.min(max_cut / unit_cut);
+    let a = unit_a * r;
+    let cut = unit_cut * r;
+
+    let normal = angle.signum() * Vec2::new(-t0.y, t0.x);
+    let to_world = |p: Vec2| vertex + (p.x - cut) * t0 + p.y * normal;
+    let dir = |th: f64| {
+        let (s, c) = th.sin_cos();
+        c * t0 + s * normal
+    };
+    let t1 = dir(theta);
+    let start = vertex - cut * t0;
+    let end = vertex + cut * t1;
+    let mut els = Vec::new();
+    let c1 = to_world(Vec2::new(2.0 * a, 0.0)) + a * dir(theta_t);
+    if a > 0.0 {
+        els.push(PathEl::Curveto(
+            to_world(Vec2::new(a, 0.0)),
+            to_world(Vec2::new(2.0 * a, 0.0)),
+            c1,
+        ));
+    }
+    if theta_arc > 0.0 {
+        let center = c1 + r * dir(theta_t + 0.5 * PI);
+        let arc = Arc::new(
+            center,
+            (r, r),
+            (c1 - center).atan2(),
+            angle.signum() * theta_arc,
+            0.0,
+        );
+        for el in arc.append_iter(ARC_ACCURACY * r) {
+            els.push(el);
+        }
+    }
+    if a > 0.0 {
+        els.push(PathEl::Curveto(end - 2.0 * a * t1, end - a * t1, end));
+    }
+    Rounding { start, end, els }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{BezPath, ContinuityTolerances, PathEl, Shape};
+
+    #[test]
+    fn fillet() {
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let rounded = square.fillet(2.0);
+        assert!((rounded.area() - (100.0 - 4.0 * (4.0 - PI))).abs() < 1e-4);
+        assert!(rounded.winding((0.3, 0.3).into()) == 0);
+        assert!(rounded.winding((1.0, 1.0).into()) != 0);
+        assert!(matches!(rounded.elements().last(), Some(PathEl::Closepath)));
+
+        // Too large a radius is reduced to fit, making a circle.
+        let circle = square.fillet(100.0);
+        assert!((circle.area() - 25.0 * PI).abs() < 1e-4);
+
+        // One corner only.
+        let one = square.fillet_with(|c| {
+            if c.point.x == 10.0 && c.point.y == 10.0 {
+                2.0
+            } else {
+                0.0
+            }
+        });
+        assert!((one.area() - (100.0 - (4.0 - PI))).abs() < 1e-4);
+
+        // An open path keeps its ends, and curves are left alone.
+        let open = BezPath::from_svg("M0 0L10 0L10 10C10 15 5 20 0 20").unwrap();
+        let rounded = open.fillet(1.0);
+        let els = rounded.elements();
+        assert!(matches!(els[0], PathEl::Moveto(p) if p.x == 0.0 && p.y == 0.0));
+        assert!(matches!(els.last(), Some(PathEl::Curveto(_, _, p)) if p.x == 0.0 && p.y == 20.0));
+        // The corner between the lines is rounded, and the one into the
+        // curve is not.
+        let n_arcs = els[3..els.len() - 1]
+            .iter()
+            .filter(|el| matches!(el, PathEl::Curveto(..)))
+            .count();
+        assert!(n_arcs >= 1);
+        assert!(matches!(els[els.len() - 2], PathEl::Lineto(p) if p.x == 10.0 && p.y == 10.0));
+    }
+
+    #[test]
+    fn smooth_corners() {
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let fillet = square.fillet(2.0);
+        let same = square.smooth_corners(2.0, 0.0);
+        assert_eq!(fillet.to_svg(), same.to_svg());
+
+        let smooth = square.smooth_corners(2.0, 0.6);
+        // Smoothing reaches further along the sides, taking more area.
+        assert!(smooth.area() < fillet.area());
+        assert!(smooth.area() > 90.0);
+        // The curvature is continuous, in particular where the blends leave
+        // the sides. Where they meet the arc, it differs slightly, as the arc
+        // is approximated by cubics.
+        let tolerances = ContinuityTolerances {
+            curvature: 1e-4,
+            ..Default::default()
+        };
+        assert!(smooth.continuity_report(&tolerances).is_empty());
+        assert!(!fillet.continuity_report(&tolerances).is_empty());
+
+        // Full smoothing, clamped to fit.
+        let full = square.smooth_corners(100.0, 1.0);
+        assert!(full.continuity_report(&tolerances).is_empty());
+        assert!(full.winding((5.0, 5.0).into()) != 0);
+        assert!(full.winding((0.5, 0.5).into()) == 0);
+
+        // A right turn, and an obtuse corner.
+        let zigzag = BezPath::from_svg("M0 0L10 0L15 -8L25 -5").unwrap();
+        let smooth = zigzag.smooth_corners(3.0, 0.5);
+        assert!(smooth.continuity_report(&tolerances).is_empty());
+    }
+}
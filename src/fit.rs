@@ -0,0 +1,233 @@
+//! Fitting cubic Béziers to sampled points.
+
+use crate::{BezPath, CubicBez, ParamCurve, ParamCurveDeriv, Vec2};
+
+/// The turning angle, in radians, above which a sample point is a corner.
+const CORNER_ANGLE: f64 = std::f64::consts::FRAC_PI_3;
+
+/// The maximum number of reparametrization passes before splitting.
+const MAX_REPARAM: usize = 4;
+
+/// Fit a sequence of cubic Béziers to sampled points.
+///
+/// This is the algorithm from "An Algorithm for Automatically Fitting
+/// Digitized Curves" by Philip J. Schneider, in Graphics Gems. The points are
+/// first split at corners, where the polyline turns by more than 60 degrees.
+/// Each run between corners is fit by least squares with fixed end tangents,
+/// improving the parametrization with Newton steps and splitting the run at
+/// the point of largest error until all points are within `tolerance`.
+///
+/// The `tangent_hints` optionally give the directions of the curve at the
+/// first and last points, which otherwise are estimated from the samples.
+pub fn fit_cubics(points: &[Vec2], tangent_hints: Option<(Vec2, Vec2)>, tolerance: f64) -> BezPath {
+    let mut pts: Vec<Vec2> = Vec::with_capacity(points.len());
+    for &p in points {
+        if pts.last() != Some(&p) {
+            pts.push(p);
+        }
+    }
+    let mut path = BezPath::new();
+    if pts.is_empty() {
+        return path;
+    }
+    path.moveto(pts[0]);
+    if pts.len() == 1 {
+        return path;
+    }
+    let mut corners = vec![0];
+    for i in 1..pts.len() - 1 {
+        let (d0, d1) = (pts[i] - pts[i - 1], pts[i + 1] - pts[i]);
+        if d0.cross(d1).atan2(d0.dot(d1)).abs() > CORNER_ANGLE {
+            corners.push(i);
+        }
+    }
+    corners.push(pts.len() - 1);
+    let n_runs = corners.len() - 1;
+    for (k, w) in corners.windows(2).enumerate() {
+        let run = &pts[w[0]..=w[1]];
+        let t0 = match tangent_hints {
+            Some((t, _)) if k == 0 => unit(t),
+            _ => unit(run[1] - run[0]),
+        };
+        let t1 = match tangent_hints {
+            Some((_, t)) if k == n_runs - 1 => -unit(t),
+            _ => unit(run[run.len() - 2] - run[run.len() - 1]),
+        };
+        fit_rec(run, t0, t1, tolerance, &mut path);
+    }
+    path
+}
+
+fn unit(v: Vec2) -> Vec2 {
+    let len = v.hypot();
+    if len > 0.0 {
+        v / len
+    } else {
+        v
+    }
+}
+
+/// Fit a run of points, with unit tangents pointing into the curve at each end.
+fn fit_rec(pts: &[Vec2], t0: Vec2, t1: Vec2, tolerance: f64, path: &mut BezPath) {
+    let n = pts.len();
+    if n == 2 {
+        let dist = (pts[1] - pts[0]).hypot() / 3.0;
+        path.curveto(pts[0] + dist * t0, pts[1] + dist * t1, pts[1]);
+        return;
+    }
+    let mut u = chord_params(pts);
+    let mut c = fit_bezier(pts, &u, t0, t1);
+    let (mut err, mut split) = max_error(pts, &c, &u);
+    if err <= tolerance * tolerance {
+        path.curveto(c.p1, c.p2, c.p3);
+        return;
+    }
+    if err <= 16.0 * tolerance * tolerance {
+        for _ in 0..MAX_REPARAM {
+            reparametrize(pts, &c, &mut u);
+            c = fit_bezier(pts, &u, t0, t1);
+            let (e, s) = max_error(pts, &c, &u);
+            err = e;
+            split = s;
+            if err <= tolerance * tolerance {
+                path.curveto(c.p1, c.p2, c.p3);
+                return;
+            }
+        }
+    }
+    let tm = unit(pts[split - 1] - pts[split + 1]);
+    fit_rec(&pts[..=split], t0, tm, tolerance, path);
+    fit_rec(&pts[split..], -tm, t1, tolerance, path);
+}
+
+/// Normalized cumulative chord length parameters.
+fn chord_params(pts: &[Vec2]) -> Vec<f64> {
+    let mut u = Vec::with_capacity(pts.len());
+    let mut len = 0.0;
+    u.push(0.0);
+    for w in pts.windows(2) {
+        len += (w[1] - w[0]).hypot();
+        u.push(len);
+    }
+    for x in u.iter_mut() {
+        *x /= len;
+    }
+    u
+}
+
+/// Least squares fit of a cubic with the given end points and tangent directions.
+fn fit_bezier(pts: &[Vec2], u: &[f64], t0: Vec2, t1: Vec2) -> CubicBez {
+    let (p0, p3) = (pts[0], pts[pts.len() - 1]);
+    let mut c = [[0.0; 2]; 2];
+    let mut x = [0.0; 2];
+    for (&p, &t) in pts.iter().zip(u) {
+        let mt = 1.0 - t;
+        let b0 = mt * mt * mt;
+        let b1 = 3.0 * t * mt * mt;
+        let b2 = 3.0 * t * t * mt;
+        let b3 = t * t * t;
+        let a0 = b1 * t0;
+        let a1 = b2 * t1;
+        c[0][0] += a0.dot(a0);
+        c[0][1] += a0.dot(a1);
+        c[1][1] += a1.dot(a1);
+        let tmp = p - (b0 + b1) * p0 - (b2 + b3) * p3;
+        x[0] += a0.dot(tmp);
+        x[1] += a1.dot(tmp);
+    }
+    c[1][0] = c[0][1];
+    let det = c[0][0] * c[1][1] - c[0][1] * c[1][0];
+    let (mut alpha0, mut alpha1) = if det.abs() > 1e-12 {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+            (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+    // Fall back to the Wu-Barsky heuristic for degenerate or reversed handles.
+    let seg_len = (p3 - p0).hypot();
+    let eps = 1e-6 * seg_len;
+    if alpha0 < eps || alpha1 < eps {
+        alpha0 = seg_len / 3.0;
+        alpha1 = alpha0;
+    }
+    CubicBez::new(p0, p0 + alpha0 * t0, p3 + alpha1 * t1, p3)
+}
+
+/// The largest squared distance between the points and the curve, and the
+/// index of the point where it occurs.
+fn max_error(pts: &[Vec2], c: &CubicBez, u: &[f64]) -> (f64, usize) {
+    let mut result = (0.0, pts.len() / 2);
+    for i in 1..pts.len() - 1 {
+        let err = (c.eval(u[i]) - pts[i]).hypot2();
+        if err >= result.0 {
+            result = (err, i);
+        }
+    }
+    result
+}
+
+/// Improve the parameters with a Newton step towards the nearest points.
+fn reparametrize(pts: &[Vec2], c: &CubicBez, u: &mut [f64]) {
+    let d1 = c.deriv();
+    let d2 = d1.deriv();
+    for (&p, t) in pts.iter().zip(u.iter_mut()) {
+        let diff = c.eval(*t) - p;
+        let q1 = d1.eval(*t);
+        let numer = diff.dot(q1);
+        let denom = q1.hypot2() + diff.dot(d2.eval(*t));
+        if denom != 0.0 {
+            *t = (*t - numer / denom).clamp(0.0, 1.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fit_cubics, BezPath, ParamCurve, Vec2};
+
+    fn dist_to_path(path: &BezPath, p: Vec2) -> f64 {
+        let mut best = f64::INFINITY;
+        for seg in path.segments() {
+            for i in 0..=10000 {
+                best = best.min((seg.eval(i as f64 * 1e-4) - p).hypot());
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn fit_circle_samples() {
+        let pts: Vec<Vec2> = (0..=50)
+            .map(|i| Vec2::from_angle(i as f64 * 0.05) * 10.0)
+            .collect();
+        for &tolerance in &[0.1, 1e-3] {
+            let path = fit_cubics(&pts, None, tolerance);
+            for &p in &pts {
+                assert!(dist_to_path(&path, p) <= tolerance * 1.1);
+            }
+        }
+        // A gentle arc needs a single cubic.
+        assert_eq!(fit_cubics(&pts[..20], None, 0.1).segments().count(), 1);
+    }
+
+    #[test]
+    fn fit_splits_corners() {
+        let mut pts: Vec<Vec2> = (0..=10).map(|i| Vec2::new(i as f64, 0.0)).collect();
+        pts.extend((1..=10).map(|i| Vec2::new(10.0, i as f64)));
+        let path = fit_cubics(&pts, None, 0.01);
+        let segs: Vec<_> = path.segments().collect();
+        assert_eq!(segs.len(), 2);
+        assert_eq!(segs[0].end(), Vec2::new(10.0, 0.0));
+        for &p in &pts {
+            assert!(dist_to_path(&path, p) <= 0.011);
+        }
+
+        let hints = Some((Vec2::new(0.0, 1.0), Vec2::new(0.0, -1.0)));
+        let path = fit_cubics(&[Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)], hints, 0.01);
+        let seg = path.segments().next().unwrap();
+        assert!((seg.tangent(0.0) - Vec2::new(0.0, 1.0)).hypot() < 1e-9);
+        assert!((seg.tangent(1.0) - Vec2::new(0.0, -1.0)).hypot() < 1e-9);
+    }
+}
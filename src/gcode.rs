@@ -0,0 +1,163 @@
+//! G-code export for plotters and CNC machines.
+
+use std::io::Write;
+
+use crate::{ArcSplineSeg, BezPath, ParamCurve, PathEl, Vec2};
+
+/// Options controlling G-code generation.
+#[derive(Clone, Copy, Debug)]
+pub struct GcodeOptions {
+    /// The maximum distance between the emitted moves and the path.
+    pub tolerance: f64,
+    /// Feed rate for cutting (`G1`) moves, in units per minute.
+    pub feed_rate: f64,
+    /// The Z height for rapid travel between subpaths.
+    pub safe_z: f64,
+    /// The Z height while cutting (or with the pen down).
+    pub cut_z: f64,
+    /// The number of digits after the decimal point in coordinates.
+    pub precision: usize,
+    /// Whether to emit curves as circular arc moves (`G2`/`G3`) rather than
+    /// flattening them to line moves.
+    pub arcs: bool,
+}
+
+impl Default for GcodeOptions {
+    fn default() -> GcodeOptions {
+        GcodeOptions {
+            tolerance: 0.01,
+            feed_rate: 1000.0,
+            safe_z: 5.0,
+            cut_z: 0.0,
+            precision: 4,
+            arcs: false,
+        }
+    }
+}
+
+impl BezPath {
+    /// Convert the path to G-code.
+    ///
+    /// Curves are flattened to `G1` line moves within the tolerance given in
+    /// the options, or if `arcs` is set, fit with `G2` and `G3` arc moves as
+    /// in [`fit_arcs`](#method.fit_arcs). Each subpath is entered by retracting
+    /// to the safe Z height, traveling with `G0`, and plunging to the cut
+    /// height; the tool is retracted again at the end of the program.
+    ///
+    /// Coordinates are emitted in absolute mode (`G90`) and in whatever units
+    /// the path is in; no unit selection (`G20`/`G21`) is emitted. Arc centers
+    /// are given relative to the start of the arc, and arcs sweeping from
+    /// positive X towards positive Y are counterclockwise (`G3`).
+    pub fn to_gcode(&self, options: &GcodeOptions) -> String {
+        let mut result = Vec::new();
+        let prec = options.precision;
+        writeln!(result, "G90").unwrap();
+        if options.arcs {
+            for segs in self.fit_arcs(options.tolerance) {
+                write_plunge(&mut result, segs[0].start(), options);
+                for seg in segs {
+                    match seg {
+                        ArcSplineSeg::Line(line) => {
+                            let p = line.p1;
+                            writeln!(result, "G1 X{:.*} Y{:.*}", prec, p.x, prec, p.y).unwrap();
+                        }
+                        ArcSplineSeg::Arc(arc) => {
+                            let cmd = if arc.sweep_angle > 0.0 { "G3" } else { "G2" };
+                            let p = arc.end();
+                            let ij = arc.center - arc.start();
+                            writeln!(
+                                result,
+                                "{} X{:.*} Y{:.*} I{:.*} J{:.*}",
+                                cmd, prec, p.x, prec, p.y, prec, ij.x, prec, ij.y
+                            )
+                            .unwrap();
+                        }
+                    }
+                }
+            }
+        } else {
+            let mut start = None;
+            let mut last = Vec2::default();
+            self.flatten(options.tolerance, |el| match el {
+                PathEl::Moveto(p) => {
+                    write_plunge(&mut result, p, options);
+                    start = Some(p);
+                    last = p;
+                }
+                PathEl::Lineto(p) => {
+                    writeln!(result, "G1 X{:.*} Y{:.*}", prec, p.x, prec, p.y).unwrap();
+                    last = p;
+                }
+                PathEl::Closepath => {
+                    if let Some(p) = start {
+                        if p != last {
+                            writeln!(result, "G1 X{:.*} Y{:.*}", prec, p.x, prec, p.y).unwrap();
+                            last = p;
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            });
+        }
+        writeln!(result, "G0 Z{:.*}", prec, options.safe_z).unwrap();
+        String::from_utf8(result).unwrap()
+    }
+}
+
+/// Retract, travel to the start of a subpath, and plunge to the cut height.
+fn write_plunge(result: &mut Vec<u8>, p: Vec2, options: &GcodeOptions) {
+    let prec = options.precision;
+    writeln!(result, "G0 Z{:.*}", prec, options.safe_z).unwrap();
+    writeln!(result, "G0 X{:.*} Y{:.*}", prec, p.x, prec, p.y).unwrap();
+    writeln!(
+        result,
+        "G1 Z{:.*} F{}",
+        prec, options.cut_z, options.feed_rate
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, GcodeOptions, Shape};
+
+    #[test]
+    fn gcode_square() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let options = GcodeOptions {
+            precision: 1,
+            ..Default::default()
+        };
+        let expected = "G90\nG0 Z5.0\nG0 X0.0 Y0.0\nG1 Z0.0 F1000\n\
+                        G1 X10.0 Y0.0\nG1 X10.0 Y10.0\nG1 X0.0 Y10.0\nG1 X0.0 Y0.0\nG0 Z5.0\n";
+        assert_eq!(path.to_gcode(&options), expected);
+    }
+
+    #[test]
+    fn gcode_curve_flattened() {
+        let path = BezPath::from_svg("M0 0Q50 100 100 0").unwrap();
+        let gcode = path.to_gcode(&Default::default());
+        let moves = gcode.lines().filter(|l| l.starts_with("G1 X")).count();
+        assert!(moves > 1);
+        assert!(gcode.contains("G1 X100.0000 Y0.0000"));
+    }
+
+    #[test]
+    fn gcode_arcs() {
+        let path = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let options = GcodeOptions {
+            precision: 1,
+            arcs: true,
+            ..Default::default()
+        };
+        let gcode = path.to_gcode(&options);
+        assert!(gcode.starts_with("G90\nG0 Z5.0\nG0 X10.0 Y0.0\nG1 Z0.0 F1000\nG3 X"));
+        let moves: Vec<_> = gcode.lines().filter(|l| l.starts_with("G3")).collect();
+        // A biarc per segment of the circle.
+        assert_eq!(moves.len(), 2 * (path.elements().len() - 2));
+        assert!(moves[0].ends_with("I-10.0 J0.0"));
+        assert!(moves.last().unwrap().starts_with("G3 X10.0 Y0.0 "));
+        assert!(!gcode.contains("G2"));
+        assert!(gcode.ends_with("G0 Z5.0\n"));
+    }
+}
@@ -0,0 +1,674 @@
+//! Conversion to and from GIS polygon representations (GeoJSON and WKT).
+
+use std::io::Write;
+
+use crate::{BezPath, PathEl, Shape, Vec2};
+
+/// The deepest nesting of JSON arrays and objects accepted when parsing.
+///
+/// GeoJSON needs fewer than ten levels; the limit keeps hostile input from
+/// overflowing the stack.
+const MAX_JSON_DEPTH: usize = 128;
+
+/// A polygon made of an exterior ring and zero or more holes.
+///
+/// This is the polygon model of GeoJSON and WKT. Rings are stored without
+/// repeating the first point at the end.
+#[derive(Clone, Debug, Default)]
+pub struct GeoPolygon {
+    /// The exterior ring.
+    pub exterior: Vec<Vec2>,
+    /// The interior rings (holes).
+    pub holes: Vec<Vec<Vec2>>,
+}
+
+/// An error which can be returned when parsing GeoJSON or WKT.
+#[derive(Debug)]
+pub enum GeoParseError {
+    Wrong,
+    UnexpectedEof,
+    /// The input is valid, but is not a polygonal geometry.
+    UnsupportedGeometry,
+    /// The input nests arrays or objects more deeply than any GeoJSON does.
+    TooDeep,
+}
+
+impl BezPath {
+    /// Flatten the path into polygons with consistently oriented rings.
+    ///
+    /// Each closed subpath becomes a ring; open subpaths are closed. Rings are
+    /// classified as exteriors or holes by their nesting depth, and each hole
+    /// is attached to the exterior immediately containing it.
+    ///
+    /// Following RFC 7946, exterior rings are oriented to have positive signed
+    /// area (counterclockwise in the y-up coordinates of geographic data), and
+    /// holes negative.
+    pub fn to_geo_polygons(&self, tolerance: f64) -> Vec<GeoPolygon> {
+        let mut rings: Vec<Vec<Vec2>> = Vec::new();
+        self.flatten(tolerance, |el| match el {
+            PathEl::Moveto(p) => rings.push(vec![p]),
+            PathEl::Lineto(p) => {
+                let ring = rings.last_mut().unwrap();
+                if ring.last() != Some(&p) {
+                    ring.push(p);
+                }
+            }
+            _ => (),
+        });
+        for ring in &mut rings {
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+        }
+        rings.retain(|ring| ring.len() >= 3);
+        let paths: Vec<BezPath> = rings.iter().map(|ring| ring_to_path(ring)).collect();
+        let containers: Vec<Vec<usize>> = (0..rings.len())
+            .map(|i| {
+                let pt = interior_point(&rings, i);
+                (0..rings.len())
+                    .filter(|&j| j != i && paths[j].winding(pt) != 0)
+                    .collect()
+            })
+            .collect();
+        let mut polygons = Vec::new();
+        let mut polygon_ix = vec![None; rings.len()];
+        let is_hole: Vec<bool> = containers.iter().map(|c| c.len() % 2 == 1).collect();
+        for (i, ring) in rings.iter().enumerate() {
+            if !is_hole[i] {
+                polygon_ix[i] = Some(polygons.len());
+                polygons.push(GeoPolygon {
+                    exterior: oriented(ring, paths[i].area(), true),
+                    holes: Vec::new(),
+                });
+            }
+        }
+        for (i, ring) in rings.iter().enumerate() {
+            if is_hole[i] {
+                let depth = containers[i].len();
+                let parent = containers[i]
+                    .iter()
+                    .find(|&&j| containers[j].len() == depth - 1)
+                    .and_then(|&j| polygon_ix[j]);
+                if let Some(parent) = parent {
+                    polygons[parent]
+                        .holes
+                        .push(oriented(ring, paths[i].area(), false));
+                }
+            }
+        }
+        polygons
+    }
+
+    /// Create a path from polygons, one closed subpath per ring.
+    pub fn from_geo_polygons(polygons: &[GeoPolygon]) -> BezPath {
+        let mut path = BezPath::new();
+        for polygon in polygons {
+            for ring in Some(&polygon.exterior).into_iter().chain(&polygon.holes) {
+                append_ring(&mut path, ring);
+            }
+        }
+        path
+    }
+
+    /// Convert the path to a WKT `POLYGON` or `MULTIPOLYGON`.
+    ///
+    /// The path is flattened and its rings oriented as in
+    /// [`to_geo_polygons`](#method.to_geo_polygons).
+    pub fn to_wkt(&self, tolerance: f64) -> String {
+        let polygons = self.to_geo_polygons(tolerance);
+        let mut result = Vec::new();
+        if polygons.is_empty() {
+            write!(result, "POLYGON EMPTY").unwrap();
+        } else if polygons.len() == 1 {
+            write!(result, "POLYGON ").unwrap();
+            write_wkt_polygon(&mut result, &polygons[0]);
+        } else {
+            write!(result, "MULTIPOLYGON (").unwrap();
+            for (i, polygon) in polygons.iter().enumerate() {
+                if i > 0 {
+                    write!(result, ", ").unwrap();
+                }
+                write_wkt_polygon(&mut result, polygon);
+            }
+            write!(result, ")").unwrap();
+        }
+        String::from_utf8(result).unwrap()
+    }
+
+    /// Parse a WKT `POLYGON` or `MULTIPOLYGON`.
+    ///
+    /// Z and M coordinates, if present, are ignored.
+    pub fn from_wkt(data: &str) -> Result<BezPath, GeoParseError> {
+        let mut lexer = GeoLexer::new(data);
+        let tag = lexer.get_word().to_ascii_uppercase();
+        let multi = match tag.as_str() {
+            "POLYGON" => false,
+            "MULTIPOLYGON" => true,
+            "" => return Err(GeoParseError::Wrong),
+            _ => return Err(GeoParseError::UnsupportedGeometry),
+        };
+        // Dimension qualifiers, such as `POLYGON Z`, and `EMPTY`.
+        let mut word = lexer.get_word().to_ascii_uppercase();
+        if word == "Z" || word == "M" || word == "ZM" {
+            word = lexer.get_word().to_ascii_uppercase();
+        }
+        if word == "EMPTY" {
+            return Ok(BezPath::new());
+        } else if !word.is_empty() {
+            return Err(GeoParseError::Wrong);
+        }
+        let polygons = if multi {
+            lexer.get_list(|lexer| lexer.get_wkt_polygon())?
+        } else {
+            vec![lexer.get_wkt_polygon()?]
+        };
+        Ok(BezPath::from_geo_polygons(&polygons))
+    }
+
+    /// Convert the path to a GeoJSON `Polygon` or `MultiPolygon` geometry object.
+    ///
+    /// The path is flattened and its rings oriented as in
+    /// [`to_geo_polygons`](#method.to_geo_polygons).
+    pub fn to_geojson(&self, tolerance: f64) -> String {
+        let polygons = self.to_geo_polygons(tolerance);
+        let mut result = Vec::new();
+        if polygons.len() == 1 {
+            write!(result, r#"{{"type":"Polygon","coordinates":"#).unwrap();
+            write_geojson_polygon(&mut result, &polygons[0]);
+        } else {
+            write!(result, r#"{{"type":"MultiPolygon","coordinates":["#).unwrap();
+            for (i, polygon) in polygons.iter().enumerate() {
+                if i > 0 {
+                    write!(result, ",").unwrap();
+                }
+                write_geojson_polygon(&mut result, polygon);
+            }
+            write!(result, "]").unwrap();
+        }
+        write!(result, "}}").unwrap();
+        String::from_utf8(result).unwrap()
+    }
+
+    /// Parse a GeoJSON `Polygon` or `MultiPolygon`.
+    ///
+    /// The input may be a geometry object, a `Feature`, or a `FeatureCollection`;
+    /// in the latter case all polygonal features are combined into one path.
+    pub fn from_geojson(data: &str) -> Result<BezPath, GeoParseError> {
+        let mut lexer = GeoLexer::new(data);
+        let value = lexer.get_json(0)?;
+        lexer.skip_ws();
+        if lexer.ix != data.len() {
+            return Err(GeoParseError::Wrong);
+        }
+        let mut polygons = Vec::new();
+        geojson_polygons(&value, &mut polygons)?;
+        Ok(BezPath::from_geo_polygons(&polygons))
+    }
+}
+
+fn ring_to_path(ring: &[Vec2]) -> BezPath {
+    let mut path = BezPath::new();
+    append_ring(&mut path, ring);
+    path
+}
+
+fn append_ring(path: &mut BezPath, ring: &[Vec2]) {
+    if let Some((&first, rest)) = ring.split_first() {
+        path.moveto(first);
+        for &p in rest {
+            path.lineto(p);
+        }
+        path.closepath();
+    }
+}
+
+/// A point inside ring `i`, not separated from its boundary by any other ring.
+///
+/// Unlike a vertex, which may touch another ring, the point tells which rings
+/// contain ring `i` as long as the rings don't cross. It lies on a horizontal
+/// line just above the lowest vertex of the ring, below any other vertex,
+/// between the leftmost crossing of the ring and the next crossing of any
+/// ring.
+fn interior_point(rings: &[Vec<Vec2>], i: usize) -> Vec2 {
+    let ring = &rings[i];
+    let y_min = ring.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let y_next = rings
+        .iter()
+        .flatten()
+        .map(|p| p.y)
+        .filter(|&y| y > y_min)
+        .fold(f64::INFINITY, f64::min);
+    if !y_next.is_finite() {
+        return ring[0];
+    }
+    let y = 0.5 * (y_min + y_next);
+    let x0 = crossings(ring, y).fold(f64::INFINITY, f64::min);
+    let x1 = rings
+        .iter()
+        .flat_map(|ring| crossings(ring, y))
+        .filter(|&x| x > x0)
+        .fold(f64::INFINITY, f64::min);
+    if x1.is_finite() {
+        Vec2::new(0.5 * (x0 + x1), y)
+    } else {
+        ring[0]
+    }
+}
+
+/// The x coordinates where the edges of a ring cross a horizontal line.
+fn crossings(ring: &[Vec2], y: f64) -> impl Iterator<Item = f64> + '_ {
+    let next = ring.iter().skip(1).chain(ring.first());
+    ring.iter().zip(next).filter_map(move |(&p, &q)| {
+        if (p.y > y) != (q.y > y) {
+            Some(p.x + (y - p.y) * (q.x - p.x) / (q.y - p.y))
+        } else {
+            None
+        }
+    })
+}
+
+fn oriented(ring: &[Vec2], area: f64, positive: bool) -> Vec<Vec2> {
+    let mut ring = ring.to_vec();
+    if (area > 0.0) != positive {
+        ring[1..].reverse();
+    }
+    ring
+}
+
+fn closed_ring(ring: &[Vec2]) -> impl Iterator<Item = &Vec2> {
+    ring.iter().chain(ring.first())
+}
+
+fn write_wkt_polygon(result: &mut Vec<u8>, polygon: &GeoPolygon) {
+    write!(result, "(").unwrap();
+    for (i, ring) in Some(&polygon.exterior)
+        .into_iter()
+        .chain(&polygon.holes)
+        .enumerate()
+    {
+        if i > 0 {
+            write!(result, ", ").unwrap();
+        }
+        write!(result, "(").unwrap();
+        for (j, p) in closed_ring(ring).enumerate() {
+            if j > 0 {
+                write!(result, ", ").unwrap();
+            }
+            write!(result, "{} {}", p.x, p.y).unwrap();
+        }
+        write!(result, ")").unwrap();
+    }
+    write!(result, ")").unwrap();
+}
+
+fn write_geojson_polygon(result: &mut Vec<u8>, polygon: &GeoPolygon) {
+    write!(result, "[").unwrap();
+    for (i, ring) in Some(&polygon.exterior)
+        .into_iter()
+        .chain(&polygon.holes)
+        .enumerate()
+    {
+        if i > 0 {
+            write!(result, ",").unwrap();
+        }
+        write!(result, "[").unwrap();
+        for (j, p) in closed_ring(ring).enumerate() {
+            if j > 0 {
+                write!(result, ",").unwrap();
+            }
+            write!(result, "[{},{}]", p.x, p.y).unwrap();
+        }
+        write!(result, "]").unwrap();
+    }
+    write!(result, "]").unwrap();
+}
+
+/// Remove the closing point that GIS formats repeat at the end of a ring.
+fn open_ring(mut ring: Vec<Vec2>) -> Vec<Vec2> {
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+/// A minimal JSON value, sufficient for reading GeoJSON geometry.
+enum Json {
+    Null,
+    Bool,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Result<&[Json], GeoParseError> {
+        match self {
+            Json::Array(items) => Ok(items),
+            _ => Err(GeoParseError::Wrong),
+        }
+    }
+}
+
+fn geojson_polygons(value: &Json, polygons: &mut Vec<GeoPolygon>) -> Result<(), GeoParseError> {
+    let ty = match value.get("type") {
+        Some(Json::String(ty)) => ty.as_str(),
+        _ => return Err(GeoParseError::Wrong),
+    };
+    match ty {
+        "FeatureCollection" => {
+            let features = value.get("features").ok_or(GeoParseError::Wrong)?;
+            for feature in features.as_array()? {
+                match geojson_polygons(feature, polygons) {
+                    Err(GeoParseError::UnsupportedGeometry) => (),
+                    r => r?,
+                }
+            }
+        }
+        "Feature" => match value.get("geometry") {
+            Some(Json::Null) | None => (),
+            Some(geometry) => geojson_polygons(geometry, polygons)?,
+        },
+        "Polygon" => {
+            let coords = value.get("coordinates").ok_or(GeoParseError::Wrong)?;
+            polygons.push(geojson_polygon(coords)?);
+        }
+        "MultiPolygon" => {
+            let coords = value.get("coordinates").ok_or(GeoParseError::Wrong)?;
+            for polygon in coords.as_array()? {
+                polygons.push(geojson_polygon(polygon)?);
+            }
+        }
+        _ => return Err(GeoParseError::UnsupportedGeometry),
+    }
+    Ok(())
+}
+
+fn geojson_polygon(coords: &Json) -> Result<GeoPolygon, GeoParseError> {
+    let mut rings = coords.as_array()?.iter().map(|ring| {
+        let points = ring
+            .as_array()?
+            .iter()
+            .map(|pos| match pos.as_array()? {
+                [Json::Number(x), Json::Number(y), ..] => Ok(Vec2::new(*x, *y)),
+                _ => Err(GeoParseError::Wrong),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(open_ring(points))
+    });
+    let exterior = match rings.next() {
+        Some(ring) => ring?,
+        None => Vec::new(),
+    };
+    let holes = rings.collect::<Result<Vec<_>, _>>()?;
+    Ok(GeoPolygon { exterior, holes })
+}
+
+struct GeoLexer<'a> {
+    data: &'a str,
+    ix: usize,
+}
+
+impl<'a> GeoLexer<'a> {
+    fn new(data: &str) -> GeoLexer<'_> {
+        GeoLexer { data, ix: 0 }
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(&c) = self.data.as_bytes().get(self.ix) {
+            if !c.is_ascii_whitespace() {
+                break;
+            }
+            self.ix += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_ws();
+        self.data.as_bytes().get(self.ix).cloned()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), GeoParseError> {
+        match self.peek() {
+            Some(x) if x == c => {
+                self.ix += 1;
+                Ok(())
+            }
+            Some(_) => Err(GeoParseError::Wrong),
+            None => Err(GeoParseError::UnexpectedEof),
+        }
+    }
+
+    fn get_word(&mut self) -> &'a str {
+        self.skip_ws();
+        let start = self.ix;
+        while let Some(&c) = self.data.as_bytes().get(self.ix) {
+            if !c.is_ascii_alphabetic() {
+                break;
+            }
+            self.ix += 1;
+        }
+        &self.data[start..self.ix]
+    }
+
+    fn get_number(&mut self) -> Result<f64, GeoParseError> {
+        self.skip_ws();
+        let start = self.ix;
+        while let Some(&c) = self.data.as_bytes().get(self.ix) {
+            if !(c.is_ascii_digit()
+                || c == b'-'
+                || c == b'+'
+                || c == b'.'
+                || c == b'e'
+                || c == b'E')
+            {
+                break;
+            }
+            self.ix += 1;
+        }
+        if start == self.ix {
+            return Err(if start == self.data.len() {
+                GeoParseError::UnexpectedEof
+            } else {
+                GeoParseError::Wrong
+            });
+        }
+        self.data[start..self.ix]
+            .parse()
+            .map_err(|_| GeoParseError::Wrong)
+    }
+
+    /// Parse a parenthesized, comma-separated list.
+    fn get_list<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, GeoParseError>,
+    ) -> Result<Vec<T>, GeoParseError> {
+        self.expect(b'(')?;
+        let mut result = vec![f(self)?];
+        while self.peek() == Some(b',') {
+            self.ix += 1;
+            result.push(f(self)?);
+        }
+        self.expect(b')')?;
+        Ok(result)
+    }
+
+    fn get_wkt_polygon(&mut self) -> Result<GeoPolygon, GeoParseError> {
+        let mut rings = self.get_list(|lexer| {
+            let ring = lexer.get_list(|lexer| {
+                let x = lexer.get_number()?;
+                let y = lexer.get_number()?;
+                // Skip Z and M coordinates.
+                while lexer.peek() != Some(b',') && lexer.peek() != Some(b')') {
+                    lexer.get_number()?;
+                }
+                Ok(Vec2::new(x, y))
+            })?;
+            Ok(open_ring(ring))
+        })?;
+        let exterior = rings.remove(0);
+        Ok(GeoPolygon {
+            exterior,
+            holes: rings,
+        })
+    }
+
+    /// Parse a JSON value, nested within `depth` arrays and objects.
+    fn get_json(&mut self, depth: usize) -> Result<Json, GeoParseError> {
+        let c = self.peek().ok_or(GeoParseError::UnexpectedEof)?;
+        if (c == b'{' || c == b'[') && depth >= MAX_JSON_DEPTH {
+            return Err(GeoParseError::TooDeep);
+        }
+        match c {
+            b'{' => {
+                self.ix += 1;
+                let mut members = Vec::new();
+                if self.peek() == Some(b'}') {
+                    self.ix += 1;
+                    return Ok(Json::Object(members));
+                }
+                loop {
+                    let key = self.get_json_string()?;
+                    self.expect(b':')?;
+                    members.push((key, self.get_json(depth + 1)?));
+                    if self.peek() == Some(b',') {
+                        self.ix += 1;
+                    } else {
+                        self.expect(b'}')?;
+                        return Ok(Json::Object(members));
+                    }
+                }
+            }
+            b'[' => {
+                self.ix += 1;
+                let mut items = Vec::new();
+                if self.peek() == Some(b']') {
+                    self.ix += 1;
+                    return Ok(Json::Array(items));
+                }
+                loop {
+                    items.push(self.get_json(depth + 1)?);
+                    if self.peek() == Some(b',') {
+                        self.ix += 1;
+                    } else {
+                        self.expect(b']')?;
+                        return Ok(Json::Array(items));
+                    }
+                }
+            }
+            b'"' => Ok(Json::String(self.get_json_string()?)),
+            b't' | b'f' | b'n' => match self.get_word() {
+                "true" | "false" => Ok(Json::Bool),
+                "null" => Ok(Json::Null),
+                _ => Err(GeoParseError::Wrong),
+            },
+            _ => Ok(Json::Number(self.get_number()?)),
+        }
+    }
+
+    /// Parse a JSON string.
+    ///
+    /// Escape sequences are kept verbatim, as GeoJSON keys and type names
+    /// don't need them.
+    fn get_json_string(&mut self) -> Result<String, GeoParseError> {
+        self.expect(b'"')?;
+        let start = self.ix;
+        let bytes = self.data.as_bytes();
+        while let Some(&c) = bytes.get(self.ix) {
+            match c {
+                b'"' => {
+                    let s = self.data[start..self.ix].to_string();
+                    self.ix += 1;
+                    return Ok(s);
+                }
+                b'\\' => self.ix += 2,
+                _ => self.ix += 1,
+            }
+        }
+        Err(GeoParseError::UnexpectedEof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, GeoParseError, Shape};
+
+    #[test]
+    fn geo_orientation() {
+        // Exterior clockwise (negative area), hole counterclockwise.
+        let path = BezPath::from_svg("M0 0L0 10L10 10L10 0Z M2 2L8 2L8 8L2 8Z").unwrap();
+        assert!(path.area() < 0.0);
+        let polygons = path.to_geo_polygons(0.1);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].holes.len(), 1);
+        let fixed = BezPath::from_geo_polygons(&polygons);
+        let segs: Vec<_> = fixed.segments().collect();
+        assert_eq!(segs.len(), 8);
+        assert!((fixed.area() - (100.0 - 36.0)).abs() < 1e-12);
+
+        // Holes whose first vertex touches the exterior.
+        for svg in &[
+            "M0 0L10 0L10 10L0 10Z M10 5L5 8L2 5L5 2Z",
+            "M0 0L10 0L10 10L0 10Z M0 5L5 2L8 5L5 8Z",
+        ] {
+            let polygons = BezPath::from_svg(svg).unwrap().to_geo_polygons(0.1);
+            assert_eq!(polygons.len(), 1, "{}", svg);
+            assert_eq!(polygons[0].holes.len(), 1, "{}", svg);
+        }
+    }
+
+    #[test]
+    fn wkt_roundtrip() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10L0 10Z M2 2L2 8L8 8L8 2Z").unwrap();
+        let wkt = path.to_wkt(0.1);
+        assert_eq!(
+            wkt,
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))"
+        );
+        let parsed = BezPath::from_wkt(&wkt).unwrap();
+        assert_eq!(parsed.to_wkt(0.1), wkt);
+
+        let multi = BezPath::from_wkt(
+            "multipolygon z (((0 0 1, 1 0 1, 1 1 1, 0 0 1)), ((5 5 0, 6 5 0, 6 6 0, 5 5 0)))",
+        )
+        .unwrap();
+        assert!((multi.area() - 1.0).abs() < 1e-12);
+        assert!(BezPath::from_wkt("POLYGON EMPTY").unwrap().is_empty());
+        assert!(BezPath::from_wkt("LINESTRING (0 0, 1 1)").is_err());
+        assert!(BezPath::from_wkt("POLYGON ((0 0, 1 0").is_err());
+    }
+
+    #[test]
+    fn geojson_roundtrip() {
+        let circle = Circle::new((0.0, 0.0), 1.0).into_bez_path(1e-3);
+        let json = circle.to_geojson(1e-3);
+        assert!(json.starts_with(r#"{"type":"Polygon","coordinates":[[[1,0],"#));
+        let parsed = BezPath::from_geojson(&json).unwrap();
+        assert!((parsed.area() - circle.area()).abs() < 1e-2);
+
+        let feature = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a \"b\""},
+             "geometry": {"type": "Point", "coordinates": [0, 0]}},
+            {"type": "Feature", "properties": null,
+             "geometry": {"type": "MultiPolygon", "coordinates":
+                [[[[0, 0], [2, 0], [2, 2], [0, 2], [0, 0]]]]}}
+        ]}"#;
+        let parsed = BezPath::from_geojson(feature).unwrap();
+        assert!((parsed.area() - 4.0).abs() < 1e-12);
+        assert!(BezPath::from_geojson(r#"{"type": "Polygon""#).is_err());
+
+        // Deep nesting is rejected rather than overflowing the stack.
+        let deep = "[".repeat(1_000_000);
+        assert!(matches!(
+            BezPath::from_geojson(&deep),
+            Err(GeoParseError::TooDeep)
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+//! Hobby's algorithm for smooth curves through points.
+
+use crate::{BezPath, Vec2};
+
+/// Construct a smooth curve through points using John Hobby's algorithm.
+///
+/// This is the algorithm used by METAFONT and MetaPost to choose the
+/// tangent directions at each point, approximately minimizing changes in
+/// curvature, and the lengths of the control handles. The `tension` controls
+/// the lengths of the handles; 1 is the default in METAFONT, and larger values
+/// make the curve tighter. Values below 0.75 may cause loops. The ends of an
+/// open curve use a "curl" of 1, making them approximately circular.
+///
+/// Consecutive duplicate points are ignored.
+///
+/// Panics if `tension` is not positive.
+pub fn hobby_spline(points: &[Vec2], tension: f64, closed: bool) -> BezPath {
+    assert!(tension > 0.0, "tension must be positive");
+    let mut pts: Vec<Vec2> = Vec::with_capacity(points.len());
+    for &p in points {
+        if pts.last() != Some(&p) {
+            pts.push(p);
+        }
+    }
+    if closed && pts.len() > 1 && pts[0] == pts[pts.len() - 1] {
+        pts.pop();
+    }
+    let n = pts.len();
+    let mut path = BezPath::new();
+    if n == 0 {
+        return path;
+    }
+    path.moveto(pts[0]);
+    if n == 1 {
+        return path;
+    }
+    if n == 2 || (closed && n < 3) {
+        path.lineto(pts[1]);
+        if closed {
+            path.closepath();
+        }
+        return path;
+    }
+    let n_segs = if closed { n } else { n - 1 };
+    let chord = |k: usize| pts[(k + 1) % n] - pts[k];
+    let d: Vec<f64> = (0..n_segs).map(|k| chord(k).hypot()).collect();
+    // The turning angle at each point; zero at the ends of an open curve.
+    let psi: Vec<f64> = (0..n)
+        .map(|k| {
+            if !closed && (k == 0 || k == n - 1) {
+                0.0
+            } else {
+                let (c0, c1) = (chord((k + n - 1) % n), chord(k));
+                c0.cross(c1).atan2(c0.dot(c1))
+            }
+        })
+        .collect();
+    // The equations for the tangent angles relative to the chords, from
+    // section 276 of "METAFONT: The Program", with uniform tension.
+    let a = tension.recip();
+    let mut lower = vec![0.0; n];
+    let mut diag = vec![0.0; n];
+    let mut upper = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for k in 0..n {
+        if !closed && k == 0 {
+            // Curl of 1 at the start.
+            diag[k] = 3.0;
+            upper[k] = 3.0;
+            rhs[k] = -3.0 * psi[1];
+        } else if !closed && k == n - 1 {
+            lower[k] = 3.0;
+            diag[k] = 3.0;
+        } else {
+            let (d0, d1) = (d[(k + n - 1) % n], d[k]);
+            lower[k] = 1.0 / (a * d0);
+            let b = (3.0 - a) / (a * a * d0);
+            let c = (3.0 - a) / (a * a * d1);
+            upper[k] = 1.0 / (a * d1);
+            diag[k] = b + c;
+            rhs[k] = -b * psi[k] - upper[k] * psi[(k + 1) % n];
+        }
+    }
+    let theta = if closed {
+        solve_cyclic_tridiagonal(&lower, &diag, &upper, &rhs)
+    } else {
+        solve_tridiagonal(&lower, &diag, &upper, &rhs)
+    };
+    for k in 0..n_segs {
+        let k1 = (k + 1) % n;
+        let th = theta[k];
+        let phi = -psi[k1] - theta[k1];
+        let c = chord(k);
+        let p1 = pts[k] + a * velocity(th, phi) * c.rotate(th);
+        let p2 = pts[k1] - a * velocity(phi, th) * c.rotate(-phi);
+        path.curveto(p1, p2, pts[k1]);
+    }
+    if closed {
+        path.closepath();
+    }
+    path
+}
+
+/// Hobby's velocity function, the handle length as a fraction of the chord.
+fn velocity(theta: f64, phi: f64) -> f64 {
+    let (st, ct) = theta.sin_cos();
+    let (sp, cp) = phi.sin_cos();
+    let sqrt5 = 5f64.sqrt();
+    let numer = 2.0 + 2f64.sqrt() * (st - sp / 16.0) * (sp - st / 16.0) * (ct - cp);
+    let denom = 3.0 * (1.0 + 0.5 * (sqrt5 - 1.0) * ct + 0.5 * (3.0 - sqrt5) * cp);
+    numer / denom
+}
+
+/// Solve a tridiagonal system, using the Thomas algorithm.
+fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let mut c = vec![0.0; n];
+    let mut x = vec![0.0; n];
+    c[0] = upper[0] / diag[0];
+    x[0] = rhs[0] / diag[0];
+    for i in 1..n {
+        let m = diag[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / m;
+        x[i] = (rhs[i] - lower[i] * x[i - 1]) / m;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] -= c[i] * x[i + 1];
+    }
+    x
+}
+
+/// Solve a cyclic tridiagonal system, where `lower[0]` and `upper[n - 1]` wrap
+/// around, using the Sherman-Morrison formula.
+fn solve_cyclic_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diag.len();
+    let (corner_top, corner_bottom) = (lower[0], upper[n - 1]);
+    let gamma = -diag[0];
+    let mut diag = diag.to_vec();
+    diag[0] -= gamma;
+    diag[n - 1] -= corner_bottom * corner_top / gamma;
+    let x = solve_tridiagonal(lower, &diag, upper, rhs);
+    let mut u = vec![0.0; n];
+    u[0] = gamma;
+    u[n - 1] = corner_bottom;
+    let z = solve_tridiagonal(lower, &diag, upper, &u);
+    let fact =
+        (x[0] + corner_top * x[n - 1] / gamma) / (1.0 + z[0] + corner_top * z[n - 1] / gamma);
+    x.iter().zip(&z).map(|(xi, zi)| xi - fact * zi).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hobby_spline, ParamCurve, Vec2};
+
+    #[test]
+    fn hobby_closed_square() {
+        // Through the corners of a square, the curve is nearly a circle.
+        let pts = [
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(0.0, -1.0),
+        ];
+        let path = hobby_spline(&pts, 1.0, true);
+        let segs: Vec<_> = path.segments().collect();
+        assert_eq!(segs.len(), 4);
+        for (i, seg) in segs.iter().enumerate() {
+            assert_eq!(seg.start(), pts[i]);
+            // The tangent is perpendicular to the radius.
+            assert!(seg.tangent(0.0).dot(pts[i]).abs() < 1e-9);
+            let mid = seg.eval(0.5).hypot();
+            assert!((mid - 1.0).abs() < 0.01, "{}", mid);
+        }
+        // Higher tension pulls the curve towards the polygon.
+        let tight = hobby_spline(&pts, 2.0, true);
+        let mid = tight.segments().next().unwrap().eval(0.5).hypot();
+        assert!(mid < 0.95);
+    }
+
+    #[test]
+    fn hobby_open() {
+        let pts = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+        ];
+        let path = hobby_spline(&pts, 1.0, false);
+        for seg in path.segments() {
+            for i in 0..=4 {
+                assert!(seg.eval(i as f64 / 4.0).y.abs() < 1e-12);
+            }
+        }
+        let pts = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(30.0, 10.0),
+        ];
+        let path = hobby_spline(&pts, 1.0, false);
+        let segs: Vec<_> = path.segments().collect();
+        assert_eq!(segs.len(), 3);
+        for pair in segs.windows(2) {
+            assert!((pair[0].tangent(1.0) - pair[1].tangent(0.0)).hypot() < 1e-9);
+        }
+        // The zigzag is symmetric under a half turn about the middle.
+        assert!((segs[1].tangent(0.0) - segs[1].tangent(1.0)).hypot() < 1e-9);
+        assert_eq!(hobby_spline(&pts[..2], 1.0, false).to_svg(), "M0 0L10 10");
+    }
+}
@@ -0,0 +1,196 @@
+//! Unbounded lines.
+
+use std::ops::Mul;
+
+use crate::{Affine, Line, Vec2};
+
+/// A line extending infinitely in both directions.
+///
+/// Unlike [`Line`](struct.Line.html), which is a segment between two points,
+/// this is the unbounded primitive used for constructions such as bisectors,
+/// clipping boundaries, and constraints.
+///
+/// The direction should be nonzero; it need not be normalized, and its
+/// length sets the scale of parameters along the line.
+#[derive(Clone, Copy, Debug)]
+pub struct InfiniteLine {
+    /// A point on the line.
+    pub point: Vec2,
+    /// The direction of the line.
+    pub direction: Vec2,
+}
+
+impl InfiniteLine {
+    /// A new line through a point, in the given direction.
+    #[inline]
+    pub fn new(point: impl Into<Vec2>, direction: impl Into<Vec2>) -> InfiniteLine {
+        InfiniteLine {
+            point: point.into(),
+            direction: direction.into(),
+        }
+    }
+
+    /// The line through two points, directed from the first to the second.
+    #[inline]
+    pub fn through(p0: impl Into<Vec2>, p1: impl Into<Vec2>) -> InfiniteLine {
+        let p0 = p0.into();
+        InfiniteLine::new(p0, p1.into() - p0)
+    }
+
+    /// The perpendicular bisector of two points.
+    ///
+    /// Its direction is the direction from `p0` to `p1` rotated by 90 degrees,
+    /// so `p0` is on its positive side.
+    pub fn perpendicular_bisector(p0: impl Into<Vec2>, p1: impl Into<Vec2>) -> InfiniteLine {
+        let (p0, p1) = (p0.into(), p1.into());
+        let d = p1 - p0;
+        InfiniteLine::new(p0.lerp(p1, 0.5), Vec2::new(-d.y, d.x))
+    }
+
+    /// The point at parameter `t`, that is, `point + t * direction`.
+    #[inline]
+    pub fn eval(&self, t: f64) -> Vec2 {
+        self.point + t * self.direction
+    }
+
+    /// The parameter of the orthogonal projection of a point onto the line.
+    #[inline]
+    pub fn param(&self, p: Vec2) -> f64 {
+        (p - self.point).dot(self.direction) / self.direction.hypot2()
+    }
+
+    /// The orthogonal projection of a point onto the line.
+    #[inline]
+    pub fn project(&self, p: Vec2) -> Vec2 {
+        self.eval(self.param(p))
+    }
+
+    /// The signed distance from the line to a point.
+    ///
+    /// This is positive for points on the side the direction turns towards
+    /// when rotated from positive X to positive Y, consistent with
+    /// [`Vec2::cross`](struct.Vec2.html#method.cross).
+    #[inline]
+    pub fn signed_distance(&self, p: Vec2) -> f64 {
+        self.direction.cross(p - self.point) / self.direction.hypot()
+    }
+
+    /// The distance from the line to a point.
+    #[inline]
+    pub fn distance(&self, p: Vec2) -> f64 {
+        self.signed_distance(p).abs()
+    }
+
+    /// Which side of the line a point is on.
+    ///
+    /// Returns 1 for the positive side (see
+    /// [`signed_distance`](#method.signed_distance)), -1 for the negative
+    /// side, and 0 for points exactly on the line.
+    #[inline]
+    pub fn side(&self, p: Vec2) -> i32 {
+        let c = self.direction.cross(p - self.point);
+        if c > 0.0 {
+            1
+        } else if c < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// The parameters of the intersection with another line.
+    ///
+    /// Returns the parameters on this line and the other, or `None` if the
+    /// lines are parallel.
+    pub fn intersect_params(&self, other: &InfiniteLine) -> Option<(f64, f64)> {
+        let denom = self.direction.cross(other.direction);
+        if denom == 0.0 {
+            return None;
+        }
+        let d = other.point - self.point;
+        Some((
+            d.cross(other.direction) / denom,
+            d.cross(self.direction) / denom,
+        ))
+    }
+
+    /// The intersection point with another line, or `None` if they are parallel.
+    pub fn intersect(&self, other: &InfiniteLine) -> Option<Vec2> {
+        self.intersect_params(other).map(|(t, _)| self.eval(t))
+    }
+
+    /// The intersection point with a line segment, if it crosses the segment.
+    pub fn intersect_segment(&self, seg: Line) -> Option<Vec2> {
+        let (_, u) = self.intersect_params(&InfiniteLine::from(seg))?;
+        if (0.0..=1.0).contains(&u) {
+            Some(seg.p0.lerp(seg.p1, u))
+        } else {
+            None
+        }
+    }
+
+    /// The parallel line offset by `distance` towards the positive side.
+    pub fn offset(&self, distance: f64) -> InfiniteLine {
+        let n = Vec2::new(-self.direction.y, self.direction.x) / self.direction.hypot();
+        InfiniteLine::new(self.point + distance * n, self.direction)
+    }
+}
+
+impl From<Line> for InfiniteLine {
+    #[inline]
+    fn from(line: Line) -> InfiniteLine {
+        InfiniteLine::through(line.p0, line.p1)
+    }
+}
+
+impl Mul<InfiniteLine> for Affine {
+    type Output = InfiniteLine;
+
+    #[inline]
+    fn mul(self, other: InfiniteLine) -> InfiniteLine {
+        let point = self * other.point;
+        InfiniteLine {
+            point,
+            direction: self * (other.point + other.direction) - point,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{InfiniteLine, Line, Vec2};
+
+    #[test]
+    fn infinite_line_queries() {
+        let l = InfiniteLine::through((0.0, 1.0), (2.0, 1.0));
+        let p = Vec2::new(5.0, 4.0);
+        assert_eq!(l.project(p), Vec2::new(5.0, 1.0));
+        assert_eq!(l.param(p), 2.5);
+        assert_eq!(l.signed_distance(p), 3.0);
+        assert_eq!(l.distance(Vec2::new(-3.0, -1.0)), 2.0);
+        assert_eq!(l.side(p), 1);
+        assert_eq!(l.side(Vec2::new(7.0, 0.0)), -1);
+        assert_eq!(l.side(Vec2::new(-7.0, 1.0)), 0);
+        assert_eq!(l.offset(2.0).eval(0.0), Vec2::new(0.0, 3.0));
+    }
+
+    #[test]
+    fn infinite_line_intersect() {
+        let a = InfiniteLine::through((0.0, 0.0), (1.0, 1.0));
+        let b = InfiniteLine::new((4.0, 0.0), (-1.0, 1.0));
+        assert_eq!(a.intersect(&b), Some(Vec2::new(2.0, 2.0)));
+        assert_eq!(a.intersect_params(&b), Some((2.0, 2.0)));
+        let parallel = InfiniteLine::new((0.0, 1.0), (2.0, 2.0));
+        assert!(a.intersect(&parallel).is_none());
+        let seg = Line::new((10.0, 0.0), (10.0, 20.0));
+        assert_eq!(a.intersect_segment(seg), Some(Vec2::new(10.0, 10.0)));
+        let short = Line::new((10.0, 0.0), (10.0, 5.0));
+        assert!(a.intersect_segment(short).is_none());
+
+        let bisector = InfiniteLine::perpendicular_bisector((0.0, 0.0), (4.0, 2.0));
+        let c = Vec2::new(0.0, 5.0);
+        let q = bisector.project(c);
+        assert!((q.hypot() - (q - Vec2::new(4.0, 2.0)).hypot()).abs() < 1e-12);
+        assert_eq!(bisector.side(Vec2::new(0.0, 0.0)), 1);
+    }
+}
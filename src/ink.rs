@@ -0,0 +1,200 @@
+//! Building ink strokes from pen input.
+
+use crate::{
+    fit_cubics, simplify_polyline, BezPath, Cap, Circle, Join, Shape, SimplifyMethod, Stroke, Vec2,
+    WidthProfile,
+};
+
+/// A sample of pen input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InkPoint {
+    /// The position of the pen.
+    pub point: Vec2,
+    /// The pressure of the pen, from 0 to 1.
+    pub pressure: f64,
+    /// The time of the sample, in any unit.
+    pub time: f64,
+}
+
+/// The result of building an ink stroke.
+#[derive(Clone, Debug)]
+pub struct InkStroke {
+    /// The smoothed path of the pen.
+    pub centerline: BezPath,
+    /// The outline of the ink, to be filled with the nonzero fill rule.
+    pub outline: BezPath,
+}
+
+/// A builder turning pen samples into an ink stroke.
+///
+/// Samples are pushed as they arrive, and [`build`](#method.build) can be
+/// called at any time, for example to show the stroke while it is being
+/// drawn. The pipeline simplifies the samples to remove jitter, fits
+/// cubic Béziers to them for the centerline, and strokes that with a width
+/// following the pressure, tapering where the pen lifts.
+#[derive(Clone, Debug)]
+pub struct InkBuilder {
+    samples: Vec<InkPoint>,
+    width: f64,
+    tolerance: f64,
+    thinning: f64,
+}
+
+impl InkPoint {
+    /// Create a new sample.
+    #[inline]
+    pub fn new<V: Into<Vec2>>(point: V, pressure: f64, time: f64) -> InkPoint {
+        InkPoint {
+            point: point.into(),
+            pressure,
+            time,
+        }
+    }
+}
+
+impl InkBuilder {
+    /// Create a builder for strokes of the given width at full pressure.
+    ///
+    /// The `tolerance` is the accuracy of both the centerline, relative to
+    /// the samples, and of the outline; input jitter smaller than it is
+    /// smoothed away.
+    pub fn new(width: f64, tolerance: f64) -> InkBuilder {
+        InkBuilder {
+            samples: Vec::new(),
+            width,
+            tolerance,
+            thinning: 0.0,
+        }
+    }
+
+    /// Builder method for setting how much the stroke thins with speed.
+    ///
+    /// The width is divided by `1 + thinning * speed`, with the speed in
+    /// units of distance per unit of time, as with a fountain pen, where
+    /// fast strokes leave less ink. The default of zero ignores speed.
+    pub fn with_thinning(mut self, thinning: f64) -> InkBuilder {
+        self.thinning = thinning;
+        self
+    }
+
+    /// Add a sample.
+    ///
+    /// Samples not later than the last one are out of order, and are
+    /// dropped, as are samples at the position of the last one.
+    pub fn push(&mut self, sample: InkPoint) {
+        if let Some(last) = self.samples.last() {
+            if sample.time <= last.time || sample.point == last.point {
+                return;
+            }
+        }
+        self.samples.push(sample);
+    }
+
+    /// The samples kept so far.
+    pub fn samples(&self) -> &[InkPoint] {
+        &self.samples
+    }
+
+    /// Remove all samples, to start a new stroke.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Build the stroke from the samples so far.
+    ///
+    /// With no samples, both paths are empty. A single sample gives a dot.
+    pub fn build(&self) -> InkStroke {
+        let samples = &self.samples;
+        let mut centerline = BezPath::new();
+        let mut outline = BezPath::new();
+        match samples.len() {
+            0 => (),
+            1 => {
+                let s = samples[0];
+                centerline.moveto(s.point);
+                let radius = 0.5 * self.width * s.pressure.clamp(0.0, 1.0);
+                outline = Circle::new(s.point, radius).into_bez_path(self.tolerance);
+            }
+            _ => {
+                let points: Vec<Vec2> = samples.iter().map(|s| s.point).collect();
+                let simplified =
+                    simplify_polyline(&points, self.tolerance, SimplifyMethod::Visvalingam);
+                centerline = fit_cubics(&simplified, None, self.tolerance);
+                let profile = self.width_profile();
+                let style = Stroke::new(self.width)
+                    .with_join(Join::Round)
+                    .with_caps(Cap::Round);
+                outline =
+                    centerline.stroke_variable(&style, |pos| profile.eval(pos), self.tolerance);
+            }
+        }
+        InkStroke {
+            centerline,
+            outline,
+        }
+    }
+
+    /// The width at each sample, keyed by its position along the samples.
+    fn width_profile(&self) -> WidthProfile {
+        let samples = &self.samples;
+        let n = samples.len();
+        let mut lens = Vec::with_capacity(n);
+        let mut len = 0.0;
+        for i in 0..n {
+            if i > 0 {
+                len += (samples[i].point - samples[i - 1].point).hypot();
+            }
+            lens.push(len);
+        }
+        WidthProfile::new((0..n).map(|i| {
+            let (prev, next) = (samples[i.max(1) - 1], samples[(i + 1).min(n - 1)]);
+            let speed = (next.point - prev.point).hypot() / (next.time - prev.time);
+            let width = samples[i].pressure.clamp(0.0, 1.0) / (1.0 + self.thinning * speed);
+            (lens[i] / len, width)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{InkBuilder, InkPoint, Shape};
+
+    #[test]
+    fn ink() {
+        let mut ink = InkBuilder::new(2.0, 0.01);
+        assert!(ink.build().outline.elements().is_empty());
+        ink.push(InkPoint::new((0.0, 0.0), 1.0, 0.0));
+        let dot = ink.build();
+        assert!((dot.outline.area().abs() - std::f64::consts::PI).abs() < 1e-2);
+
+        // A line drawn with jitter and rising pressure.
+        for i in 1..=100 {
+            let x = i as f64 * 0.1;
+            let jitter = if i % 2 == 0 { 1e-3 } else { -1e-3 };
+            ink.push(InkPoint::new((x, jitter), 0.5 + 0.005 * i as f64, i as f64));
+        }
+        // Out of order and repeated samples are dropped.
+        ink.push(InkPoint::new((20.0, 0.0), 1.0, 50.0));
+        ink.push(InkPoint::new((10.0, 0.001), 1.0, 200.0));
+        assert_eq!(ink.samples().len(), 101);
+
+        let stroke = ink.build();
+        // The jitter is smoothed into a few flat segments.
+        assert!(stroke.centerline.elements().len() < 10);
+        assert!(stroke.centerline.bounding_box().height() < 0.05);
+        // The stroke widens from one end to the other.
+        assert!(stroke.outline.winding((1.0, 0.6).into()) == 0);
+        assert!(stroke.outline.winding((9.0, 0.9).into()) != 0);
+        assert!(stroke.outline.winding((9.0, 1.1).into()) == 0);
+        // Round caps.
+        assert!(stroke.outline.winding((-0.4, 0.0).into()) != 0);
+
+        // Faster drawing gives a thinner stroke.
+        let mut fast = InkBuilder::new(2.0, 0.01).with_thinning(10.0);
+        for s in ink.samples() {
+            fast.push(*s);
+        }
+        let thin = fast.build();
+        assert!(thin.outline.area().abs() < 0.5 * stroke.outline.area().abs());
+    }
+}
@@ -0,0 +1,151 @@
+//! Insets from the edges of a rectangle.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::Size;
+
+/// Distances by which to move each edge of a rectangle inwards.
+///
+/// These are applied by [`Rect::inset`](struct.Rect.html#method.inset),
+/// and in reverse by [`Rect::outset`](struct.Rect.html#method.outset).
+/// Negative values move an edge outwards.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Insets {
+    /// The inset of the left edge.
+    pub x0: f64,
+    /// The inset of the top edge, in y-down spaces.
+    pub y0: f64,
+    /// The inset of the right edge.
+    pub x1: f64,
+    /// The inset of the bottom edge, in y-down spaces.
+    pub y1: f64,
+}
+
+impl Insets {
+    /// New insets for each edge.
+    #[inline]
+    pub fn new(x0: f64, y0: f64, x1: f64, y1: f64) -> Insets {
+        Insets { x0, y0, x1, y1 }
+    }
+
+    /// The same inset for every edge.
+    #[inline]
+    pub fn uniform(d: f64) -> Insets {
+        Insets::new(d, d, d, d)
+    }
+
+    /// Insets of `h` for the left and right edges, and `v` for the top and
+    /// bottom.
+    #[inline]
+    pub fn axis(h: f64, v: f64) -> Insets {
+        Insets::new(h, v, h, v)
+    }
+
+    /// The amount by which the insets shrink the size of a rectangle.
+    #[inline]
+    pub fn size(&self) -> Size {
+        Size::new(self.x0 + self.x1, self.y0 + self.y1)
+    }
+
+    /// The larger inset for each edge, as for padding that must satisfy
+    /// two constraints.
+    #[inline]
+    pub fn max(&self, other: Insets) -> Insets {
+        Insets::new(
+            self.x0.max(other.x0),
+            self.y0.max(other.y0),
+            self.x1.max(other.x1),
+            self.y1.max(other.y1),
+        )
+    }
+
+    /// The smaller inset for each edge.
+    #[inline]
+    pub fn min(&self, other: Insets) -> Insets {
+        Insets::new(
+            self.x0.min(other.x0),
+            self.y0.min(other.y0),
+            self.x1.min(other.x1),
+            self.y1.min(other.y1),
+        )
+    }
+}
+
+impl Add for Insets {
+    type Output = Insets;
+
+    #[inline]
+    fn add(self, other: Insets) -> Insets {
+        Insets::new(
+            self.x0 + other.x0,
+            self.y0 + other.y0,
+            self.x1 + other.x1,
+            self.y1 + other.y1,
+        )
+    }
+}
+
+impl Sub for Insets {
+    type Output = Insets;
+
+    #[inline]
+    fn sub(self, other: Insets) -> Insets {
+        self + -other
+    }
+}
+
+impl Neg for Insets {
+    type Output = Insets;
+
+    #[inline]
+    fn neg(self) -> Insets {
+        Insets::new(-self.x0, -self.y0, -self.x1, -self.y1)
+    }
+}
+
+impl Mul<f64> for Insets {
+    type Output = Insets;
+
+    #[inline]
+    fn mul(self, other: f64) -> Insets {
+        Insets::new(
+            self.x0 * other,
+            self.y0 * other,
+            self.x1 * other,
+            self.y1 * other,
+        )
+    }
+}
+
+impl Mul<Insets> for f64 {
+    type Output = Insets;
+
+    #[inline]
+    fn mul(self, other: Insets) -> Insets {
+        other * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Insets, Rect, Size};
+
+    #[test]
+    fn insets_arithmetic() {
+        let padding = Insets::axis(2.0, 1.0);
+        assert_eq!(padding, Insets::new(2.0, 1.0, 2.0, 1.0));
+        let border = Insets::uniform(0.5);
+        let total = padding + border;
+        assert_eq!(total, Insets::new(2.5, 1.5, 2.5, 1.5));
+        assert_eq!(total - border, padding);
+        assert_eq!(2.0 * border, Insets::uniform(1.0));
+        assert_eq!(total.size(), Size::new(5.0, 3.0));
+
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0).inset(total);
+        assert_eq!((rect.width(), rect.height()), (10.0 - 5.0, 10.0 - 3.0));
+
+        let other = Insets::new(3.0, 0.0, 1.0, 4.0);
+        assert_eq!(padding.max(other), Insets::new(3.0, 1.0, 2.0, 4.0));
+        assert_eq!(padding.min(other), Insets::new(2.0, 0.0, 1.0, 1.0));
+    }
+}
@@ -15,26 +15,131 @@
 //! A garden of data structures for manipulating 2D curves.
 
 mod affine;
+mod angle;
+mod arc;
+mod arclen_lut;
+mod arrangement;
+mod arrow;
 mod bezpath;
+mod biarc;
+mod bspline;
+mod cached_path;
+mod capsule;
+mod catmull_rom;
 mod circle;
+mod clip;
+mod collision;
 pub mod common;
+mod continuity;
 mod cubicbez;
+mod dash;
+mod degree;
+mod dxf;
+mod easing;
+mod ellipse;
+mod fairness;
+mod fillet;
+mod fit;
+mod gcode;
+#[cfg(feature = "geo")]
+mod geo;
+mod hobby;
+mod infinite_line;
+mod ink;
+mod insets;
 mod line;
+mod marker;
+mod min_dist;
+mod moments;
+mod morph;
+mod nurbs;
+mod packed_path;
 mod param_curve;
+mod path_distance;
+mod polar;
+mod polygon;
 mod quadbez;
+mod quadtree;
+mod rasterize;
 mod rect;
+mod region;
+mod rounded_rect;
+mod sdf;
+mod segment_index;
+mod segment_map;
 mod shape;
+mod simplify;
+mod size;
+mod smooth;
+mod snap;
+mod spiro;
+mod stroke;
 mod svg;
+mod sweep;
+mod transformed_shape;
+mod triangle;
+mod validate;
 mod vec2;
 
 pub use crate::affine::*;
+pub use crate::angle::*;
+pub use crate::arc::*;
+pub use crate::arclen_lut::*;
+pub use crate::arrangement::*;
+pub use crate::arrow::*;
 pub use crate::bezpath::*;
+pub use crate::biarc::*;
+pub use crate::bspline::*;
+pub use crate::cached_path::*;
+pub use crate::capsule::*;
+pub use crate::catmull_rom::*;
 pub use crate::circle::*;
+pub use crate::clip::*;
+pub use crate::collision::*;
+pub use crate::continuity::*;
 pub use crate::cubicbez::*;
+pub use crate::dash::*;
+pub use crate::degree::*;
+pub use crate::dxf::*;
+pub use crate::easing::*;
+pub use crate::ellipse::*;
+pub use crate::fairness::*;
+pub use crate::fit::*;
+pub use crate::gcode::*;
+#[cfg(feature = "geo")]
+pub use crate::geo::*;
+pub use crate::hobby::*;
+pub use crate::infinite_line::*;
+pub use crate::ink::*;
+pub use crate::insets::*;
 pub use crate::line::*;
+pub use crate::marker::*;
+pub use crate::min_dist::*;
+pub use crate::moments::*;
+pub use crate::morph::*;
+pub use crate::nurbs::*;
+pub use crate::packed_path::*;
 pub use crate::param_curve::*;
+pub use crate::path_distance::*;
+pub use crate::polar::*;
+pub use crate::polygon::*;
 pub use crate::quadbez::*;
+pub use crate::quadtree::*;
+pub use crate::rasterize::*;
 pub use crate::rect::*;
+pub use crate::region::*;
+pub use crate::rounded_rect::*;
+pub use crate::sdf::*;
+pub use crate::segment_index::*;
+pub use crate::segment_map::*;
 pub use crate::shape::*;
+pub use crate::simplify::*;
+pub use crate::size::*;
+pub use crate::spiro::*;
+pub use crate::stroke::*;
 pub use crate::svg::*;
+pub use crate::sweep::*;
+pub use crate::transformed_shape::*;
+pub use crate::triangle::*;
+pub use crate::validate::*;
 pub use crate::vec2::*;
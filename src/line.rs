@@ -4,10 +4,11 @@ use std::ops::{Mul, Range};
 
 use arrayvec::ArrayVec;
 
+use crate::shape::farthest;
 use crate::MAX_EXTREMA;
 use crate::{
-    Affine, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature, ParamCurveDeriv,
-    ParamCurveExtrema, ParamCurveNearest, PathEl, Rect, Shape, Vec2,
+    Affine, Circle, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature,
+    ParamCurveDeriv, ParamCurveExtrema, ParamCurveNearest, PathEl, Rect, Shape, Vec2,
 };
 
 /// A single line.
@@ -43,6 +44,14 @@ impl ParamCurve for Line {
         self.p1
     }
 
+    fn eval_many(&self, ts: &[f64], out: &mut [Vec2]) {
+        assert_eq!(ts.len(), out.len(), "ts and out must have equal length");
+        let d = self.p1 - self.p0;
+        for (p, &t) in out.iter_mut().zip(ts) {
+            *p = self.p0 + d * t;
+        }
+    }
+
     #[inline]
     fn subsegment(&self, range: Range<f64>) -> Line {
         Line {
@@ -187,6 +196,29 @@ impl Shape for Line {
         Rect::from_points(self.p0, self.p1)
     }
 
+    #[inline]
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        Rect::from_points(affine * self.p0, affine * self.p1)
+    }
+
+    /// The midpoint, which is the centroid of the line as a uniform rod.
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.p0.lerp(self.p1, 0.5)
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        Circle::new(
+            self.p0.lerp(self.p1, 0.5),
+            0.5 * (self.p1 - self.p0).hypot(),
+        )
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest([self.p0, self.p1].iter().cloned(), direction)
+    }
+
     #[inline]
     fn as_line(&self) -> Option<Line> {
         Some(*self)
@@ -0,0 +1,244 @@
+//! Placing markers along paths.
+
+use std::f64::consts::PI;
+
+use crate::{BezPath, CubicBez, Line, ParamCurve, PathEl, PathSeg, QuadBez, Vec2};
+
+/// Where a [`Marker`](struct.Marker.html) is placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkerKind {
+    /// The first vertex of the path.
+    Start,
+    /// A vertex other than the first and the last.
+    Mid,
+    /// The last vertex of the path.
+    End,
+    /// A point at a multiple of the interval along the path.
+    Interval,
+}
+
+/// A marker placed on a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Marker {
+    /// Where on the path the marker is.
+    pub kind: MarkerKind,
+    /// The position of the marker.
+    pub position: Vec2,
+    /// The direction of the path there, as an angle in radians, as for an
+    /// SVG marker with `orient="auto"`.
+    pub angle: f64,
+}
+
+/// Which markers to place along a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarkerSpec {
+    /// Whether to place a marker at the first vertex.
+    pub start: bool,
+    /// Whether to place markers at the vertices between the first and last.
+    pub mid: bool,
+    /// Whether to place a marker at the last vertex.
+    pub end: bool,
+    /// The arclength between markers placed at regular intervals, if any.
+    pub interval: Option<f64>,
+    /// The accuracy of arclengths, for markers at intervals.
+    pub accuracy: f64,
+}
+
+impl Default for MarkerSpec {
+    fn default() -> MarkerSpec {
+        MarkerSpec {
+            start: true,
+            mid: true,
+            end: true,
+            interval: None,
+            accuracy: 1e-6,
+        }
+    }
+}
+
+/// A vertex of a path, with the directions of the path into and out of it.
+struct Vertex {
+    point: Vec2,
+    dir_in: Option<Vec2>,
+    dir_out: Option<Vec2>,
+}
+
+/// The markers of a path.
+///
+/// As in SVG, the vertices are the endpoints of all segments, including
+/// the start of each subpath and the end of each closepath, so a closed
+/// subpath has a vertex at its start twice. The angle at a vertex bisects
+/// the directions of the segments on either side, and at the start or end
+/// of an open subpath is that of its only segment; the start of a closed
+/// subpath has the closing segment before it, and its end the first
+/// segment after it. Zero-length segments have no direction.
+///
+/// The markers at vertices come first, in order along the path, followed
+/// by any at intervals, which start at the start of the path and continue
+/// across subpaths, as for [`frames`](struct.BezPath.html#method.frames).
+///
+/// Panics if the interval is not positive.
+pub fn markers(path: &BezPath, spec: MarkerSpec) -> Vec<Marker> {
+    let vertices = vertices(path);
+    let n = vertices.len();
+    let mut result = Vec::new();
+    for (i, v) in vertices.iter().enumerate() {
+        let (kind, wanted) = if i == 0 {
+            (MarkerKind::Start, spec.start)
+        } else if i == n - 1 {
+            (MarkerKind::End, spec.end)
+        } else {
+            (MarkerKind::Mid, spec.mid)
+        };
+        if wanted {
+            result.push(Marker {
+                kind,
+                position: v.point,
+                angle: v.angle(),
+            });
+        }
+    }
+    if let Some(interval) = spec.interval {
+        for frame in path.frames(interval, spec.accuracy) {
+            result.push(Marker {
+                kind: MarkerKind::Interval,
+                position: frame.point,
+                angle: frame.tangent.atan2(),
+            });
+        }
+    }
+    result
+}
+
+impl Vertex {
+    fn angle(&self) -> f64 {
+        match (self.dir_in, self.dir_out) {
+            (Some(d_in), Some(d_out)) => {
+                let (a_in, a_out) = (d_in.atan2(), d_out.atan2());
+                let mut delta = (a_out - a_in).rem_euclid(2.0 * PI);
+                if delta > PI {
+                    delta -= 2.0 * PI;
+                }
+                a_in + 0.5 * delta
+            }
+            (Some(d), None) | (None, Some(d)) => d.atan2(),
+            (None, None) => 0.0,
+        }
+    }
+}
+
+/// The vertices of a path, in order.
+fn vertices(path: &BezPath) -> Vec<Vertex> {
+    let mut result: Vec<Vertex> = Vec::new();
+    // The index of the vertex at the start of the current subpath.
+    let mut start = 0;
+    let mut last = Vec2::default();
+    for &el in path.elements() {
+        let seg = match el {
+            PathEl::Moveto(p) => {
+                start = result.len();
+                result.push(Vertex {
+                    point: p,
+                    dir_in: None,
+                    dir_out: None,
+                });
+                last = p;
+                continue;
+            }
+            PathEl::Lineto(p) => PathSeg::Line(Line::new(last, p)),
+            PathEl::Quadto(p1, p2) => PathSeg::Quad(QuadBez::new(last, p1, p2)),
+            PathEl::Curveto(p1, p2, p3) => PathSeg::Cubic(CubicBez::new(last, p1, p2, p3)),
+            PathEl::Closepath if result.is_empty() => continue,
+            PathEl::Closepath => {
+                let first = result[start].point;
+                add_seg(&mut result, PathSeg::Line(Line::new(last, first)));
+                let end = result.len() - 1;
+                result[end].dir_out = result[start].dir_out;
+                result[start].dir_in = result[end].dir_in;
+                last = first;
+                continue;
+            }
+        };
+        last = seg.end();
+        add_seg(&mut result, seg);
+    }
+    result
+}
+
+/// Add the vertex at the end of a segment, and the direction out of the one
+/// at its start.
+fn add_seg(result: &mut Vec<Vertex>, seg: PathSeg) {
+    if result.is_empty() {
+        result.push(Vertex {
+            point: seg.start(),
+            dir_in: None,
+            dir_out: None,
+        });
+    }
+    let (t0, t1) = (seg.tangent(0.0), seg.tangent(1.0));
+    let prev = result.len() - 1;
+    let dir_in = if t0 == Vec2::default() {
+        result[prev].dir_in
+    } else {
+        if result[prev].dir_out.is_none() {
+            result[prev].dir_out = Some(t0);
+        }
+        Some(t1)
+    };
+    result.push(Vertex {
+        point: seg.end(),
+        dir_in,
+        dir_out: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    use crate::{markers, BezPath, MarkerKind, MarkerSpec};
+
+    #[test]
+    fn markers_at_vertices() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10").unwrap();
+        let marks = markers(&path, MarkerSpec::default());
+        assert_eq!(marks.len(), 3);
+        assert_eq!(marks[0].kind, MarkerKind::Start);
+        assert_eq!(marks[0].angle, 0.0);
+        assert_eq!(marks[1].kind, MarkerKind::Mid);
+        assert!((marks[1].angle - FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(marks[2].kind, MarkerKind::End);
+        assert!((marks[2].angle - FRAC_PI_2).abs() < 1e-12);
+
+        // A closed subpath has its start twice, oriented by the closing
+        // segment and the first.
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let marks = markers(&square, MarkerSpec::default());
+        assert_eq!(marks.len(), 5);
+        assert!((marks[0].angle + FRAC_PI_4).abs() < 1e-12);
+        assert!((marks[4].angle + FRAC_PI_4).abs() < 1e-12);
+        assert_eq!(marks[4].position, marks[0].position);
+
+        // Only the ends, and markers at intervals.
+        let spec = MarkerSpec {
+            mid: false,
+            interval: Some(5.0),
+            ..Default::default()
+        };
+        let marks = markers(&path, spec);
+        let kinds: Vec<_> = marks.iter().map(|m| m.kind).collect();
+        assert_eq!(&kinds[..2], &[MarkerKind::Start, MarkerKind::End]);
+        assert_eq!(marks.len(), 2 + 5);
+        assert!((marks[5].position.x - 10.0).abs() < 1e-9);
+        assert!((marks[5].position.y - 5.0).abs() < 1e-9);
+        assert!((marks[5].angle - FRAC_PI_2).abs() < 1e-9);
+
+        // Reversing direction turns the marker sideways, and a zero-length
+        // segment takes the direction before it.
+        let back = BezPath::from_svg("M0 0L10 0L10 0L0 0").unwrap();
+        let marks = markers(&back, MarkerSpec::default());
+        assert_eq!(marks[1].angle, 0.0);
+        assert!((marks[2].angle.abs() - FRAC_PI_2).abs() < 1e-12);
+        assert!((marks[3].angle.abs() - PI).abs() < 1e-12);
+    }
+}
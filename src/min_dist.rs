@@ -0,0 +1,201 @@
+//! Minimum distance between path segments.
+
+use crate::{Line, ParamCurve, ParamCurveNearest, PathSeg, Vec2};
+
+/// The minimum distance between two segments, and where it is attained.
+#[derive(Clone, Copy, Debug)]
+pub struct MinDistance {
+    /// The minimum distance.
+    pub distance: f64,
+    /// The parameter on the first segment of the closest point.
+    pub t1: f64,
+    /// The parameter on the second segment of the closest point.
+    pub t2: f64,
+}
+
+impl PathSeg {
+    /// The minimum distance to another segment.
+    ///
+    /// Two lines are handled in closed form. Other combinations use branch
+    /// and bound on the parameter ranges. For a pair of pieces, the distance
+    /// between their chords, less the deviation of each piece's control
+    /// points from its chord, is a lower bound; pairs that can't improve on
+    /// the best distance found so far by more than `accuracy` are discarded,
+    /// and the rest are subdivided until they are flat to within `accuracy`.
+    /// When the segments intersect, the distance is zero and the parameters
+    /// are those of an intersection point.
+    pub fn min_dist(&self, other: PathSeg, accuracy: f64) -> MinDistance {
+        if let (PathSeg::Line(l1), PathSeg::Line(l2)) = (*self, other) {
+            return line_line_dist(l1, l2);
+        }
+        // Start from the best of the endpoints projected onto the other segment.
+        let mut best = MinDistance {
+            distance: f64::INFINITY,
+            t1: 0.0,
+            t2: 0.0,
+        };
+        for &t1 in &[0.0, 1.0] {
+            let (t2, d2) = other.nearest(self.eval(t1), accuracy);
+            update(&mut best, t1, t2, d2.sqrt());
+        }
+        for &t2 in &[0.0, 1.0] {
+            let (t1, d2) = self.nearest(other.eval(t2), accuracy);
+            update(&mut best, t1, t2, d2.sqrt());
+        }
+        let mut stack = vec![(0.0..1.0, 0.0..1.0)];
+        while let Some((r1, r2)) = stack.pop() {
+            let s1 = self.subsegment(r1.clone());
+            let s2 = other.subsegment(r2.clone());
+            let (c1, c2) = (chord(&s1), chord(&s2));
+            let (dev1, dev2) = (deviation(&s1, c1), deviation(&s2, c2));
+            let chord_dist = line_line_dist(c1, c2);
+            if chord_dist.distance - dev1 - dev2 >= best.distance - accuracy {
+                continue;
+            }
+            let t1 = r1.start + chord_dist.t1 * (r1.end - r1.start);
+            let t2 = r2.start + chord_dist.t2 * (r2.end - r2.start);
+            update(&mut best, t1, t2, (self.eval(t1) - other.eval(t2)).hypot());
+            if dev1.max(dev2) <= 0.5 * accuracy {
+                continue;
+            }
+            if dev1 >= dev2 {
+                let m1 = 0.5 * (r1.start + r1.end);
+                stack.push((r1.start..m1, r2.clone()));
+                stack.push((m1..r1.end, r2));
+            } else {
+                let m2 = 0.5 * (r2.start + r2.end);
+                stack.push((r1.clone(), r2.start..m2));
+                stack.push((r1, m2..r2.end));
+            }
+        }
+        best
+    }
+}
+
+fn update(best: &mut MinDistance, t1: f64, t2: f64, distance: f64) {
+    if distance < best.distance {
+        *best = MinDistance { distance, t1, t2 };
+    }
+}
+
+fn chord(seg: &PathSeg) -> Line {
+    Line::new(seg.start(), seg.end())
+}
+
+/// An upper bound on the distance from a segment to its chord.
+///
+/// The segment lies in the convex hull of its control points, so it is no
+/// farther from the chord than the farthest control point.
+fn deviation(seg: &PathSeg, chord: Line) -> f64 {
+    let dist = |p: Vec2| chord.nearest(p, 0.0).1.sqrt();
+    match *seg {
+        PathSeg::Line(_) => 0.0,
+        PathSeg::Quad(q) => dist(q.p1),
+        PathSeg::Cubic(c) => dist(c.p1).max(dist(c.p2)),
+    }
+}
+
+fn line_line_dist(l1: Line, l2: Line) -> MinDistance {
+    let d1 = l1.p1 - l1.p0;
+    let d2 = l2.p1 - l2.p0;
+    let denom = d1.cross(d2);
+    if denom != 0.0 {
+        let v = l2.p0 - l1.p0;
+        let t1 = v.cross(d2) / denom;
+        let t2 = v.cross(d1) / denom;
+        if (0.0..=1.0).contains(&t1) && (0.0..=1.0).contains(&t2) {
+            return MinDistance {
+                distance: 0.0,
+                t1,
+                t2,
+            };
+        }
+    }
+    // Otherwise the minimum is attained at an endpoint of one of the lines.
+    let mut best = MinDistance {
+        distance: f64::INFINITY,
+        t1: 0.0,
+        t2: 0.0,
+    };
+    let project = |l: Line, p: Vec2| l.nearest(p, 0.0);
+    for &t1 in &[0.0, 1.0] {
+        let (t2, d2) = project(l2, l1.eval(t1));
+        update(&mut best, t1, t2, d2.sqrt());
+    }
+    for &t2 in &[0.0, 1.0] {
+        let (t1, d2) = project(l1, l2.eval(t2));
+        update(&mut best, t1, t2, d2.sqrt());
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Arc, BezPath, CubicBez, Line, ParamCurve, PathSeg, QuadBez, Vec2};
+
+    fn brute_force(a: PathSeg, b: PathSeg) -> f64 {
+        let n = 400;
+        let mut best = f64::INFINITY;
+        for i in 0..=n {
+            let p = a.eval(i as f64 / n as f64);
+            for j in 0..=n {
+                best = best.min((p - b.eval(j as f64 / n as f64)).hypot());
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn min_dist_all_kinds() {
+        let line = PathSeg::Line(Line::new((0.0, 0.0), (10.0, 1.0)));
+        let quad = PathSeg::Quad(QuadBez::new((0.0, 5.0), (5.0, 2.0), (10.0, 6.0)));
+        let cubic = PathSeg::Cubic(CubicBez::new(
+            (1.0, 9.0),
+            (3.0, 3.0),
+            (7.0, 12.0),
+            (9.0, 7.0),
+        ));
+        let segs = [line, quad, cubic];
+        for &a in &segs {
+            for &b in &segs {
+                let shifted = match b {
+                    PathSeg::Line(l) => PathSeg::Line(Line::new(l.p0, l.p1 + Vec2::new(0.0, 0.5))),
+                    _ => b,
+                };
+                let result = a.min_dist(shifted, 1e-9);
+                let p1 = a.eval(result.t1);
+                let p2 = shifted.eval(result.t2);
+                assert!(((p1 - p2).hypot() - result.distance).abs() < 1e-9);
+                assert!(result.distance <= brute_force(a, shifted) + 1e-9);
+            }
+        }
+        let result = line.min_dist(quad, 1e-9);
+        assert!(result.distance > 1.0);
+    }
+
+    #[test]
+    fn min_dist_concentric() {
+        let arc = |r: f64| {
+            let mut path = BezPath::new();
+            path.moveto((r, 0.0));
+            Arc {
+                center: Vec2::new(0.0, 0.0),
+                radii: Vec2::new(r, r),
+                start_angle: 0.0,
+                sweep_angle: 1.5,
+                x_rotation: 0.0,
+            }
+            .append_to_path(&mut path, 1e-9);
+            let seg = path.segments().next().unwrap();
+            seg
+        };
+        let result = arc(2.0).min_dist(arc(3.0), 1e-6);
+        assert!((result.distance - 1.0).abs() < 1e-4);
+
+        let l1 = PathSeg::Line(Line::new((0.0, 0.0), (2.0, 2.0)));
+        let l2 = PathSeg::Line(Line::new((0.0, 2.0), (2.0, 0.0)));
+        let result = l1.min_dist(l2, 1e-9);
+        assert_eq!(result.distance, 0.0);
+        assert_eq!((result.t1, result.t2), (0.5, 0.5));
+    }
+}
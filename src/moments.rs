@@ -0,0 +1,231 @@
+//! Area moments of paths.
+
+use std::ops::Add;
+
+use crate::{BezPath, CubicBez, Line, ParamCurveMoments, PathSeg, QuadBez, Shape, Vec2};
+
+/// The area moments of a region, up to second order.
+///
+/// Each field is an integral over the region, with the same sign convention
+/// as [`Shape::area`](trait.Shape.html#tymethod.area): regions bounded in the
+/// direction that rotates positive X towards positive Y count positively.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Moments {
+    /// The signed area, `∫ dA`.
+    pub area: f64,
+    /// The first moments, `∫ x dA` and `∫ y dA`.
+    pub first: Vec2,
+    /// The second moment `∫ x² dA`.
+    pub xx: f64,
+    /// The second moment `∫ y² dA`.
+    pub yy: f64,
+    /// The product of inertia `∫ x y dA`.
+    pub xy: f64,
+}
+
+impl Moments {
+    /// The centroid, or `None` if the area is zero.
+    pub fn centroid(&self) -> Option<Vec2> {
+        if self.area == 0.0 {
+            None
+        } else {
+            Some(self.first / self.area)
+        }
+    }
+
+    /// The second moments about the centroid, as `(xx, yy, xy)`.
+    ///
+    /// These are the entries of the inertia tensor of a uniform lamina, and
+    /// are unchanged by translating the region. Returns `None` if the area is
+    /// zero.
+    pub fn central(&self) -> Option<(f64, f64, f64)> {
+        let c = self.centroid()?;
+        Some((
+            self.xx - self.area * c.x * c.x,
+            self.yy - self.area * c.y * c.y,
+            self.xy - self.area * c.x * c.y,
+        ))
+    }
+}
+
+impl Add for Moments {
+    type Output = Moments;
+
+    fn add(self, other: Moments) -> Moments {
+        Moments {
+            area: self.area + other.area,
+            first: self.first + other.first,
+            xx: self.xx + other.xx,
+            yy: self.yy + other.yy,
+            xy: self.xy + other.xy,
+        }
+    }
+}
+
+impl ParamCurveMoments for CubicBez {
+    /// The contribution of the cubic to the area moments of a closed path.
+    ///
+    /// For a Bézier segment, each moment is the integral of a polynomial,
+    /// computed here exactly.
+    fn moments(&self) -> Moments {
+        let c = self;
+        let x = [
+            c.p0.x,
+            3.0 * (c.p1.x - c.p0.x),
+            3.0 * (c.p2.x - 2.0 * c.p1.x + c.p0.x),
+            c.p3.x - 3.0 * c.p2.x + 3.0 * c.p1.x - c.p0.x,
+        ];
+        let y = [
+            c.p0.y,
+            3.0 * (c.p1.y - c.p0.y),
+            3.0 * (c.p2.y - 2.0 * c.p1.y + c.p0.y),
+            c.p3.y - 3.0 * c.p2.y + 3.0 * c.p1.y - c.p0.y,
+        ];
+        let dx = [x[1], 2.0 * x[2], 3.0 * x[3]];
+        let dy = [y[1], 2.0 * y[2], 3.0 * y[3]];
+        let x2 = poly_mul(&x, &x);
+        let y2 = poly_mul(&y, &y);
+        // Each moment uses a symmetric form of Green's theorem, which keeps
+        // the results exact under the reflection x ↔ y.
+        let area = 0.5 * (integrate(&poly_mul(&x, &dy)) - integrate(&poly_mul(&y, &dx)));
+        let first = Vec2::new(
+            0.5 * integrate(&poly_mul(&x2, &dy)),
+            -0.5 * integrate(&poly_mul(&y2, &dx)),
+        );
+        let xx = integrate(&poly_mul(&poly_mul(&x2, &x), &dy)) / 3.0;
+        let yy = -integrate(&poly_mul(&poly_mul(&y2, &y), &dx)) / 3.0;
+        let xy = 0.25
+            * (integrate(&poly_mul(&poly_mul(&x2, &y), &dy))
+                - integrate(&poly_mul(&poly_mul(&y2, &x), &dx)));
+        Moments {
+            area,
+            first,
+            xx,
+            yy,
+            xy,
+        }
+    }
+}
+
+impl ParamCurveMoments for QuadBez {
+    fn moments(&self) -> Moments {
+        self.raise().moments()
+    }
+}
+
+impl ParamCurveMoments for Line {
+    fn moments(&self) -> Moments {
+        PathSeg::Line(*self).to_cubic().moments()
+    }
+}
+
+impl ParamCurveMoments for PathSeg {
+    fn moments(&self) -> Moments {
+        match *self {
+            PathSeg::Line(line) => line.moments(),
+            PathSeg::Quad(quad) => quad.moments(),
+            PathSeg::Cubic(cubic) => cubic.moments(),
+        }
+    }
+}
+
+fn poly_mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        for (j, bj) in b.iter().enumerate() {
+            result[i + j] += ai * bj;
+        }
+    }
+    result
+}
+
+/// The integral of a polynomial over `[0, 1]`.
+fn integrate(coeffs: &[f64]) -> f64 {
+    coeffs
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c / (i + 1) as f64)
+        .sum()
+}
+
+impl BezPath {
+    /// The area moments of the region enclosed by the path.
+    ///
+    /// These are computed exactly (up to roundoff) from the Bézier segments,
+    /// with no flattening. Open subpaths are treated as if closed by a line
+    /// back to their start, and the moments of multiple subpaths add, so a
+    /// hole drawn in the opposite direction is subtracted.
+    pub fn moments(&self) -> Moments {
+        let mut result = Moments::default();
+        for sub in self.subpath_segs() {
+            for &(_, seg) in &sub.segs {
+                result = result + seg.moments();
+            }
+            if !sub.closed {
+                let (_, first) = sub.segs[0];
+                let (_, last) = sub.segs[sub.segs.len() - 1];
+                let (start, end) = (first.to_cubic().p0, last.to_cubic().p3);
+                if start != end {
+                    result = result + PathSeg::Line(Line::new(end, start)).moments();
+                }
+            }
+        }
+        result
+    }
+
+    /// The centroid (center of mass) of the region enclosed by the path.
+    ///
+    /// If the enclosed area is zero, this is the center of the bounding box.
+    pub fn centroid(&self) -> Vec2 {
+        self.moments()
+            .centroid()
+            .unwrap_or_else(|| self.bounding_box().center())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, Shape};
+
+    fn assert_near(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() < epsilon, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn rect_moments() {
+        let path = BezPath::from_svg("M1 2L4 2L4 4L1 4Z").unwrap();
+        let m = path.moments();
+        assert_near(m.area, 6.0, 1e-12);
+        let c = path.centroid();
+        assert_near(c.x, 2.5, 1e-12);
+        assert_near(c.y, 3.0, 1e-12);
+        // ∫x² over [1, 4] × [2, 4] is (64 - 1) / 3 * 2.
+        assert_near(m.xx, 42.0, 1e-12);
+        assert_near(m.yy, 56.0, 1e-12);
+        assert_near(m.xy, 7.5 * 6.0, 1e-12);
+        let (cxx, cyy, cxy) = m.central().unwrap();
+        assert_near(cxx, 3.0 * 3.0 * 3.0 * 2.0 / 12.0, 1e-12);
+        assert_near(cyy, 3.0 * 2.0 * 2.0 * 2.0 / 12.0, 1e-12);
+        assert_near(cxy, 0.0, 1e-12);
+
+        // Open subpaths are implicitly closed.
+        let open = BezPath::from_svg("M1 2L4 2L4 4L1 4").unwrap();
+        assert_near(open.moments().xx, m.xx, 1e-12);
+    }
+
+    #[test]
+    fn curve_moments() {
+        // A Bézier circle is slightly off, so compare loosely.
+        let circle = Circle::new((3.0, -2.0), 2.0).into_bez_path(1e-9);
+        let m = circle.moments();
+        assert_near(m.area, circle.area(), 1e-12);
+        let c = circle.centroid();
+        assert_near(c.x, 3.0, 1e-12);
+        assert_near(c.y, -2.0, 1e-12);
+        let (cxx, cyy, cxy) = m.central().unwrap();
+        let expected = std::f64::consts::PI * 16.0 / 4.0;
+        assert_near(cxx, expected, 1e-6);
+        assert_near(cyy, expected, 1e-6);
+        assert_near(cxy, 0.0, 1e-9);
+    }
+}
@@ -0,0 +1,228 @@
+//! Shape morphing between Bézier paths.
+
+use crate::{BezPath, CubicBez, ParamCurve, ParamCurveArea, PathEl, QuadBez, Rect, Vec2};
+
+/// A pair of paths with identical element structure, suitable for tweening.
+///
+/// Created by [`morph`](fn.morph.html).
+#[derive(Clone, Debug)]
+pub struct MorphPair {
+    /// The normalized start path, geometrically equal to the first input.
+    pub start: BezPath,
+    /// The normalized end path, geometrically equal to the second input.
+    pub end: BezPath,
+}
+
+/// A subpath normalized to cubic segments.
+struct Subpath {
+    segs: Vec<CubicBez>,
+    closed: bool,
+}
+
+/// Prepare two paths for shape morphing.
+///
+/// The resulting paths have the same number of subpaths, and each pair of
+/// corresponding subpaths has the same number of segments, all of them cubic.
+/// To achieve this, lines and quadratics are raised to cubics, the longest
+/// segments are split in half until the counts agree, and missing subpaths
+/// are added as degenerate subpaths collapsed to the center of their
+/// counterpart, so they grow out of a point during the animation.
+///
+/// Subpaths are paired in order. For pairs of closed subpaths, the start
+/// point and direction of the second are chosen to minimize the distance the
+/// points travel. If only one subpath of a pair is closed, the other is closed
+/// as well, which adds a closing line segment to it.
+pub fn morph(a: &BezPath, b: &BezPath) -> MorphPair {
+    let mut subs_a = subpaths(a);
+    let mut subs_b = subpaths(b);
+    while subs_a.len() < subs_b.len() {
+        subs_a.push(degenerate(&subs_b[subs_a.len()]));
+    }
+    while subs_b.len() < subs_a.len() {
+        subs_b.push(degenerate(&subs_a[subs_b.len()]));
+    }
+    let mut start = BezPath::new();
+    let mut end = BezPath::new();
+    for (mut sa, mut sb) in subs_a.into_iter().zip(subs_b) {
+        if sa.closed != sb.closed {
+            close(&mut sa);
+            close(&mut sb);
+        }
+        let n = sa.segs.len().max(sb.segs.len());
+        split_to(&mut sa.segs, n);
+        split_to(&mut sb.segs, n);
+        if sa.closed {
+            align(&sa.segs, &mut sb.segs);
+        }
+        append_subpath(&mut start, &sa);
+        append_subpath(&mut end, &sb);
+    }
+    MorphPair { start, end }
+}
+
+impl MorphPair {
+    /// Interpolate between the two paths.
+    ///
+    /// At `t = 0` the result is the start path, and at `t = 1` the end path.
+    pub fn eval(&self, t: f64) -> BezPath {
+        self.start.try_lerp(&self.end, t).unwrap()
+    }
+}
+
+fn line_cubic(p0: Vec2, p1: Vec2) -> CubicBez {
+    CubicBez::new(p0, p0.lerp(p1, 1.0 / 3.0), p0.lerp(p1, 2.0 / 3.0), p1)
+}
+
+fn subpaths(path: &BezPath) -> Vec<Subpath> {
+    let mut result: Vec<Subpath> = Vec::new();
+    let mut start = Vec2::default();
+    let mut last = Vec2::default();
+    let mut current = Vec::new();
+    for el in path.elements() {
+        let seg = match *el {
+            PathEl::Moveto(p) => {
+                if !current.is_empty() {
+                    result.push(Subpath {
+                        segs: std::mem::take(&mut current),
+                        closed: false,
+                    });
+                }
+                start = p;
+                last = p;
+                continue;
+            }
+            PathEl::Lineto(p) => line_cubic(last, p),
+            PathEl::Quadto(p1, p2) => QuadBez::new(last, p1, p2).raise(),
+            PathEl::Curveto(p1, p2, p3) => CubicBez::new(last, p1, p2, p3),
+            PathEl::Closepath => {
+                if last != start {
+                    current.push(line_cubic(last, start));
+                }
+                if !current.is_empty() {
+                    result.push(Subpath {
+                        segs: std::mem::take(&mut current),
+                        closed: true,
+                    });
+                }
+                last = start;
+                continue;
+            }
+        };
+        last = seg.p3;
+        current.push(seg);
+    }
+    if !current.is_empty() {
+        result.push(Subpath {
+            segs: current,
+            closed: false,
+        });
+    }
+    result
+}
+
+/// A subpath with the same structure as `other`, collapsed to its center.
+fn degenerate(other: &Subpath) -> Subpath {
+    let mut bbox = Rect::from_points(other.segs[0].p0, other.segs[0].p0);
+    for seg in &other.segs {
+        bbox = bbox.union_pt(seg.p1).union_pt(seg.p2).union_pt(seg.p3);
+    }
+    let c = bbox.center();
+    Subpath {
+        segs: vec![CubicBez::new(c, c, c, c); other.segs.len()],
+        closed: other.closed,
+    }
+}
+
+fn close(sub: &mut Subpath) {
+    let start = sub.segs[0].p0;
+    let end = sub.segs[sub.segs.len() - 1].p3;
+    if !sub.closed && start != end {
+        sub.segs.push(line_cubic(end, start));
+    }
+    sub.closed = true;
+}
+
+/// Split the longest segments in half until there are `n` of them.
+fn split_to(segs: &mut Vec<CubicBez>, n: usize) {
+    fn length(c: &CubicBez) -> f64 {
+        (c.p1 - c.p0).hypot() + (c.p2 - c.p1).hypot() + (c.p3 - c.p2).hypot()
+    }
+    while segs.len() < n {
+        let mut ix = 0;
+        for i in 1..segs.len() {
+            if length(&segs[i]) > length(&segs[ix]) {
+                ix = i;
+            }
+        }
+        let (c0, c1) = segs[ix].subdivide();
+        segs[ix] = c0;
+        segs.insert(ix + 1, c1);
+    }
+}
+
+/// Choose the start point and direction of `b` to best match `a`.
+fn align(a: &[CubicBez], b: &mut [CubicBez]) {
+    let area = |segs: &[CubicBez]| segs.iter().map(|c| c.signed_area()).sum::<f64>();
+    if area(a) * area(b) < 0.0 {
+        b.reverse();
+        for c in b.iter_mut() {
+            *c = CubicBez::new(c.p3, c.p2, c.p1, c.p0);
+        }
+    }
+    let n = b.len();
+    let cost = |k: usize| -> f64 { (0..n).map(|i| (a[i].p0 - b[(i + k) % n].p0).hypot2()).sum() };
+    let best = (0..n)
+        .min_by(|&i, &j| cost(i).total_cmp(&cost(j)))
+        .unwrap_or(0);
+    b.rotate_left(best);
+}
+
+fn append_subpath(path: &mut BezPath, sub: &Subpath) {
+    path.moveto(sub.segs[0].start());
+    for c in &sub.segs {
+        path.curveto(c.p1, c.p2, c.p3);
+    }
+    if sub.closed {
+        path.closepath();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{morph, BezPath, Circle, PathEl, Shape, Vec2};
+
+    #[test]
+    fn morph_square_triangle() {
+        let square = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        let triangle = BezPath::from_svg("M20 0L30 10L20 10Z").unwrap();
+        let pair = morph(&square, &triangle);
+        assert_eq!(pair.start.elements().len(), pair.end.elements().len());
+        assert!((pair.eval(0.0).area() - 100.0).abs() < 1e-9);
+        assert!((pair.eval(1.0).area() - 50.0).abs() < 1e-9);
+        let mid = pair.eval(0.5);
+        assert_eq!(mid.elements().len(), pair.start.elements().len());
+        assert!(mid.area() > 0.0);
+    }
+
+    #[test]
+    fn morph_subpath_counts() {
+        let one = Circle::new((0.0, 0.0), 5.0).into_bez_path(1e-3);
+        let two = BezPath::from_svg("M0 0L1 0L1 1Z M5 5Q6 6 7 5").unwrap();
+        let pair = morph(&one, &two);
+        assert_eq!(pair.start.elements().len(), pair.end.elements().len());
+        assert!((pair.start.area() - one.area()).abs() < 1e-9);
+        let end_bbox = pair.end.bounding_box();
+        assert!((end_bbox.x1 - 7.0).abs() < 1e-9);
+        assert!((pair.end.perimeter(1e-9) - two.perimeter(1e-9)).abs() < 1e-6);
+
+        // Bad coordinates don't panic when aligning start points.
+        let nan = BezPath::from_vec(vec![
+            PathEl::Moveto(Vec2::new(f64::NAN, 0.0)),
+            PathEl::Lineto(Vec2::new(1.0, 0.0)),
+            PathEl::Lineto(Vec2::new(1.0, 1.0)),
+            PathEl::Closepath,
+        ]);
+        let pair = morph(&one, &nan);
+        assert_eq!(pair.start.elements().len(), pair.end.elements().len());
+    }
+}
@@ -0,0 +1,198 @@
+//! Non-uniform rational B-spline (NURBS) curves.
+
+use crate::{BSpline, BezPath, CubicBez, ParamCurve, Vec2};
+
+/// The maximum depth of subdivision when converting to Béziers.
+const MAX_DEPTH: usize = 16;
+
+/// A non-uniform rational B-spline curve.
+///
+/// This is a B-spline in homogeneous coordinates, which can represent conic
+/// sections such as circles exactly.
+#[derive(Clone, Debug)]
+pub struct Nurbs {
+    /// The B-spline of the weighted control points.
+    numer: BSpline,
+    /// The B-spline of the weights, stored in the X coordinate.
+    denom: BSpline,
+}
+
+impl Nurbs {
+    /// Create a new NURBS curve.
+    ///
+    /// Panics if the weights are not positive or don't correspond to the
+    /// control points, or under the same conditions as [`BSpline::new`].
+    ///
+    /// [`BSpline::new`]: struct.BSpline.html#method.new
+    pub fn new(control_points: &[Vec2], weights: &[f64], degree: usize, knots: Vec<f64>) -> Nurbs {
+        assert_eq!(
+            control_points.len(),
+            weights.len(),
+            "wrong number of weights"
+        );
+        assert!(weights.iter().all(|&w| w > 0.0), "weights must be positive");
+        let numer = control_points
+            .iter()
+            .zip(weights)
+            .map(|(&p, &w)| w * p)
+            .collect();
+        let denom = weights.iter().map(|&w| Vec2::new(w, 0.0)).collect();
+        Nurbs {
+            numer: BSpline::new(numer, degree, knots.clone()),
+            denom: BSpline::new(denom, degree, knots),
+        }
+    }
+
+    /// The parameter range over which the curve is defined.
+    pub fn domain(&self) -> (f64, f64) {
+        self.numer.domain()
+    }
+
+    /// Evaluate the curve at parameter `t`, which is clamped to the domain.
+    pub fn eval(&self, t: f64) -> Vec2 {
+        self.numer.eval(t) / self.denom.eval(t).x
+    }
+
+    /// The first derivative of the curve at parameter `t`.
+    pub fn deriv(&self, t: f64) -> Vec2 {
+        let (t0, t1) = self.domain();
+        let t = t.clamp(t0, t1);
+        self.deriv_in_span(self.numer.span(t), t)
+    }
+
+    /// The first derivative of the piece of the curve over knot span `k`.
+    fn deriv_in_span(&self, k: usize, t: f64) -> Vec2 {
+        if self.numer.degree == 0 {
+            return Vec2::default();
+        }
+        // Quotient rule: (A / w)' = (A' - w' (A / w)) / w. The knots of the
+        // derivatives are shifted by one, so span `k` becomes `k - 1`.
+        let a = self.numer.eval_in_span(k, t);
+        let w = self.denom.eval_in_span(k, t).x;
+        let da = self.numer.deriv().eval_in_span(k - 1, t);
+        let dw = self.denom.deriv().eval_in_span(k - 1, t).x;
+        (da - dw * (a / w)) / w
+    }
+
+    /// Approximate the curve with cubic Béziers.
+    ///
+    /// Each knot span is approximated by the cubic matching the positions and
+    /// derivatives at its ends, and subdivided until the cubic is within
+    /// `tolerance` of the curve at corresponding parameters.
+    pub fn to_path(&self, tolerance: f64) -> BezPath {
+        let mut path = BezPath::new();
+        let (t0, t1) = self.domain();
+        path.moveto(self.eval(t0));
+        let knots = &self.numer.knots;
+        for k in self.numer.degree..self.numer.control_points.len() {
+            let (a, b) = (knots[k].max(t0), knots[k + 1].min(t1));
+            if a < b {
+                self.approx_rec(k, a, b, tolerance, 0, &mut path);
+            }
+        }
+        path
+    }
+
+    /// Approximate the part of the piece over knot span `k` from `a` to `b`.
+    fn approx_rec(
+        &self,
+        k: usize,
+        a: f64,
+        b: f64,
+        tolerance: f64,
+        depth: usize,
+        path: &mut BezPath,
+    ) {
+        let (p0, p3) = (self.eval(a), self.eval(b));
+        let scale = (b - a) / 3.0;
+        // Take derivatives from this piece, even at knots where the curve has a corner.
+        let c = CubicBez::new(
+            p0,
+            p0 + scale * self.deriv_in_span(k, a),
+            p3 - scale * self.deriv_in_span(k, b),
+            p3,
+        );
+        let err = (1..8)
+            .map(|i| {
+                let s = i as f64 / 8.0;
+                (c.eval(s) - self.eval(a + s * (b - a))).hypot2()
+            })
+            .fold(0.0, f64::max);
+        if err <= tolerance * tolerance || depth >= MAX_DEPTH {
+            path.curveto(c.p1, c.p2, c.p3);
+        } else {
+            let m = 0.5 * (a + b);
+            self.approx_rec(k, a, m, tolerance, depth + 1, path);
+            self.approx_rec(k, m, b, tolerance, depth + 1, path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use crate::{Nurbs, ParamCurve, Vec2};
+
+    #[test]
+    fn nurbs_circle() {
+        // A full circle as nine control points with rational weights.
+        let w = FRAC_1_SQRT_2;
+        let pts = [
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(0.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 0.0),
+        ];
+        let weights = [1.0, w, 1.0, w, 1.0, w, 1.0, w, 1.0];
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0];
+        let circle = Nurbs::new(&pts, &weights, 2, knots);
+        for i in 0..=40 {
+            let t = i as f64 / 10.0;
+            let p = circle.eval(t);
+            assert!((p.hypot() - 1.0).abs() < 1e-12);
+            // The derivative is tangent to the circle.
+            assert!(p.dot(circle.deriv(t)).abs() < 1e-9);
+        }
+        let h = 1e-6;
+        let numeric = (circle.eval(0.5 + h) - circle.eval(0.5 - h)) / (2.0 * h);
+        assert!((circle.deriv(0.5) - numeric).hypot() < 1e-6);
+
+        for &tolerance in &[1e-2, 1e-4, 1e-6] {
+            let path = circle.to_path(tolerance);
+            for seg in path.segments() {
+                for j in 0..=10 {
+                    let p = seg.eval(j as f64 / 10.0);
+                    assert!((p.hypot() - 1.0).abs() <= tolerance);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nurbs_corner_at_large_knot() {
+        // Two parabolic pieces meeting at a corner, far from the origin in
+        // parameter space, where nudging off the knot can't change the span.
+        let pts = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(4.0, 0.0),
+        ];
+        let t0 = 1e6;
+        let knots = vec![t0, t0, t0, t0 + 1.0, t0 + 1.0, t0 + 2.0, t0 + 2.0, t0 + 2.0];
+        let curve = Nurbs::new(&pts, &[1.0; 5], 2, knots);
+        // Each piece is exactly a cubic.
+        let path = curve.to_path(1e-9);
+        let segs: Vec<_> = path.segments().collect();
+        assert_eq!(segs.len(), 2);
+        assert!((segs[0].eval(0.5) - curve.eval(t0 + 0.5)).hypot() < 1e-9);
+        assert!((segs[1].eval(0.5) - curve.eval(t0 + 1.5)).hypot() < 1e-9);
+    }
+}
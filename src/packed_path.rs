@@ -0,0 +1,253 @@
+//! A path stored as separate arrays of verbs and points.
+
+use crate::{Affine, BezPath, PathEl, Rect, Shape, Vec2};
+
+/// The kind of a path element in a [`PackedPath`](struct.PackedPath.html).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verb {
+    /// Start a new subpath, using one point.
+    Moveto = 0,
+    /// A line, using one point.
+    Lineto = 1,
+    /// A quadratic Bézier, using two points.
+    Quadto = 2,
+    /// A cubic Bézier, using three points.
+    Curveto = 3,
+    /// Close the subpath, using no points.
+    Closepath = 4,
+}
+
+impl Verb {
+    /// The number of points the verb consumes.
+    #[inline]
+    pub fn num_points(self) -> usize {
+        match self {
+            Verb::Moveto | Verb::Lineto => 1,
+            Verb::Quadto => 2,
+            Verb::Curveto => 3,
+            Verb::Closepath => 0,
+        }
+    }
+
+    /// The verb with the given numeric value, if any.
+    #[inline]
+    pub fn from_u8(value: u8) -> Option<Verb> {
+        match value {
+            0 => Some(Verb::Moveto),
+            1 => Some(Verb::Lineto),
+            2 => Some(Verb::Quadto),
+            3 => Some(Verb::Curveto),
+            4 => Some(Verb::Closepath),
+            _ => None,
+        }
+    }
+}
+
+/// A path stored as a structure of arrays.
+///
+/// Where [`BezPath`](struct.BezPath.html) stores a sequence of
+/// [`PathEl`](enum.PathEl.html) enums, each as large as the largest
+/// variant, this stores one byte-sized [`Verb`](enum.Verb.html) per element
+/// and the points of all elements in a separate contiguous array, as Skia
+/// does. This is more compact and cache-friendly, and both arrays can be
+/// copied wholesale for serialization.
+#[derive(Clone, Default, Debug)]
+pub struct PackedPath {
+    verbs: Vec<Verb>,
+    points: Vec<Vec2>,
+}
+
+impl PackedPath {
+    /// Create a new, empty path.
+    pub fn new() -> PackedPath {
+        Default::default()
+    }
+
+    /// Create a path from arrays of verbs and points.
+    ///
+    /// Returns `None` if the number of points doesn't match the verbs.
+    pub fn from_raw_parts(verbs: Vec<Verb>, points: Vec<Vec2>) -> Option<PackedPath> {
+        let n: usize = verbs.iter().map(|v| v.num_points()).sum();
+        if n == points.len() {
+            Some(PackedPath { verbs, points })
+        } else {
+            None
+        }
+    }
+
+    /// The verbs of the path, one per element.
+    #[inline]
+    pub fn verbs(&self) -> &[Verb] {
+        &self.verbs
+    }
+
+    /// The points of the path, in order.
+    #[inline]
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Push a path element onto the path.
+    pub fn push(&mut self, el: PathEl) {
+        match el {
+            PathEl::Moveto(p) => {
+                self.verbs.push(Verb::Moveto);
+                self.points.push(p);
+            }
+            PathEl::Lineto(p) => {
+                self.verbs.push(Verb::Lineto);
+                self.points.push(p);
+            }
+            PathEl::Quadto(p1, p2) => {
+                self.verbs.push(Verb::Quadto);
+                self.points.extend(&[p1, p2]);
+            }
+            PathEl::Curveto(p1, p2, p3) => {
+                self.verbs.push(Verb::Curveto);
+                self.points.extend(&[p1, p2, p3]);
+            }
+            PathEl::Closepath => self.verbs.push(Verb::Closepath),
+        }
+    }
+
+    /// Iterate over the path elements.
+    pub fn iter(&self) -> PackedPathIter<'_> {
+        PackedPathIter {
+            verbs: self.verbs.iter(),
+            points: &self.points,
+        }
+    }
+
+    /// Convert to a `BezPath`.
+    pub fn to_bez_path_exact(&self) -> BezPath {
+        BezPath::from_vec(self.iter().collect())
+    }
+
+    /// Apply an affine transform to the path.
+    pub fn apply_affine(&mut self, affine: Affine) {
+        for p in &mut self.points {
+            *p = affine * *p;
+        }
+    }
+}
+
+/// An iterator over the elements of a [`PackedPath`](struct.PackedPath.html).
+pub struct PackedPathIter<'a> {
+    verbs: std::slice::Iter<'a, Verb>,
+    points: &'a [Vec2],
+}
+
+impl<'a> Iterator for PackedPathIter<'a> {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        let verb = *self.verbs.next()?;
+        let (pts, rest) = self.points.split_at(verb.num_points());
+        self.points = rest;
+        Some(match verb {
+            Verb::Moveto => PathEl::Moveto(pts[0]),
+            Verb::Lineto => PathEl::Lineto(pts[0]),
+            Verb::Quadto => PathEl::Quadto(pts[0], pts[1]),
+            Verb::Curveto => PathEl::Curveto(pts[0], pts[1], pts[2]),
+            Verb::Closepath => PathEl::Closepath,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.verbs.size_hint()
+    }
+}
+
+impl<'a> IntoIterator for &'a PackedPath {
+    type Item = PathEl;
+    type IntoIter = PackedPathIter<'a>;
+
+    fn into_iter(self) -> PackedPathIter<'a> {
+        self.iter()
+    }
+}
+
+impl From<&BezPath> for PackedPath {
+    fn from(path: &BezPath) -> PackedPath {
+        let mut packed = PackedPath::new();
+        for &el in path.elements() {
+            packed.push(el);
+        }
+        packed
+    }
+}
+
+impl From<&PackedPath> for BezPath {
+    fn from(path: &PackedPath) -> BezPath {
+        path.to_bez_path_exact()
+    }
+}
+
+impl Shape for PackedPath {
+    type BezPathIter = std::vec::IntoIter<PathEl>;
+
+    fn to_bez_path(&self, _tolerance: f64) -> Self::BezPathIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+
+    fn area(&self) -> f64 {
+        BezPath::segments_of_iter(self.iter()).area()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        BezPath::segments_of_iter(self.iter()).arclen(accuracy)
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        BezPath::segments_of_iter(self.iter()).winding(pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        BezPath::segments_of_iter(self.iter()).bounding_box()
+    }
+
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        BezPath::segments_of_iter(self.iter().map(|el| affine * el)).bounding_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, PackedPath, Shape, Vec2, Verb};
+
+    #[test]
+    fn packed_round_trip() {
+        let path = BezPath::from_svg("M0 0L10 0C10 5 5 10 0 10ZM20 0L30 5").unwrap();
+        let packed = PackedPath::from(&path);
+        assert_eq!(
+            packed.verbs(),
+            &[
+                Verb::Moveto,
+                Verb::Lineto,
+                Verb::Curveto,
+                Verb::Closepath,
+                Verb::Moveto,
+                Verb::Lineto
+            ]
+        );
+        assert_eq!(packed.points().len(), 7);
+        assert_eq!(BezPath::from(&packed).to_svg(), path.to_svg());
+        assert_eq!(packed.area(), path.area());
+        assert_eq!(packed.perimeter(1e-9), path.perimeter(1e-9));
+        let p = Vec2::new(3.0, 3.0);
+        assert_eq!(packed.winding(p), path.winding(p));
+        let (bb0, bb1) = (packed.bounding_box(), path.bounding_box());
+        assert_eq!(
+            (bb0.x0, bb0.y0, bb0.x1, bb0.y1),
+            (bb1.x0, bb1.y0, bb1.x1, bb1.y1)
+        );
+
+        // Raw parts, as for serialization.
+        let bytes: Vec<u8> = packed.verbs().iter().map(|&v| v as u8).collect();
+        let verbs: Vec<Verb> = bytes.iter().map(|&b| Verb::from_u8(b).unwrap()).collect();
+        let copy = PackedPath::from_raw_parts(verbs.clone(), packed.points().to_vec()).unwrap();
+        assert_eq!(BezPath::from(&copy).to_svg(), path.to_svg());
+        assert!(PackedPath::from_raw_parts(verbs, vec![]).is_none());
+    }
+}
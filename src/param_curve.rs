@@ -4,7 +4,7 @@ use std::ops::Range;
 
 use arrayvec::ArrayVec;
 
-use crate::{Rect, Vec2};
+use crate::{Moments, Rect, Vec2};
 
 /// A curve parametrized by a scalar.
 ///
@@ -34,6 +34,22 @@ pub trait ParamCurve: Sized {
     fn end(&self) -> Vec2 {
         self.eval(1.0)
     }
+
+    /// Evaluate the curve at many parameters.
+    ///
+    /// The point at `ts[i]` is written to `out[i]`. Lines and Béziers
+    /// implement this by converting to polynomial form once and evaluating
+    /// with Horner's rule, which is cheaper per point than
+    /// [`eval`](#tymethod.eval) and free of branches, so suited to
+    /// sampling-heavy code.
+    ///
+    /// Panics if `ts` and `out` differ in length.
+    fn eval_many(&self, ts: &[f64], out: &mut [Vec2]) {
+        assert_eq!(ts.len(), out.len(), "ts and out must have equal length");
+        for (p, &t) in out.iter_mut().zip(ts) {
+            *p = self.eval(t);
+        }
+    }
 }
 
 // TODO: I might not want to have separate traits for all these.
@@ -126,6 +142,19 @@ pub trait ParamCurveArea {
     fn signed_area(&self) -> f64;
 }
 
+/// A parametrized curve that can have its area moments measured.
+pub trait ParamCurveMoments {
+    /// Compute the contribution of the curve to the area moments of a
+    /// closed path.
+    ///
+    /// As for [`signed_area`](trait.ParamCurveArea.html#tymethod.signed_area),
+    /// each moment of the region enclosed by a closed path is an integral
+    /// around its boundary, by Green's theorem, so it is the sum of the
+    /// contributions of the segments. The area of the result is the signed
+    /// area of the curve.
+    fn moments(&self) -> Moments;
+}
+
 /// A parametrized curve that reports the nearest point.
 pub trait ParamCurveNearest {
     /// Find the point on the curve nearest the given point.
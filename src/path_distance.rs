@@ -0,0 +1,231 @@
+//! Distances between whole paths.
+
+use crate::{BezPath, Line, ParamCurveNearest, PathEl, Shape, Vec2};
+
+/// The Hausdorff distance between two paths.
+///
+/// This is the greatest distance from a point on either path to the nearest
+/// point on the other, so it measures how far apart the two outlines are
+/// as sets, regardless of direction or parametrization. It is a natural
+/// error measure for a simplified or fitted path against its source.
+///
+/// The result is accurate to within `accuracy`. Both paths are flattened to
+/// within half of it, and the distance between the polylines is found by
+/// subdividing edges of each only where the distance to the other could
+/// exceed the largest found so far. The time taken is roughly proportional to
+/// the product of the numbers of line segments in the two flattenings, so
+/// very small accuracies on long curved paths are expensive.
+///
+/// If one path has no segments and the other does, the result is infinite.
+pub fn hausdorff_distance(a: &BezPath, b: &BezPath, accuracy: f64) -> f64 {
+    let la = flatten_lines(a, 0.5 * accuracy);
+    let lb = flatten_lines(b, 0.5 * accuracy);
+    if la.is_empty() || lb.is_empty() {
+        return if la.is_empty() && lb.is_empty() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+    }
+    directed_hausdorff(&la, &lb, accuracy).max(directed_hausdorff(&lb, &la, accuracy))
+}
+
+/// The greatest distance from a point on `a` to the nearest point on `b`.
+fn directed_hausdorff(a: &[Line], b: &[Line], accuracy: f64) -> f64 {
+    let dist = |p: Vec2| {
+        b.iter()
+            .map(|l| l.nearest(p, 0.0).1)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt()
+    };
+    // The distance to a single line is convex along an edge, so it is bounded
+    // by its values at the ends of any piece of the edge.
+    let upper_bound = |p: Vec2, q: Vec2| {
+        b.iter()
+            .map(|l| l.nearest(p, 0.0).1.max(l.nearest(q, 0.0).1))
+            .fold(f64::INFINITY, f64::min)
+            .sqrt()
+    };
+    let mut best = 0.0f64;
+    let mut stack = Vec::new();
+    for l in a {
+        best = best.max(dist(l.p0)).max(dist(l.p1));
+        stack.push((l.p0, l.p1));
+    }
+    while let Some((p, q)) = stack.pop() {
+        if upper_bound(p, q) <= best + 0.5 * accuracy {
+            continue;
+        }
+        let mid = p.lerp(q, 0.5);
+        best = best.max(dist(mid));
+        stack.push((p, mid));
+        stack.push((mid, q));
+    }
+    best
+}
+
+fn flatten_lines(path: &BezPath, tolerance: f64) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut start = Vec2::default();
+    let mut last = Vec2::default();
+    path.flatten(tolerance, |el| match el {
+        PathEl::Moveto(p) => {
+            start = p;
+            last = p;
+        }
+        PathEl::Lineto(p) => {
+            lines.push(Line::new(last, p));
+            last = p;
+        }
+        PathEl::Closepath => {
+            if last != start {
+                lines.push(Line::new(last, start));
+            }
+            last = start;
+        }
+        _ => unreachable!(),
+    });
+    lines
+}
+
+/// The discrete Fréchet distance between the flattenings of two paths.
+///
+/// Unlike the Hausdorff distance, this respects the order of points along
+/// the paths: it is the shortest leash that lets two walkers traverse the
+/// paths from start to end without backtracking, stepping from vertex to
+/// vertex. Subpaths are traversed in order, as if joined end to end.
+///
+/// Both paths are flattened with the given tolerance. The discrete distance
+/// is at least the continuous Fréchet distance between the polylines, and
+/// exceeds it by at most half the length of their longest edge.
+///
+/// This uses the dynamic programming algorithm of Eiter and Mannila, with
+/// time proportional to the product of the vertex counts. If either path is
+/// empty, the result is infinite, unless both are.
+pub fn frechet_distance(a: &BezPath, b: &BezPath, tolerance: f64) -> f64 {
+    let pa = flatten_points(a, tolerance);
+    let pb = flatten_points(b, tolerance);
+    if pa.is_empty() || pb.is_empty() {
+        return if pa.is_empty() && pb.is_empty() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+    }
+    let mut prev = vec![0.0f64; pb.len()];
+    let mut row = vec![0.0; pb.len()];
+    for (i, &p) in pa.iter().enumerate() {
+        for (j, &q) in pb.iter().enumerate() {
+            let d = (p - q).hypot();
+            row[j] = match (i, j) {
+                (0, 0) => d,
+                (0, _) => row[j - 1].max(d),
+                (_, 0) => prev[0].max(d),
+                _ => prev[j].min(prev[j - 1]).min(row[j - 1]).max(d),
+            };
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    prev[pb.len() - 1]
+}
+
+fn flatten_points(path: &BezPath, tolerance: f64) -> Vec<Vec2> {
+    let mut points = Vec::new();
+    let mut start = Vec2::default();
+    path.flatten(tolerance, |el| match el {
+        PathEl::Moveto(p) => {
+            start = p;
+            points.push(p);
+        }
+        PathEl::Lineto(p) => points.push(p),
+        PathEl::Closepath => points.push(start),
+        _ => unreachable!(),
+    });
+    points
+}
+
+impl BezPath {
+    /// Determine whether two paths have approximately the same geometry.
+    ///
+    /// This compares the outlines, not the elements: paths drawn in a
+    /// different direction, split into different segments, or using lines
+    /// where the other uses straight curves compare equal. The result is
+    /// `true` if the [Hausdorff distance](fn.hausdorff_distance.html)
+    /// between the paths is within `tolerance`, up to an error of a quarter
+    /// of it. Paths with bounding boxes further apart than `tolerance` are
+    /// rejected without computing the distance.
+    pub fn approx_eq(&self, other: &BezPath, tolerance: f64) -> bool {
+        let (a, b) = (self.bounding_box(), other.bounding_box());
+        if (a.x0 - b.x0).abs() > tolerance
+            || (a.y0 - b.y0).abs() > tolerance
+            || (a.x1 - b.x1).abs() > tolerance
+            || (a.y1 - b.y1).abs() > tolerance
+        {
+            return false;
+        }
+        hausdorff_distance(self, other, 0.25 * tolerance) <= tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{frechet_distance, hausdorff_distance, BezPath, Circle, ParamCurve, Shape};
+
+    #[test]
+    fn hausdorff_lines() {
+        let a = BezPath::from_svg("M0 0L10 0").unwrap();
+        let b = BezPath::from_svg("M0 1L10 1L10 4").unwrap();
+        assert!((hausdorff_distance(&a, &b, 1e-6) - 4.0).abs() < 1e-6);
+        assert!((hausdorff_distance(&a, &a, 1e-6)).abs() < 1e-6);
+        assert!(hausdorff_distance(&a, &BezPath::new(), 1e-6).is_infinite());
+    }
+
+    #[test]
+    fn hausdorff_curve() {
+        // A flattened circle is within the flattening tolerance of the circle.
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let mut poly = BezPath::new();
+        circle.flatten(0.1, |el| poly.push(el));
+        let d = hausdorff_distance(&circle, &poly, 1e-3);
+        assert!(d <= 0.1 + 1e-3);
+        // The farthest points are the midpoints of the chords.
+        let mut expected = 0.0f64;
+        for seg in poly.segments() {
+            for i in 0..=100 {
+                let p = seg.eval(i as f64 * 0.01);
+                expected = expected.max(10.0 - p.hypot());
+            }
+        }
+        assert!((d - expected).abs() < 1e-3);
+        let bigger = Circle::new((0.0, 0.0), 10.5).into_bez_path(1e-9);
+        assert!((hausdorff_distance(&circle, &bigger, 1e-3) - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn frechet() {
+        let a = BezPath::from_svg("M0 0L1 0L2 0L3 0").unwrap();
+        let b = BezPath::from_svg("M0 1L1 1L2 1L3 1").unwrap();
+        assert_eq!(frechet_distance(&a, &b, 0.1), 1.0);
+        // Reversing a path doesn't change the Hausdorff distance, but the
+        // walkers now start at opposite ends.
+        let reversed = BezPath::from_svg("M3 1L2 1L1 1L0 1").unwrap();
+        assert_eq!(frechet_distance(&a, &reversed, 0.1), 10.0f64.sqrt());
+        assert!((hausdorff_distance(&a, &reversed, 1e-9) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = BezPath::from_svg("M0 0L10 0L10 10Z").unwrap();
+        // Reversed, with the long edge split and drawn as a straight cubic.
+        let b = BezPath::from_svg("M10 10L10 0L0 0C2.5 2.5 5 5 5 5L10 10").unwrap();
+        assert!(a.approx_eq(&b, 1e-3));
+        assert!(b.approx_eq(&a, 1e-3));
+        let c = BezPath::from_svg("M0 0L10 0L10 10.5Z").unwrap();
+        assert!(!a.approx_eq(&c, 0.1));
+        assert!(a.approx_eq(&c, 0.6));
+        // Same bounding box, different outline.
+        let d = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        assert!(!a.approx_eq(&d, 0.1));
+        assert!(BezPath::new().approx_eq(&BezPath::new(), 0.1));
+    }
+}
@@ -0,0 +1,80 @@
+//! Polar coordinates.
+
+use crate::Vec2;
+
+/// A vector in polar coordinates.
+///
+/// The angle follows the same convention as
+/// [`Vec2::from_angle`](struct.Vec2.html#method.from_angle): zero is the
+/// positive X direction, and π/2 the positive Y direction.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Polar {
+    /// The distance from the origin.
+    pub radius: f64,
+    /// The angle, in radians.
+    pub angle: f64,
+}
+
+impl Polar {
+    /// Create a new vector in polar coordinates.
+    #[inline]
+    pub fn new(radius: f64, angle: f64) -> Polar {
+        Polar { radius, angle }
+    }
+
+    /// Convert from Cartesian coordinates.
+    ///
+    /// The angle is in the range -π to π.
+    #[inline]
+    pub fn from_vec2(v: Vec2) -> Polar {
+        Polar {
+            radius: v.hypot(),
+            angle: v.atan2(),
+        }
+    }
+
+    /// Convert to Cartesian coordinates.
+    #[inline]
+    pub fn to_vec2(self) -> Vec2 {
+        self.radius * Vec2::from_angle(self.angle)
+    }
+}
+
+impl From<Vec2> for Polar {
+    fn from(v: Vec2) -> Polar {
+        Polar::from_vec2(v)
+    }
+}
+
+impl From<Polar> for Vec2 {
+    fn from(p: Polar) -> Vec2 {
+        p.to_vec2()
+    }
+}
+
+impl Vec2 {
+    /// The point at a given distance and angle from a center.
+    ///
+    /// This places items around a dial or radial layout.
+    #[inline]
+    pub fn from_polar(center: Vec2, radius: f64, angle: f64) -> Vec2 {
+        center + Polar::new(radius, angle).to_vec2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Polar, Vec2};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn polar_round_trip() {
+        let v = Vec2::new(-3.0, 4.0);
+        let p = Polar::from(v);
+        assert_eq!(p.radius, 5.0);
+        assert!((Vec2::from(p) - v).hypot() < 1e-12);
+        assert!((Polar::from_vec2(Vec2::new(-1.0, 0.0)).angle - PI).abs() < 1e-12);
+        let q = Vec2::from_polar(Vec2::new(10.0, 10.0), 2.0, FRAC_PI_2);
+        assert!((q - Vec2::new(10.0, 12.0)).hypot() < 1e-12);
+    }
+}
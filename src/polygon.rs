@@ -0,0 +1,303 @@
+//! Regular polygons and stars.
+
+use std::f64::consts::PI;
+
+use crate::shape::farthest;
+use crate::{Circle, PathEl, Rect, Shape, Vec2};
+
+/// A regular polygon.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RegularPolygon {
+    /// The center.
+    pub center: Vec2,
+    /// The distance from the center to each vertex.
+    pub radius: f64,
+    /// The number of sides.
+    pub n: usize,
+    /// The angle of the first vertex from the center, in radians.
+    pub rotation: f64,
+}
+
+/// A star, with points on an outer circle and the corners between them on
+/// an inner circle.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Star {
+    /// The center.
+    pub center: Vec2,
+    /// The distance from the center to each point.
+    pub outer_radius: f64,
+    /// The distance from the center to each corner between points.
+    pub inner_radius: f64,
+    /// The number of points.
+    pub n: usize,
+    /// The angle of the first point from the center, in radians.
+    pub rotation: f64,
+}
+
+impl RegularPolygon {
+    /// A new regular polygon with `n` sides, with its first vertex at angle
+    /// `rotation` from the center.
+    ///
+    /// Panics if `n` is less than 3.
+    #[inline]
+    pub fn new(center: impl Into<Vec2>, radius: f64, n: usize, rotation: f64) -> RegularPolygon {
+        assert!(n >= 3, "a polygon needs at least three sides");
+        RegularPolygon {
+            center: center.into(),
+            radius,
+            n,
+            rotation,
+        }
+    }
+
+    /// The vertex with the given index.
+    pub fn vertex(&self, i: usize) -> Vec2 {
+        let th = self.rotation + 2.0 * PI * (i as f64) / (self.n as f64);
+        self.center + self.radius * Vec2::from_angle(th)
+    }
+
+    /// The distance from the center to the middle of each side.
+    pub fn inradius(&self) -> f64 {
+        self.radius * (PI / self.n as f64).cos()
+    }
+}
+
+impl Star {
+    /// A new star with `n` points, with its first point on the positive x
+    /// axis from the center.
+    ///
+    /// Panics if `n` is less than 2.
+    #[inline]
+    pub fn new(center: impl Into<Vec2>, outer_radius: f64, inner_radius: f64, n: usize) -> Star {
+        assert!(n >= 2, "a star needs at least two points");
+        Star {
+            center: center.into(),
+            outer_radius,
+            inner_radius,
+            n,
+            rotation: 0.0,
+        }
+    }
+
+    /// Builder method for setting the angle of the first point.
+    pub fn with_rotation(mut self, rotation: f64) -> Star {
+        self.rotation = rotation;
+        self
+    }
+
+    /// The vertex with the given index. Even indices are points, and odd
+    /// ones the corners between them.
+    pub fn vertex(&self, i: usize) -> Vec2 {
+        let th = self.rotation + PI * (i as f64) / (self.n as f64);
+        let r = if i % 2 == 1 {
+            self.inner_radius
+        } else {
+            self.outer_radius
+        };
+        self.center + r * Vec2::from_angle(th)
+    }
+}
+
+#[doc(hidden)]
+pub struct RegularPolygonPathIter {
+    polygon: RegularPolygon,
+    ix: usize,
+}
+
+#[doc(hidden)]
+pub struct StarPathIter {
+    star: Star,
+    ix: usize,
+}
+
+impl Shape for RegularPolygon {
+    type BezPathIter = RegularPolygonPathIter;
+
+    fn to_bez_path(&self, _tolerance: f64) -> RegularPolygonPathIter {
+        RegularPolygonPathIter {
+            polygon: *self,
+            ix: 0,
+        }
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        let n = self.n as f64;
+        0.5 * n * self.radius * self.radius * (2.0 * PI / n).sin()
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        let n = self.n as f64;
+        2.0 * n * self.radius.abs() * (PI / n).sin()
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        polygon_winding((0..self.n).map(|i| self.vertex(i)), pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        polygon_bbox((0..self.n).map(|i| self.vertex(i)))
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.center
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        Circle::new(self.center, self.radius.abs())
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest((0..self.n).map(|i| self.vertex(i)), direction)
+    }
+
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        Some((0..self.n).map(|i| self.vertex(i)).collect())
+    }
+}
+
+impl Shape for Star {
+    type BezPathIter = StarPathIter;
+
+    fn to_bez_path(&self, _tolerance: f64) -> StarPathIter {
+        StarPathIter { star: *self, ix: 0 }
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        let n = self.n as f64;
+        n * self.outer_radius * self.inner_radius * (PI / n).sin()
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        2.0 * (self.n as f64) * (self.vertex(1) - self.vertex(0)).hypot()
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        polygon_winding((0..2 * self.n).map(|i| self.vertex(i)), pt)
+    }
+
+    fn bounding_box(&self) -> Rect {
+        polygon_bbox((0..2 * self.n).map(|i| self.vertex(i)))
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.center
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        let r = self.outer_radius.abs().max(self.inner_radius.abs());
+        Circle::new(self.center, r)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest((0..2 * self.n).map(|i| self.vertex(i)), direction)
+    }
+
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        Some((0..2 * self.n).map(|i| self.vertex(i)).collect())
+    }
+}
+
+impl Iterator for RegularPolygonPathIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        let ix = self.ix;
+        self.ix += 1;
+        let n = self.polygon.n;
+        if ix == 0 {
+            Some(PathEl::Moveto(self.polygon.vertex(0)))
+        } else if ix < n {
+            Some(PathEl::Lineto(self.polygon.vertex(ix)))
+        } else if ix == n {
+            Some(PathEl::Closepath)
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for StarPathIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        let ix = self.ix;
+        self.ix += 1;
+        let n = 2 * self.star.n;
+        if ix == 0 {
+            Some(PathEl::Moveto(self.star.vertex(0)))
+        } else if ix < n {
+            Some(PathEl::Lineto(self.star.vertex(ix)))
+        } else if ix == n {
+            Some(PathEl::Closepath)
+        } else {
+            None
+        }
+    }
+}
+
+/// The winding number of a closed polygon around a point.
+fn polygon_winding(pts: impl Iterator<Item = Vec2> + Clone, pt: Vec2) -> i32 {
+    let mut winding = 0;
+    let mut next = pts.clone().skip(1).chain(pts.clone().take(1));
+    for p0 in pts {
+        let p1 = next.next().unwrap();
+        let side = (p1 - p0).cross(pt - p0);
+        if p0.y <= pt.y && p1.y > pt.y && side > 0.0 {
+            winding += 1;
+        } else if p1.y <= pt.y && p0.y > pt.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// The bounding box of the vertices of a polygon.
+fn polygon_bbox(mut pts: impl Iterator<Item = Vec2>) -> Rect {
+    let first = pts.next().unwrap();
+    pts.fold(Rect::from_points(first, first), |r, p| r.union_pt(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{RegularPolygon, Shape, Star, Vec2};
+
+    #[test]
+    fn regular_polygon_and_star() {
+        let hexagon = RegularPolygon::new((1.0, 2.0), 2.0, 6, 0.0);
+        let path = hexagon.into_bez_path(1e-9);
+        assert_eq!(path.elements().len(), 7);
+        assert!((hexagon.area() - path.area()).abs() < 1e-12);
+        assert!((hexagon.perimeter(1e-9) - 12.0).abs() < 1e-12);
+        assert!((hexagon.inradius() - 3f64.sqrt()).abs() < 1e-12);
+        let bbox = hexagon.bounding_box();
+        assert!((bbox.x0 + 1.0).abs() < 1e-12 && (bbox.x1 - 3.0).abs() < 1e-12);
+        assert!((bbox.height() - 2.0 * 3f64.sqrt()).abs() < 1e-12);
+        // A square rotated to stand on a side.
+        let square = RegularPolygon::new((0.0, 0.0), 2f64.sqrt(), 4, 0.25 * PI);
+        assert!((square.area() - 4.0).abs() < 1e-12);
+        assert!((square.vertex(0) - Vec2::new(1.0, 1.0)).hypot() < 1e-12);
+
+        let star = Star::new((0.0, 0.0), 2.0, 1.0, 5).with_rotation(0.5 * PI);
+        let path = star.into_bez_path(1e-9);
+        assert_eq!(path.elements().len(), 11);
+        assert!((star.area() - path.area()).abs() < 1e-12);
+        assert!((star.perimeter(1e-9) - path.perimeter(1e-9)).abs() < 1e-9);
+        assert!((star.vertex(0) - Vec2::new(0.0, 2.0)).hypot() < 1e-12);
+        for &(x, y) in &[(0.0, 0.0), (0.0, 1.9), (0.0, -0.9), (1.0, 0.0), (0.0, -1.5)] {
+            let pt = Vec2::new(x, y);
+            assert_eq!(star.winding(pt) != 0, path.winding(pt) != 0, "{:?}", pt);
+        }
+        assert_eq!(star.winding(Vec2::new(0.0, 0.0)), 1);
+        assert_eq!(star.winding(Vec2::new(0.0, -1.5)), 0);
+    }
+}
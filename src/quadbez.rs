@@ -4,7 +4,7 @@ use std::ops::{Mul, Range};
 
 use arrayvec::ArrayVec;
 
-use crate::common::solve_cubic;
+use crate::common::{solve_cubic, solve_quadratic};
 use crate::MAX_EXTREMA;
 use crate::{
     Affine, CubicBez, Line, ParamCurve, ParamCurveArclen, ParamCurveArea, ParamCurveCurvature,
@@ -61,6 +61,16 @@ impl ParamCurve for QuadBez {
         self.p2
     }
 
+    fn eval_many(&self, ts: &[f64], out: &mut [Vec2]) {
+        assert_eq!(ts.len(), out.len(), "ts and out must have equal length");
+        let c0 = self.p0;
+        let c1 = 2.0 * (self.p1 - self.p0);
+        let c2 = self.p0 - 2.0 * self.p1 + self.p2;
+        for (p, &t) in out.iter_mut().zip(ts) {
+            *p = c0 + (c1 + c2 * t) * t;
+        }
+    }
+
     /// Subdivide into halves, using de Casteljau.
     #[inline]
     fn subdivide(&self) -> (QuadBez, QuadBez) {
@@ -162,7 +172,7 @@ impl ParamCurveNearest for QuadBez {
             }
         }
         fn try_t(q: &QuadBez, p: Vec2, t_best: &mut f64, r_best: &mut Option<f64>, t: f64) -> bool {
-            if t < 0.0 || t > 1.0 {
+            if !(0.0..=1.0).contains(&t) {
                 return true;
             }
             eval_t(p, t_best, r_best, t, q.eval(t));
@@ -175,10 +185,16 @@ impl ParamCurveNearest for QuadBez {
         let c1 = 2.0 * d0.hypot2() + d.dot(d1);
         let c2 = 3.0 * d1.dot(d0);
         let c3 = d1.hypot2();
-        let roots = solve_cubic(c0, c1, c2, c3);
+        // For a straight quad the cubic term vanishes, and solve_cubic would
+        // divide by zero.
+        let roots: ArrayVec<[f64; 3]> = if c3 <= 1e-12 * (c1.abs() + c2.abs()) {
+            solve_quadratic(c0, c1, c2).into_iter().collect()
+        } else {
+            solve_cubic(c0, c1, c2, c3)
+        };
         let mut r_best = None;
         let mut t_best = 0.0;
-        let mut need_ends = false;
+        let mut need_ends = roots.is_empty();
         for &t in &roots {
             need_ends |= try_t(self, p, &mut t_best, &mut r_best, t);
         }
@@ -347,6 +363,11 @@ mod tests {
         verify(q.nearest((-1.1, 1.1).into(), 1e-3), 0.0);
         let a = Affine::rotate(0.5);
         verify((a * q).nearest(a * Vec2::new(0.5, 0.25), 1e-3), 0.75);
+        // A straight quad has no cubic term.
+        let q = QuadBez::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0));
+        verify(q.nearest((0.5, 1.0).into(), 1e-3), 0.25);
+        let q = QuadBez::new((1.0, 1.0), (1.0, 1.0), (1.0, 1.0));
+        verify(q.nearest((0.5, 1.0).into(), 1e-3), 0.0);
     }
 
     #[test]
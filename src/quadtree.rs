@@ -0,0 +1,249 @@
+//! A quadtree for spatial queries over many shapes.
+
+use crate::segment_index::overlaps;
+use crate::{Rect, Shape, Vec2};
+
+/// The number of items a node holds before it is subdivided.
+const MAX_ITEMS: usize = 8;
+
+/// The maximum depth of the tree.
+const MAX_DEPTH: usize = 12;
+
+/// A container of shapes supporting fast queries by rectangle and point.
+///
+/// Each shape is stored in the smallest quadrant that contains its bounding
+/// box; shapes extending outside the bounds given at construction are kept
+/// at the root, so any shape may be inserted. Inserting returns a key
+/// that identifies the shape until it is removed. To move a shape, remove
+/// it and insert it again.
+#[derive(Clone, Debug)]
+pub struct QuadTree<T> {
+    root: QuadNode,
+    entries: Vec<Option<(T, Rect)>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct QuadNode {
+    bounds: Rect,
+    items: Vec<usize>,
+    children: Option<Box<[QuadNode; 4]>>,
+}
+
+impl<T: Shape> QuadTree<T> {
+    /// Create an empty tree covering the given bounds.
+    pub fn new(bounds: Rect) -> QuadTree<T> {
+        QuadTree {
+            root: QuadNode::new(bounds.abs()),
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// The number of shapes in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the tree contains no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a shape, returning its key.
+    pub fn insert(&mut self, shape: T) -> usize {
+        let bbox = shape.bounding_box();
+        let key = if let Some(key) = self.free.pop() {
+            self.entries[key] = Some((shape, bbox));
+            key
+        } else {
+            self.entries.push(Some((shape, bbox)));
+            self.entries.len() - 1
+        };
+        self.root.insert(key, bbox, &self.entries, 0);
+        self.len += 1;
+        key
+    }
+
+    /// Remove the shape with the given key, returning it.
+    pub fn remove(&mut self, key: usize) -> Option<T> {
+        let bbox = self.entries.get(key)?.as_ref()?.1;
+        self.root.remove(key, bbox);
+        self.free.push(key);
+        self.len -= 1;
+        self.entries[key].take().map(|(shape, _)| shape)
+    }
+
+    /// Get the shape with the given key.
+    pub fn get(&self, key: usize) -> Option<&T> {
+        self.entries.get(key)?.as_ref().map(|(shape, _)| shape)
+    }
+
+    /// Iterate over the keys and shapes in the tree.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(key, entry)| entry.as_ref().map(|(shape, _)| (key, shape)))
+    }
+
+    /// The keys of all shapes whose bounding boxes overlap a rectangle.
+    pub fn query_rect(&self, rect: Rect) -> Vec<usize> {
+        let rect = rect.abs();
+        let mut result = Vec::new();
+        self.root
+            .visit(&|bounds| overlaps(bounds, &rect), &mut |key| {
+                if overlaps(&self.entries[key].as_ref().unwrap().1, &rect) {
+                    result.push(key);
+                }
+            });
+        result
+    }
+
+    /// The keys of all shapes whose bounding boxes contain a point.
+    pub fn query_point(&self, p: Vec2) -> Vec<usize> {
+        self.query_rect(Rect::from_points(p, p))
+    }
+
+    /// The keys of all shapes with a nonzero winding number at a point.
+    ///
+    /// This is a hit test for closed shapes; use
+    /// [`query_point`](#method.query_point) and a distance test for
+    /// open ones.
+    pub fn hit_test(&self, p: Vec2) -> Vec<usize> {
+        let mut result = self.query_point(p);
+        result.retain(|&key| self.get(key).unwrap().winding(p) != 0);
+        result
+    }
+}
+
+impl QuadNode {
+    fn new(bounds: Rect) -> QuadNode {
+        QuadNode {
+            bounds,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// The index of the child quadrant wholly containing `bbox`, if any.
+    fn child_for(&self, bbox: Rect) -> Option<usize> {
+        let c = self.bounds.center();
+        let x = if bbox.x1 <= c.x && bbox.x0 >= self.bounds.x0 {
+            0
+        } else if bbox.x0 >= c.x && bbox.x1 <= self.bounds.x1 {
+            1
+        } else {
+            return None;
+        };
+        let y = if bbox.y1 <= c.y && bbox.y0 >= self.bounds.y0 {
+            0
+        } else if bbox.y0 >= c.y && bbox.y1 <= self.bounds.y1 {
+            2
+        } else {
+            return None;
+        };
+        Some(x + y)
+    }
+
+    fn insert<T>(&mut self, key: usize, bbox: Rect, entries: &[Option<(T, Rect)>], depth: usize) {
+        let i = self.child_for(bbox);
+        if let (Some(children), Some(i)) = (&mut self.children, i) {
+            return children[i].insert(key, bbox, entries, depth + 1);
+        }
+        self.items.push(key);
+        if self.children.is_none() && self.items.len() > MAX_ITEMS && depth < MAX_DEPTH {
+            self.split(entries, depth);
+        }
+    }
+
+    fn split<T>(&mut self, entries: &[Option<(T, Rect)>], depth: usize) {
+        let Rect { x0, y0, x1, y1 } = self.bounds;
+        let c = self.bounds.center();
+        self.children = Some(Box::new([
+            QuadNode::new(Rect::new(x0, y0, c.x, c.y)),
+            QuadNode::new(Rect::new(c.x, y0, x1, c.y)),
+            QuadNode::new(Rect::new(x0, c.y, c.x, y1)),
+            QuadNode::new(Rect::new(c.x, c.y, x1, y1)),
+        ]));
+        let items = std::mem::take(&mut self.items);
+        for key in items {
+            let bbox = entries[key].as_ref().unwrap().1;
+            self.insert(key, bbox, entries, depth);
+        }
+    }
+
+    fn remove(&mut self, key: usize, bbox: Rect) {
+        if let Some(pos) = self.items.iter().position(|&k| k == key) {
+            self.items.swap_remove(pos);
+            return;
+        }
+        let i = self.child_for(bbox);
+        if let (Some(children), Some(i)) = (&mut self.children, i) {
+            children[i].remove(key, bbox);
+        }
+    }
+
+    fn visit(&self, accept: &impl Fn(&Rect) -> bool, f: &mut impl FnMut(usize)) {
+        for &key in &self.items {
+            f(key);
+        }
+        if let Some(children) = &self.children {
+            for child in children.iter() {
+                if accept(&child.bounds) {
+                    child.visit(accept, f);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circle, QuadTree, Rect, Shape, Vec2};
+
+    #[test]
+    fn quadtree_queries() {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let mut keys = Vec::new();
+        for i in 0..40 {
+            for j in 0..40 {
+                let center = Vec2::new(i as f64 * 2.5 + 1.0, j as f64 * 2.5 + 1.0);
+                keys.push(tree.insert(Circle::new(center, 1.0)));
+            }
+        }
+        // One shape straddling the bounds.
+        let big = tree.insert(Circle::new((100.0, 50.0), 10.0));
+        assert_eq!(tree.len(), 1601);
+
+        let rect = Rect::new(10.0, 10.0, 20.0, 15.0);
+        let mut found = tree.query_rect(rect);
+        found.sort();
+        let expected: Vec<usize> = tree
+            .iter()
+            .filter(|(_, c)| {
+                let bb = c.bounding_box();
+                bb.x0 <= rect.x1 && rect.x0 <= bb.x1 && bb.y0 <= rect.y1 && rect.y0 <= bb.y1
+            })
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(found, expected);
+
+        let hits = tree.hit_test(Vec2::new(51.2, 51.0));
+        assert_eq!(hits.len(), 1);
+        assert!((tree.get(hits[0]).unwrap().center - Vec2::new(51.0, 51.0)).hypot() < 1e-9);
+        assert!(tree.hit_test(Vec2::new(52.25, 52.25)).is_empty());
+        assert_eq!(tree.hit_test(Vec2::new(105.0, 50.0)), vec![big]);
+
+        let removed = tree.remove(hits[0]).unwrap();
+        assert_eq!(removed.center, Vec2::new(51.0, 51.0));
+        assert!(tree.hit_test(Vec2::new(51.2, 51.0)).is_empty());
+        assert!(tree.remove(hits[0]).is_none());
+        assert_eq!(tree.len(), 1600);
+        let key = tree.insert(Circle::new((51.0, 51.0), 1.0));
+        assert_eq!(key, hits[0]);
+        assert_eq!(tree.hit_test(Vec2::new(51.2, 51.0)), vec![key]);
+    }
+}
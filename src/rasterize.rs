@@ -0,0 +1,572 @@
+//! Renderer-agnostic building blocks for rasterizing paths.
+
+use crate::{Affine, BezPath, Line, ParamCurve, PathEl, Vec2};
+
+/// The flattening tolerance, in pixels.
+const RASTER_TOLERANCE: f64 = 0.1;
+
+/// The rule determining which regions are inside a path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside where the winding number is nonzero.
+    NonZero,
+    /// Inside where the winding number is odd.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Whether a region with the given winding number is inside.
+    #[inline]
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// A horizontal run of pixels inside a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    /// The pixel row.
+    pub y: i32,
+    /// The left edge of the span, in pixels.
+    pub x0: f64,
+    /// The right edge of the span, in pixels.
+    pub x1: f64,
+    /// The winding number of the path within the span.
+    pub winding: i32,
+}
+
+/// Compute the spans of a path filled under the given rule.
+///
+/// The path is scaled by `scale` pixels per unit, and each pixel row `y`
+/// from 0 to `height` is sampled along its center line, `y + 0.5`. Pixels
+/// whose centers lie in `x0..x1` are inside. Adjacent spans differ in
+/// winding number. Open subpaths are implicitly closed, as for filling;
+/// curves are flattened to within a tenth of a pixel. Edges with
+/// non-finite coordinates are ignored.
+///
+/// The spans are ordered by row, then from left to right.
+pub fn fill_spans(path: &BezPath, scale: f64, fill_rule: FillRule, height: usize) -> Vec<Span> {
+    let mut edges = raster_edges(path, scale);
+    edges.retain(|e| e.p0.y != e.p1.y && is_finite(e));
+    edges.sort_by(|a, b| y_min(a).total_cmp(&y_min(b)));
+    let mut spans = Vec::new();
+    if edges.is_empty() {
+        return spans;
+    }
+    let y_start = y_min(&edges[0]).floor().max(0.0) as i32;
+    let y_end = edges
+        .iter()
+        .map(y_max)
+        .fold(f64::NEG_INFINITY, f64::max)
+        .ceil()
+        .min(height as f64) as i32;
+
+    let mut active: Vec<&Line> = Vec::new();
+    let mut next = 0;
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    for y in y_start..y_end {
+        let yc = y as f64 + 0.5;
+        while next < edges.len() && y_min(&edges[next]) <= yc {
+            active.push(&edges[next]);
+            next += 1;
+        }
+        active.retain(|e| y_max(e) > yc);
+        crossings.clear();
+        for e in &active {
+            if y_min(e) <= yc {
+                let t = (yc - e.p0.y) / (e.p1.y - e.p0.y);
+                let x = e.p0.x + t * (e.p1.x - e.p0.x);
+                crossings.push((x, if e.p1.y > e.p0.y { 1 } else { -1 }));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            let (x0, x1) = (pair[0].0, pair[1].0);
+            if x1 > x0 && fill_rule.is_inside(winding) {
+                spans.push(Span { y, x0, x1, winding });
+            }
+        }
+    }
+    spans
+}
+
+/// Compute anti-aliased coverage of a path, filled under the given rule.
+///
+/// The path is scaled by `scale` pixels per unit, and the coverage of each
+/// pixel in a `width` by `height` grid is written to
+/// `coverage[y * width + x]`, as a value from 0 to 1. Parts of the path
+/// outside the grid are clipped. Open subpaths are implicitly closed.
+///
+/// Coverage is computed analytically: each edge adds its signed area to
+/// the cells it crosses, and a running sum along each row then gives the
+/// fractional winding number of every pixel, in the manner of font-rs.
+/// Under the even-odd rule, fractional winding numbers fold back from 1
+/// toward 0, which is exact except where edges overlap within a pixel.
+///
+/// Panics if `coverage` is shorter than `width * height`.
+pub fn fill_coverage(
+    path: &BezPath,
+    scale: f64,
+    fill_rule: FillRule,
+    width: usize,
+    height: usize,
+    coverage: &mut [f32],
+) {
+    assert!(
+        coverage.len() >= width * height,
+        "coverage buffer too small"
+    );
+    // Two extra columns absorb contributions at and beyond the right edge.
+    let stride = width + 2;
+    let mut acc = vec![0.0f64; stride * height];
+    for edge in raster_edges(path, scale) {
+        for piece in clip_to_columns(edge, width as f64) {
+            accumulate_line(&mut acc, stride, height, piece);
+        }
+    }
+    for y in 0..height {
+        let row = &acc[y * stride..y * stride + width];
+        let mut winding = 0.0;
+        for (x, &a) in row.iter().enumerate() {
+            winding += a;
+            let alpha = match fill_rule {
+                FillRule::NonZero => winding.abs().min(1.0),
+                FillRule::EvenOdd => {
+                    let w = winding.abs() % 2.0;
+                    if w > 1.0 {
+                        2.0 - w
+                    } else {
+                        w
+                    }
+                }
+            };
+            coverage[y * width + x] = alpha as f32;
+        }
+    }
+}
+
+/// Compute anti-aliased coverage of a path as 8-bit alpha values.
+///
+/// This is [`fill_coverage`](fn.fill_coverage.html), with coverage scaled
+/// to the range 0 to 255.
+///
+/// Panics if `alpha` is shorter than `width * height`.
+pub fn fill_alpha(
+    path: &BezPath,
+    scale: f64,
+    fill_rule: FillRule,
+    width: usize,
+    height: usize,
+    alpha: &mut [u8],
+) {
+    assert!(alpha.len() >= width * height, "alpha buffer too small");
+    let mut coverage = vec![0.0f32; width * height];
+    fill_coverage(path, scale, fill_rule, width, height, &mut coverage);
+    for (a, c) in alpha.iter_mut().zip(coverage) {
+        *a = (c * 255.0).round() as u8;
+    }
+}
+
+/// Split a line at `x = 0` and `x = width`, moving the parts outside onto
+/// those boundaries.
+///
+/// Area to the left of the grid still counts toward the winding number of
+/// the pixels to its right, so it is kept as a vertical line at the edge.
+fn clip_to_columns(line: Line, width: f64) -> Vec<Line> {
+    let mut ts = vec![0.0, 1.0];
+    let dx = line.p1.x - line.p0.x;
+    if dx != 0.0 {
+        for &x in &[0.0, width] {
+            let t = (x - line.p0.x) / dx;
+            if t > 0.0 && t < 1.0 {
+                ts.push(t);
+            }
+        }
+    }
+    ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let clamp = move |p: Vec2| Vec2::new(p.x.max(0.0).min(width), p.y);
+    ts.windows(2)
+        .map(|w| Line::new(clamp(line.eval(w[0])), clamp(line.eval(w[1]))))
+        .collect()
+}
+
+/// Add the signed area of a line, with `0 <= x <= width`, to the cells it crosses.
+fn accumulate_line(acc: &mut [f64], stride: usize, height: usize, line: Line) {
+    let (p0, p1) = (line.p0, line.p1);
+    if p0.y == p1.y {
+        return;
+    }
+    let (dir, p0, p1) = if p0.y < p1.y {
+        (1.0, p0, p1)
+    } else {
+        (-1.0, p1, p0)
+    };
+    let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+    let y_start = p0.y.max(0.0);
+    let y_end = p1.y.min(height as f64);
+    if y_start >= y_end {
+        return;
+    }
+    let mut x = p0.x + (y_start - p0.y) * dxdy;
+    for y in (y_start.floor() as usize)..(y_end.ceil() as usize) {
+        let row = &mut acc[y * stride..(y + 1) * stride];
+        let dy = ((y + 1) as f64).min(y_end) - (y as f64).max(y_start);
+        let x_next = x + dxdy * dy;
+        let d = dy * dir;
+        let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+        let x0_floor = x0.floor();
+        let x0i = x0_floor as usize;
+        let x1_ceil = x1.ceil();
+        let x1i = x1_ceil as usize;
+        if x1i <= x0i + 1 {
+            // The line stays within one column.
+            let xm = 0.5 * (x + x_next) - x0_floor;
+            row[x0i] += d - d * xm;
+            row[x0i + 1] += d * xm;
+        } else {
+            let s = (x1 - x0).recip();
+            let x0f = x0 - x0_floor;
+            let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+            let x1f = x1 - x1_ceil + 1.0;
+            let am = 0.5 * s * x1f * x1f;
+            row[x0i] += d * a0;
+            if x1i == x0i + 2 {
+                row[x0i + 1] += d * (1.0 - a0 - am);
+            } else {
+                let a1 = s * (1.5 - x0f);
+                row[x0i + 1] += d * (a1 - a0);
+                for cell in &mut row[x0i + 2..x1i - 1] {
+                    *cell += d * s;
+                }
+                let a2 = a1 + (x1i - x0i - 3) as f64 * s;
+                row[x1i - 1] += d * (1.0 - a2 - am);
+            }
+            row[x1i] += d * am;
+        }
+        x = x_next;
+    }
+}
+
+/// A square tile of a grid, with the parts of a path that cross it.
+#[derive(Clone, Debug)]
+pub struct Tile {
+    /// The column of the tile.
+    pub x: usize,
+    /// The row of the tile.
+    pub y: usize,
+    /// The winding number contributed by the parts of the path outside the tile.
+    pub backdrop: i32,
+    /// The lines within the tile, in pixel coordinates.
+    pub segments: Vec<Line>,
+}
+
+/// Partition a path into square tiles, as used by GPU compute rasterizers.
+///
+/// The path is scaled by `scale` pixels per unit and flattened, and the
+/// lines are clipped to a grid of `width` by `height` tiles, each
+/// `tile_size` pixels square. The result holds every tile of the grid, in
+/// row-major order, so solid tiles with no segments are included.
+///
+/// Within a tile, the winding number at a point is the backdrop plus the
+/// contributions of the tile's segments, counted as for
+/// [`Shape::winding`](trait.Shape.html#tymethod.winding) by casting a ray
+/// to the left. To make this hold, wherever the path crosses the left edge
+/// of a tile, a vertical line joining the crossing to the tile's top left
+/// corner is added to the segments, and its effect is balanced in the
+/// backdrop.
+pub fn bin_tiles(
+    path: &BezPath,
+    scale: f64,
+    tile_size: usize,
+    width: usize,
+    height: usize,
+) -> Vec<Tile> {
+    let ts = tile_size as f64;
+    let mut tiles: Vec<Tile> = (0..width * height)
+        .map(|i| Tile {
+            x: i % width,
+            y: i / width,
+            backdrop: 0,
+            segments: Vec::new(),
+        })
+        .collect();
+    // For each row, the change in backdrop at each column boundary, indexed
+    // from the left of the grid.
+    let mut deltas = vec![0i32; (width + 1) * height];
+    for edge in raster_edges(path, scale) {
+        split_at_grid(edge, ts, |piece| {
+            let mid = piece.eval(0.5);
+            let ty = (mid.y / ts).floor();
+            if ty < 0.0 || ty >= height as f64 {
+                return;
+            }
+            let ty = ty as usize;
+            let tx = (mid.x / ts).floor();
+            if tx >= width as f64 {
+                return;
+            }
+            let y0 = ty as f64 * ts;
+            // Pieces starting on the top edge cross the horizontal line
+            // there, so they add to the backdrop of every tile to the right.
+            let dir = if piece.p1.y > piece.p0.y { 1 } else { -1 };
+            if piece.p0.y.min(piece.p1.y) == y0 && piece.p0.y != piece.p1.y {
+                let col = if tx < 0.0 { 0 } else { tx as usize + 1 };
+                deltas[ty * (width + 1) + col] += dir;
+            }
+            if tx < 0.0 {
+                return;
+            }
+            let tile = &mut tiles[ty * width + tx as usize];
+            let x0 = tx * ts;
+            let corner = Vec2::new(x0, y0);
+            if piece.p0.x == x0 && piece.p0.y != y0 {
+                tile.segments.push(Line::new(corner, piece.p0));
+                tile.backdrop -= 1;
+            }
+            tile.segments.push(piece);
+            if piece.p1.x == x0 && piece.p1.y != y0 {
+                tile.segments.push(Line::new(piece.p1, corner));
+                tile.backdrop += 1;
+            }
+        });
+    }
+    for ty in 0..height {
+        let mut backdrop = deltas[ty * (width + 1)];
+        for tx in 0..width {
+            tiles[ty * width + tx].backdrop += backdrop;
+            backdrop += deltas[ty * (width + 1) + tx + 1];
+        }
+    }
+    tiles
+}
+
+/// Split a line where it crosses the lines of a square grid, calling `f`
+/// on each nonempty piece.
+///
+/// Split points are placed exactly on the grid lines they lie on.
+fn split_at_grid(line: Line, ts: f64, mut f: impl FnMut(Line)) {
+    let (p0, p1) = (line.p0, line.p1);
+    let mut splits: Vec<(f64, Vec2)> = Vec::new();
+    for &(a0, a1, is_x) in &[(p0.x, p1.x, true), (p0.y, p1.y, false)] {
+        let (lo, hi) = (a0.min(a1), a0.max(a1));
+        let mut k = (lo / ts).floor() + 1.0;
+        while k * ts < hi {
+            let t = (k * ts - a0) / (a1 - a0);
+            let mut p = line.eval(t);
+            if is_x {
+                p.x = k * ts;
+            } else {
+                p.y = k * ts;
+            }
+            splits.push((t, p));
+            k += 1.0;
+        }
+    }
+    splits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut last = p0;
+    for p in splits.into_iter().map(|(_, p)| p).chain(Some(p1)) {
+        if p != last {
+            f(Line::new(last, p));
+        }
+        last = p;
+    }
+}
+
+/// Flatten a path into lines in pixel coordinates, closing open subpaths.
+pub(crate) fn raster_edges(path: &BezPath, scale: f64) -> Vec<Line> {
+    let scaled = Affine::scale(scale) * path;
+    let mut edges = Vec::new();
+    let mut start = Vec2::default();
+    let mut last = Vec2::default();
+    scaled.flatten(RASTER_TOLERANCE, |el| match el {
+        PathEl::Moveto(p) => {
+            if last != start {
+                edges.push(Line::new(last, start));
+            }
+            start = p;
+            last = p;
+        }
+        PathEl::Lineto(p) => {
+            edges.push(Line::new(last, p));
+            last = p;
+        }
+        PathEl::Closepath => {
+            if last != start {
+                edges.push(Line::new(last, start));
+            }
+            last = start;
+        }
+        _ => unreachable!(),
+    });
+    if last != start {
+        edges.push(Line::new(last, start));
+    }
+    edges
+}
+
+fn is_finite(e: &Line) -> bool {
+    e.p0.x.is_finite() && e.p0.y.is_finite() && e.p1.x.is_finite() && e.p1.y.is_finite()
+}
+
+fn y_min(e: &Line) -> f64 {
+    e.p0.y.min(e.p1.y)
+}
+
+fn y_max(e: &Line) -> f64 {
+    e.p0.y.max(e.p1.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bin_tiles, fill_alpha, fill_coverage, fill_spans, BezPath, Circle, FillRule, PathSeg, Rect,
+        Shape, Span, Vec2,
+    };
+    use std::f64::consts::PI;
+
+    #[test]
+    fn spans_rect() {
+        let path = BezPath::from_svg("M1 1L4 1L4 3L1 3Z").unwrap();
+        let spans = fill_spans(&path, 2.0, FillRule::NonZero, 10);
+        assert_eq!(spans.len(), 4);
+        for (i, span) in spans.iter().enumerate() {
+            assert_eq!(span.y, 2 + i as i32);
+            assert_eq!((span.x0, span.x1), (2.0, 8.0));
+            assert_eq!(span.winding.abs(), 1);
+        }
+    }
+
+    #[test]
+    fn spans_fill_rule() {
+        // Two nested squares with the same orientation.
+        let path = BezPath::from_svg("M0 0L10 0L10 10L0 10ZM2 2L8 2L8 8L2 8Z").unwrap();
+        let row = |spans: &[Span]| -> Vec<(f64, f64)> {
+            spans
+                .iter()
+                .filter(|s| s.y == 5)
+                .map(|s| (s.x0, s.x1))
+                .collect()
+        };
+        let nonzero = fill_spans(&path, 1.0, FillRule::NonZero, 10);
+        assert_eq!(row(&nonzero), vec![(0.0, 2.0), (2.0, 8.0), (8.0, 10.0)]);
+        let evenodd = fill_spans(&path, 1.0, FillRule::EvenOdd, 10);
+        assert_eq!(row(&evenodd), vec![(0.0, 2.0), (8.0, 10.0)]);
+    }
+
+    #[test]
+    fn spans_circle_area() {
+        let circle = Circle::new((10.0, 10.0), 8.0);
+        let path = circle.into_bez_path(1e-3);
+        let spans = fill_spans(&path, 4.0, FillRule::NonZero, 80);
+        let area: f64 = spans.iter().map(|s| s.x1 - s.x0).sum::<f64>() / 16.0;
+        assert!((area - PI * 64.0).abs() < 0.5, "{}", area);
+    }
+
+    #[test]
+    fn spans_degenerate() {
+        // Rows outside the raster are skipped, however far the path reaches.
+        let mut path = BezPath::new();
+        path.moveto((0.0, -1e300));
+        path.lineto((4.0, -1e300));
+        path.lineto((4.0, 1e300));
+        path.lineto((0.0, 1e300));
+        path.closepath();
+        let spans = fill_spans(&path, 1.0, FillRule::NonZero, 3);
+        assert_eq!(spans.len(), 3);
+        assert!(spans.iter().all(|s| (s.x0, s.x1) == (0.0, 4.0)));
+
+        let mut path = BezPath::from_svg("M1 1L4 1L4 3L1 3Z").unwrap();
+        path.lineto(Vec2::new(f64::NAN, 2.0));
+        path.lineto(Vec2::new(2.0, f64::INFINITY));
+        let spans = fill_spans(&path, 1.0, FillRule::EvenOdd, 10);
+        assert!(spans.iter().all(|s| s.x0.is_finite() && s.x1.is_finite()));
+    }
+
+    #[test]
+    fn coverage_rect() {
+        // A rectangle covering half of its boundary pixels.
+        let path = BezPath::from_svg("M1.5 1L4.5 1L4.5 2.5L1.5 2.5Z").unwrap();
+        let mut coverage = vec![0.0f32; 36];
+        fill_coverage(&path, 1.0, FillRule::NonZero, 6, 6, &mut coverage);
+        let at = |x: usize, y: usize| coverage[y * 6 + x];
+        assert!((at(2, 1) - 1.0).abs() < 1e-6);
+        assert!((at(1, 1) - 0.5).abs() < 1e-6);
+        assert!((at(4, 1) - 0.5).abs() < 1e-6);
+        assert!((at(2, 2) - 0.5).abs() < 1e-6);
+        assert!((at(1, 2) - 0.25).abs() < 1e-6);
+        assert_eq!(at(5, 1), 0.0);
+        assert_eq!(at(2, 3), 0.0);
+        let total: f32 = coverage.iter().sum();
+        assert!((total - 4.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn coverage_clipped() {
+        let circle = Circle::new((5.0, 5.0), 6.0);
+        let path = circle.into_bez_path(1e-3);
+        let mut alpha = vec![0u8; 100];
+        fill_alpha(&path, 1.0, FillRule::NonZero, 10, 10, &mut alpha);
+        // The corner pixel is partly covered.
+        assert!(alpha[0] > 0 && alpha[0] < 128);
+        assert_eq!(alpha[55], 255);
+        assert_eq!(alpha[50], 255);
+        assert_eq!(alpha[59], 255);
+        let path = (circle + Vec2::new(5.0, 5.0)).into_bez_path(1e-3);
+        let mut coverage = vec![0.0f32; 400];
+        fill_coverage(&path, 1.0, FillRule::EvenOdd, 20, 20, &mut coverage);
+        let total: f32 = coverage.iter().sum();
+        // Coverage is exact for the flattened polygon.
+        let flat_area: f64 = super::raster_edges(&path, 1.0)
+            .iter()
+            .map(|e| 0.5 * e.p0.cross(e.p1))
+            .sum();
+        assert!((total as f64 - flat_area.abs()).abs() < 1e-3, "{}", total);
+        assert!((total as f64 - PI * 36.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn tiles_winding() {
+        // A self-intersecting star and a hole, partly outside the grid.
+        let path = BezPath::from_svg(
+            "M10 -3L16 17L0 5L20 5L4 17ZM-4 8C0 0 9 20 -2 12Z M6 6L6 10L10 10L10 6Z",
+        )
+        .unwrap();
+        let tiles = bin_tiles(&path, 1.5, 4, 7, 6);
+        assert_eq!(tiles.len(), 42);
+        let edges = super::raster_edges(&path, 1.5);
+        let mut n_nonzero = 0;
+        for tile in &tiles {
+            for j in 0..4 {
+                for i in 0..4 {
+                    let p = Vec2::new((tile.x * 4 + i) as f64 + 0.5, (tile.y * 4 + j) as f64 + 0.5);
+                    let expected: i32 = edges.iter().map(|&e| PathSeg::Line(e).winding(p)).sum();
+                    let actual = tile.backdrop
+                        + tile
+                            .segments
+                            .iter()
+                            .map(|&e| PathSeg::Line(e).winding(p))
+                            .sum::<i32>();
+                    assert_eq!(actual, expected, "tile {} {} at {:?}", tile.x, tile.y, p);
+                    if expected != 0 {
+                        n_nonzero += 1;
+                    }
+                }
+            }
+            for seg in &tile.segments {
+                let bb = Rect::from_points(seg.p0, seg.p1);
+                assert!(bb.x0 >= (tile.x * 4) as f64 && bb.x1 <= (tile.x * 4 + 4) as f64);
+                assert!(bb.y0 >= (tile.y * 4) as f64 && bb.y1 <= (tile.y * 4 + 4) as f64);
+            }
+        }
+        assert!(n_nonzero > 100);
+        // Some tile is solid.
+        assert!(tiles
+            .iter()
+            .any(|t| t.segments.is_empty() && t.backdrop != 0));
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::ops::{Add, Sub};
 
-use crate::{PathEl, Shape, Vec2};
+use crate::{Affine, Circle, Insets, PathEl, Shape, Vec2};
 
 /// A rectangle.
 #[derive(Clone, Copy, Default, Debug)]
@@ -17,6 +17,40 @@ pub struct Rect {
     pub y1: f64,
 }
 
+/// How [`Rect::fit_into`](struct.Rect.html#method.fit_into) scales a
+/// rectangle to fit another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainMode {
+    /// Scale to the largest size fitting entirely inside the target,
+    /// letterboxing if the aspect ratios differ.
+    Contain,
+    /// Scale to the smallest size covering the whole target, cropping if
+    /// the aspect ratios differ.
+    Cover,
+}
+
+/// Horizontal alignment of one rectangle within another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignX {
+    /// Align the left edges.
+    Left,
+    /// Align the centers.
+    Center,
+    /// Align the right edges.
+    Right,
+}
+
+/// Vertical alignment of one rectangle within another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlignY {
+    /// Align the top edges, in y-down spaces.
+    Top,
+    /// Align the centers.
+    Center,
+    /// Align the bottom edges, in y-down spaces.
+    Bottom,
+}
+
 impl Rect {
     /// A new rectangle from minimum and maximum coordinates.
     #[inline]
@@ -163,8 +197,289 @@ impl Rect {
             y1: self.y1 + height,
         }
     }
+
+    /// Move each edge inwards by its inset.
+    ///
+    /// If the insets add up to more than the width or height, the result
+    /// has negative width or height.
+    #[inline]
+    pub fn inset(&self, insets: Insets) -> Rect {
+        Rect {
+            x0: self.x0 + insets.x0,
+            y0: self.y0 + insets.y0,
+            x1: self.x1 - insets.x1,
+            y1: self.y1 - insets.y1,
+        }
+    }
+
+    /// Move each edge outwards by its inset, undoing
+    /// [`inset`](#method.inset).
+    #[inline]
+    pub fn outset(&self, insets: Insets) -> Rect {
+        Rect {
+            x0: self.x0 - insets.x0,
+            y0: self.y0 - insets.y0,
+            x1: self.x1 + insets.x1,
+            y1: self.y1 + insets.y1,
+        }
+    }
+
+    /// The smallest rectangle with integer coordinates containing this one.
+    ///
+    /// This never covers less than the original, as needed for damage
+    /// regions. Results are valid only if width and height are
+    /// non-negative.
+    #[inline]
+    pub fn round_out(&self) -> Rect {
+        Rect {
+            x0: self.x0.floor(),
+            y0: self.y0.floor(),
+            x1: self.x1.ceil(),
+            y1: self.y1.ceil(),
+        }
+    }
+
+    /// The largest rectangle with integer coordinates contained in this one.
+    ///
+    /// This never covers more than the original, as needed for opaque
+    /// regions that can occlude what is behind them. If no whole unit fits
+    /// in a direction, the result has zero width or height there. Results
+    /// are valid only if width and height are non-negative.
+    #[inline]
+    pub fn round_in(&self) -> Rect {
+        let x0 = self.x0.ceil();
+        let y0 = self.y0.ceil();
+        Rect {
+            x0,
+            y0,
+            x1: self.x1.floor().max(x0),
+            y1: self.y1.floor().max(y0),
+        }
+    }
+
+    /// The rectangle with each coordinate rounded to the nearest integer,
+    /// with ties to even.
+    ///
+    /// Unlike rounding ties up, this is unbiased, so rectangles that are
+    /// snapped and moved repeatedly don't drift.
+    #[inline]
+    pub fn round_half_even(&self) -> Rect {
+        Rect {
+            x0: round_half_even(self.x0),
+            y0: round_half_even(self.y0),
+            x1: round_half_even(self.x1),
+            y1: round_half_even(self.y1),
+        }
+    }
+
+    /// The largest rectangle with the given aspect ratio, width over
+    /// height, inside this one.
+    ///
+    /// The width or height is reduced, keeping the point at `anchor` in
+    /// place, where the anchor is in fractions of the width and height, so
+    /// `(0.0, 0.0)` keeps the top left corner in a y-down space and
+    /// `(0.5, 0.5)` the center. The result is normalized as by
+    /// [`abs`](#method.abs). The ratio should be positive.
+    pub fn with_aspect_ratio(&self, ratio: f64, anchor: Vec2) -> Rect {
+        let r = self.abs();
+        let (w, h) = (r.width(), r.height());
+        let (new_w, new_h) = if w > h * ratio {
+            (h * ratio, h)
+        } else {
+            (w, w / ratio)
+        };
+        let x0 = r.x0 + anchor.x * (w - new_w);
+        let y0 = r.y0 + anchor.y * (h - new_h);
+        Rect::new(x0, y0, x0 + new_w, y0 + new_h)
+    }
+
+    /// Scale the rectangle uniformly to fit another, centered within it.
+    ///
+    /// This preserves the aspect ratio, as for the `object-fit` property
+    /// in CSS. The result is normalized as by [`abs`](#method.abs); if
+    /// this rectangle has zero width or height, it is scaled to fit the
+    /// other dimension, and if both are zero, the result is the center of
+    /// the target.
+    pub fn fit_into(&self, target: &Rect, mode: ContainMode) -> Rect {
+        let target = target.abs();
+        let (w, h) = (self.width().abs(), self.height().abs());
+        let sx = target.width() / w;
+        let sy = target.height() / h;
+        let scale = match mode {
+            ContainMode::Contain => sx.min(sy),
+            ContainMode::Cover => {
+                // A zero dimension gives an infinite scale, which can't cover.
+                let s = sx.max(sy);
+                if s.is_finite() {
+                    s
+                } else {
+                    sx.min(sy)
+                }
+            }
+        };
+        let scale = if scale.is_finite() { scale } else { 0.0 };
+        let size = Vec2::new(w * scale, h * scale);
+        Rect::from_origin_size(target.center() - 0.5 * size, size)
+    }
+
+    /// Move the rectangle to a position aligned within another, without
+    /// changing its size.
+    ///
+    /// The result is normalized as by [`abs`](#method.abs). If this
+    /// rectangle is larger than the target, it overhangs the target edges
+    /// opposite the alignment.
+    pub fn align_in(&self, target: &Rect, align_x: AlignX, align_y: AlignY) -> Rect {
+        let target = target.abs();
+        let size = self.abs().size();
+        let x = match align_x {
+            AlignX::Left => target.x0,
+            AlignX::Center => target.x0 + 0.5 * (target.width() - size.x),
+            AlignX::Right => target.x1 - size.x,
+        };
+        let y = match align_y {
+            AlignY::Top => target.y0,
+            AlignY::Center => target.y0 + 0.5 * (target.height() - size.y),
+            AlignY::Bottom => target.y1 - size.y,
+        };
+        Rect::from_origin_size(Vec2::new(x, y), size)
+    }
+
+    /// Split the rectangle into left and right parts.
+    ///
+    /// The split is at `frac` of the width from the left edge; a `frac`
+    /// outside 0..1 gives a part with negative width.
+    pub fn split_h(&self, frac: f64) -> (Rect, Rect) {
+        let x = self.x0 + frac * self.width();
+        (
+            Rect::new(self.x0, self.y0, x, self.y1),
+            Rect::new(x, self.y0, self.x1, self.y1),
+        )
+    }
+
+    /// Split the rectangle into top and bottom parts, in y-down spaces.
+    ///
+    /// The split is at `frac` of the height from the top edge; a `frac`
+    /// outside 0..1 gives a part with negative height.
+    pub fn split_v(&self, frac: f64) -> (Rect, Rect) {
+        let y = self.y0 + frac * self.height();
+        (
+            Rect::new(self.x0, self.y0, self.x1, y),
+            Rect::new(self.x0, y, self.x1, self.y1),
+        )
+    }
+
+    /// Divide the rectangle into a grid of equal cells.
+    ///
+    /// This is [`grid`](#method.grid) with no gutters.
+    pub fn subdivide(&self, rows: usize, cols: usize) -> RectGrid {
+        self.grid(rows, cols, Vec2::default())
+    }
+
+    /// Divide the rectangle into a grid of equal cells separated by gutters.
+    ///
+    /// The `gutter` gives the horizontal and vertical space between
+    /// adjacent cells; there is no gutter around the outside. The cells
+    /// are iterated in row-major order, and can also be accessed by
+    /// position with [`RectGrid::cell`](struct.RectGrid.html#method.cell).
+    pub fn grid(&self, rows: usize, cols: usize, gutter: Vec2) -> RectGrid {
+        let rect = self.abs();
+        let cell = |extent: f64, n: usize, gutter: f64| {
+            if n == 0 {
+                0.0
+            } else {
+                (extent - (n - 1) as f64 * gutter) / n as f64
+            }
+        };
+        RectGrid {
+            origin: rect.origin(),
+            cell_size: Vec2::new(
+                cell(rect.width(), cols, gutter.x),
+                cell(rect.height(), rows, gutter.y),
+            ),
+            gutter,
+            rows,
+            cols,
+            ix: 0,
+        }
+    }
+
+    /// The transform mapping this rectangle onto another.
+    ///
+    /// This scales each axis independently, and is most useful with the
+    /// result of [`fit_into`](#method.fit_into) or
+    /// [`align_in`](#method.align_in), to place content drawn in this
+    /// rectangle. The rectangle must have nonzero width and height.
+    pub fn transform_to(&self, target: &Rect) -> Affine {
+        let sx = target.width() / self.width();
+        let sy = target.height() / self.height();
+        Affine::new([
+            sx,
+            0.0,
+            0.0,
+            sy,
+            target.x0 - sx * self.x0,
+            target.y0 - sy * self.y0,
+        ])
+    }
+}
+
+/// The cells of a grid dividing a rectangle.
+///
+/// This is created by [`Rect::grid`](struct.Rect.html#method.grid) and
+/// [`Rect::subdivide`](struct.Rect.html#method.subdivide), and iterates
+/// over the cells in row-major order.
+#[derive(Clone, Debug)]
+pub struct RectGrid {
+    origin: Vec2,
+    cell_size: Vec2,
+    gutter: Vec2,
+    rows: usize,
+    cols: usize,
+    ix: usize,
+}
+
+impl RectGrid {
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The cell at the given row and column, or `None` if out of range.
+    pub fn cell(&self, row: usize, col: usize) -> Option<Rect> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        let step = self.cell_size + self.gutter;
+        let p = self.origin + Vec2::new(col as f64 * step.x, row as f64 * step.y);
+        Some(Rect::from_origin_size(p, self.cell_size))
+    }
+}
+
+impl Iterator for RectGrid {
+    type Item = Rect;
+
+    fn next(&mut self) -> Option<Rect> {
+        if self.cols == 0 {
+            return None;
+        }
+        let cell = self.cell(self.ix / self.cols, self.ix % self.cols)?;
+        self.ix += 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = (self.rows * self.cols).saturating_sub(self.ix);
+        (n, Some(n))
+    }
 }
 
+impl ExactSizeIterator for RectGrid {}
+
 impl From<((f64, f64), (f64, f64))> for Rect {
     fn from(coords: ((f64, f64), (f64, f64))) -> Rect {
         let ((x0, y0), (x1, y1)) = coords;
@@ -178,6 +493,16 @@ impl From<Rect> for ((f64, f64), (f64, f64)) {
     }
 }
 
+/// Round to the nearest integer, with ties to even.
+fn round_half_even(x: f64) -> f64 {
+    let r = x.round();
+    if (r - x).abs() == 0.5 {
+        2.0 * (0.5 * x).round()
+    } else {
+        r
+    }
+}
+
 // Note: there aren't any `From` implementations from pairs of Vec2 because the
 // interpretation is ambiguous (point or vector), and it's hard to decide what to
 // do with negative width and height.
@@ -260,6 +585,33 @@ impl Shape for Rect {
         self.abs()
     }
 
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        Rect::from_points(
+            affine * Vec2::new(self.x0, self.y0),
+            affine * Vec2::new(self.x1, self.y1),
+        )
+        .union_pt(affine * Vec2::new(self.x1, self.y0))
+        .union_pt(affine * Vec2::new(self.x0, self.y1))
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.center()
+    }
+
+    #[inline]
+    fn bounding_circle(&self) -> Circle {
+        Circle::new(self.center(), 0.5 * self.width().hypot(self.height()))
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let r = self.abs();
+        Vec2::new(
+            if direction.x < 0.0 { r.x0 } else { r.x1 },
+            if direction.y < 0.0 { r.y0 } else { r.y1 },
+        )
+    }
+
     #[inline]
     fn as_rect(&self) -> Option<Rect> {
         Some(*self)
@@ -282,3 +634,98 @@ impl Iterator for RectPathIter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{AlignX, AlignY, ContainMode, Insets, Rect, Vec2};
+
+    fn assert_rect(r: Rect, expected: (f64, f64, f64, f64)) {
+        assert_eq!((r.x0, r.y0, r.x1, r.y1), expected);
+    }
+
+    #[test]
+    fn insets_and_rounding() {
+        let rect = Rect::new(1.0, 2.0, 11.0, 12.0);
+        let insets = Insets::new(1.0, 2.0, 3.0, 4.0);
+        assert_rect(rect.inset(insets), (2.0, 4.0, 8.0, 8.0));
+        assert_rect(rect.outset(insets), (0.0, 0.0, 14.0, 16.0));
+        assert_rect(rect.inset(insets).outset(insets), (1.0, 2.0, 11.0, 12.0));
+        assert_rect(rect.inset(Insets::uniform(-1.0)), (0.0, 1.0, 12.0, 13.0));
+
+        let r = Rect::new(-0.5, 0.2, 2.5, 3.5);
+        assert_rect(r.round_out(), (-1.0, 0.0, 3.0, 4.0));
+        assert_rect(r.round_in(), (0.0, 1.0, 2.0, 3.0));
+        assert_rect(r.round_half_even(), (0.0, 0.0, 2.0, 4.0));
+        assert_rect(
+            Rect::new(0.2, 0.0, 0.8, 1.0).round_in(),
+            (1.0, 0.0, 1.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn with_aspect_ratio() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let square = rect.with_aspect_ratio(1.0, Vec2::new(0.5, 0.5));
+        assert_rect(square, (25.0, 0.0, 75.0, 50.0));
+        let square = rect.with_aspect_ratio(1.0, Vec2::new(1.0, 0.0));
+        assert_rect(square, (50.0, 0.0, 100.0, 50.0));
+        let wide = rect.with_aspect_ratio(4.0, Vec2::new(0.0, 1.0));
+        assert_rect(wide, (0.0, 25.0, 100.0, 50.0));
+    }
+
+    #[test]
+    fn fit_and_align() {
+        let image = Rect::new(0.0, 0.0, 40.0, 20.0);
+        let screen = Rect::new(0.0, 0.0, 100.0, 100.0);
+        assert_rect(
+            image.fit_into(&screen, ContainMode::Contain),
+            (0.0, 25.0, 100.0, 75.0),
+        );
+        assert_rect(
+            image.fit_into(&screen, ContainMode::Cover),
+            (-50.0, 0.0, 150.0, 100.0),
+        );
+        let fitted = image.fit_into(&screen, ContainMode::Contain);
+        assert_rect(
+            fitted.align_in(&screen, AlignX::Left, AlignY::Bottom),
+            (0.0, 50.0, 100.0, 100.0),
+        );
+        assert_rect(
+            image.align_in(&screen, AlignX::Right, AlignY::Center),
+            (60.0, 40.0, 100.0, 60.0),
+        );
+        let affine = image.transform_to(&fitted);
+        assert_eq!(affine * Vec2::new(40.0, 20.0), Vec2::new(100.0, 75.0));
+        assert_eq!(affine * Vec2::new(0.0, 0.0), Vec2::new(0.0, 25.0));
+        // A degenerate rectangle.
+        let line = Rect::new(0.0, 0.0, 10.0, 0.0);
+        assert_rect(
+            line.fit_into(&screen, ContainMode::Cover),
+            (0.0, 50.0, 100.0, 50.0),
+        );
+    }
+
+    #[test]
+    fn grid() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let (left, right) = rect.split_h(0.25);
+        assert_rect(left, (0.0, 0.0, 25.0, 50.0));
+        assert_rect(right, (25.0, 0.0, 100.0, 50.0));
+        let (top, bottom) = rect.split_v(0.5);
+        assert_rect(top, (0.0, 0.0, 100.0, 25.0));
+        assert_rect(bottom, (0.0, 25.0, 100.0, 50.0));
+
+        let cells: Vec<Rect> = rect.subdivide(2, 4).collect();
+        assert_eq!(cells.len(), 8);
+        assert_rect(cells[0], (0.0, 0.0, 25.0, 25.0));
+        assert_rect(cells[7], (75.0, 25.0, 100.0, 50.0));
+
+        let grid = rect.grid(2, 3, Vec2::new(5.0, 10.0));
+        assert_eq!(grid.len(), 6);
+        assert_rect(grid.cell(0, 0).unwrap(), (0.0, 0.0, 30.0, 20.0));
+        assert_rect(grid.cell(1, 2).unwrap(), (70.0, 30.0, 100.0, 50.0));
+        assert!(grid.cell(2, 0).is_none());
+        assert_eq!(grid.last().map(|r| r.x1), Some(100.0));
+        assert_eq!(rect.subdivide(0, 3).count(), 0);
+    }
+}
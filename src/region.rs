@@ -0,0 +1,211 @@
+//! Regions made of rectangles.
+
+use crate::{Rect, Vec2};
+
+/// A set of points made up of disjoint axis-aligned rectangles.
+///
+/// This is the kind of structure used for damage regions and clip
+/// regions: the union of a few rectangles, supporting boolean operations
+/// and containment tests. The rectangles have positive area and do not
+/// overlap, though they may share edges. The decomposition into rectangles
+/// is not canonical, so two regions covering the same points may hold
+/// different rectangles.
+///
+/// As for [`Rect`](struct.Rect.html) winding, rectangles are half-open,
+/// containing their top and left edges but not their bottom and right
+/// edges.
+#[derive(Clone, Debug, Default)]
+pub struct Region {
+    rects: Vec<Rect>,
+}
+
+impl Region {
+    /// Create an empty region.
+    pub fn new() -> Region {
+        Default::default()
+    }
+
+    /// The rectangles making up the region.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Iterate over the rectangles making up the region.
+    pub fn iter(&self) -> impl Iterator<Item = Rect> + '_ {
+        self.rects.iter().cloned()
+    }
+
+    /// Returns `true` if the region contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The area of the region.
+    pub fn area(&self) -> f64 {
+        self.rects.iter().map(|r| r.area()).sum()
+    }
+
+    /// The smallest rectangle enclosing the region, or `None` if it is empty.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        Some(iter.fold(first, |acc, r| acc.union(r)))
+    }
+
+    /// Returns `true` if the region contains a point.
+    pub fn contains(&self, p: Vec2) -> bool {
+        self.rects
+            .iter()
+            .any(|r| p.x >= r.x0 && p.x < r.x1 && p.y >= r.y0 && p.y < r.y1)
+    }
+
+    /// Returns `true` if the region contains every point of a rectangle.
+    pub fn contains_rect(&self, rect: Rect) -> bool {
+        let mut rest = vec![rect.abs()];
+        for &r in &self.rects {
+            rest = rest
+                .into_iter()
+                .flat_map(|piece| subtract(piece, r))
+                .collect();
+        }
+        rest.iter().all(|r| is_empty(*r))
+    }
+
+    /// Add a rectangle to the region.
+    pub fn union_rect(&mut self, rect: Rect) {
+        let mut pieces = vec![rect.abs()];
+        for &r in &self.rects {
+            pieces = pieces.into_iter().flat_map(|p| subtract(p, r)).collect();
+        }
+        self.rects
+            .extend(pieces.into_iter().filter(|r| !is_empty(*r)));
+    }
+
+    /// Remove the points of a rectangle from the region.
+    pub fn subtract_rect(&mut self, rect: Rect) {
+        let rect = rect.abs();
+        self.rects = self.rects.iter().flat_map(|&r| subtract(r, rect)).collect();
+    }
+
+    /// Keep only the points of the region inside a rectangle.
+    pub fn intersect_rect(&mut self, rect: Rect) {
+        let rect = rect.abs();
+        self.rects = self
+            .rects
+            .iter()
+            .map(|r| r.intersect(rect))
+            .filter(|r| !is_empty(*r))
+            .collect();
+    }
+
+    /// Add the points of another region.
+    pub fn union(&mut self, other: &Region) {
+        for &r in &other.rects {
+            self.union_rect(r);
+        }
+    }
+
+    /// Remove the points of another region.
+    pub fn subtract(&mut self, other: &Region) {
+        for &r in &other.rects {
+            self.subtract_rect(r);
+        }
+    }
+
+    /// Keep only the points also in another region.
+    pub fn intersect(&mut self, other: &Region) {
+        self.rects = self
+            .rects
+            .iter()
+            .flat_map(|a| other.rects.iter().map(move |&b| a.intersect(b)))
+            .filter(|r| !is_empty(*r))
+            .collect();
+    }
+}
+
+impl From<Rect> for Region {
+    fn from(rect: Rect) -> Region {
+        let mut region = Region::new();
+        region.union_rect(rect);
+        region
+    }
+}
+
+fn is_empty(r: Rect) -> bool {
+    r.x0 >= r.x1 || r.y0 >= r.y1
+}
+
+/// The parts of `a` outside `b`, as up to four disjoint rectangles.
+///
+/// Both rectangles must have non-negative width and height.
+fn subtract(a: Rect, b: Rect) -> Vec<Rect> {
+    let i = a.intersect(b);
+    if is_empty(i) {
+        return if is_empty(a) { vec![] } else { vec![a] };
+    }
+    // Full-width bands above and below, then the sides of the middle band.
+    let pieces = [
+        Rect::new(a.x0, a.y0, a.x1, i.y0),
+        Rect::new(a.x0, i.y1, a.x1, a.y1),
+        Rect::new(a.x0, i.y0, i.x0, i.y1),
+        Rect::new(i.x1, i.y0, a.x1, i.y1),
+    ];
+    pieces.iter().cloned().filter(|r| !is_empty(*r)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rect, Region, Vec2};
+
+    fn assert_disjoint(region: &Region) {
+        let rects = region.rects();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.x0 < a.x1 && a.y0 < a.y1);
+            for b in &rects[i + 1..] {
+                assert_eq!(a.intersect(*b).area(), 0.0, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn region_ops() {
+        let mut region = Region::from(Rect::new(0.0, 0.0, 10.0, 10.0));
+        region.union_rect(Rect::new(5.0, 5.0, 15.0, 15.0));
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 175.0);
+        assert!(region.contains(Vec2::new(12.0, 12.0)));
+        assert!(!region.contains(Vec2::new(12.0, 2.0)));
+        assert!(!region.contains(Vec2::new(15.0, 15.0)));
+        let bbox = region.bounding_box().unwrap();
+        assert_eq!((bbox.x0, bbox.y0, bbox.x1, bbox.y1), (0.0, 0.0, 15.0, 15.0));
+        assert!(region.contains_rect(Rect::new(6.0, 2.0, 9.0, 14.0)));
+        assert!(!region.contains_rect(Rect::new(2.0, 2.0, 12.0, 4.0)));
+
+        region.subtract_rect(Rect::new(4.0, 4.0, 6.0, 6.0));
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 171.0);
+        assert!(!region.contains(Vec2::new(5.0, 5.0)));
+
+        let mut other = Region::from(Rect::new(0.0, 0.0, 5.0, 20.0));
+        other.union_rect(Rect::new(10.0, 0.0, 20.0, 20.0));
+        let mut both = region.clone();
+        both.intersect(&other);
+        assert_disjoint(&both);
+        // 50 - 2 in the left column, 50 in the right.
+        assert_eq!(both.area(), 98.0);
+
+        region.subtract(&other);
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 171.0 - 98.0);
+
+        region.union(&other);
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 171.0 - 98.0 + 300.0);
+
+        region.intersect_rect(Rect::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(region.area(), 1.0);
+        region.subtract_rect(Rect::new(-1.0, -1.0, 2.0, 2.0));
+        assert!(region.is_empty());
+        assert!(region.bounding_box().is_none());
+    }
+}
@@ -0,0 +1,180 @@
+//! Implementation of rounded rectangle shape.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{Arc, PathEl, Rect, Shape, Vec2};
+
+/// A rectangle with circular corners of equal radius.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct RoundedRect {
+    rect: Rect,
+    radius: f64,
+}
+
+impl RoundedRect {
+    /// A new rounded rectangle from a rectangle and a corner radius.
+    ///
+    /// The rectangle is normalized so that its area is positive, and the
+    /// radius is clamped to between zero and half the shorter side.
+    #[inline]
+    pub fn new(rect: Rect, radius: f64) -> RoundedRect {
+        let rect = rect.abs();
+        let max_radius = 0.5 * rect.width().min(rect.height());
+        RoundedRect {
+            rect,
+            radius: radius.clamp(0.0, max_radius),
+        }
+    }
+
+    /// The rectangle the corners are cut from.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// The radius of the corners.
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// The rectangle through the centers of the corner circles.
+    fn inner(&self) -> Rect {
+        let r = self.radius;
+        Rect::new(
+            self.rect.x0 + r,
+            self.rect.y0 + r,
+            self.rect.x1 - r,
+            self.rect.y1 - r,
+        )
+    }
+}
+
+impl Shape for RoundedRect {
+    type BezPathIter = std::vec::IntoIter<PathEl>;
+
+    fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+        let inner = self.inner();
+        let r = self.radius;
+        let corners = [
+            (Vec2::new(inner.x1, inner.y0), -FRAC_PI_2),
+            (Vec2::new(inner.x1, inner.y1), 0.0),
+            (Vec2::new(inner.x0, inner.y1), FRAC_PI_2),
+            (Vec2::new(inner.x0, inner.y0), PI),
+        ];
+        let mut els = vec![PathEl::Moveto(Vec2::new(inner.x0, self.rect.y0))];
+        for &(center, start_angle) in &corners {
+            let (s, c) = start_angle.sin_cos();
+            els.push(PathEl::Lineto(center + r * Vec2::new(c, s)));
+            if r > 0.0 {
+                let arc = Arc {
+                    center,
+                    radii: Vec2::new(r, r),
+                    start_angle,
+                    sweep_angle: FRAC_PI_2,
+                    x_rotation: 0.0,
+                };
+                els.extend(arc.append_iter(tolerance));
+            }
+        }
+        els.push(PathEl::Closepath);
+        els.into_iter()
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        self.rect.area() - (4.0 - PI) * self.radius * self.radius
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        2.0 * (self.rect.width() + self.rect.height()) - (8.0 - 2.0 * PI) * self.radius
+    }
+
+    /// Like that of [`Rect`](struct.Rect.html), the winding number is one
+    /// on the top and left edges and zero on the bottom and right edges.
+    fn winding(&self, pt: Vec2) -> i32 {
+        if self.rect.winding(pt) == 0 {
+            return 0;
+        }
+        let inner = self.inner();
+        let nearest = Vec2::new(
+            pt.x.clamp(inner.x0, inner.x1),
+            pt.y.clamp(inner.y0, inner.y1),
+        );
+        if (pt - nearest).hypot2() <= self.radius * self.radius {
+            1
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Rect {
+        self.rect
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        self.rect.center()
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let inner = self.inner();
+        let center = Vec2::new(
+            if direction.x < 0.0 {
+                inner.x0
+            } else {
+                inner.x1
+            },
+            if direction.y < 0.0 {
+                inner.y0
+            } else {
+                inner.y1
+            },
+        );
+        let len = direction.hypot();
+        if len > 0.0 {
+            center + direction * (self.radius / len)
+        } else {
+            center
+        }
+    }
+
+    fn as_rounded_rect(&self) -> Option<RoundedRect> {
+        Some(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rect, RoundedRect, Shape, Vec2};
+
+    #[test]
+    fn rounded_rect_shape() {
+        let rr = RoundedRect::new(Rect::new(10.0, 8.0, 0.0, 0.0), 2.0);
+        assert_eq!(rr.rect().x0, 0.0);
+        assert_eq!(RoundedRect::new(rr.rect(), 5.0).radius(), 4.0);
+
+        let path = rr.into_bez_path(1e-9);
+        assert!((rr.area() - path.area()).abs() < 1e-6);
+        assert!((rr.perimeter(1e-9) - path.perimeter(1e-9)).abs() < 1e-6);
+        for &(pt, expected) in &[
+            (Vec2::new(5.0, 4.0), 1),
+            (Vec2::new(0.5, 4.0), 1),
+            (Vec2::new(0.2, 0.2), 0),
+            (Vec2::new(1.0, 1.0), 1),
+            (Vec2::new(11.0, 4.0), 0),
+        ] {
+            assert_eq!(rr.winding(pt), expected, "{:?}", pt);
+            // Paths have the opposite sign convention, see #4.
+            assert_eq!(path.winding(pt).abs(), expected, "{:?}", pt);
+        }
+
+        let d = Vec2::new(1.0, 1.0);
+        let s = rr.support(d);
+        assert!((s - Vec2::new(8.0 + 2f64.sqrt(), 6.0 + 2f64.sqrt())).hypot() < 1e-12);
+        assert!((s.dot(d) - path.support(d).dot(d)).abs() < 1e-6);
+        assert_eq!(rr.as_rounded_rect().unwrap().radius(), 2.0);
+    }
+}
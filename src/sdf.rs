@@ -0,0 +1,298 @@
+//! Signed distance fields.
+
+use crate::shape::default_path;
+use crate::{BezPath, ParamCurve, ParamCurveNearest, PathSeg, Rect, SegmentIndex, Shape, Vec2};
+
+/// The accuracy of nearest point queries, in units of the path.
+const NEAREST_ACCURACY: f64 = 1e-9;
+
+/// Tangents whose cross product exceeds this are at a corner, for edge
+/// coloring; it is the sine of about 8 degrees.
+const CORNER_THRESHOLD: f64 = 0.14;
+
+/// The red channel of a multi-channel distance field.
+const RED: u8 = 1;
+/// The green channel of a multi-channel distance field.
+const GREEN: u8 = 2;
+/// The blue channel of a multi-channel distance field.
+const BLUE: u8 = 4;
+
+impl BezPath {
+    /// The signed distance from a point to the path.
+    ///
+    /// The magnitude is the distance to the nearest point on the path; the
+    /// sign is negative inside the path, under the nonzero winding rule,
+    /// and positive outside. The path should be closed. Returns infinity
+    /// for a path with no segments.
+    pub fn signed_distance(&self, p: Vec2, accuracy: f64) -> f64 {
+        let dist = self
+            .segments()
+            .map(|seg| seg.nearest(p, accuracy).1)
+            .fold(f64::INFINITY, f64::min)
+            .sqrt();
+        if self.winding(p) != 0 {
+            -dist
+        } else {
+            dist
+        }
+    }
+
+    /// The winding number of the path around a point, and the gradient of
+    /// the [signed distance](#method.signed_distance) there.
+    ///
+    /// The winding number is piecewise constant, so it gives optimizers
+    /// nothing to follow; the signed distance is a smooth proxy for it,
+    /// growing away from the inside of the path. Its gradient is the unit
+    /// vector pointing away from the nearest point on the path, reversed
+    /// inside, so stepping against it moves the point into the path. Where
+    /// several points are nearest, one of them is used. The gradient is
+    /// zero on the path itself, and for a path with no segments.
+    pub fn winding_and_gradient(&self, p: Vec2) -> (i32, Vec2) {
+        let winding = self.winding(p);
+        let nearest = self
+            .segments()
+            .map(|seg| {
+                let (t, d2) = seg.nearest(p, NEAREST_ACCURACY);
+                (seg.eval(t), d2)
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let gradient = match nearest {
+            Some((q, d2)) if d2 > 0.0 => {
+                let dir = (p - q) / d2.sqrt();
+                if winding != 0 {
+                    -dir
+                } else {
+                    dir
+                }
+            }
+            _ => Vec2::default(),
+        };
+        (winding, gradient)
+    }
+}
+
+/// Compute a signed distance field for a shape.
+///
+/// The field samples `rect` on a grid of `resolution` samples per unit,
+/// with `(rect.width() * resolution).ceil()` columns and
+/// `(rect.height() * resolution).ceil()` rows, and is returned in row-major
+/// order. Each sample is taken at the center of its grid cell, and holds
+/// the [signed distance](struct.BezPath.html#method.signed_distance) to the
+/// shape in units of the shape, negative inside.
+///
+/// Queries go through a [`SegmentIndex`](struct.SegmentIndex.html), so
+/// large shapes are handled efficiently.
+pub fn sdf(shape: &impl Shape, rect: Rect, resolution: f64) -> Vec<f32> {
+    let path = default_path(shape);
+    let index = SegmentIndex::new(&path);
+    grid_points(rect, resolution)
+        .map(|p| match index.nearest(p, NEAREST_ACCURACY) {
+            Some((_, _, d2)) => {
+                let dist = d2.sqrt();
+                if index.winding(p) != 0 {
+                    -dist as f32
+                } else {
+                    dist as f32
+                }
+            }
+            None => f32::INFINITY,
+        })
+        .collect()
+}
+
+/// Compute a multi-channel signed distance field for a shape.
+///
+/// The grid is the same as for [`sdf`](fn.sdf.html). Following Chlumsky's
+/// method, the segments of each subpath are colored so that the two sides of
+/// every sharp corner differ in at least one channel, and each channel holds
+/// the signed pseudo-distance to the nearest segment of its color, which
+/// extends the segment along its tangent beyond its ends. The median of the
+/// three channels reconstructs the distance to the shape while keeping
+/// corners sharp when the field is magnified.
+pub fn msdf(shape: &impl Shape, rect: Rect, resolution: f64) -> Vec<[f32; 3]> {
+    let path = default_path(shape);
+    let orientation = if path.area() < 0.0 { -1.0 } else { 1.0 };
+    let colored = color_edges(&path);
+    grid_points(rect, resolution)
+        .map(|p| {
+            let mut result = [0.0f32; 3];
+            for (channel, &mask) in [RED, GREEN, BLUE].iter().enumerate() {
+                let mut best: Option<(f64, PathSeg, f64)> = None;
+                for &(seg, color) in &colored {
+                    if color & mask == 0 {
+                        continue;
+                    }
+                    let (t, d2) = seg.nearest(p, NEAREST_ACCURACY);
+                    if best.map(|(best_d2, _, _)| d2 < best_d2).unwrap_or(true) {
+                        best = Some((d2, seg, t));
+                    }
+                }
+                result[channel] = match best {
+                    Some((_, seg, t)) => (orientation * pseudo_distance(seg, t, p)) as f32,
+                    None => f32::INFINITY,
+                };
+            }
+            result
+        })
+        .collect()
+}
+
+/// The centers of the cells of the sampling grid, in row-major order.
+fn grid_points(rect: Rect, resolution: f64) -> impl Iterator<Item = Vec2> {
+    let rect = rect.abs();
+    let width = (rect.width() * resolution).ceil() as usize;
+    let height = (rect.height() * resolution).ceil() as usize;
+    let step = resolution.recip();
+    (0..width * height).map(move |i| {
+        let x = rect.x0 + ((i % width) as f64 + 0.5) * step;
+        let y = rect.y0 + ((i / width) as f64 + 0.5) * step;
+        Vec2::new(x, y)
+    })
+}
+
+/// The signed pseudo-distance to a segment, given the parameter of the
+/// nearest point; negative to the left of the direction of travel.
+///
+/// Beyond the ends of the segment, this is the distance to the tangent line
+/// there, if the point lies ahead of (or behind) the end.
+fn pseudo_distance(seg: PathSeg, t: f64, p: Vec2) -> f64 {
+    let q = seg.eval(t);
+    let tangent = seg.tangent(t);
+    let d = p - q;
+    let dist = d.hypot();
+    let side = tangent.cross(d);
+    if (t == 0.0 && tangent.dot(d) < 0.0) || (t == 1.0 && tangent.dot(d) > 0.0) {
+        return -side;
+    }
+    if side > 0.0 {
+        -dist
+    } else {
+        dist
+    }
+}
+
+/// Assign channels to the segments of a path, so that the segments on
+/// either side of a corner share only one channel.
+fn color_edges(path: &BezPath) -> Vec<(PathSeg, u8)> {
+    const CYAN: u8 = GREEN | BLUE;
+    const MAGENTA: u8 = RED | BLUE;
+    const YELLOW: u8 = RED | GREEN;
+    let mut result = Vec::new();
+    for sub in path.subpath_segs() {
+        let segs: Vec<PathSeg> = sub.segs.iter().map(|&(_, seg)| seg).collect();
+        let n = segs.len();
+        // Segment `i` starts at a corner if it turns sharply from the previous one.
+        let corners: Vec<usize> = (0..n)
+            .filter(|&i| {
+                if i == 0 && !sub.closed {
+                    return false;
+                }
+                let a = segs[(i + n - 1) % n].tangent(1.0);
+                let b = segs[i].tangent(0.0);
+                a.dot(b) <= 0.0 || a.cross(b).abs() > CORNER_THRESHOLD
+            })
+            .collect();
+        if corners.is_empty() {
+            result.extend(segs.iter().map(|&seg| (seg, RED | GREEN | BLUE)));
+            continue;
+        }
+        // The runs of segments between corners, or each segment separately
+        // if there is just one corner.
+        let starts: Vec<usize> = if corners.len() == 1 {
+            (0..n).map(|i| (corners[0] + i) % n).collect()
+        } else {
+            corners
+        };
+        let n_runs = starts.len();
+        let mut colors: Vec<u8> = (0..n_runs)
+            .map(|i| [CYAN, MAGENTA, YELLOW][i % 3])
+            .collect();
+        if n_runs > 1 && colors[n_runs - 1] == colors[0] {
+            // Pick the color differing from both neighbors.
+            colors[n_runs - 1] = [CYAN, MAGENTA, YELLOW]
+                .iter()
+                .cloned()
+                .find(|&c| c != colors[0] && c != colors[n_runs - 2])
+                .unwrap();
+        }
+        for (run, &start) in starts.iter().enumerate() {
+            let end = starts[(run + 1) % n_runs];
+            let len = (end + n - start - 1) % n + 1;
+            for k in 0..len {
+                result.push((segs[(start + k) % n], colors[run]));
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{msdf, sdf, BezPath, Circle, Rect, Vec2};
+
+    fn median(c: [f32; 3]) -> f32 {
+        c[0].max(c[1]).min(c[0].min(c[1]).max(c[2]))
+    }
+
+    #[test]
+    fn signed_distance_square() {
+        let path = BezPath::from_svg("M0 0L4 0L4 4L0 4Z").unwrap();
+        assert!((path.signed_distance(Vec2::new(2.0, 1.0), 1e-9) + 1.0).abs() < 1e-12);
+        assert!((path.signed_distance(Vec2::new(7.0, 8.0), 1e-9) - 5.0).abs() < 1e-12);
+        // Orientation doesn't matter.
+        let rev = BezPath::from_svg("M0 0L0 4L4 4L4 0Z").unwrap();
+        assert!((rev.signed_distance(Vec2::new(2.0, 1.0), 1e-9) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn winding_and_gradient() {
+        let path = BezPath::from_svg("M0 0L4 0L4 4L0 4Z").unwrap();
+        let (winding, grad) = path.winding_and_gradient(Vec2::new(1.0, 2.0));
+        assert!(winding != 0);
+        assert_eq!(grad, Vec2::new(-1.0, 0.0));
+        let (winding, grad) = path.winding_and_gradient(Vec2::new(7.0, 8.0));
+        assert_eq!(winding, 0);
+        assert!((grad - Vec2::new(0.6, 0.8)).hypot() < 1e-12);
+        // It is the derivative of the signed distance.
+        let p = Vec2::new(5.0, 1.5);
+        let h = 1e-6;
+        let (_, grad) = path.winding_and_gradient(p);
+        let dx = path.signed_distance(p + Vec2::new(h, 0.0), 1e-12)
+            - path.signed_distance(p - Vec2::new(h, 0.0), 1e-12);
+        assert!((dx / (2.0 * h) - grad.x).abs() < 1e-6);
+        assert_eq!(
+            path.winding_and_gradient(Vec2::new(4.0, 2.0)).1,
+            Vec2::default()
+        );
+        assert_eq!(BezPath::new().winding_and_gradient(p), (0, Vec2::default()));
+    }
+
+    #[test]
+    fn sdf_circle() {
+        let circle = Circle::new((5.0, 5.0), 3.0);
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let field = sdf(&circle, rect, 2.0);
+        assert_eq!(field.len(), 400);
+        for (i, &d) in field.iter().enumerate() {
+            let p = Vec2::new((i % 20) as f64 * 0.5 + 0.25, (i / 20) as f64 * 0.5 + 0.25);
+            let expected = (p - circle.center).hypot() - 3.0;
+            assert!((d as f64 - expected).abs() < 1e-3, "{} {}", d, expected);
+        }
+    }
+
+    #[test]
+    fn msdf_square() {
+        let path = BezPath::from_svg("M1 1L7 1L7 7L1 7Z").unwrap();
+        let rect = Rect::new(0.0, 0.0, 8.0, 8.0);
+        let field = msdf(&path, rect, 2.0);
+        let plain = sdf(&path, rect, 2.0);
+        assert_eq!(field.len(), plain.len());
+        for (&c, &d) in field.iter().zip(&plain) {
+            let m = median(c);
+            assert!(m.signum() == d.signum() || d.abs() < 1e-6, "{:?} {}", c, d);
+        }
+        // Inside, away from corners, the median is the true distance.
+        let center = field[8 * 16 + 8];
+        assert!((median(center) + 2.75).abs() < 1e-6, "{:?}", center);
+    }
+}
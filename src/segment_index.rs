@@ -0,0 +1,413 @@
+//! A bounding volume hierarchy over the segments of a path.
+
+use std::ops::Range;
+
+use crate::common::solve_poly_interval;
+use crate::{
+    BezPath, MinDistance, ParamCurve, ParamCurveExtrema, ParamCurveNearest, PathSeg, Rect, Vec2,
+};
+
+/// The maximum number of segments in a leaf node.
+const LEAF_SIZE: usize = 4;
+
+/// A spatial index over the segments of a path.
+///
+/// This is a bounding volume hierarchy: a binary tree in which each node
+/// stores the bounding box of the segments below it. Queries skip any
+/// subtree whose box can't contribute, so on large paths they typically
+/// touch O(log n) segments rather than all of them. Build it once and reuse
+/// it for repeated queries against an unchanging path.
+///
+/// Segments are identified by their index in
+/// [`BezPath::segments`](struct.BezPath.html#method.segments).
+#[derive(Clone, Debug)]
+pub struct SegmentIndex {
+    segs: Vec<PathSeg>,
+    // Segment indices, permuted so that each node covers a contiguous range.
+    order: Vec<usize>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    bbox: Rect,
+    // Either a range of `order` (for a leaf) or the indices of two children.
+    kind: NodeKind,
+}
+
+#[derive(Clone, Debug)]
+enum NodeKind {
+    Leaf(Range<usize>),
+    Branch(usize, usize),
+}
+
+/// An intersection of a ray with a segment.
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    /// The index of the segment.
+    pub index: usize,
+    /// The parameter of the hit on the segment.
+    pub t: f64,
+    /// The parameter of the hit on the ray, in units of its direction.
+    pub distance: f64,
+}
+
+impl SegmentIndex {
+    /// Build an index over the segments of a path.
+    pub fn new(path: &BezPath) -> SegmentIndex {
+        let segs: Vec<PathSeg> = path.segments().collect();
+        let bboxes: Vec<Rect> = segs.iter().map(|seg| seg.bounding_box()).collect();
+        let mut order: Vec<usize> = (0..segs.len()).collect();
+        let mut nodes = Vec::new();
+        if !segs.is_empty() {
+            build(&bboxes, &mut order, 0, &mut nodes);
+        }
+        SegmentIndex { segs, order, nodes }
+    }
+
+    /// The number of segments.
+    pub fn len(&self) -> usize {
+        self.segs.len()
+    }
+
+    /// Returns `true` if there are no segments.
+    pub fn is_empty(&self) -> bool {
+        self.segs.is_empty()
+    }
+
+    /// Get the segment with the given index.
+    pub fn get(&self, index: usize) -> Option<PathSeg> {
+        self.segs.get(index).cloned()
+    }
+
+    /// The indices of all segments whose bounding boxes overlap a rectangle.
+    pub fn query_rect(&self, rect: Rect) -> Vec<usize> {
+        let rect = rect.abs();
+        let mut result = Vec::new();
+        self.visit(
+            |bbox| overlaps(bbox, &rect),
+            |ix| {
+                if overlaps(&self.segs[ix].bounding_box(), &rect) {
+                    result.push(ix);
+                }
+            },
+        );
+        result
+    }
+
+    /// Find the nearest point on the path.
+    ///
+    /// Returns the index of the segment, the parameter within that segment,
+    /// and the square of the distance to the point, as
+    /// [`BezPath::nearest`](struct.BezPath.html#method.nearest) does, or
+    /// `None` if there are no segments.
+    pub fn nearest(&self, p: Vec2, accuracy: f64) -> Option<(usize, f64, f64)> {
+        let mut best: Option<(usize, f64, f64)> = None;
+        if !self.nodes.is_empty() {
+            self.nearest_rec(0, p, accuracy, &mut best);
+        }
+        best
+    }
+
+    fn nearest_rec(
+        &self,
+        node: usize,
+        p: Vec2,
+        accuracy: f64,
+        best: &mut Option<(usize, f64, f64)>,
+    ) {
+        match self.nodes[node].kind {
+            NodeKind::Leaf(ref range) => {
+                for &ix in &self.order[range.clone()] {
+                    let (t, d2) = self.segs[ix].nearest(p, accuracy);
+                    if best.map(|(_, _, best_d2)| d2 < best_d2).unwrap_or(true) {
+                        *best = Some((ix, t, d2));
+                    }
+                }
+            }
+            NodeKind::Branch(a, b) => {
+                let da = rect_dist2(&self.nodes[a].bbox, p);
+                let db = rect_dist2(&self.nodes[b].bbox, p);
+                let children = if da <= db {
+                    [(a, da), (b, db)]
+                } else {
+                    [(b, db), (a, da)]
+                };
+                for &(child, d2) in &children {
+                    if best.map(|(_, _, best_d2)| d2 < best_d2).unwrap_or(true) {
+                        self.nearest_rec(child, p, accuracy, best);
+                    }
+                }
+            }
+        }
+    }
+
+    /// All intersections of a ray with the path, ordered by distance along the ray.
+    ///
+    /// The ray starts at `origin` and extends in the direction `dir`, which
+    /// must be nonzero.
+    pub fn ray_hits(&self, origin: Vec2, dir: Vec2) -> Vec<RayHit> {
+        let normal = Vec2::new(-dir.y, dir.x);
+        let mut hits = Vec::new();
+        self.visit(
+            |bbox| ray_hits_rect(bbox, origin, dir),
+            |ix| {
+                let c = self.segs[ix].to_cubic();
+                let coeffs = [
+                    normal.dot(c.p0 - origin),
+                    3.0 * normal.dot(c.p1 - c.p0),
+                    3.0 * normal.dot(c.p2 - 2.0 * c.p1 + c.p0),
+                    normal.dot(c.p3 - 3.0 * c.p2 + 3.0 * c.p1 - c.p0),
+                ];
+                for t in solve_poly_interval(&coeffs, 0.0, 1.0) {
+                    let distance = dir.dot(c.eval(t) - origin) / dir.hypot2();
+                    if distance >= 0.0 {
+                        hits.push(RayHit {
+                            index: ix,
+                            t,
+                            distance,
+                        });
+                    }
+                }
+            },
+        );
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// Winding number of a point, as [`Shape::winding`](trait.Shape.html#tymethod.winding)
+    /// computes for the path.
+    ///
+    /// Only segments whose bounding boxes reach the point's height at or to
+    /// the left of it are examined.
+    pub fn winding(&self, p: Vec2) -> i32 {
+        let mut winding = 0;
+        self.visit(
+            |bbox| bbox.y0 <= p.y && p.y <= bbox.y1 && bbox.x0 <= p.x,
+            |ix| winding += self.segs[ix].winding(p),
+        );
+        winding
+    }
+
+    /// The segments of the path that come within `accuracy` of a segment.
+    ///
+    /// Returns the index of each such segment along with its minimum
+    /// distance from `seg`, as computed by
+    /// [`PathSeg::min_dist`](enum.PathSeg.html#method.min_dist), where
+    /// `t1` refers to `seg` and `t2` to the path segment. The distance is
+    /// computed to half of `accuracy`, so every segment that actually
+    /// intersects `seg` is reported.
+    pub fn intersections(&self, seg: PathSeg, accuracy: f64) -> Vec<(usize, MinDistance)> {
+        let query = seg.bounding_box().inflate(accuracy, accuracy);
+        let mut result = Vec::new();
+        self.visit(
+            |bbox| overlaps(bbox, &query),
+            |ix| {
+                let dist = seg.min_dist(self.segs[ix], 0.5 * accuracy);
+                if dist.distance <= accuracy {
+                    result.push((ix, dist));
+                }
+            },
+        );
+        result
+    }
+
+    /// Call `f` on every segment in leaves reachable through nodes
+    /// accepted by `accept`.
+    fn visit(&self, accept: impl Fn(&Rect) -> bool, mut f: impl FnMut(usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !accept(&node.bbox) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf(ref range) => {
+                    for &ix in &self.order[range.clone()] {
+                        f(ix);
+                    }
+                }
+                NodeKind::Branch(a, b) => {
+                    stack.push(a);
+                    stack.push(b);
+                }
+            }
+        }
+    }
+}
+
+/// Build the subtree for `order`, which starts at `offset` in the full
+/// ordering, returning the index of its root node.
+fn build(bboxes: &[Rect], order: &mut [usize], offset: usize, nodes: &mut Vec<Node>) -> usize {
+    let bbox = order
+        .iter()
+        .map(|&ix| bboxes[ix])
+        .fold(bboxes[order[0]], |acc, bb| acc.union(bb));
+    let node = nodes.len();
+    nodes.push(Node {
+        bbox,
+        kind: NodeKind::Leaf(offset..offset + order.len()),
+    });
+    if order.len() > LEAF_SIZE {
+        // Split at the median center along the longer axis.
+        let key: fn(&Rect) -> f64 = if bbox.width() >= bbox.height() {
+            |bb| bb.x0 + bb.x1
+        } else {
+            |bb| bb.y0 + bb.y1
+        };
+        order.sort_by(|&a, &b| key(&bboxes[a]).total_cmp(&key(&bboxes[b])));
+        let mid = order.len() / 2;
+        let (lo, hi) = order.split_at_mut(mid);
+        let a = build(bboxes, lo, offset, nodes);
+        let b = build(bboxes, hi, offset + mid, nodes);
+        nodes[node].kind = NodeKind::Branch(a, b);
+    }
+    node
+}
+
+/// Whether two rectangles overlap, counting shared edges.
+pub(crate) fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.x0 <= b.x1 && b.x0 <= a.x1 && a.y0 <= b.y1 && b.y0 <= a.y1
+}
+
+/// The squared distance from a point to a rectangle, zero if inside.
+fn rect_dist2(rect: &Rect, p: Vec2) -> f64 {
+    let dx = (rect.x0 - p.x).max(p.x - rect.x1).max(0.0);
+    let dy = (rect.y0 - p.y).max(p.y - rect.y1).max(0.0);
+    dx * dx + dy * dy
+}
+
+/// Whether a ray meets a rectangle, by the slab method.
+fn ray_hits_rect(rect: &Rect, origin: Vec2, dir: Vec2) -> bool {
+    let mut t_min = 0.0f64;
+    let mut t_max = f64::INFINITY;
+    for &(o, d, lo, hi) in &[
+        (origin.x, dir.x, rect.x0, rect.x1),
+        (origin.y, dir.y, rect.y0, rect.y1),
+    ] {
+        if d == 0.0 {
+            if o < lo || o > hi {
+                return false;
+            }
+        } else {
+            let t0 = (lo - o) / d;
+            let t1 = (hi - o) / d;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+    }
+    t_min <= t_max
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BezPath, Line, ParamCurve, ParamCurveExtrema, PathSeg, Rect, SegmentIndex, Shape, Vec2,
+    };
+    use std::f64::consts::PI;
+
+    /// A wiggly closed path with many segments.
+    fn wiggle(n: usize) -> BezPath {
+        let mut path = BezPath::new();
+        for i in 0..n {
+            let th = 2.0 * PI * (i as f64) / (n as f64);
+            let r = 10.0 + 2.0 * (7.0 * th).sin();
+            let p = Vec2::from_angle(th) * r;
+            if i == 0 {
+                path.moveto(p);
+            } else if i % 2 == 0 {
+                path.lineto(p);
+            } else {
+                path.quadto(p * 1.05, p);
+            }
+        }
+        path.closepath();
+        path
+    }
+
+    #[test]
+    fn index_nearest() {
+        let path = wiggle(200);
+        let index = SegmentIndex::new(&path);
+        assert_eq!(index.len(), 200);
+        for &p in &[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(11.0, 3.0),
+            Vec2::new(-30.0, 5.0),
+        ] {
+            let (ix, t, d2) = index.nearest(p, 1e-9).unwrap();
+            let (ix_ref, _, d2_ref) = path.nearest(p, 1e-9);
+            assert!((d2 - d2_ref).abs() < 1e-9, "{} {}", d2, d2_ref);
+            let q = index.get(ix).unwrap().eval(t);
+            assert!(((q - p).hypot2() - d2).abs() < 1e-9);
+            if ix != ix_ref {
+                // Ties happen only at shared endpoints.
+                assert!(d2 == d2_ref);
+            }
+        }
+        assert!(SegmentIndex::new(&BezPath::new())
+            .nearest(Vec2::default(), 1e-9)
+            .is_none());
+    }
+
+    #[test]
+    fn index_winding_and_rays() {
+        let path = wiggle(200);
+        let index = SegmentIndex::new(&path);
+        for &p in &[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(11.0, 3.0),
+            Vec2::new(-30.0, 5.0),
+        ] {
+            assert_eq!(index.winding(p), path.winding(p));
+        }
+        let hits = index.ray_hits(Vec2::new(0.0, 0.3), Vec2::new(1.0, 0.0));
+        assert_eq!(hits.len(), 1);
+        let hit = hits[0];
+        let q = index.get(hit.index).unwrap().eval(hit.t);
+        assert!((q.y - 0.3).abs() < 1e-9 && (q.x - hit.distance).abs() < 1e-9);
+        let hits = index.ray_hits(Vec2::new(-20.0, 0.3), Vec2::new(1.0, 0.0));
+        assert_eq!(hits.len(), 2);
+        assert!(hits[0].distance < hits[1].distance);
+    }
+
+    #[test]
+    fn index_queries() {
+        let path = wiggle(200);
+        let index = SegmentIndex::new(&path);
+        let rect = Rect::new(8.0, -1.0, 13.0, 1.0);
+        let mut found = index.query_rect(rect);
+        found.sort();
+        let expected: Vec<usize> = path
+            .segments()
+            .enumerate()
+            .filter(|(_, seg)| {
+                let bb = seg.bounding_box();
+                bb.x0 <= rect.x1 && rect.x0 <= bb.x1 && bb.y0 <= rect.y1 && rect.y0 <= bb.y1
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+        assert_eq!(found, expected);
+        assert!(!found.is_empty());
+
+        let chord = PathSeg::Line(Line::new((0.0, 0.0), (20.0, 0.1)));
+        let hits = index.intersections(chord, 1e-6);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].1.distance <= 1e-6);
+    }
+
+    #[test]
+    fn index_non_finite() {
+        let mut path = wiggle(40);
+        path.lineto((f64::NAN, 1.0));
+        path.lineto((2.0, f64::INFINITY));
+        path.closepath();
+        let index = SegmentIndex::new(&path);
+        assert_eq!(index.len(), 43);
+        let hits = index.ray_hits(Vec2::new(-20.0, 0.3), Vec2::new(1.0, 0.0));
+        assert!(hits.windows(2).all(|w| w[0].distance <= w[1].distance));
+    }
+}
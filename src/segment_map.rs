@@ -0,0 +1,166 @@
+//! Mapping between segment and element indices of a path.
+
+use crate::{BezPath, BezPathSlice, PathEl, PathSeg, Vec2};
+
+/// A precomputed mapping between the segments of a path and its elements.
+///
+/// Hit tests such as [`BezPath::nearest`](struct.BezPath.html#method.nearest)
+/// report segment indices, counting segments as produced by
+/// [`segments`](struct.BezPath.html#method.segments), while editing works
+/// on the element list, which also contains `Moveto` elements and
+/// `Closepath` elements that may not produce a segment. This maps between
+/// the two in constant time, and stores the segments for constant time
+/// access. It must be rebuilt when the path changes.
+#[derive(Clone, Debug, Default)]
+pub struct SegmentMap {
+    /// Each segment, with the index of the element that produced it.
+    segs: Vec<(usize, PathSeg)>,
+    /// The segment index of each element, if it produced one.
+    el_segs: Vec<Option<usize>>,
+}
+
+impl SegmentMap {
+    /// Build the mapping for a path.
+    pub fn new<'a>(path: impl Into<BezPathSlice<'a>>) -> SegmentMap {
+        let elements = path.into().elements();
+        let segs: Vec<_> = segment_elements(elements)
+            .zip(BezPathSlice::new(elements).segments())
+            .collect();
+        let mut el_segs = vec![None; elements.len()];
+        for (seg_ix, &(el_ix, _)) in segs.iter().enumerate() {
+            el_segs[el_ix] = Some(seg_ix);
+        }
+        SegmentMap { segs, el_segs }
+    }
+
+    /// The number of segments.
+    pub fn len(&self) -> usize {
+        self.segs.len()
+    }
+
+    /// Returns `true` if the path has no segments.
+    pub fn is_empty(&self) -> bool {
+        self.segs.is_empty()
+    }
+
+    /// The segment with the given segment index.
+    pub fn get_seg(&self, seg_index: usize) -> Option<PathSeg> {
+        self.segs.get(seg_index).map(|&(_, seg)| seg)
+    }
+
+    /// The index of the element that produced a segment.
+    pub fn seg_to_el(&self, seg_index: usize) -> Option<usize> {
+        self.segs.get(seg_index).map(|&(ix, _)| ix)
+    }
+
+    /// The index of the segment produced by an element, if any.
+    pub fn el_to_seg(&self, el_index: usize) -> Option<usize> {
+        self.el_segs.get(el_index).cloned().flatten()
+    }
+}
+
+/// The indices of the elements that produce segments in `BezPath::segments`.
+///
+/// A `Closepath` produces a segment only if the subpath isn't already
+/// back at its start.
+fn segment_elements<'a>(elements: &'a [PathEl]) -> impl Iterator<Item = usize> + 'a {
+    let mut start = Vec2::default();
+    let mut last = Vec2::default();
+    elements.iter().enumerate().filter_map(move |(ix, el)| {
+        let (p, produces) = match *el {
+            PathEl::Moveto(p) => {
+                start = p;
+                (p, false)
+            }
+            PathEl::Lineto(p) | PathEl::Quadto(_, p) | PathEl::Curveto(_, _, p) => (p, true),
+            PathEl::Closepath => (start, last != start),
+        };
+        last = p;
+        if produces {
+            Some(ix)
+        } else {
+            None
+        }
+    })
+}
+
+impl<'a> BezPathSlice<'a> {
+    /// The index of the element that produced the segment with the given
+    /// segment index.
+    ///
+    /// This takes time linear in the size of the path; build a
+    /// [`SegmentMap`](struct.SegmentMap.html) for repeated queries.
+    pub fn seg_to_el(self, seg_index: usize) -> Option<usize> {
+        segment_elements(self.elements()).nth(seg_index)
+    }
+
+    /// The segment index of the segment produced by the element with the
+    /// given element index, if it produced one.
+    ///
+    /// This takes time linear in the size of the path; build a
+    /// [`SegmentMap`](struct.SegmentMap.html) for repeated queries.
+    pub fn el_to_seg(self, el_index: usize) -> Option<usize> {
+        segment_elements(self.elements())
+            .take_while(|&ix| ix <= el_index)
+            .position(|ix| ix == el_index)
+    }
+}
+
+impl BezPath {
+    /// The index of the element that produced the segment with the given
+    /// segment index, as reported by [`nearest`](#method.nearest).
+    ///
+    /// This takes time linear in the size of the path; build a
+    /// [`SegmentMap`](struct.SegmentMap.html) for repeated queries.
+    pub fn seg_to_el(&self, seg_index: usize) -> Option<usize> {
+        self.as_slice().seg_to_el(seg_index)
+    }
+
+    /// The segment index of the segment produced by the element with the
+    /// given element index, if it produced one.
+    ///
+    /// This takes time linear in the size of the path; build a
+    /// [`SegmentMap`](struct.SegmentMap.html) for repeated queries.
+    pub fn el_to_seg(&self, el_index: usize) -> Option<usize> {
+        self.as_slice().el_to_seg(el_index)
+    }
+
+    /// Build a mapping between segment and element indices.
+    pub fn segment_map(&self) -> SegmentMap {
+        SegmentMap::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, ParamCurve, Vec2};
+
+    #[test]
+    fn seg_el_mapping() {
+        // The first Closepath is already at the start, so produces no segment.
+        let path = BezPath::from_svg("M0 0L10 0L0 0ZM20 0L30 0L30 10Z").unwrap();
+        let map = path.segment_map();
+        assert_eq!(map.len(), 5);
+        let els: Vec<_> = (0..5).map(|i| map.seg_to_el(i).unwrap()).collect();
+        assert_eq!(els, vec![1, 2, 5, 6, 7]);
+        for (seg_ix, &el_ix) in els.iter().enumerate() {
+            assert_eq!(map.el_to_seg(el_ix), Some(seg_ix));
+            assert_eq!(path.seg_to_el(seg_ix), Some(el_ix));
+            assert_eq!(path.el_to_seg(el_ix), Some(seg_ix));
+            let seg = map.get_seg(seg_ix).unwrap();
+            let expected = path.get_seg(el_ix).unwrap();
+            assert_eq!(seg.start(), expected.start());
+            assert_eq!(seg.end(), expected.end());
+        }
+        for &el_ix in &[0, 3, 4, 8] {
+            assert_eq!(map.el_to_seg(el_ix), None);
+            assert_eq!(path.el_to_seg(el_ix), None);
+        }
+        assert_eq!(map.seg_to_el(5), None);
+        assert_eq!(path.seg_to_el(5), None);
+
+        // A hit test result maps back to the element that was hit.
+        let (seg_ix, _, _) = path.nearest(Vec2::new(31.0, 5.0), 1e-9);
+        assert_eq!(map.seg_to_el(seg_ix), Some(6));
+    }
+}
@@ -1,6 +1,13 @@
 //! A generic trait for shapes.
 
-use crate::{BezPath, Circle, Line, PathEl, Rect, Vec2};
+use crate::common::solve_quadratic;
+use crate::{
+    Affine, Arc, BezPath, Circle, Ellipse, Line, ParamCurve, PathEl, PathSeg, Rect, RoundedRect,
+    Triangle, Vec2,
+};
+
+/// The tolerance used by default methods that need a Bézier path.
+const DEFAULT_TOLERANCE: f64 = 1e-6;
 
 /// A generic trait for open and closed shapes.
 pub trait Shape: Sized {
@@ -35,7 +42,18 @@ pub trait Shape: Sized {
     fn area(&self) -> f64;
 
     /// Total length of perimeter.
-    fn perimeter(&self, accuracy: f64) -> f64;
+    ///
+    /// The default implementation sums the arclengths of the segments of
+    /// the shape's Bézier path, converted with `accuracy` as the tolerance.
+    /// Shapes with an exact or cheaper measure, such as circles, rectangles
+    /// and ellipses, override it.
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        if let Some(slice) = self.as_path_slice() {
+            BezPath::segments_of_iter(slice.iter().cloned()).arclen(accuracy)
+        } else {
+            BezPath::segments_of_iter(self.to_bez_path(accuracy)).arclen(accuracy)
+        }
+    }
 
     /// Winding number of point.
     ///
@@ -47,6 +65,70 @@ pub trait Shape: Sized {
     /// The smallest rectangle that encloses the shape.
     fn bounding_box(&self) -> Rect;
 
+    /// The smallest rectangle that encloses the shape after applying a transform.
+    ///
+    /// This transforms the shape rather than its bounding box, so the result
+    /// stays tight under rotation and skew. The default implementation
+    /// transforms the path elements as they are iterated, converted with a
+    /// tolerance of 1e-6, without allocating a transformed path.
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        if let Some(slice) = self.as_path_slice() {
+            BezPath::segments_of_iter(slice.iter().map(|&el| affine * el)).bounding_box()
+        } else {
+            let els = self.to_bez_path(DEFAULT_TOLERANCE).map(|el| affine * el);
+            BezPath::segments_of_iter(els).bounding_box()
+        }
+    }
+
+    /// The centroid (center of mass) of the area enclosed by the shape.
+    ///
+    /// The default implementation computes the moments of the shape's Bézier
+    /// path, converted with a tolerance of 1e-6; see
+    /// [`BezPath::centroid`](struct.BezPath.html#method.centroid). Shapes with
+    /// a closed form override it.
+    fn centroid(&self) -> Vec2 {
+        default_path(self).centroid()
+    }
+
+    /// A circle enclosing the shape.
+    ///
+    /// The default implementation returns the smallest circle enclosing the
+    /// control points of the shape's Bézier path. A shape that is not
+    /// itself a path is converted with a tolerance of 1e-6, and the circle
+    /// enlarged by that amount. This contains the shape, but is not
+    /// necessarily the smallest circle that does. Shapes with a closed form
+    /// override it.
+    fn bounding_circle(&self) -> Circle {
+        let c = Circle::enclosing(&control_points(&default_path(self)));
+        if self.as_path_slice().is_some() {
+            c
+        } else {
+            Circle::new(c.center, c.radius + DEFAULT_TOLERANCE)
+        }
+    }
+
+    /// The farthest point of the shape in a direction.
+    ///
+    /// This is a point of the shape with the greatest dot product with
+    /// `direction`, the support point used by GJK and related collision
+    /// algorithms. It is also the farthest point of the convex hull of the
+    /// shape, so those algorithms see a shape that isn't convex as its hull.
+    /// If several points are farthest, as along a side of a polygon, any of
+    /// them may be returned, and if the direction is zero, any point of the
+    /// shape.
+    ///
+    /// The default implementation finds the farthest point on the segments
+    /// of the shape's Bézier path, converted with a tolerance of 1e-6, or
+    /// the origin if there are none. Shapes with a closed form override it.
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest(
+            default_path(self)
+                .segments()
+                .map(|seg| segment_support(seg, direction)),
+            direction,
+        )
+    }
+
     /// If the shape is a line, make it available.
     fn as_line(&self) -> Option<Line> {
         None
@@ -57,11 +139,42 @@ pub trait Shape: Sized {
         None
     }
 
+    /// If the shape is a rounded rectangle, make it available.
+    fn as_rounded_rect(&self) -> Option<RoundedRect> {
+        None
+    }
+
     /// If the shape is a circle, make it available.
     fn as_circle(&self) -> Option<Circle> {
         None
     }
 
+    /// If the shape is an ellipse, make it available.
+    ///
+    /// Circles are ellipses too, so a renderer with a fast path only for
+    /// ellipses can take it for both.
+    fn as_ellipse(&self) -> Option<Ellipse> {
+        None
+    }
+
+    /// If the shape is an elliptical arc, make it available.
+    fn as_arc(&self) -> Option<Arc> {
+        None
+    }
+
+    /// If the shape is a triangle, make it available.
+    fn as_triangle(&self) -> Option<Triangle> {
+        None
+    }
+
+    /// If the shape is a polygon, make its vertices available.
+    ///
+    /// The polygon is closed, with an edge from the last vertex back to the
+    /// first. Unlike the other `as_` methods, this allocates.
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        None
+    }
+
     /// If the shape is stored as a slice of path elements, make
     /// that available.
     ///
@@ -71,13 +184,55 @@ pub trait Shape: Sized {
     fn as_path_slice(&self) -> Option<&[PathEl]> {
         None
     }
+}
+
+/// The Bézier path of a shape, for default method implementations.
+pub(crate) fn default_path(shape: &impl Shape) -> BezPath {
+    if let Some(slice) = shape.as_path_slice() {
+        BezPath::from_vec(slice.to_vec())
+    } else {
+        BezPath::from_vec(shape.to_bez_path(DEFAULT_TOLERANCE).collect())
+    }
+}
+
+/// All the points of a path, including off-curve control points.
+pub(crate) fn control_points(path: &BezPath) -> Vec<Vec2> {
+    let mut points = Vec::new();
+    for el in path.elements() {
+        match *el {
+            PathEl::Moveto(p) | PathEl::Lineto(p) => points.push(p),
+            PathEl::Quadto(p1, p2) => points.extend(&[p1, p2]),
+            PathEl::Curveto(p1, p2, p3) => points.extend(&[p1, p2, p3]),
+            PathEl::Closepath => (),
+        }
+    }
+    points
+}
 
-    // TODO: we'll have as_circle and probably as_rounded_rect,
-    // as it's likely renderers will special-case on those.
+/// The farthest of some points in a direction, or the origin if there are
+/// none.
+pub(crate) fn farthest(pts: impl Iterator<Item = Vec2>, direction: Vec2) -> Vec2 {
+    pts.max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+        .unwrap_or_default()
+}
+
+/// The farthest point of a segment in a direction.
+fn segment_support(seg: PathSeg, direction: Vec2) -> Vec2 {
+    let c = seg.to_cubic();
+    // The projection of the derivative, as a quadratic in Bernstein form.
+    let q0 = (c.p1 - c.p0).dot(direction);
+    let q1 = (c.p2 - c.p1).dot(direction);
+    let q2 = (c.p3 - c.p2).dot(direction);
+    let roots = solve_quadratic(q0, 2.0 * (q1 - q0), q0 - 2.0 * q1 + q2);
+    let ts = roots.into_iter().filter(|t| (0.0..=1.0).contains(t));
+    farthest(
+        [0.0, 1.0].iter().cloned().chain(ts).map(|t| c.eval(t)),
+        direction,
+    )
 }
 
 /// Blanket implementation so `impl Shape` will accept owned or reference.
-impl<'a, T: Shape> Shape for &'a T {
+impl<T: Shape> Shape for &T {
     type BezPathIter = T::BezPathIter;
 
     fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
@@ -100,6 +255,22 @@ impl<'a, T: Shape> Shape for &'a T {
         (*self).bounding_box()
     }
 
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        (*self).bounding_box_affine(affine)
+    }
+
+    fn centroid(&self) -> Vec2 {
+        (*self).centroid()
+    }
+
+    fn bounding_circle(&self) -> Circle {
+        (*self).bounding_circle()
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        (*self).support(direction)
+    }
+
     fn as_line(&self) -> Option<Line> {
         (*self).as_line()
     }
@@ -108,7 +279,107 @@ impl<'a, T: Shape> Shape for &'a T {
         (*self).as_rect()
     }
 
+    fn as_rounded_rect(&self) -> Option<RoundedRect> {
+        (*self).as_rounded_rect()
+    }
+
+    fn as_circle(&self) -> Option<Circle> {
+        (*self).as_circle()
+    }
+
+    fn as_ellipse(&self) -> Option<Ellipse> {
+        (*self).as_ellipse()
+    }
+
+    fn as_arc(&self) -> Option<Arc> {
+        (*self).as_arc()
+    }
+
+    fn as_triangle(&self) -> Option<Triangle> {
+        (*self).as_triangle()
+    }
+
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        (*self).as_polygon()
+    }
+
     fn as_path_slice(&self) -> Option<&[PathEl]> {
         (*self).as_path_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Affine, Arc, BezPath, Circle, Ellipse, Line, PathEl, Rect, Shape, Star, TransformedShape,
+        Triangle, Vec2,
+    };
+
+    /// A circle that only knows how to draw itself.
+    struct Outline(Circle);
+
+    impl Shape for Outline {
+        type BezPathIter = <Circle as Shape>::BezPathIter;
+
+        fn to_bez_path(&self, tolerance: f64) -> Self::BezPathIter {
+            self.0.to_bez_path(tolerance)
+        }
+
+        fn area(&self) -> f64 {
+            self.0.area()
+        }
+
+        fn winding(&self, pt: Vec2) -> i32 {
+            self.0.winding(pt)
+        }
+
+        fn bounding_box(&self) -> Rect {
+            self.0.bounding_box()
+        }
+    }
+
+    #[test]
+    fn default_perimeter() {
+        let circle = Circle::new((1.0, 2.0), 3.0);
+        let perimeter = Outline(circle).perimeter(1e-9);
+        assert!((perimeter - circle.perimeter(1e-9)).abs() < 1e-6);
+    }
+
+    /// Check the support point against a fine flattening of the shape.
+    fn check_support(shape: impl Shape) {
+        let path = BezPath::from_vec(shape.to_bez_path(1e-9).collect());
+        let mut pts = Vec::new();
+        path.flatten(1e-6, |el| match el {
+            PathEl::Moveto(p) | PathEl::Lineto(p) => pts.push(p),
+            _ => (),
+        });
+        for i in 0..16 {
+            let dir = 2.5 * Vec2::from_angle(i as f64 * 0.4 + 0.1);
+            let expected = pts.iter().map(|p| p.dot(dir)).fold(f64::MIN, f64::max);
+            let support = shape.support(dir);
+            assert!((support.dot(dir) - expected).abs() < 1e-4, "{:?}", dir);
+            assert!(path.nearest(support, 1e-9).2 < 1e-8, "{:?}", dir);
+        }
+    }
+
+    #[test]
+    fn support() {
+        check_support(Circle::new((1.0, 2.0), 3.0));
+        check_support(Rect::new(4.0, 3.0, 1.0, 2.0));
+        check_support(Line::new((1.0, 2.0), (3.0, -1.0)));
+        check_support(Triangle::new((0.0, 0.0), (4.0, 1.0), (1.0, 3.0)));
+        check_support(Star::new((0.0, 0.0), 2.0, 1.0, 5).with_rotation(0.3));
+        check_support(Ellipse::new((1.0, 2.0), (3.0, 1.0), 0.7));
+        check_support(Arc::new((1.0, 2.0), (3.0, 1.0), 0.3, -2.5, 0.7));
+        let affine = Affine::rotate(0.3) * Affine::new([3.0, 0.5, 0.0, 1.0, 2.0, 0.0]);
+        check_support(TransformedShape::new(Circle::new((1.0, 0.0), 1.0), affine));
+        // A path that isn't convex, by the default method.
+        let path = BezPath::from_svg("M0 0C10 0 0 10 10 10L5 3Z").unwrap();
+        check_support(&path);
+        assert_eq!(path.support(Vec2::new(-1.0, -1.0)), Vec2::new(0.0, 0.0));
+        // A NaN direction gives some point rather than panicking.
+        let triangle = Triangle::new((0.0, 0.0), (4.0, 1.0), (1.0, 3.0));
+        let _ = triangle.support(Vec2::new(f64::NAN, 1.0));
+        let _ = path.support(Vec2::new(f64::NAN, 1.0));
+    }
+}
@@ -0,0 +1,180 @@
+//! Simplification of polylines.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::{Line, ParamCurveNearest, Vec2};
+
+/// The algorithm used by [`simplify_polyline`](fn.simplify_polyline.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimplifyMethod {
+    /// The Ramer–Douglas–Peucker algorithm.
+    ///
+    /// The tolerance is the maximum distance from a removed point to the
+    /// simplified polyline.
+    DouglasPeucker,
+    /// The Visvalingam–Whyatt algorithm.
+    ///
+    /// Points are removed in order of the area of the triangle they form with
+    /// their neighbors, as long as that area is less than the square of the
+    /// tolerance. This tends to give more natural results than
+    /// Douglas-Peucker for cartographic and hand-drawn input.
+    Visvalingam,
+}
+
+/// Simplify a polyline by removing points.
+///
+/// The first and last points are always kept, as are all points of a
+/// polyline with fewer than three points. Consecutive duplicate points are
+/// removed regardless of the tolerance.
+pub fn simplify_polyline(points: &[Vec2], tolerance: f64, method: SimplifyMethod) -> Vec<Vec2> {
+    let mut pts = Vec::with_capacity(points.len());
+    for &p in points {
+        if pts.last() != Some(&p) {
+            pts.push(p);
+        }
+    }
+    if pts.len() < 3 {
+        return pts;
+    }
+    let keep = match method {
+        SimplifyMethod::DouglasPeucker => douglas_peucker(&pts, tolerance),
+        SimplifyMethod::Visvalingam => visvalingam(&pts, tolerance * tolerance),
+    };
+    pts.iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(&p, _)| p)
+        .collect()
+}
+
+fn douglas_peucker(pts: &[Vec2], tolerance: f64) -> Vec<bool> {
+    let n = pts.len();
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i0, i1)) = stack.pop() {
+        let line = Line::new(pts[i0], pts[i1]);
+        let mut best = (0.0, i0);
+        for (i, &p) in pts.iter().enumerate().take(i1).skip(i0 + 1) {
+            let d = line.nearest(p, 0.0).1;
+            if d > best.0 {
+                best = (d, i);
+            }
+        }
+        if best.0 > tolerance * tolerance {
+            keep[best.1] = true;
+            stack.push((i0, best.1));
+            stack.push((best.1, i1));
+        }
+    }
+    keep
+}
+
+/// A candidate for removal, ordered so the smallest area is popped first.
+struct Candidate {
+    area: f64,
+    ix: usize,
+    version: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then(other.ix.cmp(&self.ix))
+    }
+}
+
+fn visvalingam(pts: &[Vec2], max_area: f64) -> Vec<bool> {
+    let n = pts.len();
+    let mut keep = vec![true; n];
+    let mut prev: Vec<usize> = (0..n).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| i + 1).collect();
+    let mut version = vec![0; n];
+    let area = |a: Vec2, b: Vec2, c: Vec2| 0.5 * (b - a).cross(c - a).abs();
+    let mut heap: BinaryHeap<Candidate> = (1..n - 1)
+        .map(|ix| Candidate {
+            area: area(pts[ix - 1], pts[ix], pts[ix + 1]),
+            ix,
+            version: 0,
+        })
+        .collect();
+    // The effective area of a point never drops below that of a point removed
+    // before it, so that removal order matches the original algorithm.
+    let mut min_area = 0.0f64;
+    while let Some(c) = heap.pop() {
+        if c.version != version[c.ix] {
+            continue;
+        }
+        if c.area >= max_area {
+            break;
+        }
+        min_area = min_area.max(c.area);
+        keep[c.ix] = false;
+        let (p, q) = (prev[c.ix], next[c.ix]);
+        next[p] = q;
+        prev[q] = p;
+        for &ix in &[p, q] {
+            if ix != 0 && ix != n - 1 {
+                version[ix] += 1;
+                heap.push(Candidate {
+                    area: area(pts[prev[ix]], pts[ix], pts[next[ix]]).max(min_area),
+                    ix,
+                    version: version[ix],
+                });
+            }
+        }
+    }
+    keep
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{simplify_polyline, SimplifyMethod, Vec2};
+
+    #[test]
+    fn simplify_zigzag() {
+        let pts: Vec<Vec2> = (0..=10)
+            .map(|i| Vec2::new(i as f64, if i % 2 == 1 { 0.1 } else { 0.0 }))
+            .chain(Some(Vec2::new(10.0, 10.0)))
+            .collect();
+        for &method in &[SimplifyMethod::DouglasPeucker, SimplifyMethod::Visvalingam] {
+            let result = simplify_polyline(&pts, 1.0, method);
+            assert_eq!(
+                result,
+                vec![
+                    Vec2::new(0.0, 0.0),
+                    Vec2::new(10.0, 0.0),
+                    Vec2::new(10.0, 10.0)
+                ]
+            );
+            assert_eq!(simplify_polyline(&pts, 0.01, method), pts);
+        }
+    }
+
+    #[test]
+    fn simplify_degenerate() {
+        let p = Vec2::new(1.0, 2.0);
+        let method = SimplifyMethod::DouglasPeucker;
+        assert!(simplify_polyline(&[], 1.0, method).is_empty());
+        assert_eq!(simplify_polyline(&[p, p, p], 1.0, method), vec![p]);
+    }
+}
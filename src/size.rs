@@ -0,0 +1,96 @@
+//! A 2D size.
+
+use crate::Vec2;
+
+/// A 2D size, for layout.
+///
+/// Geometry elsewhere, such as [`Rect::size`](struct.Rect.html#method.size),
+/// uses a [`Vec2`](struct.Vec2.html) for sizes; this converts to and from
+/// one.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Size {
+    /// The width.
+    pub width: f64,
+    /// The height.
+    pub height: f64,
+}
+
+impl Size {
+    /// A new size.
+    #[inline]
+    pub fn new(width: f64, height: f64) -> Size {
+        Size { width, height }
+    }
+
+    /// The size with each dimension limited to the range between those of
+    /// `min` and `max`.
+    ///
+    /// If a dimension of `min` is greater than that of `max`, `min` wins.
+    #[inline]
+    pub fn clamp(&self, min: Size, max: Size) -> Size {
+        Size {
+            width: self.width.min(max.width).max(min.width),
+            height: self.height.min(max.height).max(min.height),
+        }
+    }
+
+    /// The smallest size with the given aspect ratio, width over height,
+    /// that contains this one.
+    ///
+    /// One dimension is kept, and the other grows. The ratio should be
+    /// positive.
+    #[inline]
+    pub fn expand_to_aspect(&self, ratio: f64) -> Size {
+        if self.width < self.height * ratio {
+            Size::new(self.height * ratio, self.height)
+        } else {
+            Size::new(self.width, self.width / ratio)
+        }
+    }
+}
+
+impl From<Vec2> for Size {
+    #[inline]
+    fn from(v: Vec2) -> Size {
+        Size::new(v.x, v.y)
+    }
+}
+
+impl From<Size> for Vec2 {
+    #[inline]
+    fn from(s: Size) -> Vec2 {
+        Vec2::new(s.width, s.height)
+    }
+}
+
+impl From<(f64, f64)> for Size {
+    #[inline]
+    fn from(v: (f64, f64)) -> Size {
+        Size::new(v.0, v.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Rect, Size, Vec2};
+
+    #[test]
+    fn size_constraints() {
+        let min = Size::new(10.0, 10.0);
+        let max = Size::new(100.0, 50.0);
+        assert_eq!(Size::new(5.0, 80.0).clamp(min, max), Size::new(10.0, 50.0));
+        assert_eq!(Size::new(40.0, 20.0).clamp(min, max), Size::new(40.0, 20.0));
+
+        assert_eq!(
+            Size::new(40.0, 20.0).expand_to_aspect(1.0),
+            Size::new(40.0, 40.0)
+        );
+        assert_eq!(
+            Size::new(40.0, 20.0).expand_to_aspect(4.0),
+            Size::new(80.0, 20.0)
+        );
+        let size: Size = Rect::new(1.0, 2.0, 4.0, 6.0).size().into();
+        assert_eq!(size, Size::new(3.0, 4.0));
+        assert_eq!(Vec2::from(size), Vec2::new(3.0, 4.0));
+    }
+}
@@ -0,0 +1,130 @@
+//! Enforcing tangent continuity at nearly smooth joints.
+
+use crate::{BezPath, PathEl, PathSeg, Vec2};
+
+impl BezPath {
+    /// Make the tangents collinear at joints that are nearly smooth.
+    ///
+    /// At each joint between segments where the tangent direction changes by
+    /// less than `angle_tolerance` radians, the control handles of the adjacent
+    /// cubic segments are rotated about the joint so that both tangents follow a
+    /// common direction. Handle lengths and all on-curve points are preserved,
+    /// so the path moves by at most the handle length times the angle. Joints
+    /// where a handle would move farther than `max_offset` are left alone, so
+    /// that long handles don't visibly change the shape.
+    ///
+    /// Lines and quadratic segments are left unchanged, since moving the single
+    /// control point of a quadratic would change its tangent at the other end
+    /// too; a cubic handle next to one of them is aligned to its tangent instead.
+    /// Joints with larger angles are considered intentional corners.
+    pub fn smooth(&mut self, angle_tolerance: f64, max_offset: f64) {
+        let mut els = self.elements().to_vec();
+        for sub in self.subpath_segs() {
+            let segs = &sub.segs;
+            let n = segs.len();
+            let n_junctions = if sub.closed { n } else { n - 1 };
+            for i in 0..n_junctions {
+                let (ix0, seg0) = segs[i];
+                let (ix1, seg1) = segs[(i + 1) % n];
+                let t0 = seg0.tangent(1.0);
+                let t1 = seg1.tangent(0.0);
+                let angle = t0.cross(t1).atan2(t0.dot(t1)).abs();
+                if angle == 0.0 || angle >= angle_tolerance {
+                    continue;
+                }
+                let adjust0 = matches!(seg0, PathSeg::Cubic(c) if c.p2 != c.p3);
+                let adjust1 = matches!(seg1, PathSeg::Cubic(c) if c.p1 != c.p0);
+                let dir = match (adjust0, adjust1) {
+                    (true, true) => {
+                        let sum = t0 + t1;
+                        sum / sum.hypot()
+                    }
+                    (true, false) => t1,
+                    (false, true) => t0,
+                    (false, false) => continue,
+                };
+                let handle0 = match seg0 {
+                    PathSeg::Cubic(c) if adjust0 => {
+                        Some((c.p2, c.p3 - (c.p3 - c.p2).hypot() * dir))
+                    }
+                    _ => None,
+                };
+                let handle1 = match seg1 {
+                    PathSeg::Cubic(c) if adjust1 => {
+                        Some((c.p1, c.p0 + (c.p1 - c.p0).hypot() * dir))
+                    }
+                    _ => None,
+                };
+                let too_far = |h: Option<(Vec2, Vec2)>| {
+                    h.map(|(old, new)| (new - old).hypot() > max_offset)
+                        .unwrap_or(false)
+                };
+                if too_far(handle0) || too_far(handle1) {
+                    continue;
+                }
+                if let Some((_, p2)) = handle0 {
+                    set_handle(&mut els[ix0], |p1, _, _| (p1, p2));
+                }
+                if let Some((_, p1)) = handle1 {
+                    set_handle(&mut els[ix1], |_, p2, _| (p1, p2));
+                }
+            }
+        }
+        *self = BezPath::from_vec(els);
+    }
+}
+
+/// Replace the control points of a `Curveto` element.
+fn set_handle(el: &mut PathEl, f: impl FnOnce(Vec2, Vec2, Vec2) -> (Vec2, Vec2)) {
+    if let PathEl::Curveto(p1, p2, p3) = *el {
+        let (p1, p2) = f(p1, p2, p3);
+        *el = PathEl::Curveto(p1, p2, p3);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, ContinuityTolerances, DiscontinuityKind, PathEl, Vec2};
+
+    #[test]
+    fn smooth_joints() {
+        // A slight kink between two curves, and a corner into a line.
+        let mut path = BezPath::from_svg("M0 0C5 0 10 5 10 10C10.3 15 15 20 20 20L20 30").unwrap();
+        let tolerances = ContinuityTolerances {
+            angle: 1e-9,
+            ..Default::default()
+        };
+        let kinds = |path: &BezPath| -> Vec<_> {
+            path.continuity_report(&tolerances)
+                .iter()
+                .filter(|d| d.kind == DiscontinuityKind::Tangent)
+                .map(|d| d.index)
+                .collect()
+        };
+        assert_eq!(kinds(&path), vec![2, 3]);
+        let before = path.elements().to_vec();
+        let mut unchanged = path.clone();
+        unchanged.smooth(0.1, 0.01);
+        assert_eq!(kinds(&unchanged), vec![2, 3]);
+        path.smooth(0.1, 1.0);
+        assert_eq!(kinds(&path), vec![3]);
+        for (a, b) in before.iter().zip(path.elements()) {
+            match (*a, *b) {
+                (PathEl::Curveto(a1, a2, a3), PathEl::Curveto(b1, b2, b3)) => {
+                    assert_eq!(a3, b3);
+                    assert!((a1 - b1).hypot() < 0.3 && (a2 - b2).hypot() < 0.3);
+                }
+                (PathEl::Lineto(a), PathEl::Lineto(b)) => assert_eq!(a, b),
+                _ => (),
+            }
+        }
+
+        // The handle next to a line follows the line.
+        let mut path = BezPath::from_svg("M0 0L10 0C15 0.1 20 5 20 10").unwrap();
+        path.smooth(0.1, 1.0);
+        match path.elements()[2] {
+            PathEl::Curveto(p1, _, _) => assert!((p1 - Vec2::new(15.0, 0.0)).hypot() < 1e-3),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Snapping coordinates to grids and pixels.
+
+use std::ops::Range;
+
+use crate::{
+    BezPath, CubicBez, Line, ParamCurve, PathEl, PathSeg, QuadBez, Rect, SegmentIndex, Vec2,
+};
+
+/// How many times the grid is halved for points that can't be quantized
+/// without changing the topology of a path, before they are left alone.
+const MAX_REFINE: u32 = 8;
+
+impl BezPath {
+    /// The path with every point, on-curve and control points alike,
+    /// moved to the nearest point of a square grid with the given spacing.
+    pub fn snap_to_grid(&self, spacing: f64) -> BezPath {
+        let mut result = self.clone();
+        result.map_points(|p| snap(p, spacing, 0.0));
+        result
+    }
+
+    /// The path with its points rounded onto a square grid with the given
+    /// spacing, without changing its topology.
+    ///
+    /// Points are moved to the nearest grid point, as for
+    /// [`snap_to_grid`](#method.snap_to_grid), unless that makes segments
+    /// that were apart touch or cross, or collapses a subpath to a single
+    /// point. The points of the segments at fault are then rounded to a grid
+    /// of half the spacing, and so on, up to eight halvings, after which
+    /// they are left where they were. The result only depends on the path
+    /// and the grid, so quantizing documents before saving them gives
+    /// stable output that diffs cleanly.
+    pub fn quantize(&self, grid: f64) -> BezPath {
+        let els = self.elements();
+        let (orig_segs, info, subpaths) = walk(els);
+        let eps = 1e-9 * grid;
+        let mut levels = vec![0; els.len()];
+        loop {
+            let path = BezPath::from_vec(
+                els.iter()
+                    .zip(&levels)
+                    .map(|(&el, &level)| quantize_el(el, grid, level))
+                    .collect(),
+            );
+            let (segs, _, _) = walk(path.elements());
+            let mut faults = Vec::new();
+            for sub in &subpaths {
+                let pts = el_points(&els[sub.els.clone()]);
+                let new_pts = el_points(&path.elements()[sub.els.clone()]);
+                if new_pts.iter().all(|&p| p == new_pts[0]) && pts.iter().any(|&p| p != pts[0]) {
+                    faults.extend(sub.els.clone());
+                }
+            }
+            // Each segment as a subpath of its own, so the indices of the
+            // index match ours, with closing segments of zero length.
+            let mut pieces = BezPath::new();
+            for seg in &segs {
+                pieces.moveto(seg.start());
+                pieces.push(seg.as_path_el());
+            }
+            let index = SegmentIndex::new(&pieces);
+            for (i, seg) in segs.iter().enumerate() {
+                for (j, _) in index.intersections(*seg, eps) {
+                    if j > i
+                        && !adjacent(&info, &subpaths, i, j)
+                        && orig_segs[i].min_dist(orig_segs[j], eps).distance > eps
+                    {
+                        faults.extend(&info[i].els);
+                        faults.extend(&info[j].els);
+                    }
+                }
+            }
+            faults.sort_unstable();
+            faults.dedup();
+            let mut changed = false;
+            for ix in faults {
+                if levels[ix] <= MAX_REFINE {
+                    levels[ix] += 1;
+                    changed = true;
+                }
+            }
+            if !changed {
+                return path;
+            }
+        }
+    }
+}
+
+impl Rect {
+    /// The rectangle with its edges moved so a stroke of the given width
+    /// draws crisply on a pixel grid.
+    ///
+    /// Coordinates are in logical units, with `dpi_scale` device pixels to
+    /// each. The stroke width is rounded to a whole number of pixels, at
+    /// least one, and the edges are moved to the nearest pixel centers if
+    /// that is odd, or pixel boundaries if it is even, so the stroke covers
+    /// whole pixels rather than blurring across two. A stroke width of zero
+    /// aligns the edges of a fill to pixel boundaries.
+    pub fn pixel_align(&self, dpi_scale: f64, stroke_width: f64) -> Rect {
+        let offset = pixel_offset(dpi_scale, stroke_width);
+        let p0 = snap(Vec2::new(self.x0, self.y0), dpi_scale.recip(), offset);
+        let p1 = snap(Vec2::new(self.x1, self.y1), dpi_scale.recip(), offset);
+        Rect::new(p0.x, p0.y, p1.x, p1.y)
+    }
+}
+
+impl Line {
+    /// The line with its ends moved so a stroke of the given width draws
+    /// crisply on a pixel grid, as for
+    /// [`Rect::pixel_align`](struct.Rect.html#method.pixel_align).
+    ///
+    /// This makes horizontal and vertical hairlines sharp; other lines are
+    /// antialiased regardless.
+    pub fn pixel_align(&self, dpi_scale: f64, stroke_width: f64) -> Line {
+        let offset = pixel_offset(dpi_scale, stroke_width);
+        Line::new(
+            snap(self.p0, dpi_scale.recip(), offset),
+            snap(self.p1, dpi_scale.recip(), offset),
+        )
+    }
+}
+
+/// The offset of the grid from the origin, in units of its spacing, to
+/// align a stroke of the given width.
+fn pixel_offset(dpi_scale: f64, stroke_width: f64) -> f64 {
+    if stroke_width == 0.0 {
+        return 0.0;
+    }
+    let pixels = (stroke_width * dpi_scale).round().max(1.0);
+    if pixels % 2.0 == 1.0 {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// Move a point to the nearest point of a grid, offset from the origin by
+/// `offset` times the spacing. Ties round up, so they go the same way on
+/// either side of the origin.
+fn snap(p: Vec2, spacing: f64, offset: f64) -> Vec2 {
+    let round = |c: f64| ((c / spacing - offset + 0.5).floor() + offset) * spacing;
+    Vec2::new(round(p.x), round(p.y))
+}
+
+/// A segment of a path being quantized.
+struct SegInfo {
+    /// The elements with the points of the segment: the one it starts at,
+    /// and the one drawing it, or the `Moveto` it closes back to.
+    els: [usize; 2],
+    /// The index of its subpath.
+    subpath: usize,
+}
+
+/// A subpath of a path being quantized.
+struct SubpathInfo {
+    /// The range of its segments.
+    segs: Range<usize>,
+    /// The range of its elements.
+    els: Range<usize>,
+    closed: bool,
+}
+
+/// The segments of a path, including closing segments of zero length, and
+/// how they fit together.
+fn walk(els: &[PathEl]) -> (Vec<PathSeg>, Vec<SegInfo>, Vec<SubpathInfo>) {
+    let mut segs = Vec::new();
+    let mut info = Vec::new();
+    let mut subpaths: Vec<SubpathInfo> = Vec::new();
+    let (mut start, mut last) = (Vec2::default(), Vec2::default());
+    let (mut start_el, mut last_el) = (0, 0);
+    for (ix, &el) in els.iter().enumerate() {
+        let (seg, end_el) = match el {
+            PathEl::Moveto(p) => {
+                subpaths.push(SubpathInfo {
+                    segs: segs.len()..segs.len(),
+                    els: ix..ix + 1,
+                    closed: false,
+                });
+                start = p;
+                last = p;
+                start_el = ix;
+                last_el = ix;
+                continue;
+            }
+            PathEl::Lineto(p) => (PathSeg::Line(Line::new(last, p)), ix),
+            PathEl::Quadto(p1, p2) => (PathSeg::Quad(QuadBez::new(last, p1, p2)), ix),
+            PathEl::Curveto(p1, p2, p3) => (PathSeg::Cubic(CubicBez::new(last, p1, p2, p3)), ix),
+            PathEl::Closepath => (PathSeg::Line(Line::new(last, start)), start_el),
+        };
+        if subpaths.is_empty() {
+            subpaths.push(SubpathInfo {
+                segs: 0..0,
+                els: 0..0,
+                closed: false,
+            });
+        }
+        let sub = subpaths.len() - 1;
+        info.push(SegInfo {
+            els: [last_el, end_el],
+            subpath: sub,
+        });
+        segs.push(seg);
+        subpaths[sub].segs.end = segs.len();
+        subpaths[sub].els.end = ix + 1;
+        subpaths[sub].closed = matches!(el, PathEl::Closepath);
+        last = seg.end();
+        last_el = end_el;
+    }
+    (segs, info, subpaths)
+}
+
+/// Whether two segments, with `i < j`, follow each other in a subpath, so
+/// they touch by design.
+fn adjacent(info: &[SegInfo], subpaths: &[SubpathInfo], i: usize, j: usize) -> bool {
+    let sub = &subpaths[info[i].subpath];
+    info[i].subpath == info[j].subpath
+        && (j == i + 1 || (sub.closed && i == sub.segs.start && j == sub.segs.end - 1))
+}
+
+/// An element with its points rounded to the grid halved `level` times, or
+/// left alone beyond the last halving.
+fn quantize_el(el: PathEl, grid: f64, level: u32) -> PathEl {
+    if level > MAX_REFINE {
+        return el;
+    }
+    let q = |p| snap(p, grid / f64::from(1 << level), 0.0);
+    match el {
+        PathEl::Moveto(p) => PathEl::Moveto(q(p)),
+        PathEl::Lineto(p) => PathEl::Lineto(q(p)),
+        PathEl::Quadto(p1, p2) => PathEl::Quadto(q(p1), q(p2)),
+        PathEl::Curveto(p1, p2, p3) => PathEl::Curveto(q(p1), q(p2), q(p3)),
+        PathEl::Closepath => PathEl::Closepath,
+    }
+}
+
+/// All the points of some elements.
+fn el_points(els: &[PathEl]) -> Vec<Vec2> {
+    crate::shape::control_points(&BezPath::from_vec(els.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Line, Rect};
+
+    #[test]
+    fn snap_and_pixel_align() {
+        let path = BezPath::from_svg("M0.1 0.2L3.9 2.6C1 1 2.26 3.74 5 5Z").unwrap();
+        assert_eq!(
+            path.snap_to_grid(0.5).to_svg(),
+            "M0 0L4 2.5C1 1 2.5 3.5 5 5Z"
+        );
+
+        // A hairline on the edges of pixels covers two half pixels, and is
+        // moved to the centers.
+        let rect = Rect::new(10.0, 10.2, 20.0, 19.9);
+        let aligned = rect.pixel_align(1.0, 1.0);
+        assert_eq!((aligned.x0, aligned.y0), (10.5, 10.5));
+        assert_eq!((aligned.x1, aligned.y1), (20.5, 19.5));
+        // An even width, or a fill, lines up with pixel edges.
+        let aligned = rect.pixel_align(1.0, 2.0);
+        assert_eq!((aligned.x0, aligned.y0, aligned.y1), (10.0, 10.0, 20.0));
+        let aligned = rect.pixel_align(1.0, 0.0);
+        assert_eq!((aligned.x0, aligned.y0, aligned.y1), (10.0, 10.0, 20.0));
+        // At double density, a 0.5 wide stroke is one pixel.
+        let aligned = rect.pixel_align(2.0, 0.5);
+        assert_eq!((aligned.x0, aligned.y0), (10.25, 10.25));
+
+        let line = Line::new((0.0, 3.0), (8.2, 3.0)).pixel_align(1.0, 1.0);
+        assert_eq!((line.p0.x, line.p0.y, line.p1.x), (0.5, 3.5, 8.5));
+    }
+
+    #[test]
+    fn quantize() {
+        // Nothing goes wrong, so this is the same as snapping.
+        let path = BezPath::from_svg("M0.1 0.2L3.9 2.6C1 1 2.26 3.74 5 5Z").unwrap();
+        assert_eq!(path.quantize(0.5).to_svg(), path.snap_to_grid(0.5).to_svg());
+
+        // A thin rectangle would fold flat.
+        let thin = BezPath::from_svg("M0 0L10 0L10 0.3L0 0.3Z").unwrap();
+        assert_eq!(thin.quantize(1.0).to_svg(), "M0 0L10 0L10 0.5L0 0.5Z");
+        // A small triangle would collapse to a point.
+        let tiny = BezPath::from_svg("M0.1 0.1L0.3 0.1L0.2 0.3Z").unwrap();
+        assert_eq!(tiny.quantize(1.0).to_svg(), "M0 0L0.5 0L0 0.5Z");
+        // Separate subpaths would touch; only the points at fault move to
+        // the finer grid.
+        let two = BezPath::from_svg("M0 0L4 0L4 4ZM4.4 0L8.2 0L8 4Z").unwrap();
+        assert_eq!(two.quantize(1.0).to_svg(), "M0 0L4 0L4 4ZM4.5 0L8 0L8 4Z");
+    }
+}
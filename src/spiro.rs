@@ -0,0 +1,444 @@
+//! Spiro curves, as used in font editors.
+//!
+//! A Spiro curve passes through a sequence of control points, and is made of
+//! segments whose curvature is a polynomial in arclength (at most cubic),
+//! chosen so that the curve is as smooth as the point types allow. See Raph
+//! Levien's thesis, "From Spiral to Spline", for the theory.
+
+use std::f64::consts::PI;
+
+use crate::common::GAUSS_LEGENDRE_COEFFS_11;
+use crate::{BezPath, CubicBez, ParamCurve, Vec2};
+
+/// The maximum number of Newton iterations when solving for the curve.
+const MAX_ITER: usize = 50;
+
+/// The maximum depth of subdivision when converting to Béziers.
+const MAX_DEPTH: usize = 12;
+
+/// The greatest bend of a segment, the sum of the magnitudes of its
+/// curvature coefficients, that the solver accepts. Beyond this a segment
+/// winds around more than a hundred times, and the solver gives up.
+const MAX_BEND: f64 = 1e3;
+
+/// The type of a Spiro control point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpiroType {
+    /// A corner, with no continuity between the adjacent segments.
+    Corner,
+    /// A smooth point with continuous curvature and its first two derivatives.
+    G4,
+    /// A smooth point with continuous curvature.
+    G2,
+    /// A transition from a straight line on the left to a curve on the right,
+    /// with continuous tangent.
+    Left,
+    /// A transition from a curve on the left to a straight line on the right,
+    /// with continuous tangent.
+    Right,
+    /// The start of an open curve.
+    OpenStart,
+    /// The end of an open curve.
+    OpenEnd,
+}
+
+/// A Spiro control point.
+#[derive(Clone, Copy, Debug)]
+pub struct SpiroCp {
+    /// The position of the point.
+    pub point: Vec2,
+    /// The type of the point.
+    pub ty: SpiroType,
+}
+
+/// A segment between two control points.
+struct Seg {
+    p0: Vec2,
+    chord: Vec2,
+    /// Coefficients of curvature as a cubic polynomial in arclength, with the
+    /// segment scaled to unit length and parametrized over -0.5..0.5.
+    ks: [f64; 4],
+    /// The number of nonzero coefficients.
+    dof: usize,
+}
+
+impl SpiroType {
+    /// The number of equations at a point, split between the segments on the left and right.
+    fn dof(self) -> (usize, usize) {
+        match self {
+            SpiroType::G4 => (2, 2),
+            SpiroType::G2 => (1, 1),
+            SpiroType::Left => (0, 1),
+            SpiroType::Right => (1, 0),
+            _ => (0, 0),
+        }
+    }
+}
+
+/// Convert Spiro control points to a path of cubic Béziers.
+///
+/// The curve is open if the first point is `OpenStart`, in which case the
+/// last point is treated as `OpenEnd`; otherwise it is closed. Segments between
+/// two corners are straight lines. The Béziers are within `tolerance` of the
+/// Spiro curve.
+///
+/// Returns `None` if the curve can't be solved, which happens when consecutive
+/// points coincide, or for extreme configurations where the solver doesn't
+/// converge.
+pub fn spiro_to_path(cps: &[SpiroCp], tolerance: f64) -> Option<BezPath> {
+    let n = cps.len();
+    let mut path = BezPath::new();
+    if n == 0 {
+        return Some(path);
+    }
+    let closed = cps[0].ty != SpiroType::OpenStart;
+    let n_segs = if closed { n } else { n - 1 };
+    let node_dof = |i: usize| {
+        if !closed && (i == 0 || i == n - 1) {
+            (0, 0)
+        } else {
+            cps[i].ty.dof()
+        }
+    };
+    let mut segs = Vec::with_capacity(n_segs);
+    for i in 0..n_segs {
+        let p0 = cps[i].point;
+        let chord = cps[(i + 1) % n].point - p0;
+        if chord.hypot2() == 0.0 {
+            return None;
+        }
+        segs.push(Seg {
+            p0,
+            chord,
+            ks: [0.0; 4],
+            dof: node_dof(i).1 + node_dof((i + 1) % n).0,
+        });
+    }
+    solve(&mut segs, cps, closed)?;
+    path.moveto(cps[0].point);
+    for seg in &segs {
+        seg.append_to_path(&mut path, tolerance);
+    }
+    if closed {
+        path.closepath();
+    }
+    Some(path)
+}
+
+/// Solve for the curvature coefficients using Newton's method.
+fn solve(segs: &mut [Seg], cps: &[SpiroCp], closed: bool) -> Option<()> {
+    let n_vars: usize = segs.iter().map(|s| s.dof).sum();
+    if n_vars == 0 {
+        return Some(());
+    }
+    let mut x = vec![0.0; n_vars];
+    for _ in 0..MAX_ITER {
+        let r = residuals(segs, cps, closed, &x);
+        debug_assert_eq!(r.len(), n_vars);
+        if segs
+            .iter()
+            .any(|seg| seg.bend().is_nan() || seg.bend() > MAX_BEND)
+        {
+            return None;
+        }
+        if r.iter().all(|v| v.abs() < 1e-12) {
+            return Some(());
+        }
+        // Numeric Jacobian by central differences.
+        let h = 1e-6;
+        let mut jac = vec![vec![0.0; n_vars]; n_vars];
+        for j in 0..n_vars {
+            let mut xp = x.clone();
+            xp[j] += h;
+            let rp = residuals(segs, cps, closed, &xp);
+            xp[j] -= 2.0 * h;
+            let rm = residuals(segs, cps, closed, &xp);
+            for i in 0..n_vars {
+                jac[i][j] = (rp[i] - rm[i]) / (2.0 * h);
+            }
+        }
+        let dx = solve_linear(jac, r.iter().map(|v| -v).collect())?;
+        // Backtrack if the full step doesn't reduce the error.
+        let norm = |r: &[f64]| r.iter().map(|v| v * v).sum::<f64>();
+        let norm0 = norm(&r);
+        let mut step = 1.0;
+        loop {
+            let xn: Vec<f64> = x.iter().zip(&dx).map(|(xi, dxi)| xi + step * dxi).collect();
+            if step < 1e-3 || norm(&residuals(segs, cps, closed, &xn)) < norm0 {
+                x = xn;
+                break;
+            }
+            step *= 0.5;
+        }
+    }
+    let r = residuals(segs, cps, closed, &x);
+    if r.iter().all(|v| v.abs() < 1e-9) {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Load the variables into the segments, and compute the continuity errors.
+fn residuals(segs: &mut [Seg], cps: &[SpiroCp], closed: bool, x: &[f64]) -> Vec<f64> {
+    let mut ix = 0;
+    for seg in segs.iter_mut() {
+        seg.ks = [0.0; 4];
+        seg.ks[..seg.dof].copy_from_slice(&x[ix..ix + seg.dof]);
+        ix += seg.dof;
+    }
+    let n = cps.len();
+    let n_segs = segs.len();
+    let mut result = Vec::with_capacity(x.len());
+    let nodes = if closed { 0..n } else { 1..n - 1 };
+    for i in nodes {
+        let left = &segs[(i + n_segs - 1) % n_segs];
+        let right = &segs[i % n_segs];
+        let n_eq = match cps[i].ty {
+            SpiroType::G4 => 4,
+            SpiroType::G2 => 2,
+            SpiroType::Left | SpiroType::Right => 1,
+            _ => 0,
+        };
+        if n_eq == 0 {
+            continue;
+        }
+        let l = left.end_derivs(0.5);
+        let r = right.end_derivs(-0.5);
+        let mut dth = l[0] - r[0];
+        dth -= 2.0 * PI * (dth / (2.0 * PI)).round();
+        result.push(dth);
+        // Scale curvature derivatives to be dimensionless.
+        let len = 0.5 * (left.chord.hypot() + right.chord.hypot());
+        let mut scale = 1.0;
+        for k in 1..n_eq {
+            scale *= len;
+            result.push((l[k] - r[k]) * scale);
+        }
+    }
+    result
+}
+
+impl Seg {
+    /// The polynomial of the tangent angle, in the segment's unit frame.
+    fn theta(&self, s: f64) -> f64 {
+        let k = &self.ks;
+        s * (k[0] + s * (0.5 * k[1] + s * (k[2] / 6.0 + s * k[3] / 24.0)))
+    }
+
+    /// The sum of the magnitudes of the curvature coefficients, bounding
+    /// how far the tangent turns along the segment.
+    fn bend(&self) -> f64 {
+        self.ks.iter().map(|k| k.abs()).sum()
+    }
+
+    /// Integrate the unit tangent from `s0` to `s1`, in the unit frame.
+    ///
+    /// The number of steps grows with the bend, up to `MAX_BEND`.
+    fn integrate(&self, s0: f64, s1: f64) -> Vec2 {
+        let bend = self.bend().min(MAX_BEND);
+        let n = 1 + (bend * (s1 - s0).abs()).ceil() as usize;
+        let h = (s1 - s0) / n as f64;
+        let mut result = Vec2::default();
+        for i in 0..n {
+            let a = s0 + i as f64 * h;
+            for &(w, xi) in GAUSS_LEGENDRE_COEFFS_11 {
+                let th = self.theta(a + 0.5 * h * (xi + 1.0));
+                result += (0.5 * h * w) * Vec2::new(th.cos(), th.sin());
+            }
+        }
+        result
+    }
+
+    /// The rotation and scale from the unit frame to the actual chord.
+    fn frame(&self) -> (f64, f64) {
+        let xy = self.integrate(-0.5, 0.5);
+        let rot = self.chord.atan2() - xy.atan2();
+        let scale = self.chord.hypot() / xy.hypot();
+        (rot, scale)
+    }
+
+    /// The tangent angle, curvature, and the first two derivatives of curvature
+    /// with respect to arclength, at `s`.
+    fn end_derivs(&self, s: f64) -> [f64; 4] {
+        let (rot, scale) = self.frame();
+        let k = &self.ks;
+        let k0 = k[0] + s * (k[1] + s * (0.5 * k[2] + s * k[3] / 6.0));
+        let k1 = k[1] + s * (k[2] + 0.5 * s * k[3]);
+        let k2 = k[2] + s * k[3];
+        [
+            self.theta(s) + rot,
+            k0 / scale,
+            k1 / (scale * scale),
+            k2 / (scale * scale * scale),
+        ]
+    }
+
+    /// Append the segment as cubic Béziers.
+    fn append_to_path(&self, path: &mut BezPath, tolerance: f64) {
+        let (rot, scale) = self.frame();
+        let (sin, cos) = rot.sin_cos();
+        let to_world = |v: Vec2| scale * Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos);
+        let point = |s: f64| self.p0 + to_world(self.integrate(-0.5, s));
+        let end = self.p0 + self.chord;
+        self.append_rec(-0.5, 0.5, &point, &to_world, end, tolerance, 0, path);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn append_rec(
+        &self,
+        s0: f64,
+        s1: f64,
+        point: &impl Fn(f64) -> Vec2,
+        to_world: &impl Fn(Vec2) -> Vec2,
+        end: Vec2,
+        tolerance: f64,
+        depth: usize,
+        path: &mut BezPath,
+    ) {
+        let arm = (s1 - s0) / 3.0;
+        let p0 = point(s0);
+        let p3 = if s1 == 0.5 { end } else { point(s1) };
+        let (t0, t1) = (self.theta(s0), self.theta(s1));
+        let p1 = p0 + to_world(arm * Vec2::new(t0.cos(), t0.sin()));
+        let p2 = p3 - to_world(arm * Vec2::new(t1.cos(), t1.sin()));
+        let c = CubicBez::new(p0, p1, p2, p3);
+        let within = depth >= MAX_DEPTH
+            || [0.25, 0.5, 0.75].iter().all(|&t| {
+                let p = point(s0 + t * (s1 - s0));
+                (c.eval(t) - p).hypot() <= tolerance
+            });
+        if within {
+            path.curveto(p1, p2, p3);
+        } else {
+            let sm = 0.5 * (s0 + s1);
+            self.append_rec(s0, sm, point, to_world, end, tolerance, depth + 1, path);
+            self.append_rec(sm, s1, point, to_world, end, tolerance, depth + 1, path);
+        }
+    }
+}
+
+/// Solve a dense linear system using Gaussian elimination with partial pivoting.
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        // NaN sorts above every number, so is chosen as the pivot and rejected.
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].is_nan() || a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let f = a[row][col] / a[col][col];
+            if f != 0.0 {
+                let (above, below) = a.split_at_mut(row);
+                for (x, &p) in below[0][col..].iter_mut().zip(&above[col][col..]) {
+                    *x -= f * p;
+                }
+                b[row] -= f * b[col];
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{spiro_to_path, ParamCurve, SpiroCp, SpiroType, Vec2};
+
+    fn cps(points: &[(f64, f64)], types: &[SpiroType]) -> Vec<SpiroCp> {
+        points
+            .iter()
+            .zip(types)
+            .map(|(&p, &ty)| SpiroCp {
+                point: p.into(),
+                ty,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spiro_circle() {
+        // Smooth points at the corners of a square make a circle.
+        for &ty in &[SpiroType::G4, SpiroType::G2] {
+            let pts = [(10.0, 0.0), (0.0, 10.0), (-10.0, 0.0), (0.0, -10.0)];
+            let path = spiro_to_path(&cps(&pts, &[ty; 4]), 1e-6).unwrap();
+            for seg in path.segments() {
+                for i in 0..=8 {
+                    let p = seg.eval(i as f64 / 8.0);
+                    assert!((p.hypot() - 10.0).abs() < 1e-4, "{:?}", p);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spiro_corners_and_constraints() {
+        use SpiroType::*;
+        let pts = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)];
+        let path = spiro_to_path(&cps(&pts, &[Corner; 3]), 1e-6).unwrap();
+        for seg in path.segments() {
+            // Straight segments.
+            let mid = seg.eval(0.5);
+            assert!((mid - seg.start().lerp(seg.end(), 0.5)).hypot() < 1e-9);
+        }
+
+        // A straight line leading tangentially into a curve.
+        let pts = [(0.0, 0.0), (10.0, 0.0), (20.0, 5.0), (25.0, 15.0)];
+        let path = spiro_to_path(&cps(&pts, &[OpenStart, Left, G2, OpenEnd]), 1e-6).unwrap();
+        let segs: Vec<_> = path.segments().collect();
+        assert!((segs[0].tangent(0.5) - Vec2::new(1.0, 0.0)).hypot() < 1e-9);
+        let junction = segs
+            .windows(2)
+            .find(|w| (w[0].end() - Vec2::new(10.0, 0.0)).hypot() < 1e-9)
+            .unwrap();
+        assert!((junction[0].tangent(1.0) - junction[1].tangent(0.0)).hypot() < 1e-6);
+        assert!((segs[segs.len() - 1].end() - Vec2::new(25.0, 15.0)).hypot() < 1e-9);
+
+        // Mixed smooth point types.
+        let pts = [
+            (0.0, 0.0),
+            (10.0, 3.0),
+            (20.0, 4.0),
+            (30.0, 3.0),
+            (40.0, 0.0),
+            (50.0, -5.0),
+        ];
+        let types = [OpenStart, G4, G4, G2, G4, OpenEnd];
+        let path = spiro_to_path(&cps(&pts, &types), 1e-6).unwrap();
+        let segs: Vec<_> = path.segments().collect();
+        for &p in &pts[1..5] {
+            let ix = segs
+                .iter()
+                .position(|s| (s.end() - p.into()).hypot() < 1e-9)
+                .unwrap();
+            assert!((segs[ix].tangent(1.0) - segs[ix + 1].tangent(0.0)).hypot() < 1e-6);
+        }
+
+        assert!(spiro_to_path(&cps(&[(0.0, 0.0), (0.0, 0.0)], &[G4, G4]), 1e-3).is_none());
+    }
+
+    #[test]
+    fn spiro_degenerate() {
+        use SpiroType::*;
+        let pts = [(0.0, 0.0), (10.0, f64::NAN), (20.0, 0.0)];
+        assert!(spiro_to_path(&cps(&pts, &[G2; 3]), 1e-3).is_none());
+        let nan = vec![vec![1.0, 0.0], vec![f64::NAN, 1.0]];
+        assert!(super::solve_linear(nan, vec![1.0, 1.0]).is_none());
+
+        // Integrating a wildly bent segment takes a bounded number of steps.
+        let seg = super::Seg {
+            p0: Vec2::default(),
+            chord: Vec2::new(1.0, 0.0),
+            ks: [1e15, 0.0, 0.0, 0.0],
+            dof: 1,
+        };
+        assert!(seg.integrate(-0.5, 0.5).hypot() <= 1.0 + 1e-9);
+    }
+}
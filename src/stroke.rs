@@ -0,0 +1,928 @@
+//! Expanding strokes into filled outlines.
+
+use std::f64::consts::PI;
+
+use crate::{BezPath, DashPattern, ParamCurve, ParamCurveExtrema, PathEl, PathSeg, Rect, Vec2};
+
+/// The shape of a stroke where two segments meet at a corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Join {
+    /// The outer edges are joined by a straight line.
+    Bevel,
+    /// The outer edges are extended until they meet, up to the miter limit,
+    /// beyond which the join is beveled.
+    Miter,
+    /// The outer edges are joined by a circular arc.
+    Round,
+}
+
+/// The shape of a stroke at the ends of an open subpath.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cap {
+    /// The stroke ends square at the endpoint.
+    Butt,
+    /// The stroke ends square, half its width beyond the endpoint.
+    Square,
+    /// The stroke ends in a semicircle around the endpoint.
+    Round,
+}
+
+/// The style of a stroke.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stroke {
+    /// The width of the stroke.
+    pub width: f64,
+    /// The shape of corners.
+    pub join: Join,
+    /// The greatest ratio of the length of a miter to the width, as in SVG.
+    pub miter_limit: f64,
+    /// The shape of the start of each open subpath.
+    pub start_cap: Cap,
+    /// The shape of the end of each open subpath.
+    pub end_cap: Cap,
+    /// The dash pattern, if the stroke is dashed.
+    pub dash: Option<DashPattern>,
+}
+
+/// A width profile given by keyframes along the length of a stroke.
+///
+/// Each keyframe is a position, as a fraction of the arclength from 0 to 1,
+/// and a width there, as a multiple of the width of the stroke. Between
+/// keyframes, the width is interpolated linearly; before the first and after
+/// the last, it is held constant.
+#[derive(Clone, Debug, Default)]
+pub struct WidthProfile {
+    keyframes: Vec<(f64, f64)>,
+}
+
+/// A subpath flattened for stroking.
+struct Polyline {
+    pts: Vec<StrokePoint>,
+    closed: bool,
+}
+
+/// A point of a flattened subpath.
+#[derive(Clone, Copy)]
+struct StrokePoint {
+    p: Vec2,
+    /// Half the width of the stroke.
+    half: f64,
+    /// Whether the direction of the path changes abruptly here.
+    corner: bool,
+    /// The directions of the path into and out of the point, where it is a
+    /// junction between segments or an end, rather than a point within a
+    /// flattened curve.
+    tangents: Option<(Vec2, Vec2)>,
+}
+
+impl Default for Stroke {
+    fn default() -> Stroke {
+        Stroke {
+            width: 1.0,
+            join: Join::Miter,
+            miter_limit: 4.0,
+            start_cap: Cap::Butt,
+            end_cap: Cap::Butt,
+            dash: None,
+        }
+    }
+}
+
+impl Stroke {
+    /// A stroke of the given width, with the defaults of SVG otherwise.
+    pub fn new(width: f64) -> Stroke {
+        Stroke {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Builder method for setting the join.
+    pub fn with_join(mut self, join: Join) -> Stroke {
+        self.join = join;
+        self
+    }
+
+    /// Builder method for setting the miter limit.
+    pub fn with_miter_limit(mut self, limit: f64) -> Stroke {
+        self.miter_limit = limit;
+        self
+    }
+
+    /// Builder method for setting both caps.
+    pub fn with_caps(mut self, cap: Cap) -> Stroke {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
+
+    /// Builder method for setting the start cap.
+    pub fn with_start_cap(mut self, cap: Cap) -> Stroke {
+        self.start_cap = cap;
+        self
+    }
+
+    /// Builder method for setting the end cap.
+    pub fn with_end_cap(mut self, cap: Cap) -> Stroke {
+        self.end_cap = cap;
+        self
+    }
+
+    /// Builder method for setting the dash pattern.
+    pub fn with_dash(mut self, pattern: DashPattern) -> Stroke {
+        self.dash = Some(pattern);
+        self
+    }
+}
+
+impl WidthProfile {
+    /// Create a profile from keyframes of position and width.
+    ///
+    /// The keyframes need not be in order. Keyframes whose position is not
+    /// finite are ignored.
+    pub fn new(keyframes: impl IntoIterator<Item = (f64, f64)>) -> WidthProfile {
+        let mut keyframes: Vec<_> = keyframes.into_iter().filter(|k| k.0.is_finite()).collect();
+        keyframes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        WidthProfile { keyframes }
+    }
+
+    /// The width at a position, as a multiple of the stroke width.
+    ///
+    /// An empty profile has a width of 1 everywhere.
+    pub fn eval(&self, pos: f64) -> f64 {
+        let keys = &self.keyframes;
+        match keys.iter().position(|k| k.0 > pos) {
+            None => keys.last().map(|k| k.1).unwrap_or(1.0),
+            Some(0) => keys[0].1,
+            Some(i) => {
+                let (p0, w0) = keys[i - 1];
+                let (p1, w1) = keys[i];
+                w0 + (w1 - w0) * (pos - p0) / (p1 - p0)
+            }
+        }
+    }
+}
+
+impl BezPath {
+    /// The outline of the stroke of the path.
+    ///
+    /// The result is made of lines, within `tolerance` of the exact outline,
+    /// and is to be filled with the nonzero fill rule; where the stroke
+    /// overlaps itself, so do the subpaths of the outline. Each open subpath
+    /// gives one closed subpath, and each closed one gives two, for its
+    /// outer and inner edges. A dashed stroke gives an outline for each
+    /// dash. As in SVG, an open subpath of zero length gives just its caps,
+    /// facing along the x axis, so round and square caps draw a dot.
+    pub fn stroke(&self, style: &Stroke, tolerance: f64) -> BezPath {
+        self.stroke_variable(style, |_| 1.0, tolerance)
+    }
+
+    /// The outline of a stroke of the path whose width varies along it.
+    ///
+    /// The width at each point is that of the style times the value of the
+    /// callback at the position of the point, as a fraction from 0 to 1 of
+    /// the arclength of its subpath, so that each subpath gets the whole
+    /// profile; a [`WidthProfile`](struct.WidthProfile.html) can be used
+    /// as `|pos| profile.eval(pos)`. Negative widths are taken as zero. This
+    /// gives the tapered and calligraphic strokes of brushes and ink.
+    ///
+    /// Points are added along the path until the width between them is
+    /// within `tolerance` of linear, so a profile with sharp changes in
+    /// slope will have them slightly rounded. The profile is taken along
+    /// the whole of each subpath, also when the stroke is dashed. Otherwise,
+    /// this is as [`stroke`](#method.stroke).
+    pub fn stroke_variable(
+        &self,
+        style: &Stroke,
+        mut width: impl FnMut(f64) -> f64,
+        tolerance: f64,
+    ) -> BezPath {
+        let mut result = BezPath::new();
+        let dots = style.start_cap != Cap::Butt || style.end_cap != Cap::Butt;
+        for mut line in flatten_subpaths(self, tolerance, dots) {
+            line.set_widths(style.width, &mut width, tolerance);
+            let pattern = match &style.dash {
+                Some(pattern) => pattern,
+                None => {
+                    line.stroke_into(
+                        style,
+                        (style.start_cap, style.end_cap),
+                        tolerance,
+                        &mut result,
+                    );
+                    continue;
+                }
+            };
+            let total = line.length();
+            let ranges = pattern.ranges(total, line.closed);
+            if line.closed && ranges == [(0.0, total)] {
+                line.stroke_into(
+                    style,
+                    (style.start_cap, style.end_cap),
+                    tolerance,
+                    &mut result,
+                );
+                continue;
+            }
+            for (s0, s1) in ranges {
+                let dash = line.cut(s0, s1);
+                if dash.pts.len() < 2 {
+                    continue;
+                }
+                // Only the ends of open subpaths have caps, unless every dash
+                // does.
+                let cap = |at_end: bool, cap: Cap| {
+                    if pattern.caps_per_dash || (at_end && !line.closed) {
+                        cap
+                    } else {
+                        Cap::Butt
+                    }
+                };
+                let caps = (
+                    cap(s0 == 0.0, style.start_cap),
+                    cap(s1 == total, style.end_cap),
+                );
+                dash.stroke_into(style, caps, tolerance, &mut result);
+            }
+        }
+        result
+    }
+}
+
+impl BezPath {
+    /// The bounding box of the stroke of the path, without expanding it.
+    ///
+    /// The box is found from the offsets of the segments at their ends,
+    /// their extrema, and the points where the offset on the inside of a
+    /// tight curve has a cusp, together with the extents of the joins and
+    /// caps. It is the box of the exact stroke, which the outline from
+    /// [`stroke`](#method.stroke) approximates, so it suits damage
+    /// rectangles. Dashes are ignored, so the box of a dashed stroke may
+    /// not be tight.
+    pub fn stroke_bounding_box(&self, style: &Stroke) -> Rect {
+        let half = 0.5 * style.width;
+        let mut bbox: Option<Rect> = None;
+        let mut add = |p: Vec2| {
+            bbox = Some(match bbox {
+                Some(bbox) => bbox.union_pt(p),
+                None => Rect::from_points(p, p),
+            });
+        };
+        for sub in self.subpath_segs() {
+            let segs: Vec<PathSeg> = sub
+                .segs
+                .iter()
+                .map(|s| s.1)
+                .filter(|seg| seg.tangent(0.0) != Vec2::default())
+                .collect();
+            let n = segs.len();
+            if n == 0 {
+                // Only the caps of a subpath of zero length are drawn.
+                if let Some(&(_, seg)) = sub.segs.first() {
+                    let dir = Vec2::new(1.0, 0.0);
+                    cap_extents(seg.start(), -dir, half, style.start_cap, &mut add);
+                    cap_extents(seg.start(), dir, half, style.end_cap, &mut add);
+                }
+                continue;
+            }
+            for seg in &segs {
+                for t in offset_extrema(seg, half) {
+                    let frame = seg.frame(t);
+                    add(frame.point + half * frame.normal);
+                    add(frame.point - half * frame.normal);
+                }
+            }
+            for i in 0..n {
+                if i == 0 && !sub.closed {
+                    continue;
+                }
+                let (t_in, t_out) = (segs[(i + n - 1) % n].tangent(1.0), segs[i].tangent(0.0));
+                if is_corner(Some(t_in), t_out) {
+                    join_extents(segs[i].start(), t_in, t_out, half, style, &mut add);
+                }
+            }
+            if !sub.closed {
+                let (start, end) = (segs[0], segs[n - 1]);
+                let start_dir = -start.tangent(0.0);
+                cap_extents(start.start(), start_dir, half, style.start_cap, &mut add);
+                cap_extents(end.end(), end.tangent(1.0), half, style.end_cap, &mut add);
+            }
+        }
+        bbox.unwrap_or_default()
+    }
+}
+
+impl Polyline {
+    /// Set the half widths from a profile over the normalized arclength,
+    /// adding points where the profile is not close to linear.
+    fn set_widths(&mut self, scale: f64, width: &mut impl FnMut(f64) -> f64, tolerance: f64) {
+        let total = self.length();
+        let mut half_width = |s: f64| {
+            let pos = if total > 0.0 { s / total } else { 0.0 };
+            0.5 * scale * width(pos).max(0.0)
+        };
+        let n = self.pts.len();
+        let n_edges = if self.closed { n } else { n - 1 };
+        let mut pts = Vec::with_capacity(n);
+        let mut s = 0.0;
+        let mut h0 = half_width(0.0);
+        for i in 0..n_edges {
+            let (p0, p1) = (self.pts[i], self.pts[(i + 1) % n]);
+            let len = (p1.p - p0.p).hypot();
+            pts.push(StrokePoint { half: h0, ..p0 });
+            let h1 = half_width(s + len);
+            subdivide_width(
+                (p0.p, s, h0),
+                (p1.p, s + len, h1),
+                &mut half_width,
+                tolerance,
+                0,
+                &mut |p, half| pts.push(StrokePoint::new(p, half)),
+            );
+            s += len;
+            h0 = h1;
+        }
+        if !self.closed {
+            pts.push(StrokePoint {
+                half: h0,
+                ..self.pts[n - 1]
+            });
+        }
+        self.pts = pts;
+    }
+
+    /// The length of the polyline, including the closing edge if closed.
+    fn length(&self) -> f64 {
+        let n = self.pts.len();
+        let n_edges = if self.closed { n } else { n - 1 };
+        (0..n_edges)
+            .map(|i| (self.pts[(i + 1) % n].p - self.pts[i].p).hypot())
+            .sum()
+    }
+
+    /// The open polyline between two arclengths, which may go once around
+    /// past the end of a closed polyline.
+    fn cut(&self, s0: f64, s1: f64) -> Polyline {
+        let n = self.pts.len();
+        let mut pts: Vec<StrokePoint> = Vec::new();
+        let mut push = |pt: StrokePoint| {
+            if pts.last().map(|last| last.p) != Some(pt.p) {
+                pts.push(pt);
+            }
+        };
+        let n_vertices = if self.closed { 2 * n + 1 } else { n };
+        let mut s = 0.0;
+        for k in 0..n_vertices {
+            let pt = self.pts[k % n];
+            if k > 0 {
+                let prev = self.pts[(k - 1) % n];
+                let len = (pt.p - prev.p).hypot();
+                for &cut in &[s0, s1] {
+                    if s < cut && cut < s + len {
+                        let t = (cut - s) / len;
+                        let half = prev.half + t * (pt.half - prev.half);
+                        push(StrokePoint::new(prev.p.lerp(pt.p, t), half));
+                    }
+                }
+                s += len;
+            }
+            if s > s1 {
+                break;
+            }
+            if s >= s0 {
+                push(pt);
+            }
+        }
+        Polyline { pts, closed: false }
+    }
+
+    /// Add the outline of the stroke of the polyline to a path, with the
+    /// given start and end caps if it is open.
+    fn stroke_into(self, style: &Stroke, caps: (Cap, Cap), tolerance: f64, out: &mut BezPath) {
+        let mut pts = Vec::new();
+        if self.closed {
+            self.offset_side(style, tolerance, &mut pts);
+            push_polygon(out, &pts);
+            pts.clear();
+            self.reverse().offset_side(style, tolerance, &mut pts);
+            push_polygon(out, &pts);
+        } else {
+            let (start, end) = (self.pts[0], self.pts[self.pts.len() - 1]);
+            let (start_dir, end_dir) = (self.dir_out(0), self.dir_in(self.pts.len() - 1));
+            self.offset_side(style, tolerance, &mut pts);
+            push_cap(end.p, end_dir, end.half, caps.1, tolerance, &mut pts);
+            self.reverse().offset_side(style, tolerance, &mut pts);
+            push_cap(start.p, -start_dir, start.half, caps.0, tolerance, &mut pts);
+            push_polygon(out, &pts);
+        }
+    }
+
+    /// The same polyline, traversed the other way.
+    fn reverse(mut self) -> Polyline {
+        self.pts.reverse();
+        for pt in &mut self.pts {
+            pt.tangents = pt.tangents.map(|(t_in, t_out)| (-t_out, -t_in));
+        }
+        self
+    }
+
+    /// The direction of the path into point `i`.
+    fn dir_in(&self, i: usize) -> Vec2 {
+        match self.pts[i].tangents {
+            Some((t_in, _)) => t_in,
+            None => {
+                let n = self.pts.len();
+                unit(self.pts[i].p - self.pts[(i + n - 1) % n].p)
+            }
+        }
+    }
+
+    /// The direction of the path out of point `i`.
+    fn dir_out(&self, i: usize) -> Vec2 {
+        match self.pts[i].tangents {
+            Some((_, t_out)) => t_out,
+            None => unit(self.pts[(i + 1) % self.pts.len()].p - self.pts[i].p),
+        }
+    }
+
+    /// Add the points of the offset of the polyline on its left, with joins
+    /// at its corners.
+    fn offset_side(&self, style: &Stroke, tolerance: f64, out: &mut Vec<Vec2>) {
+        let n = self.pts.len();
+        let left = |d: Vec2| Vec2::new(-d.y, d.x);
+        for i in 0..n {
+            let StrokePoint { p, half: h, .. } = self.pts[i];
+            if !self.closed && i == 0 {
+                out.push(p + h * left(self.dir_out(i)));
+                continue;
+            }
+            if !self.closed && i == n - 1 {
+                out.push(p + h * left(self.dir_in(i)));
+                continue;
+            }
+            let (d_in, d_out) = (self.dir_in(i), self.dir_out(i));
+            let (n_in, n_out) = (left(d_in), left(d_out));
+            let dot = d_in.dot(d_out);
+            let gap = h * (n_out - n_in).hypot();
+            let corner = self.pts[i].corner;
+            if dot > 0.0 && (!corner || gap <= tolerance) {
+                let n_avg = unit(n_in + n_out);
+                out.push(p + (h / n_avg.dot(n_in)) * n_avg);
+                continue;
+            }
+            out.push(p + h * n_in);
+            if d_in.cross(d_out) > 0.0 {
+                // The inside of a left turn. Going through the center keeps
+                // the overlapping region filled.
+                out.push(p);
+            } else {
+                let join = if corner { style.join } else { Join::Round };
+                match join {
+                    Join::Bevel => (),
+                    Join::Miter => {
+                        let cos_half = (0.5 * (1.0 + dot)).sqrt();
+                        if cos_half * style.miter_limit >= 1.0 {
+                            out.push(p + (h / (1.0 + dot)) * (n_in + n_out));
+                        }
+                    }
+                    Join::Round => {
+                        let angle = -d_in.cross(d_out).atan2(dot).abs();
+                        push_arc(p, n_in, angle, h, tolerance, out);
+                        out.pop();
+                    }
+                }
+            }
+            out.push(p + h * n_out);
+        }
+    }
+}
+
+impl StrokePoint {
+    /// A point within a flattened curve.
+    fn new(p: Vec2, half: f64) -> StrokePoint {
+        StrokePoint {
+            p,
+            half,
+            corner: false,
+            tangents: None,
+        }
+    }
+}
+
+/// Add points between two along an edge, where the width deviates from
+/// linear by more than the tolerance.
+fn subdivide_width(
+    (p0, s0, h0): (Vec2, f64, f64),
+    (p1, s1, h1): (Vec2, f64, f64),
+    half_width: &mut impl FnMut(f64) -> f64,
+    tolerance: f64,
+    depth: usize,
+    push: &mut impl FnMut(Vec2, f64),
+) {
+    const MAX_DEPTH: usize = 12;
+    let s = 0.5 * (s0 + s1);
+    let h = half_width(s);
+    if depth >= MAX_DEPTH || (h - 0.5 * (h0 + h1)).abs() <= tolerance {
+        return;
+    }
+    let p = p0.lerp(p1, 0.5);
+    let mid = (p, s, h);
+    subdivide_width((p0, s0, h0), mid, half_width, tolerance, depth + 1, push);
+    push(p, h);
+    subdivide_width(mid, (p1, s1, h1), half_width, tolerance, depth + 1, push);
+}
+
+/// Flatten each subpath into a polyline, remembering the junctions between
+/// segments, and the directions of the segments there.
+///
+/// If `dots` is set, a subpath of zero length gives a polyline of two
+/// coincident points facing along the x axis, so that its caps are drawn.
+fn flatten_subpaths(path: &BezPath, tolerance: f64, dots: bool) -> Vec<Polyline> {
+    let mut result = Vec::new();
+    for sub in path.subpath_segs() {
+        let segs: Vec<PathSeg> = sub
+            .segs
+            .iter()
+            .map(|s| s.1)
+            .filter(|seg| seg.tangent(0.0) != Vec2::default())
+            .collect();
+        if segs.is_empty() {
+            if let Some(&(_, seg)) = sub.segs.first().filter(|_| dots) {
+                let dir = Vec2::new(1.0, 0.0);
+                let pt = StrokePoint {
+                    corner: true,
+                    tangents: Some((dir, dir)),
+                    ..StrokePoint::new(seg.start(), 0.0)
+                };
+                result.push(Polyline {
+                    pts: vec![pt, pt],
+                    closed: false,
+                });
+            }
+            continue;
+        }
+        let mut pts = vec![StrokePoint::new(segs[0].start(), 0.0)];
+        let mut last_tangent = None;
+        for seg in &segs {
+            let tangent = seg.tangent(0.0);
+            let last = pts.len() - 1;
+            pts[last].corner = is_corner(last_tangent, tangent);
+            pts[last].tangents = Some((last_tangent.unwrap_or(tangent), tangent));
+            last_tangent = Some(seg.tangent(1.0));
+            let path = BezPath::from_vec(vec![PathEl::Moveto(seg.start()), seg.as_path_el()]);
+            path.flatten(tolerance, |el| {
+                if let PathEl::Lineto(p) = el {
+                    if p != pts[pts.len() - 1].p {
+                        pts.push(StrokePoint::new(p, 0.0));
+                    }
+                }
+            });
+        }
+        let end_tangent = last_tangent.unwrap();
+        let last = pts.len() - 1;
+        pts[last].corner = true;
+        pts[last].tangents = Some((end_tangent, end_tangent));
+        let closed = sub.closed && pts.len() > 3;
+        if closed {
+            pts.pop();
+            let first_tangent = segs[0].tangent(0.0);
+            pts[0].corner = is_corner(Some(end_tangent), first_tangent);
+            pts[0].tangents = Some((end_tangent, first_tangent));
+        }
+        if pts.len() >= 2 {
+            result.push(Polyline { pts, closed });
+        }
+    }
+    result
+}
+
+/// Whether the direction changes abruptly between two unit tangents.
+fn is_corner(before: Option<Vec2>, after: Vec2) -> bool {
+    before.map_or(true, |t| (after - t).hypot() > 1e-9)
+}
+
+/// The parameters where the offsets of a segment by `half` on either side
+/// may be extreme: the ends, the extrema of the segment, where the offsets
+/// are parallel to it, and the cusps of the offsets, where the radius of
+/// curvature is `half`.
+fn offset_extrema(seg: &PathSeg, half: f64) -> Vec<f64> {
+    const SAMPLES: usize = 32;
+    let mut result = vec![0.0, 1.0];
+    result.extend(seg.extrema());
+    if let PathSeg::Line(_) = seg {
+        return result;
+    }
+    // Find where the curvature crosses plus or minus the inverse of the
+    // half width, by bisecting sign changes between samples.
+    for &target in &[half.recip(), -half.recip()] {
+        let f = |t: f64| seg.frame(t).curvature - target;
+        let mut t0 = 0.0;
+        let mut f0 = f(t0);
+        for i in 1..=SAMPLES {
+            let t1 = i as f64 / SAMPLES as f64;
+            let f1 = f(t1);
+            if (f0 < 0.0) != (f1 < 0.0) {
+                let (mut lo, mut hi, f_lo) = (t0, t1, f0);
+                for _ in 0..50 {
+                    let mid = 0.5 * (lo + hi);
+                    if (f(mid) < 0.0) == (f_lo < 0.0) {
+                        lo = mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                result.push(0.5 * (lo + hi));
+            }
+            t0 = t1;
+            f0 = f1;
+        }
+    }
+    result
+}
+
+/// Add the points extending the bounding box of a join beyond the offsets
+/// of the segments.
+fn join_extents(
+    p: Vec2,
+    t_in: Vec2,
+    t_out: Vec2,
+    half: f64,
+    style: &Stroke,
+    add: &mut impl FnMut(Vec2),
+) {
+    let left = |d: Vec2| Vec2::new(-d.y, d.x);
+    // The join is on the outside of the turn.
+    let side = if t_in.cross(t_out) > 0.0 { -1.0 } else { 1.0 };
+    let (n_in, n_out) = (side * left(t_in), side * left(t_out));
+    let dot = t_in.dot(t_out);
+    match style.join {
+        Join::Bevel => (),
+        Join::Miter => {
+            let cos_half = (0.5 * (1.0 + dot)).sqrt();
+            if cos_half * style.miter_limit >= 1.0 {
+                add(p + (half / (1.0 + dot)) * (n_in + n_out));
+            }
+        }
+        Join::Round => {
+            let mut angle = n_in.cross(n_out).atan2(n_in.dot(n_out));
+            // Reversing direction, the join goes around the front.
+            if n_in.cross(n_out).abs() < 1e-12 && n_in.rotate(0.5 * angle).dot(t_in) < 0.0 {
+                angle = -angle;
+            }
+            arc_extents(p, n_in, angle, half, add);
+        }
+    }
+}
+
+/// Add the points extending the bounding box of a cap at an end going in
+/// direction `dir` beyond the offsets of the segment.
+fn cap_extents(p: Vec2, dir: Vec2, half: f64, cap: Cap, add: &mut impl FnMut(Vec2)) {
+    let n = Vec2::new(-dir.y, dir.x);
+    match cap {
+        Cap::Butt => (),
+        Cap::Square => {
+            add(p + half * (n + dir));
+            add(p + half * (dir - n));
+        }
+        Cap::Round => arc_extents(p, n, -PI, half, add),
+    }
+}
+
+/// Add the points of a circular arc that are extreme in either axis, from
+/// unit direction `from` around `center`, sweeping `angle` radians.
+fn arc_extents(center: Vec2, from: Vec2, angle: f64, radius: f64, add: &mut impl FnMut(Vec2)) {
+    let start = from.atan2();
+    for i in 0..4 {
+        let axis = 0.5 * PI * i as f64;
+        let delta = (axis - start).rem_euclid(2.0 * PI);
+        let within = if angle >= 0.0 {
+            delta <= angle
+        } else {
+            delta == 0.0 || delta >= 2.0 * PI + angle
+        };
+        if within {
+            add(center + radius * Vec2::from_angle(axis));
+        }
+    }
+}
+
+/// Add the points of a cap at the end of a stroke going in direction `dir`,
+/// after the point on its left edge.
+fn push_cap(p: Vec2, dir: Vec2, half: f64, cap: Cap, tolerance: f64, out: &mut Vec<Vec2>) {
+    let n = Vec2::new(-dir.y, dir.x);
+    match cap {
+        Cap::Butt => out.push(p - half * n),
+        Cap::Square => {
+            out.push(p + half * (n + dir));
+            out.push(p + half * (dir - n));
+            out.push(p - half * n);
+        }
+        Cap::Round => push_arc(p, n, -PI, half, tolerance, out),
+    }
+}
+
+/// Add the points of a circular arc, after its start, from unit direction
+/// `from` around `center`, sweeping `angle` radians.
+fn push_arc(
+    center: Vec2,
+    from: Vec2,
+    angle: f64,
+    radius: f64,
+    tolerance: f64,
+    out: &mut Vec<Vec2>,
+) {
+    // The greatest angle whose chord is within the tolerance of the arc.
+    let max_step = if tolerance < radius {
+        2.0 * (1.0 - tolerance / radius).acos()
+    } else {
+        PI
+    };
+    let n = ((angle.abs() / max_step).ceil() as usize).max(1);
+    let (s, c) = (angle / n as f64).sin_cos();
+    let mut d = from;
+    for _ in 0..n {
+        d = Vec2::new(c * d.x - s * d.y, s * d.x + c * d.y);
+        out.push(center + radius * d);
+    }
+}
+
+/// The vector scaled to unit length, or zero if it is zero.
+fn unit(v: Vec2) -> Vec2 {
+    let len = v.hypot();
+    if len > 0.0 {
+        v / len
+    } else {
+        v
+    }
+}
+
+/// Add a closed polygon to a path, leaving out repeated points.
+fn push_polygon(path: &mut BezPath, pts: &[Vec2]) {
+    let mut last = pts[0];
+    path.moveto(last);
+    for &p in &pts[1..] {
+        if p != last {
+            path.lineto(p);
+            last = p;
+        }
+    }
+    path.closepath();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{BezPath, Cap, Circle, DashPattern, Join, Rect, Shape, Stroke, WidthProfile};
+
+    #[test]
+    fn stroke() {
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        let butt = line.stroke(&Stroke::new(2.0), 1e-3);
+        assert!((butt.area().abs() - 20.0).abs() < 1e-9);
+        let square = line.stroke(&Stroke::new(2.0).with_caps(Cap::Square), 1e-3);
+        assert!((square.area().abs() - 24.0).abs() < 1e-9);
+        let round = line.stroke(&Stroke::new(2.0).with_caps(Cap::Round), 1e-3);
+        assert!((round.area().abs() - (20.0 + PI)).abs() < 1e-2);
+
+        let rect = BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap();
+        for &(join, corner, inside) in &[
+            (Join::Miter, true, true),
+            (Join::Bevel, false, false),
+            (Join::Round, false, true),
+        ] {
+            let outline = rect.stroke(&Stroke::new(2.0).with_join(join), 1e-3);
+            assert!(outline.winding((5.0, 0.5).into()) != 0);
+            assert!(outline.winding((5.0, 10.9).into()) != 0);
+            assert!(outline.winding((5.0, 5.0).into()) == 0);
+            assert!(outline.winding((5.0, 11.5).into()) == 0);
+            assert_eq!(outline.winding((10.9, 10.9).into()) != 0, corner);
+            assert_eq!(outline.winding((10.6, -0.6).into()) != 0, inside);
+        }
+        // A sharp corner beyond the miter limit is beveled.
+        let sharp = BezPath::from_svg("M0 0L10 0L0 1").unwrap();
+        let outline = sharp.stroke(&Stroke::new(1.0).with_miter_limit(2.0), 1e-3);
+        assert!(outline.winding((10.6, 0.0).into()) == 0);
+
+        // A circle gives an annulus.
+        let circle = Circle::new((0.0, 0.0), 10.0).into_bez_path(1e-9);
+        let ring = circle.stroke(&Stroke::new(2.0), 1e-4);
+        assert!((ring.area().abs() - 40.0 * PI).abs() < 1e-2);
+        assert!(ring.winding((0.0, 0.0).into()) == 0);
+        assert!(ring.winding((0.0, 10.5).into()) != 0);
+
+        // An open subpath of zero length is a dot, or nothing with butt caps.
+        let dot = BezPath::from_svg("M3 4L3 4").unwrap();
+        assert!(dot.stroke(&Stroke::new(2.0), 1e-3).elements().is_empty());
+        let square = dot.stroke(&Stroke::new(2.0).with_caps(Cap::Square), 1e-3);
+        assert!((square.area().abs() - 4.0).abs() < 1e-9);
+        assert!(square.winding((3.9, 4.9).into()) != 0);
+        let round = dot.stroke(&Stroke::new(2.0).with_caps(Cap::Round), 1e-3);
+        assert!((round.area().abs() - PI).abs() < 1e-2);
+        assert!(round.winding((3.0, 4.0).into()) != 0);
+        assert!(round.winding((3.9, 4.9).into()) == 0);
+    }
+
+    #[test]
+    fn stroke_variable() {
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        let profile = WidthProfile::new(vec![(1.0, 0.0), (0.5, 1.0), (0.0, 0.0)]);
+        assert_eq!(profile.eval(0.25), 0.5);
+        assert_eq!(profile.eval(2.0), 0.0);
+        let profile_nan = WidthProfile::new(vec![(1.0, 0.0), (f64::NAN, 5.0), (0.0, 0.0)]);
+        assert_eq!(profile_nan.eval(0.5), 0.0);
+        let taper = line.stroke_variable(&Stroke::new(2.0), |pos| profile.eval(pos), 1e-3);
+        assert!((taper.area().abs() - 10.0).abs() < 1e-9);
+
+        // A curved width is followed within the tolerance.
+        let bulge = line.stroke_variable(&Stroke::new(2.0), |pos| (PI * pos).sin(), 1e-4);
+        assert!((bulge.area().abs() - 40.0 / PI).abs() < 1e-2);
+        assert!(bulge.winding((5.0, 0.9).into()) != 0);
+        assert!(bulge.winding((0.5, 0.9).into()) == 0);
+    }
+
+    #[test]
+    fn stroke_dashed() {
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        let pattern = DashPattern::new(vec![2.0, 1.0]);
+        let dashed = line.stroke(&Stroke::new(2.0).with_dash(pattern.clone()), 1e-3);
+        assert!((dashed.area().abs() - 14.0).abs() < 1e-9);
+        assert!(dashed.winding((2.5, 0.0).into()) == 0);
+        let round = Stroke::new(2.0)
+            .with_caps(Cap::Round)
+            .with_dash(pattern.clone());
+        let dashed = line.stroke(&round, 1e-3);
+        assert!(dashed.winding((2.5, 0.0).into()) != 0);
+        // Without caps per dash, only the ends of the line are round.
+        let ends_only = round.with_dash(pattern.with_caps_per_dash(false));
+        let dashed = line.stroke(&ends_only, 1e-3);
+        assert!(dashed.winding((2.5, 0.0).into()) == 0);
+        assert!(dashed.winding((-0.5, 0.0).into()) != 0);
+        assert!(dashed.winding((10.5, 0.0).into()) != 0);
+
+        // The dash over the seam of a closed path turns the corner.
+        let square = BezPath::from_svg("M0 0L4 0L4 4L0 4Z").unwrap();
+        let style = Stroke::new(1.0).with_dash(DashPattern::new(vec![3.0, 2.0]).with_phase(1.0));
+        let dashed = square.stroke(&style, 1e-3);
+        assert!(dashed.winding((-0.4, -0.4).into()) != 0);
+        assert!(dashed.winding((4.4, 4.4).into()) == 0);
+        assert!(dashed.winding((4.4, -0.4).into()) == 0);
+        assert!(dashed.winding((4.0, 2.0).into()) != 0);
+    }
+
+    #[test]
+    fn stroke_bounding_box() {
+        let line = BezPath::from_svg("M0 0L10 0").unwrap();
+        let near = |a: Rect, b: Rect, eps: f64| {
+            let d = [a.x0 - b.x0, a.y0 - b.y0, a.x1 - b.x1, a.y1 - b.y1];
+            d.iter().all(|d| d.abs() < eps)
+        };
+        let bbox = line.stroke_bounding_box(&Stroke::new(2.0));
+        assert!(near(bbox, Rect::new(0.0, -1.0, 10.0, 1.0), 1e-12));
+        let bbox = line.stroke_bounding_box(&Stroke::new(2.0).with_caps(Cap::Round));
+        assert!(near(bbox, Rect::new(-1.0, -1.0, 11.0, 1.0), 1e-12));
+
+        // The box is that of the outline, for various shapes and styles.
+        let mut paths = vec![
+            BezPath::from_svg("M0 0L10 0L10 10L0 10Z").unwrap(),
+            BezPath::from_svg("M0 0L10 0L0 3").unwrap(),
+            BezPath::from_svg("M0 0C10 0 10 10 0 10L-5 3").unwrap(),
+        ];
+        // A tight curve, whose inner offset has cusps.
+        let mut tight = BezPath::new();
+        tight.moveto((0.0, 0.0));
+        tight.curveto((4.0, 3.0), (-1.0, 3.0), (3.0, 0.0));
+        paths.push(tight);
+        paths.push(BezPath::from_svg("M3 4L3 4").unwrap());
+        let styles = [
+            Stroke::new(3.0),
+            Stroke::new(3.0)
+                .with_join(Join::Bevel)
+                .with_caps(Cap::Square),
+            Stroke::new(3.0)
+                .with_join(Join::Round)
+                .with_caps(Cap::Round),
+            Stroke::new(3.0).with_miter_limit(100.0),
+        ];
+        for path in &paths {
+            for style in &styles {
+                let expected = path.stroke(style, 1e-6).bounding_box();
+                let bbox = path.stroke_bounding_box(style);
+                for &(a, b) in &[
+                    (bbox.x0, expected.x0),
+                    (bbox.y0, expected.y0),
+                    (bbox.x1, expected.x1),
+                    (bbox.y1, expected.y1),
+                ] {
+                    assert!(
+                        (a - b).abs() < 1e-4,
+                        "{:?} {:?} {:?}",
+                        style,
+                        bbox,
+                        expected
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
 //! SVG path representation.
 
-use std::f64::consts::{FRAC_PI_2, PI};
+use std::f64::consts::PI;
 use std::io::Write;
 
-use crate::{BezPath, PathEl, Vec2};
+use crate::{Arc, BezPath, PathEl, Vec2};
 
 // Note: the SVG arc logic is heavily adapted from https://github.com/nical/lyon
 struct SvgArc {
@@ -15,15 +15,6 @@ struct SvgArc {
     pub sweep: bool,
 }
 
-// TODO: consider adding this to the public API.
-struct Arc {
-    pub center: Vec2,
-    pub radii: Vec2,
-    pub start_angle: f64,
-    pub sweep_angle: f64,
-    pub x_rotation: f64,
-}
-
 impl BezPath {
     /// Convert the path to an SVG path string representation.
     ///
@@ -150,7 +141,7 @@ struct SvgLexer<'a> {
 }
 
 impl<'a> SvgLexer<'a> {
-    fn new(data: &str) -> SvgLexer {
+    fn new(data: &str) -> SvgLexer<'_> {
         SvgLexer {
             data,
             ix: 0,
@@ -170,9 +161,9 @@ impl<'a> SvgLexer<'a> {
     fn get_cmd(&mut self, last_cmd: u8) -> Option<u8> {
         self.skip_ws();
         if let Some(c) = self.get_byte() {
-            if (c >= b'a' && c <= b'z') || (c >= b'A' && c <= b'Z') {
+            if c.is_ascii_alphabetic() {
                 return Some(c);
-            } else if last_cmd != 0 && (c == b'-' || c == b'.' || (c >= b'0' && c <= b'9')) {
+            } else if last_cmd != 0 && (c == b'-' || c == b'.' || c.is_ascii_digit()) {
                 // Plausible number start
                 self.unget();
                 return Some(last_cmd);
@@ -204,7 +195,7 @@ impl<'a> SvgLexer<'a> {
         let mut digit_count = 0;
         let mut seen_period = false;
         while let Some(c) = self.get_byte() {
-            if c >= b'0' && c <= b'9' {
+            if c.is_ascii_digit() {
                 digit_count += 1;
             } else if c == b'.' && !seen_period {
                 seen_period = true;
@@ -232,7 +223,7 @@ impl<'a> SvgLexer<'a> {
 
     fn get_maybe_relative(&mut self, cmd: u8) -> Result<Vec2, SvgParseError> {
         let pt = self.get_number_pair()?;
-        if cmd >= b'a' && cmd <= b'z' {
+        if cmd.is_ascii_lowercase() {
             Ok(pt + self.last_pt)
         } else {
             Ok(pt)
@@ -321,45 +312,10 @@ impl Arc {
             center,
             radii: Vec2::new(rx, ry),
             start_angle,
-            sweep_angle: sweep_angle,
+            sweep_angle,
             x_rotation: arc.x_rotation,
         }
     }
-
-    fn append_to_path(&self, path: &mut BezPath, tolerance: f64) {
-        let scaled_err = self.radii.x.max(self.radii.y) / tolerance;
-        // Number of subdivisions per circle based on error tolerance.
-        // Note: this may slightly underestimate the error for quadrants.
-        let n_err = (1.1163 * scaled_err).powf(1.0 / 6.0).max(3.999_999);
-        let n = (n_err * self.sweep_angle.abs() * (1.0 / (2.0 * PI))).ceil();
-        let angle_step = self.sweep_angle / n;
-        let n = n as usize;
-        let arm_len = (4.0 / 3.0) * (0.25 * angle_step).abs().tan();
-        let mut angle0 = self.start_angle;
-        let mut p0 = sample_ellipse(self.radii, self.x_rotation, angle0);
-        for _ in 0..n {
-            let angle1 = angle0 + angle_step;
-            let p1 = p0 + arm_len * sample_ellipse(self.radii, self.x_rotation, angle0 + FRAC_PI_2);
-            let p3 = sample_ellipse(self.radii, self.x_rotation, angle1);
-            let p2 = p3 - arm_len * sample_ellipse(self.radii, self.x_rotation, angle1 + FRAC_PI_2);
-            path.curveto(self.center + p1, self.center + p2, self.center + p3);
-            angle0 = angle1;
-            p0 = p3;
-        }
-    }
-}
-
-fn sample_ellipse(radii: Vec2, x_rotation: f64, angle: f64) -> Vec2 {
-    let u = radii.x * angle.cos();
-    let v = radii.y * angle.sin();
-    rotate_pt(Vec2::new(u, v), x_rotation)
-}
-
-fn rotate_pt(pt: Vec2, angle: f64) -> Vec2 {
-    Vec2::new(
-        pt.x * angle.cos() - pt.y * angle.sin(),
-        pt.x * angle.sin() + pt.y * angle.cos(),
-    )
 }
 
 #[cfg(test)]
@@ -0,0 +1,177 @@
+//! Sweeping a brush shape along a path.
+
+use crate::{BezPath, Shape, Vec2};
+
+/// The outline of a brush swept along a path.
+///
+/// The brush is placed with its origin on each point of the path, and the
+/// result covers every point covered by the brush on the way, as the
+/// Minkowski sum of the shape and the path. This is the stroke of a
+/// painting application with a brush of any shape, such as a calligraphic
+/// nib.
+///
+/// Both the brush and the path are flattened to `tolerance`, and each
+/// subpath of the brush is taken as a filled region. The result is the
+/// union of the brush at every vertex of the path with the regions swept by
+/// each edge of the brush along each edge of the path. These are all
+/// oriented alike, so they overlap, and the result is to be filled with the
+/// nonzero fill rule.
+pub fn sweep(brush: &impl Shape, along: &BezPath, tolerance: f64) -> BezPath {
+    let polygons = brush_polygons(brush, tolerance);
+    let mut result = BezPath::new();
+    for line in polylines(along, tolerance) {
+        for &p in &line {
+            for poly in &polygons {
+                push_polygon(&mut result, poly.iter().map(|&q| p + q));
+            }
+        }
+        for edge in line.windows(2) {
+            let v = edge[1] - edge[0];
+            for poly in &polygons {
+                for i in 0..poly.len() {
+                    let (q0, q1) = (edge[0] + poly[i], edge[0] + poly[(i + 1) % poly.len()]);
+                    let area = (q1 - q0).cross(v);
+                    if area > 0.0 {
+                        push_polygon(&mut result, vec![q0, q1, q1 + v, q0 + v]);
+                    } else if area < 0.0 {
+                        push_polygon(&mut result, vec![q1, q0, q0 + v, q1 + v]);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Copies of a brush stamped at even spacing along a path.
+///
+/// The brush is placed with its origin at the start of each subpath of the
+/// path, and then at every `spacing` of arclength along it, measured on the
+/// path flattened to `tolerance`. This is the dabbing of a painting
+/// application, which leaves gaps between the stamps when the spacing is
+/// larger than the brush; [`sweep`](fn.sweep.html) gives the continuous
+/// stroke. The stamps are oriented alike, and the result is to be filled
+/// with the nonzero fill rule.
+///
+/// Panics if `spacing` is not positive.
+pub fn stamp(brush: &impl Shape, along: &BezPath, spacing: f64, tolerance: f64) -> BezPath {
+    assert!(spacing > 0.0, "the spacing of stamps must be positive");
+    let polygons = brush_polygons(brush, tolerance);
+    let mut result = BezPath::new();
+    for line in polylines(along, tolerance) {
+        // The distance along the current edge to the next stamp.
+        let mut next = 0.0;
+        for edge in line.windows(2) {
+            let v = edge[1] - edge[0];
+            let len = v.hypot();
+            while next <= len {
+                let p = if len > 0.0 {
+                    edge[0] + (next / len) * v
+                } else {
+                    edge[0]
+                };
+                for poly in &polygons {
+                    push_polygon(&mut result, poly.iter().map(|&q| p + q));
+                }
+                next += spacing;
+            }
+            next -= len;
+        }
+    }
+    result
+}
+
+/// The polylines of a flattened path.
+fn polylines(path: &BezPath, tolerance: f64) -> Vec<Vec<Vec2>> {
+    let mut points = Vec::new();
+    let mut ends = Vec::new();
+    path.flatten_into(tolerance, &mut points, &mut ends);
+    let mut start = 0;
+    ends.iter()
+        .map(|&end| {
+            let line = points[start..end].to_vec();
+            start = end;
+            line
+        })
+        .collect()
+}
+
+/// The subpaths of the brush as polygons, none with negative area.
+fn brush_polygons(brush: &impl Shape, tolerance: f64) -> Vec<Vec<Vec2>> {
+    let path = BezPath::from_vec(brush.to_bez_path(tolerance).collect());
+    let mut result = Vec::new();
+    for mut poly in polylines(&path, tolerance) {
+        if poly.len() > 1 && poly[0] == poly[poly.len() - 1] {
+            poly.pop();
+        }
+        let n = poly.len();
+        let area: f64 = (0..n).map(|i| poly[i].cross(poly[(i + 1) % n])).sum();
+        if area < 0.0 {
+            poly.reverse();
+        }
+        if n > 1 {
+            result.push(poly);
+        }
+    }
+    result
+}
+
+/// Add a closed polygon to a path.
+fn push_polygon(path: &mut BezPath, pts: impl IntoIterator<Item = Vec2>) {
+    let mut pts = pts.into_iter();
+    if let Some(p) = pts.next() {
+        path.moveto(p);
+        for p in pts {
+            path.lineto(p);
+        }
+        path.closepath();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{stamp, sweep, BezPath, Circle, Rect, Shape};
+
+    #[test]
+    fn sweep_brush() {
+        let path = BezPath::from_svg("M0 0L10 0L10 10").unwrap();
+        let square = Rect::new(-1.0, -1.0, 1.0, 1.0);
+        let swept = sweep(&square, &path, 1e-3);
+        for &(x, y, inside) in &[
+            (5.0, 0.9, true),
+            (5.0, -0.9, true),
+            (5.0, 1.1, false),
+            (10.9, -0.9, true),
+            (10.9, 10.9, true),
+            (9.1, 5.0, true),
+            (8.9, 5.0, false),
+            (-1.1, 0.0, false),
+        ] {
+            assert_eq!(swept.winding((x, y).into()) != 0, inside, "{} {}", x, y);
+        }
+
+        // A circular brush gives a round stroke.
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let swept = sweep(&circle, &path, 1e-3);
+        assert!(swept.winding((10.6, -0.6).into()) != 0);
+        assert!(swept.winding((10.8, -0.8).into()) == 0);
+
+        // A diagonal nib makes thin and thick strokes.
+        let nib = BezPath::from_svg("M-1 -1L1 1").unwrap();
+        let swept = sweep(&nib, &path, 1e-3);
+        assert!(swept.winding((5.0, 0.9).into()) != 0);
+        assert!(swept.winding((0.0, 0.5).into()) == 0);
+    }
+
+    #[test]
+    fn stamp_brush() {
+        let path = BezPath::from_svg("M0 0L10 0").unwrap();
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let stamps = stamp(&circle, &path, 2.5, 1e-3);
+        assert!((stamps.area() - 5.0 * PI).abs() < 1e-2);
+        assert!(stamps.winding((7.5, 0.0).into()) != 0);
+        assert!(stamps.winding((1.25, 0.9).into()) == 0);
+    }
+}
@@ -0,0 +1,276 @@
+//! A shape with an affine transform applied lazily.
+
+use std::ops::Mul;
+
+use crate::{Affine, BezPath, Line, PathEl, Rect, RoundedRect, Shape, Triangle, Vec2};
+
+/// A shape viewed through an affine transform.
+///
+/// The transform is applied on the fly, as path elements are iterated, so
+/// that a transformed shape can be measured or drawn without first building
+/// a transformed [`BezPath`](struct.BezPath.html).
+#[derive(Clone, Copy, Debug)]
+pub struct TransformedShape<S> {
+    /// The untransformed shape.
+    pub shape: S,
+    /// The transform applied to the shape.
+    pub affine: Affine,
+}
+
+impl<S: Shape> TransformedShape<S> {
+    /// Apply a transform to a shape.
+    #[inline]
+    pub fn new(shape: S, affine: Affine) -> TransformedShape<S> {
+        TransformedShape { shape, affine }
+    }
+
+    /// The largest factor by which the transform stretches any vector.
+    fn max_scale(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.affine.as_coeffs();
+        let s = a * a + b * b + c * c + d * d;
+        let det = self.affine.determinant();
+        (0.5 * (s + (s * s - 4.0 * det * det).max(0.0).sqrt())).sqrt()
+    }
+
+    /// The path elements of the untransformed shape.
+    fn inner_elements(&self, tolerance: f64) -> Box<dyn Iterator<Item = PathEl> + '_> {
+        if let Some(slice) = self.shape.as_path_slice() {
+            Box::new(slice.iter().cloned())
+        } else {
+            Box::new(self.shape.to_bez_path(tolerance))
+        }
+    }
+
+    /// The transformed path elements, converting the inner shape so that
+    /// the result is within `tolerance`.
+    fn elements(&self, tolerance: f64) -> impl Iterator<Item = PathEl> + '_ {
+        let affine = self.affine;
+        self.inner_elements(tolerance / self.max_scale())
+            .map(move |el| affine * el)
+    }
+}
+
+#[doc(hidden)]
+pub struct TransformedPathIter<I> {
+    inner: I,
+    affine: Affine,
+}
+
+impl<I: Iterator<Item = PathEl>> Iterator for TransformedPathIter<I> {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        self.inner.next().map(|el| self.affine * el)
+    }
+}
+
+impl<S: Shape> Shape for TransformedShape<S> {
+    type BezPathIter = TransformedPathIter<S::BezPathIter>;
+
+    fn to_bez_path(&self, tolerance: f64) -> TransformedPathIter<S::BezPathIter> {
+        TransformedPathIter {
+            inner: self.shape.to_bez_path(tolerance / self.max_scale()),
+            affine: self.affine,
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.shape.area() * self.affine.determinant()
+    }
+
+    fn perimeter(&self, accuracy: f64) -> f64 {
+        BezPath::segments_of_iter(self.elements(accuracy)).arclen(accuracy)
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        let det = self.affine.determinant();
+        if det == 0.0 {
+            return 0;
+        }
+        let winding = self.shape.winding(self.affine.inverse() * pt);
+        if det < 0.0 {
+            -winding
+        } else {
+            winding
+        }
+    }
+
+    fn bounding_box(&self) -> Rect {
+        self.shape.bounding_box_affine(self.affine)
+    }
+
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        self.shape.bounding_box_affine(affine * self.affine)
+    }
+
+    fn centroid(&self) -> Vec2 {
+        self.affine * self.shape.centroid()
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        // The dot product with the direction, pulled back through the transform.
+        let [a, b, c, d, _, _] = self.affine.as_coeffs();
+        let back = Vec2::new(
+            a * direction.x + b * direction.y,
+            c * direction.x + d * direction.y,
+        );
+        self.affine * self.shape.support(back)
+    }
+
+    fn as_line(&self) -> Option<Line> {
+        self.shape
+            .as_line()
+            .map(|line| Line::new(self.affine * line.p0, self.affine * line.p1))
+    }
+
+    /// A rounded rectangle stays one under translation and uniform scaling,
+    /// including by a negative factor.
+    fn as_rounded_rect(&self) -> Option<RoundedRect> {
+        let [a, b, c, d, _, _] = self.affine.as_coeffs();
+        if a != d || b != 0.0 || c != 0.0 {
+            return None;
+        }
+        self.shape.as_rounded_rect().map(|rr| {
+            let r = rr.rect();
+            let rect = Rect::from_points(
+                self.affine * Vec2::new(r.x0, r.y0),
+                self.affine * Vec2::new(r.x1, r.y1),
+            );
+            RoundedRect::new(rect, rr.radius() * a.abs())
+        })
+    }
+
+    fn as_triangle(&self) -> Option<Triangle> {
+        self.shape
+            .as_triangle()
+            .map(|t| Triangle::new(self.affine * t.a, self.affine * t.b, self.affine * t.c))
+    }
+
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        self.shape
+            .as_polygon()
+            .map(|pts| pts.into_iter().map(|p| self.affine * p).collect())
+    }
+}
+
+impl<S: Shape> Mul<TransformedShape<S>> for Affine {
+    type Output = TransformedShape<S>;
+
+    #[inline]
+    fn mul(self, other: TransformedShape<S>) -> TransformedShape<S> {
+        TransformedShape::new(other.shape, self * other.affine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Affine, BezPath, Circle, Ellipse, Rect, RoundedRect, Shape, Star, TransformedShape,
+        Triangle, Vec2,
+    };
+    use std::f64::consts::PI;
+
+    fn assert_rect_near(r0: Rect, r1: Rect, tol: f64) {
+        assert!(
+            (r0.x0 - r1.x0).abs() < tol
+                && (r0.y0 - r1.y0).abs() < tol
+                && (r0.x1 - r1.x1).abs() < tol
+                && (r0.y1 - r1.y1).abs() < tol,
+            "{:?} != {:?}",
+            r0,
+            r1
+        );
+    }
+
+    #[test]
+    fn transformed_rect() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 1.0);
+        let affine = Affine::translate((1.0, 1.0)) * Affine::rotate(PI / 2.0);
+        let shape = TransformedShape::new(rect, affine);
+        let path = affine * rect.into_bez_path(1e-9);
+
+        assert!((shape.area() - path.area()).abs() < 1e-9);
+        assert!((shape.perimeter(1e-9) - 6.0).abs() < 1e-9);
+        assert_rect_near(shape.bounding_box(), Rect::new(0.0, 1.0, 1.0, 3.0), 1e-9);
+        assert_eq!(
+            shape.winding(Vec2::new(0.5, 2.0)),
+            rect.winding(Vec2::new(1.0, 0.5))
+        );
+        assert_eq!(shape.winding(Vec2::new(1.5, 2.0)), 0);
+
+        let elements: Vec<_> = shape.to_bez_path(1e-9).collect();
+        assert_eq!(elements.len(), path.elements().len());
+    }
+
+    #[test]
+    fn transformed_mirror() {
+        let circle = Circle::new((1.0, 0.0), 1.0);
+        let affine = Affine::new([-2.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        let shape = TransformedShape::new(circle, affine);
+        assert!((shape.area() + 2.0 * PI).abs() < 1e-9);
+        assert_eq!(
+            shape.winding(Vec2::new(-2.0, 0.0)),
+            -circle.winding(circle.center)
+        );
+        assert_rect_near(shape.bounding_box(), Rect::new(-4.0, -1.0, 0.0, 1.0), 1e-9);
+        let c = shape.centroid();
+        assert!((c - Vec2::new(-2.0, 0.0)).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn transformed_rotated_bbox() {
+        let circle = Circle::new((0.0, 0.0), 1.0);
+        let affine = Affine::rotate(0.3) * Affine::new([3.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+        let shape = TransformedShape::new(circle, affine);
+        let path: BezPath = affine * circle.into_bez_path(1e-9);
+        assert_rect_near(shape.bounding_box(), path.bounding_box(), 1e-6);
+        let composed = Affine::translate((1.0, 2.0)) * shape;
+        let bb = composed.bounding_box();
+        assert_rect_near(bb, path.bounding_box() + Vec2::new(1.0, 2.0), 1e-6);
+    }
+
+    #[test]
+    fn transformed_downcasts() {
+        let circle = Circle::new((1.0, 2.0), 3.0);
+        let ellipse = circle.as_ellipse().unwrap();
+        assert_eq!(
+            (ellipse.center, ellipse.radii),
+            (circle.center, Vec2::new(3.0, 3.0))
+        );
+        assert!(Ellipse::new((0.0, 0.0), (2.0, 1.0), 0.0)
+            .as_circle()
+            .is_none());
+
+        let triangle = Triangle::new((0.0, 0.0), (1.0, 0.0), (0.0, 1.0));
+        let shape = TransformedShape::new(triangle, Affine::translate((1.0, 1.0)));
+        let t = shape.as_triangle().unwrap();
+        assert_eq!(t.a, Vec2::new(1.0, 1.0));
+        assert_eq!(t.b, Vec2::new(2.0, 1.0));
+        assert_eq!(t.c, Vec2::new(1.0, 2.0));
+        assert_eq!(shape.as_polygon().unwrap().len(), 3);
+        assert!(TransformedShape::new(circle, Affine::scale(2.0))
+            .as_ellipse()
+            .is_none());
+
+        let rr = RoundedRect::new(Rect::new(0.0, 0.0, 4.0, 2.0), 1.0);
+        let affine = Affine::translate((1.0, 1.0)) * Affine::scale(-2.0);
+        let moved = TransformedShape::new(rr, affine).as_rounded_rect().unwrap();
+        assert_rect_near(moved.rect(), Rect::new(-7.0, -3.0, 1.0, 1.0), 1e-12);
+        assert_eq!(moved.radius(), 2.0);
+        assert!(TransformedShape::new(rr, Affine::rotate(0.5))
+            .as_rounded_rect()
+            .is_none());
+        assert!(Shape::as_rounded_rect(&&rr).is_some());
+
+        // Through a reference.
+        fn polygon(shape: impl Shape) -> Option<Vec<Vec2>> {
+            shape.as_polygon()
+        }
+        let star = Star::new((0.0, 0.0), 2.0, 1.0, 5);
+        let star_ref = &star;
+        let pts = polygon(star_ref).unwrap();
+        assert_eq!(pts.len(), 10);
+        assert_eq!(pts[0], Vec2::new(2.0, 0.0));
+        assert!(Rect::new(0.0, 0.0, 1.0, 1.0).as_polygon().is_none());
+    }
+}
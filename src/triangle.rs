@@ -0,0 +1,211 @@
+//! Implementation of triangle shape.
+
+use std::ops::{Add, Sub};
+
+use crate::circle::circumcircle;
+use crate::shape::farthest;
+use crate::{Affine, Circle, PathEl, Rect, Shape, Vec2};
+
+/// A triangle.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Triangle {
+    /// The first vertex.
+    pub a: Vec2,
+    /// The second vertex.
+    pub b: Vec2,
+    /// The third vertex.
+    pub c: Vec2,
+}
+
+impl Triangle {
+    /// A new triangle from its vertices.
+    #[inline]
+    pub fn new(a: impl Into<Vec2>, b: impl Into<Vec2>, c: impl Into<Vec2>) -> Triangle {
+        Triangle {
+            a: a.into(),
+            b: b.into(),
+            c: c.into(),
+        }
+    }
+
+    /// The circle passing through all three vertices, or `None` if they are collinear.
+    pub fn circumcircle(&self) -> Option<Circle> {
+        circumcircle(self.a, self.b, self.c)
+    }
+}
+
+impl Add<Vec2> for Triangle {
+    type Output = Triangle;
+
+    #[inline]
+    fn add(self, v: Vec2) -> Triangle {
+        Triangle::new(self.a + v, self.b + v, self.c + v)
+    }
+}
+
+impl Sub<Vec2> for Triangle {
+    type Output = Triangle;
+
+    #[inline]
+    fn sub(self, v: Vec2) -> Triangle {
+        Triangle::new(self.a - v, self.b - v, self.c - v)
+    }
+}
+
+#[doc(hidden)]
+pub struct TrianglePathIter {
+    triangle: Triangle,
+    ix: usize,
+}
+
+impl Shape for Triangle {
+    type BezPathIter = TrianglePathIter;
+
+    fn to_bez_path(&self, _tolerance: f64) -> TrianglePathIter {
+        TrianglePathIter {
+            triangle: *self,
+            ix: 0,
+        }
+    }
+
+    #[inline]
+    fn area(&self) -> f64 {
+        0.5 * (self.b - self.a).cross(self.c - self.a)
+    }
+
+    #[inline]
+    fn perimeter(&self, _accuracy: f64) -> f64 {
+        (self.b - self.a).hypot() + (self.c - self.b).hypot() + (self.a - self.c).hypot()
+    }
+
+    fn winding(&self, pt: Vec2) -> i32 {
+        let s0 = (self.b - self.a).cross(pt - self.a);
+        let s1 = (self.c - self.b).cross(pt - self.b);
+        let s2 = (self.a - self.c).cross(pt - self.c);
+        if s0 > 0.0 && s1 > 0.0 && s2 > 0.0 {
+            1
+        } else if s0 < 0.0 && s1 < 0.0 && s2 < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    fn bounding_box(&self) -> Rect {
+        Rect::from_points(self.a, self.b).union_pt(self.c)
+    }
+
+    #[inline]
+    fn bounding_box_affine(&self, affine: Affine) -> Rect {
+        Rect::from_points(affine * self.a, affine * self.b).union_pt(affine * self.c)
+    }
+
+    #[inline]
+    fn centroid(&self) -> Vec2 {
+        (self.a + self.b + self.c) / 3.0
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest([self.a, self.b, self.c].iter().cloned(), direction)
+    }
+
+    /// The smallest enclosing circle.
+    ///
+    /// For an acute triangle this is the circumcircle; otherwise it has the
+    /// longest side as a diameter.
+    fn bounding_circle(&self) -> Circle {
+        let Triangle { a, b, c } = *self;
+        // Find the longest side, and the opposite vertex.
+        let (p, q, r) = [(a, b, c), (b, c, a), (c, a, b)]
+            .iter()
+            .cloned()
+            .max_by(|x, y| {
+                let dx = (x.1 - x.0).hypot2();
+                let dy = (y.1 - y.0).hypot2();
+                dx.partial_cmp(&dy).unwrap()
+            })
+            .unwrap();
+        // The opposite angle is at least a right angle.
+        if (p - r).dot(q - r) <= 0.0 {
+            Circle::new(p.lerp(q, 0.5), 0.5 * (q - p).hypot())
+        } else {
+            self.circumcircle()
+                .unwrap_or_else(|| Circle::new(p.lerp(q, 0.5), 0.5 * (q - p).hypot()))
+        }
+    }
+
+    fn as_triangle(&self) -> Option<Triangle> {
+        Some(*self)
+    }
+
+    fn as_polygon(&self) -> Option<Vec<Vec2>> {
+        Some(vec![self.a, self.b, self.c])
+    }
+}
+
+impl Iterator for TrianglePathIter {
+    type Item = PathEl;
+
+    fn next(&mut self) -> Option<PathEl> {
+        let t = &self.triangle;
+        self.ix += 1;
+        match self.ix {
+            1 => Some(PathEl::Moveto(t.a)),
+            2 => Some(PathEl::Lineto(t.b)),
+            3 => Some(PathEl::Lineto(t.c)),
+            4 => Some(PathEl::Closepath),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, Circle, Ellipse, Line, Rect, Shape, Triangle, Vec2};
+
+    fn assert_near(p: Vec2, q: Vec2) {
+        assert!((p - q).hypot() < 1e-9, "{:?} != {:?}", p, q);
+    }
+
+    #[test]
+    fn triangle_shape() {
+        let t = Triangle::new((0.0, 0.0), (4.0, 0.0), (1.0, 3.0));
+        assert_eq!(t.area(), 6.0);
+        assert_eq!(t.winding(Vec2::new(1.0, 1.0)), 1);
+        assert_eq!(t.winding(Vec2::new(3.0, 3.0)), 0);
+        assert_near(t.centroid(), Vec2::new(5.0 / 3.0, 1.0));
+        let path = t.into_bez_path(1e-9);
+        assert_eq!(path.area(), 6.0);
+        assert_near(path.centroid(), t.centroid());
+
+        // Acute: the circumcircle.
+        let c = t.bounding_circle();
+        assert_near(c.center, Vec2::new(2.0, 1.0));
+        // Obtuse: the longest side is a diameter.
+        let obtuse = Triangle::new((0.0, 0.0), (4.0, 0.0), (1.0, 0.5));
+        let c = obtuse.bounding_circle();
+        assert_near(c.center, Vec2::new(2.0, 0.0));
+        assert!((c.radius - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn centroid_and_bounding_circle() {
+        let rect = Rect::new(0.0, 0.0, 6.0, 8.0);
+        assert_near(rect.centroid(), Vec2::new(3.0, 4.0));
+        assert_eq!(rect.bounding_circle().radius, 5.0);
+        let ellipse = Ellipse::new((1.0, 1.0), (2.0, 3.0), 0.5);
+        assert_eq!(ellipse.bounding_circle().radius, 3.0);
+        let line = Line::new((0.0, 0.0), (2.0, 0.0));
+        assert_near(line.centroid(), Vec2::new(1.0, 0.0));
+
+        // The path-based versions agree with the closed forms.
+        let circle = Circle::new((2.0, 3.0), 1.0);
+        let path = circle.into_bez_path(1e-9);
+        assert_near(path.centroid(), circle.centroid());
+        let c = path.bounding_circle();
+        assert!(c.radius >= 1.0);
+        let path = BezPath::from_svg("M0 0L6 0L6 8L0 8Z").unwrap();
+        assert_near(path.bounding_circle().center, rect.bounding_circle().center);
+    }
+}
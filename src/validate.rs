@@ -0,0 +1,195 @@
+//! Validation and repair of paths.
+
+use crate::{BezPath, PathEl, Vec2};
+
+/// A problem found by [`BezPath::validate`](struct.BezPath.html#method.validate).
+///
+/// Each variant holds the index of the offending element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathIssue {
+    /// The element has a NaN or infinite coordinate.
+    NonFinite(usize),
+    /// The element draws a segment but there is no current point, because
+    /// it comes before any `Moveto` or directly after a `Closepath`.
+    MissingMoveto(usize),
+    /// The element draws a segment with all its points at its start.
+    ZeroLength(usize),
+    /// The subpath starting at this element is not closed.
+    Unclosed(usize),
+}
+
+impl BezPath {
+    /// Check the path for problems.
+    ///
+    /// The issues are reported in element order. An empty result means the
+    /// path is finite, every subpath starts with a `Moveto` and is closed,
+    /// and every segment has nonzero length. Open subpaths are fine for
+    /// stroking, so [`PathIssue::Unclosed`](enum.PathIssue.html) may not be
+    /// an error for a particular use.
+    pub fn validate(&self) -> Vec<PathIssue> {
+        let mut issues = Vec::new();
+        let mut last: Option<Vec2> = None;
+        let mut open: Option<usize> = None;
+        for (ix, &el) in self.elements().iter().enumerate() {
+            if !is_finite(el) {
+                issues.push(PathIssue::NonFinite(ix));
+                continue;
+            }
+            match el {
+                PathEl::Moveto(p) => {
+                    if let Some(open_ix) = open {
+                        issues.push(PathIssue::Unclosed(open_ix));
+                    }
+                    open = Some(ix);
+                    last = Some(p);
+                }
+                PathEl::Closepath => {
+                    open = None;
+                    last = None;
+                }
+                _ => match last {
+                    None => {
+                        // Carry on as though this were a `Moveto`, so that
+                        // one missing `Moveto` is reported once.
+                        issues.push(PathIssue::MissingMoveto(ix));
+                        if let Some(open_ix) = open {
+                            issues.push(PathIssue::Unclosed(open_ix));
+                        }
+                        open = Some(ix);
+                        last = end_point(el);
+                    }
+                    Some(p0) => {
+                        if is_degenerate(p0, el) {
+                            issues.push(PathIssue::ZeroLength(ix));
+                        }
+                        last = end_point(el);
+                    }
+                },
+            }
+        }
+        if let Some(open_ix) = open {
+            issues.push(PathIssue::Unclosed(open_ix));
+        }
+        issues
+    }
+
+    /// Repair the problems reported by [`validate`](#method.validate).
+    ///
+    /// Elements with non-finite coordinates and zero-length segments are
+    /// dropped. A segment directly after a `Closepath` gets a `Moveto` to
+    /// the start of the closed subpath, which is where it would otherwise
+    /// be drawn from; a segment before any `Moveto` is replaced by a
+    /// `Moveto` to its end point. Open subpaths are left as they are.
+    pub fn sanitize(&mut self) {
+        let mut result = Vec::with_capacity(self.elements().len());
+        let mut last: Option<Vec2> = None;
+        let mut closed_start: Option<Vec2> = None;
+        let mut start = Vec2::default();
+        for &el in self.elements() {
+            if !is_finite(el) {
+                continue;
+            }
+            match el {
+                PathEl::Moveto(p) => {
+                    start = p;
+                    last = Some(p);
+                }
+                PathEl::Closepath => {
+                    closed_start = Some(start);
+                    last = None;
+                }
+                _ => {
+                    let p0 = match last.or(closed_start) {
+                        Some(p0) => p0,
+                        None => {
+                            let p = end_point(el).unwrap();
+                            start = p;
+                            last = Some(p);
+                            result.push(PathEl::Moveto(p));
+                            continue;
+                        }
+                    };
+                    if is_degenerate(p0, el) {
+                        continue;
+                    }
+                    if last.is_none() {
+                        start = p0;
+                        result.push(PathEl::Moveto(p0));
+                    }
+                    last = end_point(el);
+                }
+            }
+            result.push(el);
+        }
+        *self = BezPath::from_vec(result);
+    }
+}
+
+fn is_finite(el: PathEl) -> bool {
+    let ok = |p: Vec2| p.x.is_finite() && p.y.is_finite();
+    match el {
+        PathEl::Moveto(p) | PathEl::Lineto(p) => ok(p),
+        PathEl::Quadto(p1, p2) => ok(p1) && ok(p2),
+        PathEl::Curveto(p1, p2, p3) => ok(p1) && ok(p2) && ok(p3),
+        PathEl::Closepath => true,
+    }
+}
+
+/// Whether a segment element starting at `p0` has all its points at `p0`.
+fn is_degenerate(p0: Vec2, el: PathEl) -> bool {
+    match el {
+        PathEl::Lineto(p) => p == p0,
+        PathEl::Quadto(p1, p2) => p1 == p0 && p2 == p0,
+        PathEl::Curveto(p1, p2, p3) => p1 == p0 && p2 == p0 && p3 == p0,
+        _ => false,
+    }
+}
+
+fn end_point(el: PathEl) -> Option<Vec2> {
+    match el {
+        PathEl::Moveto(p) | PathEl::Lineto(p) | PathEl::Quadto(_, p) | PathEl::Curveto(_, _, p) => {
+            Some(p)
+        }
+        PathEl::Closepath => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BezPath, PathEl, PathIssue, Vec2};
+
+    #[test]
+    fn validate_and_sanitize() {
+        let good = BezPath::from_svg("M0 0L10 0L10 10ZM20 0L30 0C30 5 25 10 20 10Z").unwrap();
+        assert!(good.validate().is_empty());
+
+        let mut path = BezPath::from_vec(vec![
+            PathEl::Lineto(Vec2::new(1.0, 1.0)),
+            PathEl::Lineto(Vec2::new(5.0, 1.0)),
+            PathEl::Lineto(Vec2::new(5.0, 1.0)),
+            PathEl::Lineto(Vec2::new(f64::NAN, 1.0)),
+            PathEl::Lineto(Vec2::new(5.0, 5.0)),
+            PathEl::Closepath,
+            PathEl::Lineto(Vec2::new(0.0, 5.0)),
+            PathEl::Moveto(Vec2::new(10.0, 10.0)),
+            PathEl::Lineto(Vec2::new(20.0, 10.0)),
+        ]);
+        assert_eq!(
+            path.validate(),
+            vec![
+                PathIssue::MissingMoveto(0),
+                PathIssue::ZeroLength(2),
+                PathIssue::NonFinite(3),
+                PathIssue::MissingMoveto(6),
+                PathIssue::Unclosed(6),
+                PathIssue::Unclosed(7),
+            ]
+        );
+        path.sanitize();
+        assert_eq!(path.to_svg(), "M1 1L5 1L5 5ZM1 1L0 5M10 10L20 10");
+        assert_eq!(
+            path.validate(),
+            vec![PathIssue::Unclosed(4), PathIssue::Unclosed(6)]
+        );
+    }
+}
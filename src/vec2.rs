@@ -2,6 +2,8 @@
 
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use crate::Affine;
+
 /// A 2D vector.
 ///
 /// This can be interpreted as a point in 2-space, a translation, a
@@ -76,6 +78,80 @@ impl Vec2 {
     pub fn lerp(&self, other: Vec2, t: f64) -> Vec2 {
         *self + t * (other - *self)
     }
+
+    /// The perp-dot product of two vectors.
+    ///
+    /// This is the same as [`cross`](#method.cross), under the name used
+    /// in some texts: the dot product of the vector rotated a quarter turn
+    /// with the other.
+    #[inline]
+    pub fn perp_dot(&self, other: Vec2) -> f64 {
+        self.cross(other)
+    }
+
+    /// The projection of the vector onto the line through the origin in
+    /// the direction of `other`.
+    ///
+    /// The result is NaN if `other` is zero.
+    #[inline]
+    pub fn project_onto(&self, other: Vec2) -> Vec2 {
+        (self.dot(other) / other.hypot2()) * other
+    }
+
+    /// The component of the vector perpendicular to `other`.
+    ///
+    /// This is the vector minus its [projection](#method.project_onto), so
+    /// the two sum to the original vector.
+    #[inline]
+    pub fn reject_from(&self, other: Vec2) -> Vec2 {
+        *self - self.project_onto(other)
+    }
+
+    /// The mirror image of the vector in the line through the origin in the
+    /// direction of `axis`.
+    #[inline]
+    pub fn reflect_across(&self, axis: Vec2) -> Vec2 {
+        2.0 * self.project_onto(axis) - *self
+    }
+
+    /// Rotate the vector by an angle in radians.
+    ///
+    /// The direction of rotation follows the same convention as
+    /// [`from_angle`](#method.from_angle).
+    #[inline]
+    pub fn rotate(&self, th: f64) -> Vec2 {
+        let (s, c) = th.sin_cos();
+        Vec2::new(c * self.x - s * self.y, s * self.x + c * self.y)
+    }
+
+    /// The signed angle from this vector to another, in radians.
+    ///
+    /// The result is in the range -π to π, and rotating this vector by it,
+    /// as by [`rotate`](#method.rotate), gives the direction of `other`.
+    #[inline]
+    pub fn angle_between(&self, other: Vec2) -> f64 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    /// Express this point in the coordinates of a space.
+    ///
+    /// The space is given by the transform from its coordinates to the
+    /// current ones, as for
+    /// [`Affine::change_basis`](struct.Affine.html#method.change_basis).
+    /// This undoes [`to_parent`](#method.to_parent).
+    #[inline]
+    pub fn to_space(self, space: Affine) -> Vec2 {
+        space.inverse() * self
+    }
+
+    /// Express this point, given in the coordinates of a space, in the
+    /// coordinates of its parent, the current ones.
+    ///
+    /// This is `space * self`, and undoes [`to_space`](#method.to_space).
+    #[inline]
+    pub fn to_parent(self, space: Affine) -> Vec2 {
+        space * self
+    }
 }
 
 impl From<(f64, f64)> for Vec2 {
@@ -174,6 +250,7 @@ impl Div<f64> for Vec2 {
     ///
     /// This is more efficient but has different roundoff behavior than division.
     #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: f64) -> Vec2 {
         self * other.recip()
     }
@@ -181,6 +258,7 @@ impl Div<f64> for Vec2 {
 
 impl DivAssign<f64> for Vec2 {
     #[inline]
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn div_assign(&mut self, other: f64) {
         *self *= other.recip();
     }
@@ -214,3 +292,32 @@ impl From<mint::Vector2<f64>> for Vec2 {
         Vec2 { x: p.x, y: p.y }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Vec2;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!((a - b).hypot() < 1e-12, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn vec2_algebra() {
+        let v = Vec2::new(3.0, 4.0);
+        let axis = Vec2::new(2.0, 0.0);
+        assert_near(v.project_onto(axis), Vec2::new(3.0, 0.0));
+        assert_near(v.reject_from(axis), Vec2::new(0.0, 4.0));
+        assert_near(v.reflect_across(axis), Vec2::new(3.0, -4.0));
+        assert_near(v.reflect_across(Vec2::new(1.0, 1.0)), Vec2::new(4.0, 3.0));
+        assert_near(v.rotate(FRAC_PI_2), Vec2::new(-4.0, 3.0));
+        assert_near(Vec2::new(1.0, 0.0).rotate(1.0), Vec2::from_angle(1.0));
+        assert!((axis.angle_between(Vec2::new(0.0, 5.0)) - FRAC_PI_2).abs() < 1e-12);
+        let th = v.angle_between(Vec2::new(-1.0, 2.0));
+        assert_near(
+            v.rotate(th) / v.hypot(),
+            Vec2::new(-1.0, 2.0) / 5.0f64.sqrt(),
+        );
+        assert_eq!(v.perp_dot(axis), v.cross(axis));
+    }
+}